@@ -2,7 +2,10 @@
 use deno_ast::SourceRange;
 use deno_ast::SourceRanged;
 use deno_ast::SourceTextInfo;
+use deno_lint::diagnostic::compact::to_compact;
 use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintRelatedLocation;
+use deno_lint::diagnostic::Range;
 use std::fmt::Display;
 
 pub fn display_diagnostics(
@@ -12,23 +15,25 @@ pub fn display_diagnostics(
   format: Option<&str>,
 ) {
   match format {
-    Some("compact") => print_compact(diagnostics, filename),
+    Some("compact") => eprint!("{}", to_compact(diagnostics)),
     Some("pretty") => print_pretty(diagnostics, source_file, filename),
     _ => unreachable!("Invalid output format specified"),
   }
 }
 
-fn print_compact(diagnostics: &[LintDiagnostic], filename: &str) {
-  for diagnostic in diagnostics {
-    eprintln!(
-      "{}: line {}, col {}, Error - {} ({})",
-      filename,
-      diagnostic.range.start.line_index + 1,
-      diagnostic.range.start.column_index + 1,
-      diagnostic.message,
-      diagnostic.code
-    )
-  }
+/// Formats a related location as a note, including the file it's in since it
+/// may be a different one than the diagnostic's.
+fn format_related_location(
+  related: &LintRelatedLocation,
+  filename: &str,
+) -> String {
+  format!(
+    "{}: line {}, col {}, Note - {}",
+    related.filename(filename),
+    related.range.start.line_index + 1,
+    related.range.start.column_index + 1,
+    related.message
+  )
 }
 
 fn print_pretty(
@@ -36,23 +41,38 @@ fn print_pretty(
   source_file: &SourceTextInfo,
   filename: &str,
 ) {
+  let reporter = miette::GraphicalReportHandler::new();
   for diagnostic in diagnostics {
-    let reporter = miette::GraphicalReportHandler::new();
-    let miette_source_code = MietteSourceCode {
-      source: source_file,
-      filename,
-    };
-
-    let mut s = String::new();
-    let miette_diag = MietteDiagnostic {
-      source_code: &miette_source_code,
-      lint_diagnostic: diagnostic,
-    };
-    reporter.render_report(&mut s, &miette_diag).unwrap();
-    eprintln!("{}", s);
+    eprintln!(
+      "{}",
+      format_pretty(&reporter, diagnostic, source_file, filename)
+    );
+    // Locations in other files can't be labeled in the source snippet
+    for related in diagnostic.related.iter().filter(|r| r.specifier.is_some()) {
+      eprintln!("  {}", format_related_location(related, filename));
+    }
   }
 }
 
+fn format_pretty(
+  reporter: &miette::GraphicalReportHandler,
+  diagnostic: &LintDiagnostic,
+  source_file: &SourceTextInfo,
+  filename: &str,
+) -> String {
+  let miette_source_code = MietteSourceCode {
+    source: source_file,
+    filename,
+  };
+  let miette_diag = MietteDiagnostic {
+    source_code: &miette_source_code,
+    lint_diagnostic: diagnostic,
+  };
+  let mut s = String::new();
+  reporter.render_report(&mut s, &miette_diag).unwrap();
+  s
+}
+
 #[derive(Debug)]
 struct MietteDiagnostic<'a> {
   source_code: &'a MietteSourceCode<'a>,
@@ -86,18 +106,25 @@ impl miette::Diagnostic for MietteDiagnostic<'_> {
   fn labels(
     &self,
   ) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
-    let len = self.lint_diagnostic.range.end.byte_index
-      - self.lint_diagnostic.range.start.byte_index;
-    let start =
-      miette::SourceOffset::from(self.lint_diagnostic.range.start.byte_index);
-    let len = miette::SourceOffset::from(len);
-    let span = miette::SourceSpan::new(start, len);
+    // There's no line to point at in an empty file
+    if self.source_code.source.text_str().is_empty() {
+      return None;
+    }
     let text = self
       .lint_diagnostic
       .hint
       .as_ref()
       .map(|help| help.to_string());
-    let labels = vec![miette::LabeledSpan::new_with_span(text, span)];
+    let span = self.source_code.span(&self.lint_diagnostic.range);
+    let mut labels = vec![miette::LabeledSpan::new_with_span(text, span)];
+    for related in &self.lint_diagnostic.related {
+      if related.specifier.is_none() {
+        labels.push(miette::LabeledSpan::new_with_span(
+          Some(related.message.clone()),
+          self.source_code.span(&related.range),
+        ));
+      }
+    }
     Some(Box::new(labels.into_iter()))
   }
 }
@@ -108,35 +135,51 @@ struct MietteSourceCode<'a> {
   filename: &'a str,
 }
 
+impl MietteSourceCode<'_> {
+  /// The span of the range. miette draws a caret for an empty span. An empty
+  /// span at the end of a file that ends with a line break is on a line
+  /// without any text, so it's moved to the end of the last line with text.
+  fn span(&self, range: &Range) -> miette::SourceSpan {
+    let start = range.start.byte_index;
+    let len = range.end.byte_index - start;
+    let text = self.source.text_str();
+    let start = if len == 0 && start == text.len() {
+      let without_line_break = text
+        .strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .unwrap_or(text);
+      without_line_break.len()
+    } else {
+      start
+    };
+    miette::SourceSpan::new(start.into(), len.into())
+  }
+}
+
 impl miette::SourceCode for MietteSourceCode<'_> {
   fn read_span<'a>(
     &'a self,
     span: &miette::SourceSpan,
-    context_lines_before: usize,
+    _context_lines_before: usize,
     context_lines_after: usize,
   ) -> Result<Box<dyn miette::SpanContents<'a> + 'a>, miette::MietteError> {
     let start_pos = self.source.range().start;
     let lo = start_pos + span.offset();
     let hi = lo + span.len();
 
+    // miette numbers the lines starting from `line` and also shows it as the
+    // location of the diagnostic, so the lines before the span are left out
+    // to keep both right. Leaving them out also keeps an empty span at the
+    // start of a line from being drawn at the end of the line before it.
     let start_line_column = self.source.line_and_column_index(lo);
-
-    let start_line_index =
-      if context_lines_before > start_line_column.line_index {
-        0
-      } else {
-        start_line_column.line_index - context_lines_before
-      };
-    let src_start = self.source.line_start(start_line_index);
+    let src_start = self.source.line_start(start_line_column.line_index);
     let end_line_column = self.source.line_and_column_index(hi);
     let line_count = self.source.lines_count();
     let end_line_index = std::cmp::min(
       end_line_column.line_index + context_lines_after,
-      self.source.text_str().len(),
+      line_count - 1,
     );
-    let src_end = self
-      .source
-      .line_end(std::cmp::min(end_line_index, line_count - 1));
+    let src_end = self.source.line_end(end_line_index);
     let range = SourceRange::new(src_start, src_end);
     let src_text = range.text_fast(self.source);
     let byte_range = range.as_byte_range(start_pos);
@@ -190,3 +233,122 @@ impl<'a> miette::SpanContents<'a> for SpanContentsImpl<'a> {
     self.name.as_deref()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use deno_lint::diagnostic::{Position, Range, Severity};
+
+  fn range(line_index: usize, column_index: usize) -> Range {
+    let position = Position {
+      line_index,
+      column_index,
+      byte_index: 0,
+    };
+    Range {
+      start: position,
+      end: position,
+    }
+  }
+
+  fn diagnostic(
+    line_index: usize,
+    column_index: usize,
+    code: &str,
+  ) -> LintDiagnostic {
+    LintDiagnostic {
+      range: range(line_index, column_index),
+      filename: "main.ts".to_string(),
+      message: format!("`{}` was violated", code),
+      original_message: None,
+      code: code.to_string(),
+      hint: None,
+      severity: Severity::Error,
+      fixes: vec![],
+      related: vec![],
+    }
+  }
+
+  /// Renders a diagnostic with the hint "hint" at the byte range of the
+  /// source, without colors.
+  fn pretty(source: &str, start: usize, end: usize) -> String {
+    let source_file = SourceTextInfo::from_string(source.to_string());
+    let position = |byte_index: usize| {
+      Position::new(
+        byte_index,
+        source_file
+          .line_and_column_index(source_file.range().start + byte_index),
+      )
+    };
+    let diagnostic = LintDiagnostic {
+      range: Range {
+        start: position(start),
+        end: position(end),
+      },
+      hint: Some("hint".to_string()),
+      ..diagnostic(0, 0, "no-var")
+    };
+    let reporter = miette::GraphicalReportHandler::new_themed(
+      miette::GraphicalTheme::none(),
+    );
+    format_pretty(&reporter, &diagnostic, &source_file, "main.ts")
+  }
+
+  #[test]
+  fn format_pretty_empty_range() {
+    let source = "let a = 1;\nlet b = 2;\nlet c = 3;\n";
+    let frame = |location: &str, caret: &str| {
+      format!(
+        "no-var
+
+  x `no-var` was violated
+   ,-[main.ts:{}]
+ 2 | let b = 2;
+   : {}^
+   : {}`-- hint
+ 3 | let c = 3;
+   `----
+  help: https://lint.deno.land/#no-var
+",
+        location, caret, caret
+      )
+    };
+    // Start, middle and end of a line
+    assert_eq!(pretty(source, 11, 11), frame("2:1", ""));
+    assert_eq!(pretty(source, 15, 15), frame("2:5", "    "));
+    assert_eq!(pretty(source, 21, 21), frame("2:11", "          "));
+  }
+
+  #[test]
+  fn format_pretty_empty_range_at_end_of_file() {
+    let caret = "   :   ^\n";
+    assert!(pretty("a;", 2, 2).contains(caret));
+    // The caret is drawn at the end of the last line with text
+    assert!(pretty("a;\n", 3, 3).contains(caret));
+    assert!(pretty("a;\r\n", 4, 4).contains(caret));
+    assert!(pretty("a;\n\nb;", 3, 3).contains(" 2 | \n   : ^\n"));
+    assert!(!pretty("", 0, 0).contains('^'));
+  }
+
+  #[test]
+  fn format_cross_file_related_location() {
+    let related = LintRelatedLocation {
+      specifier: Some("other.ts".to_string()),
+      range: range(2, 4),
+      message: "`foo` is declared here".to_string(),
+    };
+    assert_eq!(
+      format_related_location(&related, "main.ts"),
+      "other.ts: line 3, col 5, Note - `foo` is declared here"
+    );
+
+    let related = LintRelatedLocation {
+      specifier: None,
+      ..related
+    };
+    assert_eq!(
+      format_related_location(&related, "main.ts"),
+      "main.ts: line 3, col 5, Note - `foo` is declared here"
+    );
+  }
+}
@@ -0,0 +1,910 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::diagnostic::Severity;
+use crate::ignore_directives::file_leading_comments;
+use deno_ast::swc::common::comments::Comment;
+use deno_ast::view as ast_view;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use derive_more::Display;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Configuration applied to the rules that the linter runs.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct LintConfig {
+  /// Options for each rule, keyed by rule code.
+  pub rules: HashMap<String, Value>,
+  /// Tags whose rules are run in addition to the rules selected with the
+  /// `LinterBuilder`, e.g. `recommended`.
+  pub tags: Vec<String>,
+  /// If set, `deno-lint-config` comments in linted files are ignored, so a
+  /// file can't change how it gets linted.
+  pub no_external_config: bool,
+  /// Codes of rules whose diagnostics are reported without their fixes, so
+  /// they aren't applied when fixing files.
+  pub no_fix: HashSet<String>,
+  /// Replacements for the messages and hints of each rule's diagnostics,
+  /// keyed by rule code. A message template of the rule is applied to the
+  /// replaced message.
+  pub message_overrides: HashMap<String, MessageOverride>,
+  /// Templates for the messages of each rule's diagnostics, keyed by rule
+  /// code.
+  pub message_templates: HashMap<String, MessageTemplate>,
+  /// Severity of each rule's diagnostics, keyed by rule code. Rules that
+  /// aren't listed report errors.
+  pub severity: HashMap<String, Severity>,
+}
+
+impl LintConfig {
+  /// Options specified for the rule with the given code.
+  pub fn rule_options(&self, code: &str) -> Option<&Value> {
+    self.rules.get(code)
+  }
+
+  /// Codes in `no_fix` that don't belong to any rule, in alphabetical order.
+  pub fn unknown_no_fix_codes(&self) -> Vec<&str> {
+    unknown_codes(self.no_fix.iter())
+  }
+
+  /// Codes in `message_overrides` that don't belong to any rule, in
+  /// alphabetical order.
+  pub fn unknown_message_override_codes(&self) -> Vec<&str> {
+    unknown_codes(self.message_overrides.keys())
+  }
+
+  /// Codes in `message_templates` that don't belong to any rule, in
+  /// alphabetical order.
+  pub fn unknown_message_template_codes(&self) -> Vec<&str> {
+    unknown_codes(self.message_templates.keys())
+  }
+
+  /// Codes in `severity` that don't belong to any rule, in alphabetical
+  /// order.
+  pub fn unknown_severity_codes(&self) -> Vec<&str> {
+    unknown_codes(self.severity.keys())
+  }
+
+  /// Tags in `tags` that no rule has, in alphabetical order.
+  pub fn unknown_tags(&self) -> Vec<&str> {
+    let known_tags = crate::rules::get_all_rules()
+      .into_iter()
+      .flat_map(|rule| rule.tags().iter().copied())
+      .collect::<HashSet<_>>();
+    let mut unknown_tags = self
+      .tags
+      .iter()
+      .map(String::as_str)
+      .filter(|tag| !known_tags.contains(tag))
+      .collect::<Vec<_>>();
+    unknown_tags.sort_unstable();
+    unknown_tags.dedup();
+    unknown_tags
+  }
+
+  /// Merges per-rule options on top of this config. When both sides are
+  /// objects their keys are merged, with `rules` taking precedence; any other
+  /// value replaces the existing one.
+  pub fn merge_rules(&mut self, rules: HashMap<String, Value>) {
+    for (code, options) in rules {
+      match (self.rules.get_mut(&code), options) {
+        (Some(Value::Object(existing)), Value::Object(options)) => {
+          existing.extend(options);
+        }
+        (_, options) => {
+          self.rules.insert(code, options);
+        }
+      }
+    }
+  }
+}
+
+fn unknown_codes<'a>(codes: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+  let known_codes = crate::rules::get_all_rules()
+    .into_iter()
+    .map(|rule| rule.code())
+    .collect::<HashSet<_>>();
+  let mut unknown_codes = codes
+    .map(String::as_str)
+    .filter(|code| !known_codes.contains(code))
+    .collect::<Vec<_>>();
+  unknown_codes.sort_unstable();
+  unknown_codes
+}
+
+/// Text that replaces the message or hint reported by a rule, e.g. to
+/// translate it. Whatever is `None` is kept as reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageOverride {
+  pub message: Option<String>,
+  pub hint: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+  Text(String),
+  Message,
+  Code,
+}
+
+/// A template for the messages of a rule's diagnostics, e.g.
+/// `"{message} (see https://example.com/lint/{code})"`.
+///
+/// `{message}` is replaced with the message reported by the rule and `{code}`
+/// with the rule's code. Literal braces are written as `{{` and `}}`. Any
+/// other placeholder is an error when the template is parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct MessageTemplate {
+  parts: Vec<TemplatePart>,
+}
+
+impl MessageTemplate {
+  pub fn parse(template: &str) -> Result<Self, String> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+      match c {
+        '{' if chars.as_str().starts_with('{') => {
+          chars.next();
+          text.push('{');
+        }
+        '}' if chars.as_str().starts_with('}') => {
+          chars.next();
+          text.push('}');
+        }
+        '{' => {
+          let rest = chars.as_str();
+          let Some(end) = rest.find('}') else {
+            return Err("unclosed \"{\" in message template".to_string());
+          };
+          let part = match &rest[..end] {
+            "message" => TemplatePart::Message,
+            "code" => TemplatePart::Code,
+            name => {
+              return Err(format!(
+                "unknown placeholder \"{{{}}}\" in message template",
+                name
+              ))
+            }
+          };
+          if !text.is_empty() {
+            parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+          }
+          parts.push(part);
+          chars = rest[end + 1..].chars();
+        }
+        '}' => {
+          return Err("unmatched \"}\" in message template".to_string());
+        }
+        c => text.push(c),
+      }
+    }
+    if !text.is_empty() {
+      parts.push(TemplatePart::Text(text));
+    }
+    Ok(Self { parts })
+  }
+
+  /// Fills in the placeholders of the template.
+  pub fn render(&self, message: &str, code: &str) -> String {
+    self
+      .parts
+      .iter()
+      .map(|part| match part {
+        TemplatePart::Text(text) => text.as_str(),
+        TemplatePart::Message => message,
+        TemplatePart::Code => code,
+      })
+      .collect()
+  }
+}
+
+impl TryFrom<String> for MessageTemplate {
+  type Error = String;
+
+  fn try_from(template: String) -> Result<Self, Self::Error> {
+    Self::parse(&template)
+  }
+}
+
+/// A problem found by [`validate_config`] or [`validate_config_json`].
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display(fmt = "{}", kind)]
+pub struct ConfigError {
+  pub kind: ConfigErrorKind,
+  /// Byte range of the offending key or value in the config's JSON text.
+  /// Only known when the config was validated from its text.
+  pub span: Option<Range<usize>>,
+}
+
+impl From<ConfigErrorKind> for ConfigError {
+  fn from(kind: ConfigErrorKind) -> Self {
+    Self { kind, span: None }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum ConfigErrorKind {
+  #[display(fmt = "{}", message)]
+  Invalid { message: String },
+  #[display(fmt = "rules: unknown rule \"{}\"", code)]
+  UnknownRule { code: String },
+  #[display(fmt = "tags: unknown tag \"{}\"", tag)]
+  UnknownTag { tag: String },
+  #[display(fmt = "noFix: unknown rule \"{}\"", code)]
+  UnknownNoFixCode { code: String },
+  #[display(fmt = "messageOverrides: unknown rule \"{}\"", code)]
+  UnknownMessageOverrideCode { code: String },
+  #[display(fmt = "messageTemplates: unknown rule \"{}\"", code)]
+  UnknownMessageTemplateCode { code: String },
+  #[display(fmt = "severity: unknown rule \"{}\"", code)]
+  UnknownSeverityCode { code: String },
+  #[display(fmt = "rules.{}: invalid options: {}", code, message)]
+  InvalidRuleOptions { code: String, message: String },
+}
+
+impl ConfigErrorKind {
+  /// The field of the config and the key or array element within it that the
+  /// problem is about.
+  fn location(&self) -> Option<(&'static str, &str)> {
+    match self {
+      Self::Invalid { .. } => None,
+      Self::UnknownRule { code } | Self::InvalidRuleOptions { code, .. } => {
+        Some(("rules", code))
+      }
+      Self::UnknownTag { tag } => Some(("tags", tag)),
+      Self::UnknownNoFixCode { code } => Some(("noFix", code)),
+      Self::UnknownMessageOverrideCode { code } => {
+        Some(("messageOverrides", code))
+      }
+      Self::UnknownMessageTemplateCode { code } => {
+        Some(("messageTemplates", code))
+      }
+      Self::UnknownSeverityCode { code } => Some(("severity", code)),
+    }
+  }
+}
+
+/// Checks the config without linting anything, collecting every problem
+/// instead of stopping at the first one. Errors are sorted by rule code or
+/// tag within each field, and have no span.
+///
+/// Rule options that can't be deserialized are otherwise silently replaced
+/// with the rule's defaults during linting.
+pub fn validate_config(config: &LintConfig) -> Result<(), Vec<ConfigError>> {
+  let rules = crate::rules::get_all_rules()
+    .into_iter()
+    .map(|rule| (rule.code(), rule))
+    .collect::<HashMap<_, _>>();
+
+  let mut codes = config.rules.keys().collect::<Vec<_>>();
+  codes.sort_unstable();
+  let mut errors = Vec::new();
+  for code in codes {
+    let Some(rule) = rules.get(code.as_str()) else {
+      errors.push(ConfigErrorKind::UnknownRule { code: code.clone() }.into());
+      continue;
+    };
+    if let Err(message) = rule.validate_options(&config.rules[code]) {
+      errors.push(
+        ConfigErrorKind::InvalidRuleOptions {
+          code: code.clone(),
+          message,
+        }
+        .into(),
+      );
+    }
+  }
+  errors.extend(config.unknown_tags().into_iter().map(|tag| {
+    ConfigErrorKind::UnknownTag {
+      tag: tag.to_string(),
+    }
+    .into()
+  }));
+  errors.extend(config.unknown_no_fix_codes().into_iter().map(|code| {
+    ConfigErrorKind::UnknownNoFixCode {
+      code: code.to_string(),
+    }
+    .into()
+  }));
+  errors.extend(config.unknown_message_override_codes().into_iter().map(
+    |code| {
+      ConfigErrorKind::UnknownMessageOverrideCode {
+        code: code.to_string(),
+      }
+      .into()
+    },
+  ));
+  errors.extend(config.unknown_message_template_codes().into_iter().map(
+    |code| {
+      ConfigErrorKind::UnknownMessageTemplateCode {
+        code: code.to_string(),
+      }
+      .into()
+    },
+  ));
+  errors.extend(config.unknown_severity_codes().into_iter().map(|code| {
+    ConfigErrorKind::UnknownSeverityCode {
+      code: code.to_string(),
+    }
+    .into()
+  }));
+
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(errors)
+  }
+}
+
+/// Parses and checks a config from its JSON text like [`validate_config`],
+/// attaching to each error the span of the key or array element it is about.
+/// If the text isn't a valid config, the only error is the one that stopped
+/// parsing, spanning the position where it was found.
+pub fn validate_config_json(
+  text: &str,
+) -> Result<LintConfig, Vec<ConfigError>> {
+  let config = serde_json::from_str::<LintConfig>(text).map_err(|err| {
+    let offset = json_error_offset(text, &err);
+    vec![ConfigError {
+      kind: ConfigErrorKind::Invalid {
+        message: err.to_string(),
+      },
+      span: offset.map(|offset| offset..offset),
+    }]
+  })?;
+  let Err(mut errors) = validate_config(&config) else {
+    return Ok(config);
+  };
+  let spans = json_entry_spans(text);
+  for error in &mut errors {
+    error.span = error.kind.location().and_then(|(field, name)| {
+      spans.get(&(field.to_string(), name.to_string())).cloned()
+    });
+  }
+  Err(errors)
+}
+
+/// Byte offset just past the line and column reported by a JSON error, which
+/// is the last character the parser read.
+fn json_error_offset(text: &str, err: &serde_json::Error) -> Option<usize> {
+  if err.line() == 0 {
+    return None;
+  }
+  let line_start = text
+    .split_inclusive('\n')
+    .take(err.line() - 1)
+    .map(str::len)
+    .sum::<usize>();
+  let mut offset = (line_start + err.column()).min(text.len());
+  while !text.is_char_boundary(offset) {
+    offset -= 1;
+  }
+  Some(offset)
+}
+
+/// Spans of the keys of the objects and the strings of the arrays that are
+/// the values of the top-level fields of a JSON text, keyed by the field and
+/// the key or string. The text must be valid JSON.
+fn json_entry_spans(text: &str) -> HashMap<(String, String), Range<usize>> {
+  let mut scanner = JsonScanner {
+    text,
+    pos: 0,
+    spans: HashMap::new(),
+  };
+  scanner.value(None, 0);
+  scanner.spans
+}
+
+struct JsonScanner<'a> {
+  text: &'a str,
+  pos: usize,
+  spans: HashMap<(String, String), Range<usize>>,
+}
+
+impl JsonScanner<'_> {
+  fn peek(&mut self) -> Option<u8> {
+    let rest = &self.text[self.pos..];
+    self.pos += rest.len() - rest.trim_start().len();
+    self.text.as_bytes().get(self.pos).copied()
+  }
+
+  fn string(&mut self) -> (String, Range<usize>) {
+    let bytes = self.text.as_bytes();
+    let start = self.pos;
+    let mut end = start + 1;
+    while end < bytes.len() && bytes[end] != b'"' {
+      end += if bytes[end] == b'\\' { 2 } else { 1 };
+    }
+    let end = (end + 1).min(bytes.len());
+    self.pos = end;
+    let value =
+      serde_json::from_str(&self.text[start..end]).unwrap_or_default();
+    (value, start..end)
+  }
+
+  fn record(&mut self, field: Option<&str>, name: String, span: Range<usize>) {
+    if let Some(field) = field {
+      self.spans.entry((field.to_string(), name)).or_insert(span);
+    }
+  }
+
+  /// Scans the value at the current position. `field` is the top-level
+  /// field that the value belongs to when it is that field's value.
+  fn value(&mut self, field: Option<&str>, depth: usize) {
+    match self.peek() {
+      Some(b'{') => {
+        self.pos += 1;
+        loop {
+          match self.peek() {
+            Some(b'"') => {
+              let (key, span) = self.string();
+              self.record(field, key.clone(), span);
+              // Skip the colon
+              self.peek();
+              self.pos += 1;
+              let field = (depth == 0).then_some(key.as_str());
+              self.value(field, depth + 1);
+            }
+            Some(b',') => self.pos += 1,
+            Some(b'}') => {
+              self.pos += 1;
+              break;
+            }
+            _ => break,
+          }
+        }
+      }
+      Some(b'[') => {
+        self.pos += 1;
+        loop {
+          match self.peek() {
+            Some(b'"') => {
+              let (value, span) = self.string();
+              self.record(field, value, span);
+            }
+            Some(b',') => self.pos += 1,
+            Some(b']') => {
+              self.pos += 1;
+              break;
+            }
+            Some(_) => {
+              let pos = self.pos;
+              self.value(None, depth + 1);
+              if self.pos == pos {
+                break;
+              }
+            }
+            None => break,
+          }
+        }
+      }
+      Some(b'"') => {
+        self.string();
+      }
+      Some(_) => {
+        let rest = &self.text[self.pos..];
+        self.pos += rest
+          .find(|c: char| c == ',' || c == '}' || c == ']' || c.is_whitespace())
+          .unwrap_or(rest.len());
+      }
+      None => {}
+    }
+  }
+}
+
+/// Deserializes the options of a rule.
+pub(crate) fn parse_rule_options<T>(options: &Value) -> Result<T, String>
+where
+  T: DeserializeOwned,
+{
+  T::deserialize(options).map_err(|e| e.to_string())
+}
+
+/// Checks that the options of a rule deserialize into `T`, for implementing
+/// [`LintRule::validate_options`](crate::rules::LintRule::validate_options).
+pub(crate) fn validate_rule_options<T>(options: &Value) -> Result<(), String>
+where
+  T: DeserializeOwned,
+{
+  parse_rule_options::<T>(options).map(drop)
+}
+
+/// A `deno-lint-config` comment placed at the top of a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDirective {
+  range: SourceRange,
+  result: Result<HashMap<String, Value>, String>,
+}
+
+impl ConfigDirective {
+  pub fn range(&self) -> SourceRange {
+    self.range
+  }
+
+  /// Rule options embedded in the comment, or a description of why they
+  /// couldn't be parsed.
+  pub fn rules(&self) -> Result<&HashMap<String, Value>, &str> {
+    self.result.as_ref().map_err(|e| e.as_str())
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigComment {
+  #[serde(default)]
+  rules: HashMap<String, Value>,
+}
+
+pub(crate) fn parse_config_directive(
+  config_directive: &str,
+  program: ast_view::Program,
+) -> Option<ConfigDirective> {
+  file_leading_comments(program)
+    .find_map(|comment| parse_config_comment(config_directive, comment))
+}
+
+fn parse_config_comment(
+  config_directive: &str,
+  comment: &Comment,
+) -> Option<ConfigDirective> {
+  let comment_text = comment.text.trim();
+  let json = comment_text.strip_prefix(config_directive)?;
+  // Make sure that e.g. `deno-lint-configuration` isn't treated as a directive
+  if !json.is_empty() && !json.starts_with(char::is_whitespace) {
+    return None;
+  }
+
+  let result = serde_json::from_str::<ConfigComment>(json.trim())
+    .map(|config| config.rules)
+    .map_err(|e| e.to_string());
+  for (code, options) in result.iter().flatten() {
+    if !options.is_object() {
+      return Some(ConfigDirective {
+        range: comment.range(),
+        result: Err(format!("options for \"{}\" must be an object", code)),
+      });
+    }
+  }
+
+  Some(ConfigDirective {
+    range: comment.range(),
+    result,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util;
+  use serde_json::json;
+
+  fn parse(source_code: &str) -> Option<ConfigDirective> {
+    test_util::parse(source_code)
+      .with_view(|program| parse_config_directive("deno-lint-config", program))
+  }
+
+  #[test]
+  fn test_parse_config_directive() {
+    let directive = parse(
+      r#"// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["fetch"] } } }
+window.fetch();"#,
+    )
+    .unwrap();
+    let rules = directive.rules().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules["no-window-prefix"], json!({ "allow": ["fetch"] }));
+
+    let directive = parse(
+      r#"/* deno-lint-config {
+  "rules": { "camelcase": {} }
+} */"#,
+    )
+    .unwrap();
+    assert_eq!(directive.rules().unwrap()["camelcase"], json!({}));
+
+    let directive = parse(
+      "#!/usr/bin/env -S deno run\n// deno-lint-config {}\nconst a = 42;",
+    )
+    .unwrap();
+    assert!(directive.rules().unwrap().is_empty());
+
+    assert!(parse("const a = 42;\n// deno-lint-config {}").is_none());
+    assert!(parse("// deno-lint-configuration {}").is_none());
+  }
+
+  #[test]
+  fn test_parse_malformed_config_directive() {
+    let directive = parse(r#"// deno-lint-config { "rules": "#).unwrap();
+    assert!(directive.rules().is_err());
+
+    let directive = parse("// deno-lint-config").unwrap();
+    assert!(directive.rules().is_err());
+
+    let directive =
+      parse(r#"// deno-lint-config { "noExternalConfig": false }"#).unwrap();
+    assert!(directive.rules().is_err());
+
+    let directive =
+      parse(r#"// deno-lint-config { "rules": { "eqeqeq": true } }"#).unwrap();
+    assert_eq!(
+      directive.rules().unwrap_err(),
+      "options for \"eqeqeq\" must be an object"
+    );
+  }
+
+  #[test]
+  fn test_merge_rules() {
+    let mut config = LintConfig::default();
+    config.rules.insert(
+      "no-window-prefix".to_string(),
+      json!({ "allow": ["fetch"], "foo": 1 }),
+    );
+    config
+      .rules
+      .insert("camelcase".to_string(), json!({ "bar": 2 }));
+
+    config.merge_rules(
+      [
+        (
+          "no-window-prefix".to_string(),
+          json!({ "allow": ["alert"] }),
+        ),
+        ("eqeqeq".to_string(), json!({ "baz": 3 })),
+      ]
+      .into_iter()
+      .collect(),
+    );
+
+    assert_eq!(
+      config.rule_options("no-window-prefix"),
+      Some(&json!({ "allow": ["alert"], "foo": 1 }))
+    );
+    assert_eq!(config.rule_options("camelcase"), Some(&json!({ "bar": 2 })));
+    assert_eq!(config.rule_options("eqeqeq"), Some(&json!({ "baz": 3 })));
+  }
+
+  #[test]
+  fn test_unknown_no_fix_codes() {
+    let config: LintConfig = serde_json::from_value(json!({
+      "noFix": ["prefer-const", "no-such-rule", "another-unknown-rule"]
+    }))
+    .unwrap();
+    assert!(config.no_fix.contains("prefer-const"));
+    assert_eq!(
+      config.unknown_no_fix_codes(),
+      vec!["another-unknown-rule", "no-such-rule"]
+    );
+    assert!(LintConfig::default().unknown_no_fix_codes().is_empty());
+  }
+
+  #[test]
+  fn test_validate_config() {
+    assert_eq!(validate_config(&LintConfig::default()), Ok(()));
+
+    let config: LintConfig = serde_json::from_value(json!({
+      "rules": {
+        "no-window-prefix": { "allow": ["fetch"] },
+        "max-nested-callbacks": { "max": 4 },
+        "eqeqeq": {}
+      },
+      "noFix": ["prefer-const"]
+    }))
+    .unwrap();
+    assert_eq!(validate_config(&config), Ok(()));
+  }
+
+  #[test]
+  fn test_unknown_config_fields() {
+    let result = serde_json::from_value::<LintConfig>(json!({
+      "noFixes": ["prefer-const"]
+    }));
+    assert!(result.unwrap_err().to_string().contains("unknown field"));
+  }
+
+  #[test]
+  fn test_validate_config_collects_all_errors() {
+    let config: LintConfig = serde_json::from_value(json!({
+      "rules": {
+        "no-such-rule": {},
+        "max-nested-callbacks": { "max": "three" },
+        "no-window-prefix": { "allow": "fetch" },
+        "eqeqeq": {}
+      },
+      "tags": ["recommended", "no-such-tag"],
+      "noFix": ["prefer-const", "another-unknown-rule"]
+    }))
+    .unwrap();
+    let errors = validate_config(&config).unwrap_err();
+    assert_eq!(errors.len(), 5);
+    assert!(matches!(
+      &errors[0].kind,
+      ConfigErrorKind::InvalidRuleOptions { code, .. } if code == "max-nested-callbacks"
+    ));
+    assert_eq!(
+      errors[1],
+      ConfigError::from(ConfigErrorKind::UnknownRule {
+        code: "no-such-rule".to_string()
+      })
+    );
+    assert!(matches!(
+      &errors[2].kind,
+      ConfigErrorKind::InvalidRuleOptions { code, .. } if code == "no-window-prefix"
+    ));
+    assert_eq!(
+      errors[3].kind,
+      ConfigErrorKind::UnknownTag {
+        tag: "no-such-tag".to_string()
+      }
+    );
+    assert_eq!(
+      errors[4].kind,
+      ConfigErrorKind::UnknownNoFixCode {
+        code: "another-unknown-rule".to_string()
+      }
+    );
+    assert!(errors.iter().all(|error| error.span.is_none()));
+    assert_eq!(
+      errors[1].to_string(),
+      "rules: unknown rule \"no-such-rule\""
+    );
+    assert_eq!(errors[3].to_string(), "tags: unknown tag \"no-such-tag\"");
+    assert!(errors[0]
+      .to_string()
+      .starts_with("rules.max-nested-callbacks: invalid options: "));
+  }
+
+  #[test]
+  fn test_validate_config_json() {
+    let text = r#"{
+  "rules": {
+    "no-such-rule": {},
+    "max-nested-callbacks": { "max": "three" }
+  },
+  "tags": ["recommended", "no-such-tag"],
+  "noFix": ["prefer-const", "another-unknown-rule"],
+  "severity": { "no-var": "warning", "unknown-\u0072ule": "error" }
+}"#;
+    let errors = validate_config_json(text).unwrap_err();
+    let spans = errors
+      .iter()
+      .map(|error| &text[error.span.clone().unwrap()])
+      .collect::<Vec<_>>();
+    assert_eq!(
+      spans,
+      vec![
+        "\"max-nested-callbacks\"",
+        "\"no-such-rule\"",
+        "\"no-such-tag\"",
+        "\"another-unknown-rule\"",
+        "\"unknown-\\u0072ule\"",
+      ]
+    );
+    assert_eq!(
+      errors[4].kind,
+      ConfigErrorKind::UnknownSeverityCode {
+        code: "unknown-rule".to_string()
+      }
+    );
+
+    let config =
+      validate_config_json(r#"{ "tags": ["recommended"] }"#).unwrap();
+    assert_eq!(config.tags, vec!["recommended"]);
+
+    let errors = validate_config_json("{\n  \"noFixes\": []\n}").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+      &errors[0].kind,
+      ConfigErrorKind::Invalid { message } if message.contains("unknown field")
+    ));
+    assert_eq!(errors[0].span, Some(13..13));
+  }
+
+  #[test]
+  fn test_message_template() {
+    let template =
+      MessageTemplate::parse("{message} ({code}, see {{wiki}})").unwrap();
+    assert_eq!(
+      template.render("`var` keyword is not allowed", "no-var"),
+      "`var` keyword is not allowed (no-var, see {wiki})"
+    );
+    assert_eq!(MessageTemplate::parse("").unwrap().render("a", "b"), "");
+
+    assert_eq!(
+      MessageTemplate::parse("{message} {hint}").unwrap_err(),
+      "unknown placeholder \"{hint}\" in message template"
+    );
+    assert!(MessageTemplate::parse("{message").is_err());
+    assert!(MessageTemplate::parse("message}").is_err());
+  }
+
+  #[test]
+  fn test_message_overrides_in_config() {
+    let config: LintConfig = serde_json::from_value(json!({
+      "messageOverrides": {
+        "no-var": { "message": "Use `let` or `const`" },
+        "no-such-rule": { "hint": "hint" }
+      }
+    }))
+    .unwrap();
+    assert_eq!(
+      config.message_overrides["no-var"],
+      MessageOverride {
+        message: Some("Use `let` or `const`".to_string()),
+        hint: None,
+      }
+    );
+    assert_eq!(
+      validate_config(&config).unwrap_err(),
+      vec![ConfigError::from(
+        ConfigErrorKind::UnknownMessageOverrideCode {
+          code: "no-such-rule".to_string()
+        }
+      )]
+    );
+
+    assert!(serde_json::from_value::<LintConfig>(json!({
+      "messageOverrides": { "no-var": { "text": "Use `let`" } }
+    }))
+    .is_err());
+  }
+
+  #[test]
+  fn test_message_templates_in_config() {
+    let config: LintConfig = serde_json::from_value(json!({
+      "messageTemplates": {
+        "no-var": "{message} [{code}]",
+        "no-such-rule": "{message}"
+      }
+    }))
+    .unwrap();
+    assert_eq!(
+      config.message_templates["no-var"].render("message", "no-var"),
+      "message [no-var]"
+    );
+    assert_eq!(
+      validate_config(&config).unwrap_err(),
+      vec![ConfigError::from(
+        ConfigErrorKind::UnknownMessageTemplateCode {
+          code: "no-such-rule".to_string()
+        }
+      )]
+    );
+
+    let err = serde_json::from_value::<LintConfig>(json!({
+      "messageTemplates": { "no-var": "{message} {link}" }
+    }))
+    .unwrap_err();
+    assert!(err
+      .to_string()
+      .contains("unknown placeholder \"{link}\" in message template"));
+  }
+
+  #[test]
+  fn test_severity_in_config() {
+    let config: LintConfig = serde_json::from_value(json!({
+      "severity": { "no-var": "warning", "no-such-rule": "error" }
+    }))
+    .unwrap();
+    assert_eq!(config.severity["no-var"], Severity::Warning);
+    assert_eq!(config.severity["no-such-rule"], Severity::Error);
+    assert_eq!(
+      validate_config(&config).unwrap_err(),
+      vec![ConfigError::from(ConfigErrorKind::UnknownSeverityCode {
+        code: "no-such-rule".to_string()
+      })]
+    );
+
+    assert!(serde_json::from_value::<LintConfig>(json!({
+      "severity": { "no-var": "info" }
+    }))
+    .is_err());
+  }
+}
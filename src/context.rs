@@ -1,21 +1,34 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::config::{parse_rule_options, ConfigDirective, LintConfig};
 use crate::control_flow::ControlFlow;
-use crate::diagnostic::{LintDiagnostic, Position, Range};
+use crate::diagnostic::{
+  LintDiagnostic, LintFix, LintRelatedLocation, Position, Range, Severity,
+};
+#[cfg(feature = "profiling")]
+use crate::explain::{ExplainRecorder, SuppressedBy};
+use crate::globals::GLOBALS;
 use crate::ignore_directives::{
   CodeStatus, FileIgnoreDirective, LineIgnoreDirective,
 };
+use crate::linter::{IsGlobal, KnownGlobals};
 use crate::rules::{self, get_all_rules, LintRule};
+use crate::time::Instant;
+use deno_ast::swc::ast::Id;
 use deno_ast::swc::common::comments::Comment;
 use deno_ast::swc::common::SyntaxContext;
+use deno_ast::swc::parser::token::TokenAndSpan;
 use deno_ast::MediaType;
 use deno_ast::Scope;
+use deno_ast::SourceRanged;
 use deno_ast::SourceTextInfo;
+use deno_ast::Var;
 use deno_ast::{
   view as ast_view, ParsedSource, RootNode, SourcePos, SourceRange,
 };
+use serde::de::DeserializeOwned;
 use std::collections::{HashMap, HashSet};
-
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// `Context` stores data needed while performing all lint rules to a file.
 pub struct Context<'view> {
@@ -25,10 +38,21 @@ pub struct Context<'view> {
   program: ast_view::Program<'view>,
   file_ignore_directive: Option<FileIgnoreDirective>,
   line_ignore_directives: HashMap<usize, LineIgnoreDirective>,
+  config_directive: Option<ConfigDirective>,
+  config: LintConfig,
   scope: Scope,
   control_flow: ControlFlow,
   traverse_flow: TraverseFlow,
   check_unknown_rules: bool,
+  /// Codes of the plugin rules, which are known in addition to the builtin
+  /// ones.
+  plugin_rule_codes: HashSet<&'static str>,
+  known_globals: Arc<KnownGlobals>,
+  is_global: Option<Arc<IsGlobal>>,
+  cancellation_flag: Option<Arc<AtomicBool>>,
+  /// Set while `Linter::explain` lints the file.
+  #[cfg(feature = "profiling")]
+  pub(crate) explain: Option<ExplainRecorder>,
 }
 
 impl<'view> Context<'view> {
@@ -39,9 +63,15 @@ impl<'view> Context<'view> {
     program: ast_view::Program<'view>,
     file_ignore_directive: Option<FileIgnoreDirective>,
     line_ignore_directives: HashMap<usize, LineIgnoreDirective>,
+    config_directive: Option<ConfigDirective>,
+    config: LintConfig,
     scope: Scope,
     control_flow: ControlFlow,
     check_unknown_rules: bool,
+    plugin_rule_codes: HashSet<&'static str>,
+    known_globals: Arc<KnownGlobals>,
+    is_global: Option<Arc<IsGlobal>>,
+    cancellation_flag: Option<Arc<AtomicBool>>,
   ) -> Self {
     Self {
       parsed_source,
@@ -49,11 +79,19 @@ impl<'view> Context<'view> {
       program,
       file_ignore_directive,
       line_ignore_directives,
+      config_directive,
+      config,
       scope,
       control_flow,
       diagnostics: Vec::new(),
       traverse_flow: TraverseFlow::default(),
       check_unknown_rules,
+      plugin_rule_codes,
+      known_globals,
+      is_global,
+      cancellation_flag,
+      #[cfg(feature = "profiling")]
+      explain: None,
     }
   }
 
@@ -73,11 +111,46 @@ impl<'view> Context<'view> {
     &self.diagnostics
   }
 
+  /// Whether the flag passed to [`LinterBuilder::cancellation_flag`] is set.
+  /// Traversals with [`Traverse`] stop on their own once it is; rules that
+  /// walk the AST in other ways can check it to return early.
+  ///
+  /// [`LinterBuilder::cancellation_flag`]: crate::linter::LinterBuilder::cancellation_flag
+  /// [`Traverse`]: crate::handler::Traverse
+  pub fn is_cancelled(&self) -> bool {
+    self
+      .cancellation_flag
+      .as_ref()
+      .map_or(false, |flag| flag.load(Ordering::Relaxed))
+  }
+
+  /// Drops the diagnostics reported after the first `len` ones.
+  pub(crate) fn truncate_diagnostics(&mut self, len: usize) {
+    self.diagnostics.truncate(len);
+  }
+
   /// Information about the file text.
   pub fn text_info(&self) -> &SourceTextInfo {
     self.parsed_source.text_info()
   }
 
+  /// The source text of the given range, e.g. of a node. Slices the file
+  /// text, so no allocation is involved.
+  pub fn text_of(&self, range: SourceRange) -> &str {
+    range.text_fast(self.text_info())
+  }
+
+  /// The tokens of the given range, e.g. of a node, in source order. Tokens
+  /// that only partly overlap the range aren't included.
+  ///
+  /// This is a lower-level API than the AST, for the few things that the AST
+  /// doesn't keep, like whether a statement ends with a semicolon or where an
+  /// operator is. It slices the tokens that the parser already collected, so
+  /// no text is scanned again.
+  pub fn tokens_in(&self, range: SourceRange) -> &'view [TokenAndSpan] {
+    tokens_in(self.program, range)
+  }
+
   /// The AST view of the program, which for example can be used for getting
   /// comments
   pub fn program(&self) -> ast_view::Program<'view> {
@@ -95,16 +168,150 @@ impl<'view> Context<'view> {
     &self.line_ignore_directives
   }
 
+  /// File-level config directive (`deno-lint-config`)
+  pub fn config_directive(&self) -> Option<&ConfigDirective> {
+    self.config_directive.as_ref()
+  }
+
+  /// Configuration for this file, including the options from the config
+  /// directive
+  pub fn config(&self) -> &LintConfig {
+    &self.config
+  }
+
+  /// Options configured for the rule with the given code. Falls back to the
+  /// default options if none are configured or they can't be deserialized.
+  pub fn rule_options<T>(&self, code: &str) -> T
+  where
+    T: DeserializeOwned + Default,
+  {
+    self
+      .config
+      .rule_options(code)
+      .and_then(|options| parse_rule_options(options).ok())
+      .unwrap_or_default()
+  }
+
   /// Scope analysis result
   pub fn scope(&self) -> &Scope {
     &self.scope
   }
 
+  /// Whether the identifier refers to a global rather than to a binding
+  /// declared in the file. For example, `window` in `window.alert()` is
+  /// global unless the file declares its own `window`.
+  pub fn is_global_ident(&self, ident: &ast_view::Ident) -> bool {
+    self.is_global(&ident.inner.to_id())
+  }
+
+  /// Like [`Context::is_global_ident`], for the identifier with the given id.
+  ///
+  /// Any identifier that isn't declared in the file is global, unless a
+  /// predicate set with `LinterBuilder::is_global` says there's no such
+  /// global.
+  pub fn is_global(&self, id: &Id) -> bool {
+    self.scope.is_global(id)
+      && (self.is_global.is_none() || self.global_exists(&id.0))
+  }
+
+  /// Whether a global value with the given name exists. Known globals
+  /// always exist; other names are checked with the predicate set with
+  /// `LinterBuilder::is_global`, or else against the builtin globals.
+  pub fn global_exists(&self, name: &str) -> bool {
+    self.known_globals.has_value(name)
+      || match &self.is_global {
+        Some(is_global) => is_global(name),
+        None => GLOBALS.iter().any(|(global, _)| *global == name),
+      }
+  }
+
+  /// The binding declared in the file that the identifier with the given id
+  /// refers to, or `None` if it refers to a global.
+  pub fn binding_of(&self, id: &Id) -> Option<&Var> {
+    self.scope.var(id)
+  }
+
+  /// Globals declared outside of the file, which are defined in addition to
+  /// the builtin ones.
+  pub fn known_globals(&self) -> &KnownGlobals {
+    &self.known_globals
+  }
+
   /// Control-flow analysis result
   pub fn control_flow(&self) -> &ControlFlow {
     &self.control_flow
   }
 
+  /// Whether the node is strict mode code, i.e. it's part of an ES module or
+  /// a class, or in a script or function with a `"use strict"` directive.
+  pub fn is_strict(&self, node: ast_view::Node) -> bool {
+    is_strict(node)
+  }
+
+  /// The nearest function, method, constructor, accessor or arrow function
+  /// that the node is in, not counting the node itself.
+  pub fn enclosing_function<'a>(
+    &self,
+    node: ast_view::Node<'a>,
+  ) -> Option<FunctionLike<'a>> {
+    enclosing_function(node, true)
+  }
+
+  /// Like `enclosing_function`, but skips arrow functions. This is the
+  /// function that `this` and `arguments` refer to, except in class field
+  /// initializers and static blocks: they aren't functions, so for a node in
+  /// one of them this is the function around the class, if any.
+  pub fn enclosing_non_arrow_function<'a>(
+    &self,
+    node: ast_view::Node<'a>,
+  ) -> Option<FunctionLike<'a>> {
+    enclosing_function(node, false)
+  }
+
+  /// The nearest `for`, `for...in`, `for...of`, `while` or `do...while` loop
+  /// that the node is in, not counting the node itself. The search stops at
+  /// function and static block boundaries, since `break` and `continue` can't
+  /// cross them.
+  pub fn enclosing_loop<'a>(
+    &self,
+    node: ast_view::Node<'a>,
+  ) -> Option<ast_view::Node<'a>> {
+    enclosing_loop(node)
+  }
+
+  /// The loop that `continue label` at the node would continue, e.g. the
+  /// outer loop for `outer: for (;;) { for (;;) { continue outer; } }`. Labels
+  /// of labeled statements nested in each other, like `a: b: while (x) {}`,
+  /// all refer to the loop. `None` if there's no such label around the node
+  /// in its function, or if the label isn't on a loop.
+  pub fn loop_for_label<'a>(
+    &self,
+    node: ast_view::Node<'a>,
+    label: &str,
+  ) -> Option<ast_view::Node<'a>> {
+    loop_for_label(node, label)
+  }
+
+  /// The ancestors of the node, from its parent up to the `Program` node.
+  /// The iterator walks the parent links without allocating.
+  pub fn ancestors<'a>(
+    &self,
+    node: ast_view::Node<'a>,
+  ) -> ast_view::AncestorIterator<'a> {
+    use deno_ast::view::NodeTrait;
+    node.ancestors()
+  }
+
+  /// The nearest ancestor of the node that is a `T`, not counting the node
+  /// itself. For example, `ctx.nearest_ancestor::<ast_view::CallExpr>(node)`
+  /// finds the call that the node is an argument or callee of, if any.
+  pub fn nearest_ancestor<'a, T: ast_view::CastableNode<'a>>(
+    &self,
+    node: ast_view::Node<'a>,
+  ) -> Option<&'a T> {
+    nearest_ancestor(node)
+  }
+
   /// The `SyntaxContext` of any unresolved identifiers
   pub(crate) fn unresolved_ctxt(&self) -> SyntaxContext {
     self.parsed_source.unresolved_context()
@@ -118,7 +325,16 @@ impl<'view> Context<'view> {
     self.traverse_flow.should_stop()
   }
 
-  pub(crate) fn stop_traverse(&mut self) {
+  #[cfg(feature = "profiling")]
+  pub(crate) fn record_visit(&mut self, node: ast_view::Node) {
+    if let Some(recorder) = &mut self.explain {
+      recorder.record_visit(node);
+    }
+  }
+
+  /// Stops the traversal of the children of the node being visited by a
+  /// [`Handler`](crate::handler::Handler), e.g. to skip nested functions.
+  pub fn stop_traverse(&mut self) {
     self.traverse_flow.set_stop_traverse();
   }
 
@@ -150,6 +366,11 @@ impl<'view> Context<'view> {
     for diagnostic in self.diagnostics.iter().cloned() {
       if let Some(f) = self.file_ignore_directive.as_mut() {
         if f.check_used(&diagnostic.code) {
+          #[cfg(feature = "profiling")]
+          if let Some(recorder) = &mut self.explain {
+            recorder
+              .record_suppressed(&diagnostic, SuppressedBy::FileDirective);
+          }
           continue;
         }
       }
@@ -160,6 +381,15 @@ impl<'view> Context<'view> {
           self.line_ignore_directives.get_mut(&(diagnostic_line - 1))
         {
           if l.check_used(&diagnostic.code) {
+            #[cfg(feature = "profiling")]
+            if let Some(recorder) = &mut self.explain {
+              recorder.record_suppressed(
+                &diagnostic,
+                SuppressedBy::LineDirective {
+                  line_index: diagnostic_line - 1,
+                },
+              );
+            }
             continue;
           }
         }
@@ -176,7 +406,7 @@ impl<'view> Context<'view> {
   /// works for diagnostics reported by other rules.
   pub(crate) fn ban_unused_ignore(
     &self,
-    specified_rules: &[&'static dyn LintRule],
+    specified_rules: &[&dyn LintRule],
   ) -> Vec<LintDiagnostic> {
     const CODE: &str = "ban-unused-ignore";
 
@@ -239,8 +469,10 @@ impl<'view> Context<'view> {
   pub(crate) fn ban_unknown_rule_code(&mut self) -> Vec<LintDiagnostic> {
     let builtin_all_rule_codes: HashSet<&'static str> =
       get_all_rules().iter().map(|r| r.code()).collect();
-    let is_unknown_rule =
-      |code: &&String| !builtin_all_rule_codes.contains(code.as_str());
+    let is_unknown_rule = |code: &&String| {
+      !builtin_all_rule_codes.contains(code.as_str())
+        && !self.plugin_rule_codes.contains(code.as_str())
+    };
 
     let mut diagnostics = Vec::new();
 
@@ -317,14 +549,57 @@ impl<'view> Context<'view> {
     self.diagnostics.push(diagnostic);
   }
 
-  pub(crate) fn create_diagnostic(
-    &self,
+  /// Adds a diagnostic along with fixes that can be applied to resolve it.
+  /// A fix may consist of several changes, which must not overlap each
+  /// other. Different fixes may overlap, as only one of them is applied.
+  pub fn add_diagnostic_with_fixes(
+    &mut self,
     range: SourceRange,
     code: impl ToString,
     message: impl ToString,
     maybe_hint: Option<String>,
-  ) -> LintDiagnostic {
-    let time_start = Instant::now();
+    fixes: Vec<LintFix>,
+  ) {
+    debug_assert!(
+      fixes.iter().all(|fix| !fix.has_overlapping_changes()),
+      "the changes of a fix must not overlap"
+    );
+    let mut diagnostic =
+      self.create_diagnostic(range, code, message, maybe_hint);
+    diagnostic.fixes = fixes;
+    self.diagnostics.push(diagnostic);
+  }
+
+  /// Adds a diagnostic along with other locations relevant to it. Locations
+  /// in the linted file can be created with [`Context::related_location`].
+  pub fn add_diagnostic_with_related(
+    &mut self,
+    range: SourceRange,
+    code: impl ToString,
+    message: impl ToString,
+    maybe_hint: Option<String>,
+    related: Vec<LintRelatedLocation>,
+  ) {
+    let mut diagnostic =
+      self.create_diagnostic(range, code, message, maybe_hint);
+    diagnostic.related = related;
+    self.diagnostics.push(diagnostic);
+  }
+
+  /// Creates a location in the linted file that's related to a diagnostic.
+  pub fn related_location(
+    &self,
+    range: SourceRange,
+    message: impl ToString,
+  ) -> LintRelatedLocation {
+    LintRelatedLocation {
+      specifier: None,
+      range: self.lint_range(range),
+      message: message.to_string(),
+    }
+  }
+
+  fn lint_range(&self, range: SourceRange) -> Range {
     let text_info = self.text_info();
     let start = Position::new(
       range.start.as_byte_index(text_info.range().start),
@@ -334,13 +609,27 @@ impl<'view> Context<'view> {
       range.end.as_byte_index(text_info.range().start),
       text_info.line_and_column_index(range.end),
     );
+    Range { start, end }
+  }
 
+  pub(crate) fn create_diagnostic(
+    &self,
+    range: SourceRange,
+    code: impl ToString,
+    message: impl ToString,
+    maybe_hint: Option<String>,
+  ) -> LintDiagnostic {
+    let time_start = Instant::now();
     let diagnostic = LintDiagnostic {
-      range: Range { start, end },
+      range: self.lint_range(range),
       filename: self.file_name().to_string(),
       message: message.to_string(),
+      original_message: None,
       code: code.to_string(),
       hint: maybe_hint,
+      severity: Severity::Error,
+      fixes: Vec::new(),
+      related: Vec::new(),
     };
 
     let time_end = Instant::now();
@@ -381,3 +670,607 @@ impl TraverseFlow {
     stop
   }
 }
+
+/// A node that has a function body.
+#[derive(Clone, Copy)]
+pub enum FunctionLike<'a> {
+  /// A function declaration or expression, or a method
+  Function(&'a ast_view::Function<'a>),
+  Arrow(&'a ast_view::ArrowExpr<'a>),
+  Constructor(&'a ast_view::Constructor<'a>),
+  /// A getter in an object literal
+  Getter(&'a ast_view::GetterProp<'a>),
+  /// A setter in an object literal
+  Setter(&'a ast_view::SetterProp<'a>),
+}
+
+impl<'a> FunctionLike<'a> {
+  pub fn from_node(node: ast_view::Node<'a>) -> Option<Self> {
+    use deno_ast::view::Node;
+    match node {
+      Node::Function(function) => Some(Self::Function(function)),
+      Node::ArrowExpr(arrow) => Some(Self::Arrow(arrow)),
+      Node::Constructor(constructor) => Some(Self::Constructor(constructor)),
+      Node::GetterProp(getter) => Some(Self::Getter(getter)),
+      Node::SetterProp(setter) => Some(Self::Setter(setter)),
+      _ => None,
+    }
+  }
+
+  pub fn as_node(&self) -> ast_view::Node<'a> {
+    use deno_ast::view::NodeTrait;
+    match self {
+      Self::Function(function) => function.as_node(),
+      Self::Arrow(arrow) => arrow.as_node(),
+      Self::Constructor(constructor) => constructor.as_node(),
+      Self::Getter(getter) => getter.as_node(),
+      Self::Setter(setter) => setter.as_node(),
+    }
+  }
+
+  pub fn is_arrow(&self) -> bool {
+    matches!(self, Self::Arrow(_))
+  }
+
+  pub fn is_async(&self) -> bool {
+    match self {
+      Self::Function(function) => function.is_async(),
+      Self::Arrow(arrow) => arrow.is_async(),
+      Self::Constructor(_) | Self::Getter(_) | Self::Setter(_) => false,
+    }
+  }
+}
+
+fn enclosing_function(
+  node: ast_view::Node,
+  include_arrows: bool,
+) -> Option<FunctionLike> {
+  use deno_ast::view::NodeTrait;
+  node
+    .ancestors()
+    .filter_map(FunctionLike::from_node)
+    .find(|function| include_arrows || !function.is_arrow())
+}
+
+fn tokens_in(
+  program: ast_view::Program,
+  range: SourceRange,
+) -> &[TokenAndSpan] {
+  program
+    .token_container()
+    .get_tokens_in_range(range.start, range.end)
+}
+
+pub(crate) fn is_loop(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  matches!(
+    node,
+    Node::ForStmt(_)
+      | Node::ForInStmt(_)
+      | Node::ForOfStmt(_)
+      | Node::WhileStmt(_)
+      | Node::DoWhileStmt(_)
+  )
+}
+
+/// The ancestors of the node up to, and not including, the nearest function
+/// or static block.
+fn ancestors_in_function(
+  node: ast_view::Node,
+) -> impl Iterator<Item = ast_view::Node> {
+  use deno_ast::view::{Node, NodeTrait};
+  node.ancestors().take_while(|ancestor| {
+    FunctionLike::from_node(*ancestor).is_none()
+      && !matches!(ancestor, Node::StaticBlock(_))
+  })
+}
+
+fn enclosing_loop(node: ast_view::Node) -> Option<ast_view::Node> {
+  ancestors_in_function(node).find(|ancestor| is_loop(*ancestor))
+}
+
+fn loop_for_label<'a>(
+  node: ast_view::Node<'a>,
+  label: &str,
+) -> Option<ast_view::Node<'a>> {
+  use deno_ast::view::{Node, NodeTrait};
+  let mut body =
+    ancestors_in_function(node).find_map(|ancestor| match ancestor {
+      Node::LabeledStmt(labeled) if labeled.label.sym() == label => {
+        Some(labeled.body.as_node())
+      }
+      _ => None,
+    })?;
+  while let Node::LabeledStmt(labeled) = body {
+    body = labeled.body.as_node();
+  }
+  is_loop(body).then_some(body)
+}
+
+fn nearest_ancestor<'a, T: ast_view::CastableNode<'a>>(
+  node: ast_view::Node<'a>,
+) -> Option<&'a T> {
+  use deno_ast::view::NodeTrait;
+  node.ancestors().find_map(|ancestor| ancestor.to::<T>())
+}
+
+fn is_strict(node: ast_view::Node) -> bool {
+  use deno_ast::view::{BlockStmtOrExpr, Node, NodeTrait};
+  std::iter::once(node)
+    .chain(node.ancestors())
+    .any(|node| match node {
+      Node::Module(_) | Node::Class(_) => true,
+      Node::Script(script) => has_use_strict_directive(&script.body),
+      Node::Function(function) => function
+        .body
+        .map_or(false, |body| has_use_strict_directive(&body.stmts)),
+      Node::ArrowExpr(arrow) => match arrow.body {
+        BlockStmtOrExpr::BlockStmt(body) => {
+          has_use_strict_directive(&body.stmts)
+        }
+        BlockStmtOrExpr::Expr(_) => false,
+      },
+      Node::GetterProp(getter) => getter
+        .body
+        .map_or(false, |body| has_use_strict_directive(&body.stmts)),
+      Node::SetterProp(setter) => setter
+        .body
+        .map_or(false, |body| has_use_strict_directive(&body.stmts)),
+      _ => false,
+    })
+}
+
+/// Checks the directive prologue, i.e. the string literal statements at the
+/// start of a body, for `"use strict"`. Directives can't contain escapes.
+fn has_use_strict_directive(stmts: &[ast_view::Stmt]) -> bool {
+  use deno_ast::view::{Expr, Lit, Stmt};
+  stmts
+    .iter()
+    .map_while(|stmt| match stmt {
+      Stmt::Expr(expr_stmt) => match expr_stmt.expr {
+        Expr::Lit(Lit::Str(str)) => Some(str),
+        _ => None,
+      },
+      _ => None,
+    })
+    .any(|str| {
+      matches!(
+        str.raw().as_deref(),
+        Some("\"use strict\"") | Some("'use strict'")
+      )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_util::parse;
+  use deno_ast::view::{Node, NodeTrait};
+
+  /// Checks strictness at each `target` identifier in the source.
+  fn targets_strictness(program: ast_view::Program) -> Vec<bool> {
+    fn visit(node: Node, result: &mut Vec<bool>) {
+      if let Node::Ident(ident) = node {
+        if ident.sym() == "target" {
+          result.push(is_strict(node));
+        }
+      }
+      for child in node.children() {
+        visit(child, result);
+      }
+    }
+    let mut result = Vec::new();
+    visit(program.as_node(), &mut result);
+    result
+  }
+
+  /// Sources without imports or exports are parsed as scripts.
+  fn script_strictness(source_code: &str) -> Vec<bool> {
+    parse(source_code).with_view(targets_strictness)
+  }
+
+  #[test]
+  fn is_strict_in_modules() {
+    let parsed_source = parse("import foo from './foo.ts'; target;");
+    assert_eq!(parsed_source.with_view(targets_strictness), vec![true]);
+  }
+
+  #[test]
+  fn is_strict_in_scripts() {
+    assert_eq!(script_strictness("target;"), vec![false]);
+    assert_eq!(script_strictness("'use strict'; target;"), vec![true]);
+    assert_eq!(
+      script_strictness("'foo'; \"use strict\"; target;"),
+      vec![true]
+    );
+    // Not part of the directive prologue, or not a directive at all
+    assert_eq!(
+      script_strictness("foo(); 'use strict'; target;"),
+      vec![false]
+    );
+    assert_eq!(script_strictness("('use strict'); target;"), vec![false]);
+    assert_eq!(script_strictness("'use\\x20strict'; target;"), vec![false]);
+  }
+
+  #[test]
+  fn is_strict_in_functions() {
+    assert_eq!(
+      script_strictness("function f(target) { 'use strict'; target; } target;"),
+      vec![true, true, false]
+    );
+    assert_eq!(
+      script_strictness("'use strict'; function f() { return () => target; }"),
+      vec![true]
+    );
+    assert_eq!(
+      script_strictness(
+        "const f = () => { 'use strict'; return () => target; }; target;"
+      ),
+      vec![true, false]
+    );
+    assert_eq!(
+      script_strictness(
+        "const o = { get a() { 'use strict'; return target; } }; target;"
+      ),
+      vec![true, false]
+    );
+  }
+
+  /// Describes the enclosing function at each `target` identifier in the
+  /// source.
+  fn targets_enclosing_functions(
+    source_code: &str,
+    include_arrows: bool,
+  ) -> Vec<Option<&'static str>> {
+    fn visit(node: Node, include_arrows: bool, result: &mut Vec<Option<&str>>) {
+      if let Node::Ident(ident) = node {
+        if ident.sym() == "target" {
+          let function = enclosing_function(node, include_arrows);
+          result.push(function.map(|function| match function {
+            FunctionLike::Function(function) if function.is_async() => {
+              "async function"
+            }
+            FunctionLike::Function(_) => "function",
+            FunctionLike::Arrow(_) => "arrow",
+            FunctionLike::Constructor(_) => "constructor",
+            FunctionLike::Getter(_) => "getter",
+            FunctionLike::Setter(_) => "setter",
+          }));
+        }
+      }
+      for child in node.children() {
+        visit(child, include_arrows, result);
+      }
+    }
+    parse(source_code).with_view(|program| {
+      let mut result = Vec::new();
+      visit(program.as_node(), include_arrows, &mut result);
+      result
+    })
+  }
+
+  #[test]
+  fn enclosing_function_of_nested_functions() {
+    let source_code =
+      "target; function f() { target; async function g() { target; } target; }";
+    assert_eq!(
+      targets_enclosing_functions(source_code, true),
+      vec![
+        None,
+        Some("function"),
+        Some("async function"),
+        Some("function")
+      ]
+    );
+    assert_eq!(
+      targets_enclosing_functions(
+        "class A { constructor() { target; } m() { target; } static x = target; }",
+        true
+      ),
+      vec![Some("constructor"), Some("function"), None]
+    );
+    assert_eq!(
+      targets_enclosing_functions(
+        "const o = { get a() { return target; }, set a(v) { target; } };",
+        true
+      ),
+      vec![Some("getter"), Some("setter")]
+    );
+  }
+
+  #[test]
+  fn enclosing_function_of_arrows() {
+    let source_code =
+      "const f = () => target; function g() { return () => () => target; }";
+    assert_eq!(
+      targets_enclosing_functions(source_code, true),
+      vec![Some("arrow"), Some("arrow")]
+    );
+    assert_eq!(
+      targets_enclosing_functions(source_code, false),
+      vec![None, Some("function")]
+    );
+    // Class field initializers and static blocks aren't functions
+    assert_eq!(
+      targets_enclosing_functions(
+        "function f() { class A { x = () => target; static { target; } } }",
+        false
+      ),
+      vec![Some("function"), Some("function")]
+    );
+  }
+
+  #[test]
+  fn ancestors_of_nested_node() {
+    parse("function f() { foo(bar, () => target); }").with_view(|program| {
+      fn find_target(node: Node) -> Option<Node> {
+        match node {
+          Node::Ident(ident) if ident.sym() == "target" => Some(node),
+          _ => node.children().into_iter().find_map(find_target),
+        }
+      }
+      let target = find_target(program.as_node()).unwrap();
+
+      let kinds = target
+        .ancestors()
+        .map(|ancestor| ancestor.kind().to_string())
+        .collect::<Vec<_>>();
+      assert_eq!(
+        kinds,
+        vec![
+          "ArrowExpr",
+          "ExprOrSpread",
+          "CallExpr",
+          "ExprStmt",
+          "BlockStmt",
+          "Function",
+          "FnDecl",
+          "Script",
+        ]
+      );
+
+      let call = nearest_ancestor::<ast_view::CallExpr>(target).unwrap();
+      assert_eq!(call.args.len(), 2);
+      assert!(nearest_ancestor::<ast_view::ArrowExpr>(target).is_some());
+      assert!(nearest_ancestor::<ast_view::Class>(target).is_none());
+      // The node itself isn't counted
+      let arrow = target.parent().unwrap();
+      assert!(nearest_ancestor::<ast_view::ArrowExpr>(arrow).is_none());
+    });
+  }
+
+  /// Finds the loop of each `target` identifier in the source, as the byte
+  /// index of the loop's start.
+  fn targets_loops(
+    source_code: &str,
+    find_loop: impl for<'a> Fn(Node<'a>) -> Option<Node<'a>>,
+  ) -> Vec<Option<usize>> {
+    fn visit<'a>(
+      node: Node<'a>,
+      find_loop: &impl Fn(Node<'a>) -> Option<Node<'a>>,
+      result: &mut Vec<Option<Node<'a>>>,
+    ) {
+      if let Node::Ident(ident) = node {
+        if ident.sym() == "target" {
+          result.push(find_loop(node));
+        }
+      }
+      for child in node.children() {
+        visit(child, find_loop, result);
+      }
+    }
+    parse(source_code).with_view(|program| {
+      let mut loops = Vec::new();
+      visit(program.as_node(), &find_loop, &mut loops);
+      loops
+        .into_iter()
+        .map(|node| node.map(|node| node.start() - program.start()))
+        .collect()
+    })
+  }
+
+  #[test]
+  fn enclosing_loop_of_nested_loops() {
+    let source_code = "target; for (;;) { target; while (a) { do { target; } while (b); target; } }";
+    let for_loop = source_code.find("for").unwrap();
+    let while_loop = source_code.find("while").unwrap();
+    let do_loop = source_code.find("do").unwrap();
+    assert_eq!(
+      targets_loops(source_code, enclosing_loop),
+      vec![None, Some(for_loop), Some(do_loop), Some(while_loop)]
+    );
+
+    let source_code =
+      "for (const a of b) { label: { target; } if (c) target; }";
+    assert_eq!(
+      targets_loops(source_code, enclosing_loop),
+      vec![Some(0), Some(0)]
+    );
+    // The loop is outside of the function
+    assert_eq!(
+      targets_loops(
+        "for (const a in b) { () => { target; }; class A { static { target; } } }",
+        enclosing_loop
+      ),
+      vec![None, None]
+    );
+  }
+
+  #[test]
+  fn loop_for_label_of_labeled_loops() {
+    let source_code = "outer: for (;;) { inner: while (a) { target; } }";
+    let inner_loop = source_code.find("while").unwrap();
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "outer")),
+      vec![Some(7)]
+    );
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "inner")),
+      vec![Some(inner_loop)]
+    );
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "other")),
+      vec![None]
+    );
+
+    // Both labels refer to the loop
+    let source_code = "a: b: for (const c of d) { target; }";
+    let for_loop = source_code.find("for").unwrap();
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "a")),
+      vec![Some(for_loop)]
+    );
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "b")),
+      vec![Some(for_loop)]
+    );
+
+    // Each label refers to its own loop, whatever kind of loop it is
+    let source_code = "a: for (;;) { b: do { target; } while (c); }";
+    let do_loop = source_code.find("do").unwrap();
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "a")),
+      vec![Some(3)]
+    );
+    assert_eq!(
+      targets_loops(source_code, |node| loop_for_label(node, "b")),
+      vec![Some(do_loop)]
+    );
+
+    // Not a loop, or outside of the function
+    assert_eq!(
+      targets_loops("a: { for (;;) { target; } }", |node| {
+        loop_for_label(node, "a")
+      }),
+      vec![None]
+    );
+    assert_eq!(
+      targets_loops("a: for (;;) { () => { target; }; }", |node| {
+        loop_for_label(node, "a")
+      }),
+      vec![None]
+    );
+  }
+
+  #[test]
+  fn tokens_in_expression() {
+    use deno_ast::swc::parser::token::{BinOpToken, Token};
+    use deno_ast::view::{Expr, Stmt};
+
+    use deno_ast::SourceRangedForSpanned;
+
+    let parsed_source = parse("foo; a + (b * 1);");
+    parsed_source.with_view(|program| {
+      let ast_view::Program::Script(script) = program else {
+        unreachable!();
+      };
+      let Stmt::Expr(expr_stmt) = script.body[1] else {
+        unreachable!();
+      };
+      let text_of = |token: &TokenAndSpan| {
+        token
+          .range()
+          .text_fast(parsed_source.text_info())
+          .to_string()
+      };
+
+      let tokens = tokens_in(program, expr_stmt.expr.range());
+      assert_eq!(
+        tokens.iter().map(text_of).collect::<Vec<_>>(),
+        vec!["a", "+", "(", "b", "*", "1", ")"]
+      );
+      assert_eq!(tokens[1].token, Token::BinOp(BinOpToken::Add));
+      assert!(matches!(tokens[5].token, Token::Num { .. }));
+      // The statement includes the semicolon
+      let tokens = tokens_in(program, expr_stmt.range());
+      assert_eq!(tokens.last().unwrap().token, Token::Semi);
+
+      let Expr::Bin(bin_expr) = expr_stmt.expr else {
+        unreachable!();
+      };
+      let tokens = tokens_in(program, bin_expr.right.range());
+      assert_eq!(
+        tokens.iter().map(text_of).collect::<Vec<_>>(),
+        vec!["(", "b", "*", "1", ")"]
+      );
+    });
+  }
+
+  #[test]
+  fn is_strict_in_classes() {
+    assert_eq!(
+      script_strictness(
+        "class A extends target { m() { target; } static x = target; } target;"
+      ),
+      vec![true, true, true, false]
+    );
+    assert_eq!(
+      script_strictness("const A = class { m() { return target; } };"),
+      vec![true]
+    );
+  }
+
+  /// Reports every `window` identifier, with a message telling whether it's
+  /// global and what kind of binding it refers to otherwise.
+  #[derive(Debug)]
+  struct WindowBindings;
+
+  impl LintRule for WindowBindings {
+    fn lint_program_with_ast_view(
+      &self,
+      context: &mut Context,
+      program: ast_view::Program,
+    ) {
+      fn visit(node: Node, ctx: &mut Context) {
+        if let Node::Ident(ident) = node {
+          if ident.sym() == "window" {
+            let message = if ctx.is_global_ident(ident) {
+              "global".to_string()
+            } else {
+              let binding = ctx.binding_of(&ident.inner.to_id()).unwrap();
+              format!("{:?}", binding.kind())
+            };
+            ctx.add_diagnostic(ident.range(), "window-bindings", message);
+          }
+        }
+        for child in node.children() {
+          visit(child, ctx);
+        }
+      }
+      visit(program.as_node(), context);
+    }
+
+    fn code(&self) -> &'static str {
+      "window-bindings"
+    }
+
+    #[cfg(feature = "docs")]
+    fn docs(&self) -> &'static str {
+      ""
+    }
+  }
+
+  fn window_bindings(source: &str) -> Vec<String> {
+    crate::test_util::lint(&WindowBindings, source, "foo.ts")
+      .into_iter()
+      .map(|diagnostic| diagnostic.message)
+      .collect()
+  }
+
+  #[test]
+  fn is_global_ident_and_binding_of() {
+    assert_eq!(window_bindings("window.alert();"), vec!["global"]);
+    assert_eq!(
+      window_bindings("const window = {}; window.alert();"),
+      vec!["Const", "Const"]
+    );
+    assert_eq!(
+      window_bindings("function f(window) { window.alert(); } window.alert();"),
+      vec!["Param", "Param", "global"]
+    );
+    assert_eq!(
+      window_bindings("import { window } from './mod.ts'; window.alert();"),
+      vec!["ValueImport", "ValueImport"]
+    );
+  }
+}
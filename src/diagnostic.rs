@@ -1,17 +1,39 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use crate::rules::{get_rule_by_code, LintRule};
+use crate::sort_imports;
+use deno_ast::MediaType;
+use deno_ast::SourceRange;
+use deno_ast::StartSourcePos;
+use deno_ast::TextChange;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub mod baseline;
+pub mod compact;
+pub mod github;
+pub mod json;
+pub mod junit;
+pub mod sarif;
+
+pub use json::to_json;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Position {
-  /// The 0-indexed line index.
+  /// The 0-indexed line index. `\r\n` counts as a single line terminator.
   #[serde(rename(serialize = "line"))]
   #[serde(serialize_with = "to_one_indexed")]
   pub line_index: usize,
   /// The 0-indexed column index.
   #[serde(rename(serialize = "col"))]
   pub column_index: usize,
+  /// The 0-indexed byte index, not counting a byte order mark at the start
+  /// of the file.
   #[serde(rename(serialize = "bytePos"))]
   pub byte_index: usize,
 }
@@ -33,17 +55,795 @@ where
   s.serialize_u32((x + 1) as u32)
 }
 
+/// How severe a diagnostic is, so consumers can e.g. only fail a build on
+/// errors.
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+  Warning,
+  #[default]
+  Error,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Range {
   pub start: Position,
   pub end: Position,
 }
 
+/// A single text edit of a fix: replaces the text in `range` with `new_text`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFixChange {
+  pub new_text: Cow<'static, str>,
+  #[serde(serialize_with = "to_byte_range")]
+  pub range: SourceRange,
+}
+
+/// A fix for a diagnostic that can be applied to the source text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LintFix {
+  /// Short description of what the fix does, e.g. "Remove `String.raw`".
+  pub description: Cow<'static, str>,
+  pub changes: Vec<LintFixChange>,
+}
+
+impl LintFix {
+  /// The range covering all changes of the fix, or `None` if it has none.
+  pub fn range(&self) -> Option<SourceRange> {
+    let start = self.changes.iter().map(|change| change.range.start).min()?;
+    let end = self.changes.iter().map(|change| change.range.end).max()?;
+    Some(SourceRange::new(start, end))
+  }
+
+  /// Whether any change of the fix overlaps a change of the other fix, in
+  /// which case they can't both be applied.
+  pub fn overlaps(&self, other: &LintFix) -> bool {
+    self
+      .conflicting_change(other.changes.iter().map(|c| &c.range))
+      .is_some()
+  }
+
+  /// Whether two changes of the fix overlap, which makes it impossible to
+  /// apply.
+  pub fn has_overlapping_changes(&self) -> bool {
+    self.overlapping_change().is_some()
+  }
+
+  /// The first of `ranges` that overlaps a change of the fix.
+  fn conflicting_change<'a>(
+    &self,
+    mut ranges: impl Iterator<Item = &'a SourceRange>,
+  ) -> Option<SourceRange> {
+    ranges
+      .find(|range| {
+        self
+          .changes
+          .iter()
+          .any(|change| overlaps(&change.range, range))
+      })
+      .copied()
+  }
+
+  /// The first change that overlaps an earlier change of the fix.
+  fn overlapping_change(&self) -> Option<SourceRange> {
+    self.changes.iter().enumerate().find_map(|(i, change)| {
+      self.changes[..i]
+        .iter()
+        .any(|earlier| overlaps(&earlier.range, &change.range))
+        .then_some(change.range)
+    })
+  }
+}
+
+fn to_byte_range<S>(range: &SourceRange, s: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  range
+    .as_byte_range(StartSourcePos::START_SOURCE_POS)
+    .serialize(s)
+}
+
+/// Another location that's relevant to a diagnostic, e.g. where a conflicting
+/// declaration is.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LintRelatedLocation {
+  /// Specifier of the file the location is in, if it isn't the file of the
+  /// diagnostic. The linter only sees one file at a time, so such a location
+  /// is purely informational and has to be supplied by whoever reports it.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub specifier: Option<String>,
+  pub range: Range,
+  pub message: String,
+}
+
+impl LintRelatedLocation {
+  /// The file the location is in, given the filename of its diagnostic.
+  pub fn filename<'a>(&'a self, diagnostic_filename: &'a str) -> &'a str {
+    self.specifier.as_deref().unwrap_or(diagnostic_filename)
+  }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct LintDiagnostic {
   pub range: Range,
   pub filename: String,
   pub message: String,
+  /// The message reported by the rule, if `message` was replaced or built
+  /// from a template in the config.
+  #[serde(rename = "originalMessage", skip_serializing_if = "Option::is_none")]
+  pub original_message: Option<String>,
   pub code: String,
   pub hint: Option<String>,
+  /// `Error` unless the config overrides the severity of the rule.
+  pub severity: Severity,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub fixes: Vec<LintFix>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related: Vec<LintRelatedLocation>,
+}
+
+/// Splits the byte order mark, if there's one, off the start of `source`.
+/// Byte indexes of diagnostics and fixes are relative to the rest.
+pub(crate) fn split_bom(source: &str) -> (&str, &str) {
+  match source.strip_prefix('\u{FEFF}') {
+    Some(rest) => (&source[..source.len() - rest.len()], rest),
+    None => ("", source),
+  }
+}
+
+/// The text of the position's line before the position, or `None` if the
+/// position isn't in `source`.
+pub(crate) fn line_before<'a>(
+  source: &'a str,
+  position: &Position,
+) -> Option<&'a str> {
+  let (_, source) = split_bom(source);
+  let before = source.get(..position.byte_index)?;
+  let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+  Some(&before[line_start..])
+}
+
+impl LintDiagnostic {
+  /// The message reported by the rule, without any override or message
+  /// template applied.
+  pub fn untemplated_message(&self) -> &str {
+    self.original_message.as_deref().unwrap_or(&self.message)
+  }
+
+  /// The builtin rule that reported the diagnostic, or `None` if the code
+  /// doesn't belong to a builtin rule.
+  pub fn rule(&self) -> Option<&'static dyn LintRule> {
+    get_rule_by_code(&self.code)
+  }
+}
+
+/// Merges diagnostics that share both their range and rule code into one.
+///
+/// The first diagnostic's message is kept, and the distinct hints of all
+/// merged diagnostics are joined with newlines. Diagnostics with different
+/// codes are never merged. The order of the remaining diagnostics is kept.
+pub fn merge_duplicate_diagnostics(
+  diagnostics: Vec<LintDiagnostic>,
+) -> Vec<LintDiagnostic> {
+  let mut merged: Vec<(LintDiagnostic, Vec<String>)> = Vec::new();
+  let mut indexes: HashMap<(String, String, usize, usize), usize> =
+    HashMap::new();
+
+  for mut diagnostic in diagnostics {
+    let key = (
+      diagnostic.filename.clone(),
+      diagnostic.code.clone(),
+      diagnostic.range.start.byte_index,
+      diagnostic.range.end.byte_index,
+    );
+    match indexes.get(&key) {
+      Some(&index) => {
+        let (existing, hints) = &mut merged[index];
+        if let Some(hint) = diagnostic.hint {
+          if !hints.contains(&hint) {
+            hints.push(hint);
+          }
+        }
+        for fix in diagnostic.fixes {
+          if !existing.fixes.contains(&fix) {
+            existing.fixes.push(fix);
+          }
+        }
+        for related in diagnostic.related {
+          if !existing.related.contains(&related) {
+            existing.related.push(related);
+          }
+        }
+      }
+      None => {
+        indexes.insert(key, merged.len());
+        let hints = diagnostic.hint.take().into_iter().collect();
+        merged.push((diagnostic, hints));
+      }
+    }
+  }
+
+  merged
+    .into_iter()
+    .map(|(mut diagnostic, hints)| {
+      if !hints.is_empty() {
+        diagnostic.hint = Some(hints.join("\n"));
+      }
+      diagnostic
+    })
+    .collect()
+}
+
+/// Keeps the first `max` diagnostics of each rule code per file, in the order
+/// they're given in. If more were reported, the first dropped diagnostic is
+/// replaced by a summary saying how many were dropped, which keeps its range
+/// and severity but has no fixes. A `max` of zero keeps all diagnostics.
+pub fn limit_diagnostics_per_rule(
+  diagnostics: Vec<LintDiagnostic>,
+  max: usize,
+) -> Vec<LintDiagnostic> {
+  if max == 0 {
+    return diagnostics;
+  }
+
+  let mut counts: HashMap<(String, String), usize> = HashMap::new();
+  // The index of the summary of each rule whose diagnostics were dropped
+  let mut summaries: Vec<((String, String), usize)> = Vec::new();
+  let mut limited = Vec::new();
+  for diagnostic in diagnostics {
+    let key = (diagnostic.filename.clone(), diagnostic.code.clone());
+    let count = counts.entry(key.clone()).or_default();
+    *count += 1;
+    if *count <= max {
+      limited.push(diagnostic);
+    } else if *count == max + 1 {
+      summaries.push((key, limited.len()));
+      limited.push(LintDiagnostic {
+        hint: Some(format!(
+          "Only the first {} diagnostics of each rule are reported per file",
+          max
+        )),
+        original_message: None,
+        fixes: Vec::new(),
+        related: Vec::new(),
+        ..diagnostic
+      });
+    }
+  }
+
+  for (key, index) in summaries {
+    let suppressed = counts[&key] - max;
+    let summary = &mut limited[index];
+    summary.message = format!(
+      "{} more {} of {} suppressed",
+      suppressed,
+      if suppressed == 1 {
+        "occurrence"
+      } else {
+        "occurrences"
+      },
+      summary.code
+    );
+  }
+  limited
+}
+
+/// Groups diagnostics by their rule code, with the codes in alphabetical
+/// order.
+///
+/// Within each group the diagnostics keep the order they're given in, so
+/// groups built from the sorted output of the linter are in source order.
+pub fn group_diagnostics_by_code(
+  diagnostics: &[LintDiagnostic],
+) -> BTreeMap<&str, Vec<&LintDiagnostic>> {
+  let mut groups: BTreeMap<&str, Vec<&LintDiagnostic>> = BTreeMap::new();
+  for diagnostic in diagnostics {
+    groups
+      .entry(diagnostic.code.as_str())
+      .or_default()
+      .push(diagnostic);
+  }
+  groups
+}
+
+/// A fix that [`apply_fixes`] applied.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AppliedFix {
+  /// The rule code of the diagnostic the fix belongs to.
+  pub code: String,
+  pub fix: LintFix,
+}
+
+/// A fix that [`apply_fixes`] skipped because it overlaps another fix.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFix {
+  /// The rule code of the diagnostic the fix belongs to.
+  pub code: String,
+  pub fix: LintFix,
+  /// The range of the applied change that this fix overlaps, or of its own
+  /// change that overlaps another one of its changes.
+  #[serde(serialize_with = "to_byte_range")]
+  pub conflicting_range: SourceRange,
+}
+
+/// What happened to each fix in a run of [`apply_fixes`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixRunReport {
+  pub applied: Vec<AppliedFix>,
+  pub skipped: Vec<SkippedFix>,
+  /// Whether linting and fixing the new source text again may apply more
+  /// fixes, which is the case when any fix was skipped.
+  pub rerun_recommended: bool,
+  /// Whether the imports at the start of the file were sorted after applying
+  /// the fixes. See [`FixOptions::sort_imports`].
+  pub imports_sorted: bool,
+}
+
+/// Options for [`apply_fixes_with_options`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixOptions {
+  /// Sort the import declarations at the start of the file by their
+  /// specifiers if any applied fix changed them. Blank lines and imports
+  /// without bindings split them into groups that are sorted separately, and
+  /// comments above an import move with it.
+  pub sort_imports: bool,
+}
+
+fn overlaps(a: &SourceRange, b: &SourceRange) -> bool {
+  // Two insertions at the same position conflict too, as their order would
+  // be arbitrary
+  a == b || (a.start < b.end && b.start < a.end)
+}
+
+/// Applies the first fix of each diagnostic to the source text the
+/// diagnostics were reported for.
+///
+/// Fixes are applied in source order. A fix with a change that overlaps a
+/// change of one that's already applied is skipped, and the returned report
+/// says which change it conflicts with. Changes of a fix may be far apart, and
+/// other fixes between them are still applied. Returns the new source text
+/// along with the report.
+pub fn apply_fixes(
+  source: &str,
+  diagnostics: &[LintDiagnostic],
+) -> (String, FixRunReport) {
+  apply_fixes_with_options(source, diagnostics, FixOptions::default())
+}
+
+/// Like [`apply_fixes`], with additional cleanups after applying the fixes.
+pub fn apply_fixes_with_options(
+  source: &str,
+  diagnostics: &[LintDiagnostic],
+  options: FixOptions,
+) -> (String, FixRunReport) {
+  let mut fixes = diagnostics
+    .iter()
+    .filter_map(|diagnostic| {
+      let fix = diagnostic.fixes.first()?;
+      Some((fix.range()?, diagnostic.code.as_str(), fix))
+    })
+    .collect::<Vec<_>>();
+  // The sort is stable, so fixes starting at the same position keep the
+  // order of their diagnostics
+  fixes.sort_by_key(|(range, _, _)| range.start);
+
+  let mut applied_ranges: Vec<SourceRange> = Vec::new();
+  let mut report = FixRunReport::default();
+  for (_, code, fix) in fixes {
+    let conflict = fix
+      .overlapping_change()
+      .or_else(|| fix.conflicting_change(applied_ranges.iter()));
+    match conflict {
+      Some(conflicting_range) => report.skipped.push(SkippedFix {
+        code: code.to_string(),
+        fix: fix.clone(),
+        conflicting_range,
+      }),
+      None => {
+        applied_ranges.extend(fix.changes.iter().map(|change| change.range));
+        report.applied.push(AppliedFix {
+          code: code.to_string(),
+          fix: fix.clone(),
+        });
+      }
+    }
+  }
+  report.rerun_recommended = !report.skipped.is_empty();
+
+  let changes = report
+    .applied
+    .iter()
+    .flat_map(|applied| &applied.fix.changes)
+    .map(|change| TextChange {
+      range: change.range.as_byte_range(StartSourcePos::START_SOURCE_POS),
+      new_text: change.new_text.to_string(),
+    })
+    .collect::<Vec<_>>();
+
+  let (bom, source) = split_bom(source);
+  let file_name = diagnostics.first().map_or("", |d| d.filename.as_str());
+  let media_type = MediaType::from_path(Path::new(file_name));
+  let touches_imports = options.sort_imports
+    && sort_imports::import_block_range(file_name, media_type, source).map_or(
+      false,
+      |imports| {
+        changes.iter().any(|change| {
+          change.range.start <= imports.end && imports.start <= change.range.end
+        })
+      },
+    );
+
+  let mut text = deno_ast::apply_text_changes(source, changes);
+  if touches_imports {
+    if let Some(sorted) =
+      sort_imports::sort_import_block(file_name, media_type, &text)
+    {
+      text = sorted;
+      report.imports_sorted = true;
+    }
+  }
+  (format!("{}{}", bom, text), report)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn diagnostic(
+    code: &str,
+    start: usize,
+    end: usize,
+    message: &str,
+    hint: Option<&str>,
+  ) -> LintDiagnostic {
+    let position = |byte_index| Position {
+      line_index: 0,
+      column_index: byte_index,
+      byte_index,
+    };
+    LintDiagnostic {
+      range: Range {
+        start: position(start),
+        end: position(end),
+      },
+      filename: "file.ts".to_string(),
+      message: message.to_string(),
+      original_message: None,
+      code: code.to_string(),
+      hint: hint.map(ToString::to_string),
+      severity: Severity::Error,
+      fixes: Vec::new(),
+      related: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn finds_rule_of_diagnostic() {
+    use crate::rules::no_var::NoVar;
+    let diagnostics = crate::test_util::lint(&NoVar, "var a;", "foo.ts");
+    let rule = diagnostics[0].rule().unwrap();
+    assert_eq!(rule.code(), "no-var");
+    assert_eq!(rule.tags(), &["recommended"]);
+
+    assert!(diagnostic("some-plugin-rule", 0, 3, "message", None)
+      .rule()
+      .is_none());
+  }
+
+  #[test]
+  fn merges_diagnostics_with_same_range_and_code() {
+    let merged = merge_duplicate_diagnostics(vec![
+      diagnostic("foo", 0, 3, "first", Some("hint a")),
+      diagnostic("foo", 0, 3, "second", Some("hint b")),
+      diagnostic("foo", 0, 3, "third", Some("hint a")),
+      diagnostic("foo", 0, 3, "fourth", None),
+    ]);
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].message, "first");
+    assert_eq!(merged[0].hint.as_deref(), Some("hint a\nhint b"));
+
+    let merged = merge_duplicate_diagnostics(vec![
+      diagnostic("foo", 0, 3, "first", None),
+      diagnostic("foo", 0, 3, "second", Some("hint")),
+    ]);
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].message, "first");
+    assert_eq!(merged[0].hint.as_deref(), Some("hint"));
+  }
+
+  #[test]
+  fn does_not_merge_different_codes_or_ranges() {
+    let merged = merge_duplicate_diagnostics(vec![
+      diagnostic("foo", 0, 3, "first", None),
+      diagnostic("bar", 0, 3, "second", None),
+      diagnostic("foo", 0, 4, "third", None),
+      diagnostic("foo", 0, 3, "fourth", None),
+    ]);
+    let messages = merged
+      .iter()
+      .map(|d| d.message.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(messages, vec!["first", "second", "third"]);
+  }
+
+  #[test]
+  fn limits_diagnostics_per_rule() {
+    let diagnostics = vec![
+      diagnostic("foo", 0, 1, "first foo", None),
+      diagnostic("bar", 2, 3, "first bar", None),
+      diagnostic("foo", 4, 5, "second foo", None),
+      diagnostic("foo", 6, 7, "third foo", Some("hint")),
+      diagnostic("bar", 8, 9, "second bar", None),
+      diagnostic("foo", 10, 11, "fourth foo", None),
+      diagnostic("bar", 12, 13, "third bar", None),
+    ];
+
+    let limited = limit_diagnostics_per_rule(diagnostics.clone(), 2);
+    let messages = limited
+      .iter()
+      .map(|d| d.message.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      messages,
+      vec![
+        "first foo",
+        "first bar",
+        "second foo",
+        "2 more occurrences of foo suppressed",
+        "second bar",
+        "1 more occurrence of bar suppressed",
+      ]
+    );
+    assert_eq!(limited[3].code, "foo");
+    assert_eq!(limited[3].range.start.byte_index, 6);
+    assert_eq!(
+      limited[3].hint.as_deref(),
+      Some("Only the first 2 diagnostics of each rule are reported per file")
+    );
+
+    assert_eq!(limit_diagnostics_per_rule(diagnostics.clone(), 0).len(), 7);
+    assert_eq!(limit_diagnostics_per_rule(diagnostics, 3).len(), 7);
+  }
+
+  #[test]
+  fn groups_diagnostics_by_code() {
+    let diagnostics = vec![
+      diagnostic("foo", 0, 1, "first foo", None),
+      diagnostic("bar", 2, 3, "first bar", None),
+      diagnostic("foo", 4, 5, "second foo", None),
+      diagnostic("baz", 6, 7, "first baz", None),
+      diagnostic("bar", 8, 9, "second bar", None),
+    ];
+    let groups = group_diagnostics_by_code(&diagnostics);
+    let groups = groups
+      .iter()
+      .map(|(code, diagnostics)| {
+        let messages = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        (*code, messages)
+      })
+      .collect::<Vec<(&str, Vec<&str>)>>();
+    assert_eq!(
+      groups,
+      vec![
+        ("bar", vec!["first bar", "second bar"]),
+        ("baz", vec!["first baz"]),
+        ("foo", vec!["first foo", "second foo"]),
+      ]
+    );
+  }
+
+  fn fixed_diagnostic(
+    code: &str,
+    start: usize,
+    end: usize,
+    new_text: &'static str,
+  ) -> LintDiagnostic {
+    let mut diagnostic = diagnostic(code, start, end, "", None);
+    diagnostic.fixes = vec![LintFix {
+      description: format!("Fix {}", code).into(),
+      changes: vec![LintFixChange {
+        new_text: new_text.into(),
+        range: SourceRange::new(
+          StartSourcePos::START_SOURCE_POS + start,
+          StartSourcePos::START_SOURCE_POS + end,
+        ),
+      }],
+    }];
+    diagnostic
+  }
+
+  #[test]
+  fn applies_non_overlapping_fixes() {
+    let (text, report) = apply_fixes(
+      "let a = 1; let b = 2;",
+      &[
+        fixed_diagnostic("bar", 11, 14, "const"),
+        diagnostic("baz", 4, 5, "", None),
+        fixed_diagnostic("foo", 0, 3, "const"),
+      ],
+    );
+    assert_eq!(text, "const a = 1; const b = 2;");
+    let codes = report
+      .applied
+      .iter()
+      .map(|applied| applied.code.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(codes, vec!["foo", "bar"]);
+    assert!(report.skipped.is_empty());
+    assert!(!report.rerun_recommended);
+  }
+
+  #[test]
+  fn applies_fixes_after_byte_order_mark() {
+    // Byte indexes don't count the byte order mark
+    let (text, _) = apply_fixes(
+      "\u{FEFF}let a = 1;\r\nlet b = 2;",
+      &[
+        fixed_diagnostic("foo", 0, 3, "const"),
+        fixed_diagnostic("bar", 12, 15, "const"),
+      ],
+    );
+    assert_eq!(text, "\u{FEFF}const a = 1;\r\nconst b = 2;");
+  }
+
+  #[test]
+  fn skips_overlapping_fixes() {
+    let (text, report) = apply_fixes(
+      "abcdefgh",
+      &[
+        fixed_diagnostic("second", 1, 5, "2"),
+        fixed_diagnostic("first", 0, 4, "1"),
+        fixed_diagnostic("third", 3, 6, "3"),
+      ],
+    );
+    assert_eq!(text, "1efgh");
+    assert_eq!(report.applied.len(), 1);
+    assert_eq!(report.applied[0].code, "first");
+
+    let skipped = report
+      .skipped
+      .iter()
+      .map(|skipped| {
+        let range = skipped
+          .conflicting_range
+          .as_byte_range(StartSourcePos::START_SOURCE_POS);
+        (skipped.code.as_str(), range)
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(skipped, vec![("second", 0..4), ("third", 0..4)]);
+    assert!(report.rerun_recommended);
+  }
+
+  #[test]
+  fn insertions_at_same_position_conflict() {
+    let (text, report) = apply_fixes(
+      "foo",
+      &[
+        fixed_diagnostic("first", 0, 0, "a"),
+        fixed_diagnostic("second", 0, 0, "b"),
+        fixed_diagnostic("third", 3, 3, ";"),
+      ],
+    );
+    assert_eq!(text, "afoo;");
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].code, "second");
+  }
+
+  fn change(start: usize, end: usize, new_text: &'static str) -> LintFixChange {
+    LintFixChange {
+      new_text: new_text.into(),
+      range: SourceRange::new(
+        StartSourcePos::START_SOURCE_POS + start,
+        StartSourcePos::START_SOURCE_POS + end,
+      ),
+    }
+  }
+
+  #[test]
+  fn applies_fix_with_multiple_changes() {
+    let mut wrap = diagnostic("wrap", 0, 9, "", None);
+    wrap.fixes = vec![LintFix {
+      description: "Wrap in parens".into(),
+      changes: vec![change(0, 0, "("), change(9, 9, ")")],
+    }];
+    let (text, report) =
+      apply_fixes("a + b * c", &[wrap, fixed_diagnostic("inner", 4, 5, "d")]);
+    assert_eq!(text, "(a + d * c)");
+    assert_eq!(report.applied.len(), 2);
+    assert!(report.skipped.is_empty());
+  }
+
+  #[test]
+  fn detects_overlapping_fixes() {
+    let fix = |changes| LintFix {
+      description: "".into(),
+      changes,
+    };
+    let wrap = fix(vec![change(0, 0, "("), change(9, 9, ")")]);
+    assert!(!wrap.overlaps(&fix(vec![change(4, 5, "d")])));
+    assert!(wrap.overlaps(&fix(vec![change(8, 10, "")])));
+    assert_eq!(wrap.range(), Some(change(0, 9, "").range));
+    assert!(!wrap.has_overlapping_changes());
+
+    let broken = fix(vec![change(0, 3, "a"), change(2, 4, "b")]);
+    assert!(broken.has_overlapping_changes());
+    let mut diagnostic = diagnostic("broken", 0, 4, "", None);
+    diagnostic.fixes = vec![broken];
+    let (text, report) = apply_fixes("abcd", &[diagnostic]);
+    assert_eq!(text, "abcd");
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].conflicting_range, change(2, 4, "").range);
+  }
+
+  #[test]
+  fn sorts_imports_changed_by_fixes() {
+    let source =
+      "import { readFile } from \"fs\";\nimport { merge } from \"lodash\";\n";
+    let mut diagnostic = diagnostic("node-protocol", 25, 29, "", None);
+    diagnostic.fixes = vec![LintFix {
+      description: "Use `node:fs`".into(),
+      changes: vec![change(25, 29, "\"node:fs\"")],
+    }];
+    let diagnostics = [diagnostic];
+
+    let (text, report) = apply_fixes(source, &diagnostics);
+    assert_eq!(
+      text,
+      "import { readFile } from \"node:fs\";\nimport { merge } from \"lodash\";\n"
+    );
+    assert!(!report.imports_sorted);
+
+    let options = FixOptions { sort_imports: true };
+    let (text, report) =
+      apply_fixes_with_options(source, &diagnostics, options);
+    assert_eq!(
+      text,
+      "import { merge } from \"lodash\";\nimport { readFile } from \"node:fs\";\n"
+    );
+    assert!(report.imports_sorted);
+
+    // Fixes elsewhere leave the imports alone
+    let source = "import { b } from \"./b.ts\";\nimport { a } from \"./a.ts\";\nlet x = 1;\n";
+    let (text, report) = apply_fixes_with_options(
+      source,
+      &[fixed_diagnostic("prefer-const", 56, 59, "const")],
+      options,
+    );
+    assert_eq!(text, source.replace("let", "const"));
+    assert!(!report.imports_sorted);
+  }
+
+  #[test]
+  fn related_location_in_other_file() {
+    let related = diagnostic("foo", 4, 6, "", None).range;
+    let mut diagnostic = diagnostic("foo", 0, 3, "message", None);
+    diagnostic.related = vec![
+      LintRelatedLocation {
+        specifier: None,
+        range: related.clone(),
+        message: "same file".to_string(),
+      },
+      LintRelatedLocation {
+        specifier: Some("file:///other.ts".to_string()),
+        range: related,
+        message: "other file".to_string(),
+      },
+    ];
+    assert_eq!(diagnostic.related[0].filename("file.ts"), "file.ts");
+    assert_eq!(
+      diagnostic.related[1].filename("file.ts"),
+      "file:///other.ts"
+    );
+
+    let json = serde_json::to_value(&diagnostic).unwrap();
+    assert!(json["related"][0].get("specifier").is_none());
+    assert_eq!(json["related"][1]["specifier"], "file:///other.ts");
+    assert_eq!(json["related"][1]["message"], "other file");
+  }
 }
@@ -0,0 +1,270 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Baselines of diagnostics that already exist in a codebase, so that a rule
+//! can be enabled without fixing all of its diagnostics first, and only new
+//! ones are reported.
+//!
+//! A diagnostic is identified by its file, its rule code and a hash of the
+//! text of the lines it spans, with surrounding whitespace trimmed from each
+//! line. Its line number isn't part of it, so edits elsewhere in the file,
+//! like adding lines above the diagnostic, don't change it.
+
+use super::{split_bom, LintDiagnostic};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The version of the format of [`Baseline`], which is increased whenever
+/// the hash of a diagnostic changes.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// The diagnostics recorded by [`generate_baseline`], serializable to JSON:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "files": {
+///     "file:///main.ts": {
+///       "no-var": { "58ff227add3c9eee": 1 }
+///     }
+///   }
+/// }
+/// ```
+///
+/// Each hash maps to the number of diagnostics that have it, so that
+/// identical lines are each suppressed once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+  pub version: u32,
+  pub files: BTreeMap<String, BTreeMap<String, BTreeMap<String, usize>>>,
+}
+
+impl Default for Baseline {
+  fn default() -> Self {
+    Self {
+      version: BASELINE_VERSION,
+      files: BTreeMap::new(),
+    }
+  }
+}
+
+/// Error that prevented `apply_baseline` from applying a baseline.
+#[derive(Debug, Display, PartialEq, Eq)]
+pub enum BaselineError {
+  #[display(
+    fmt = "Unsupported baseline version {}, expected {}",
+    _0,
+    BASELINE_VERSION
+  )]
+  UnsupportedVersion(u32),
+}
+
+impl std::error::Error for BaselineError {}
+
+impl Baseline {
+  /// Records the diagnostics of a file with the given source text.
+  pub fn add(&mut self, diagnostics: &[LintDiagnostic], source: &str) {
+    for diagnostic in diagnostics {
+      *self
+        .files
+        .entry(diagnostic.filename.clone())
+        .or_default()
+        .entry(diagnostic.code.clone())
+        .or_default()
+        .entry(context_hash(diagnostic, source))
+        .or_default() += 1;
+    }
+  }
+
+  fn count(&self, filename: &str, code: &str, hash: &str) -> usize {
+    self
+      .files
+      .get(filename)
+      .and_then(|codes| codes.get(code))
+      .and_then(|hashes| hashes.get(hash))
+      .copied()
+      .unwrap_or(0)
+  }
+}
+
+/// FNV-1a, which unlike the hashers of the standard library is guaranteed to
+/// give the same result in every build.
+fn fnv1a(text: &str) -> u64 {
+  text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  })
+}
+
+/// Hashes the trimmed lines that the diagnostic spans.
+fn context_hash(diagnostic: &LintDiagnostic, source: &str) -> String {
+  let (_, source) = split_bom(source);
+  let start = diagnostic.range.start.line_index;
+  let end = diagnostic.range.end.line_index.max(start);
+  let context = source
+    .lines()
+    .skip(start)
+    .take(end - start + 1)
+    .map(str::trim)
+    .collect::<Vec<_>>()
+    .join("\n");
+  format!("{:016x}", fnv1a(&context))
+}
+
+/// Records the diagnostics of the given files, each along with the source
+/// text they were reported for.
+pub fn generate_baseline<'a>(
+  files: impl IntoIterator<Item = (&'a [LintDiagnostic], &'a str)>,
+) -> Baseline {
+  let mut baseline = Baseline::default();
+  for (diagnostics, source) in files {
+    baseline.add(diagnostics, source);
+  }
+  baseline
+}
+
+/// Removes the diagnostics of a file with the given source text that are
+/// recorded in the baseline, and returns the others in their order.
+///
+/// If the baseline has fewer diagnostics with the same file, code and hash
+/// than there are now, the first ones are removed and the rest are kept.
+///
+/// Returns an error if the baseline was generated with another version of
+/// the format, since its hashes can't be compared.
+pub fn apply_baseline(
+  baseline: &Baseline,
+  diagnostics: Vec<LintDiagnostic>,
+  source: &str,
+) -> Result<Vec<LintDiagnostic>, BaselineError> {
+  if baseline.version != BASELINE_VERSION {
+    return Err(BaselineError::UnsupportedVersion(baseline.version));
+  }
+
+  let mut suppressed: BTreeMap<(String, String, String), usize> =
+    BTreeMap::new();
+  Ok(
+    diagnostics
+      .into_iter()
+      .filter(|diagnostic| {
+        let hash = context_hash(diagnostic, source);
+        let allowed =
+          baseline.count(&diagnostic.filename, &diagnostic.code, &hash);
+        let count = suppressed
+          .entry((diagnostic.filename.clone(), diagnostic.code.clone(), hash))
+          .or_default();
+        if *count < allowed {
+          *count += 1;
+          false
+        } else {
+          true
+        }
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::no_explicit_any::NoExplicitAny;
+  use crate::rules::no_var::NoVar;
+  use serde_json::json;
+
+  fn lint(source: &str) -> Vec<LintDiagnostic> {
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoVar, &NoExplicitAny])
+      .build();
+    linter
+      .lint("file:///main.ts".to_string(), source.to_string())
+      .unwrap()
+      .1
+  }
+
+  #[test]
+  fn suppresses_baselined_diagnostics_after_unrelated_edits() {
+    let source = "var a = 1;\nlet b: any = 2;\n";
+    let baseline = generate_baseline([(&lint(source)[..], source)]);
+    assert!(apply_baseline(&baseline, lint(source), source)
+      .unwrap()
+      .is_empty());
+
+    // Lines added above and the indentation don't matter
+    let source = "import './setup.ts';\n\nfunction f() {\n  var a = 1;\n  let b: any = 2;\n}\n";
+    assert!(apply_baseline(&baseline, lint(source), source)
+      .unwrap()
+      .is_empty());
+
+    // A new diagnostic, or an edit of a baselined line, is reported
+    let source = "var a = 1;\nlet b: any = 3;\nvar c;\n";
+    let diagnostics = apply_baseline(&baseline, lint(source), source).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].code, "no-explicit-any");
+    assert_eq!(diagnostics[0].range.start.line_index, 1);
+    assert_eq!(diagnostics[1].code, "no-var");
+    assert_eq!(diagnostics[1].range.start.line_index, 2);
+  }
+
+  #[test]
+  fn suppresses_each_baselined_diagnostic_once() {
+    let source = "var a;\n";
+    let baseline = generate_baseline([(&lint(source)[..], source)]);
+
+    let source = "var a;\nvar a;\n";
+    let diagnostics = apply_baseline(&baseline, lint(source), source).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line_index, 1);
+
+    // Other files aren't affected
+    let mut diagnostics = lint(source);
+    for diagnostic in &mut diagnostics {
+      diagnostic.filename = "file:///other.ts".to_string();
+    }
+    assert_eq!(
+      apply_baseline(&baseline, diagnostics, source)
+        .unwrap()
+        .len(),
+      2
+    );
+  }
+
+  #[test]
+  fn serializes_baseline() {
+    let source = "\u{FEFF}var a;\r\n";
+    let baseline = generate_baseline([(&lint(source)[..], source)]);
+    let value = serde_json::to_value(&baseline).unwrap();
+    assert_eq!(
+      value,
+      json!({
+        "version": 1,
+        "files": {
+          "file:///main.ts": {
+            "no-var": { "58ff227add3c9eee": 1 }
+          }
+        }
+      })
+    );
+    let deserialized: Baseline = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, baseline);
+
+    // The line ending and byte order mark don't matter
+    let source = "var a;\n";
+    assert!(apply_baseline(&deserialized, lint(source), source)
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn rejects_unsupported_version() {
+    let source = "var a;\n";
+    let mut baseline = generate_baseline([(&lint(source)[..], source)]);
+    baseline.version = BASELINE_VERSION + 1;
+    let error = apply_baseline(&baseline, lint(source), source).unwrap_err();
+    assert_eq!(
+      error,
+      BaselineError::UnsupportedVersion(BASELINE_VERSION + 1)
+    );
+    assert_eq!(
+      error.to_string(),
+      "Unsupported baseline version 2, expected 1"
+    );
+  }
+}
@@ -0,0 +1,77 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to the compact format, one
+//! `file:line:col: message [code]` line per diagnostic, which is what many CI
+//! log parsers expect.
+
+use super::LintDiagnostic;
+use std::fmt::Write;
+
+/// Converts diagnostics, possibly of several files, to the compact format.
+/// Each line is the file name, the 1-based line and column of the start of
+/// the diagnostic, the message and the rule code in brackets. Hints and
+/// related locations are left out.
+///
+/// Diagnostics are sorted by file name, position and code, so the output
+/// doesn't depend on the order of `diagnostics`.
+pub fn to_compact(diagnostics: &[LintDiagnostic]) -> String {
+  let mut diagnostics = diagnostics.iter().collect::<Vec<_>>();
+  diagnostics.sort_by_key(|d| {
+    (
+      &d.filename,
+      d.range.start.line_index,
+      d.range.start.column_index,
+      &d.code,
+    )
+  });
+
+  let mut output = String::new();
+  for diagnostic in diagnostics {
+    writeln!(
+      output,
+      "{}:{}:{}: {} [{}]",
+      diagnostic.filename,
+      diagnostic.range.start.line_index + 1,
+      diagnostic.range.start.column_index + 1,
+      diagnostic.message,
+      diagnostic.code,
+    )
+    .unwrap();
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::eqeqeq::Eqeqeq;
+  use crate::rules::no_var::NoVar;
+
+  #[test]
+  fn converts_diagnostics_to_compact() {
+    let mut diagnostics =
+      crate::test_util::lint(&NoVar, "let a;\n  var b;", "src/b.ts");
+    diagnostics.extend(crate::test_util::lint(
+      &Eqeqeq,
+      "var c = a == b;",
+      "src/a.ts",
+    ));
+    diagnostics.extend(crate::test_util::lint(
+      &NoVar,
+      "var c = a == b;",
+      "src/a.ts",
+    ));
+    diagnostics.reverse();
+
+    assert_eq!(
+      to_compact(&diagnostics),
+      "src/a.ts:1:1: `var` keyword is not allowed. [no-var]\n\
+       src/a.ts:1:9: expected '===' and instead saw '=='. [eqeqeq]\n\
+       src/b.ts:2:3: `var` keyword is not allowed. [no-var]\n"
+    );
+  }
+
+  #[test]
+  fn converts_no_diagnostics_to_compact() {
+    assert_eq!(to_compact(&[]), "");
+  }
+}
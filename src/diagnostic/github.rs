@@ -0,0 +1,99 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to GitHub Actions [workflow commands], which
+//! GitHub shows as annotations on the lines of a pull request.
+//!
+//! [workflow commands]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+
+use super::{LintDiagnostic, Severity};
+use std::fmt::Write;
+
+/// Escapes the message of a command, which ends at the end of the line.
+fn escape_data(text: &str) -> String {
+  text
+    .replace('%', "%25")
+    .replace('\r', "%0D")
+    .replace('\n', "%0A")
+}
+
+/// Escapes the value of a command property, which additionally ends at a
+/// `,` and can't contain the `:` that starts the message.
+fn escape_property(text: &str) -> String {
+  escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Converts diagnostics, possibly of several files, to workflow commands, one
+/// line per diagnostic. Warnings become `::warning` commands and errors
+/// `::error` commands, titled with the rule code. The hint, if any, is added
+/// to the message on a line of its own.
+///
+/// Diagnostics are sorted by file name, position and code, so the output
+/// doesn't depend on the order of `diagnostics`. Lines and columns are
+/// 1-based.
+pub fn to_github_actions(diagnostics: &[LintDiagnostic]) -> String {
+  let mut diagnostics = diagnostics.iter().collect::<Vec<_>>();
+  diagnostics.sort_by_key(|d| {
+    (
+      &d.filename,
+      d.range.start.line_index,
+      d.range.start.column_index,
+      &d.code,
+    )
+  });
+
+  let mut output = String::new();
+  for diagnostic in diagnostics {
+    let command = match diagnostic.severity {
+      Severity::Warning => "warning",
+      Severity::Error => "error",
+    };
+    let start = &diagnostic.range.start;
+    let end = &diagnostic.range.end;
+    let mut message = diagnostic.message.clone();
+    if let Some(hint) = &diagnostic.hint {
+      write!(message, "\nHint: {}", hint).unwrap();
+    }
+    writeln!(
+      output,
+      "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+      command,
+      escape_property(&diagnostic.filename),
+      start.line_index + 1,
+      start.column_index + 1,
+      end.line_index + 1,
+      end.column_index + 1,
+      escape_property(&diagnostic.code),
+      escape_data(&message),
+    )
+    .unwrap();
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::no_var::NoVar;
+
+  #[test]
+  fn converts_diagnostics_to_github_actions() {
+    let mut diagnostics =
+      crate::test_util::lint(&NoVar, "let a;\nvar b;", "b.ts");
+    let mut other =
+      crate::test_util::lint(&NoVar, "var a; var c;", "dir/a,b:c.ts");
+    other[0].hint = Some("Use `let`\r\nor `const`, 100% of the time".into());
+    other[1].severity = Severity::Warning;
+    diagnostics.extend(other);
+
+    assert_eq!(
+      to_github_actions(&diagnostics),
+      "::error file=b.ts,line=2,col=1,endLine=2,endColumn=7,title=no-var::`var` keyword is not allowed.\n\
+       ::error file=dir/a%2Cb%3Ac.ts,line=1,col=1,endLine=1,endColumn=7,title=no-var::`var` keyword is not allowed.%0AHint: Use `let`%0D%0Aor `const`, 100%25 of the time\n\
+       ::warning file=dir/a%2Cb%3Ac.ts,line=1,col=8,endLine=1,endColumn=14,title=no-var::`var` keyword is not allowed.\n"
+    );
+  }
+
+  #[test]
+  fn converts_no_diagnostics_to_github_actions() {
+    assert_eq!(to_github_actions(&[]), "");
+  }
+}
@@ -0,0 +1,290 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to JSON for editor integrations and other tools.
+//!
+//! The output has a top-level `version`, which is increased whenever a field
+//! is removed or changes its meaning. New fields may be added without
+//! changing it.
+
+use super::{
+  line_before, LintDiagnostic, LintRelatedLocation, Position, Range, Severity,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The version of the schema of [`to_json`]'s output.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonOutput<'a> {
+  version: u32,
+  diagnostics: Vec<JsonDiagnostic<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+  code: &'a str,
+  message: &'a str,
+  hint: Option<&'a str>,
+  filename: &'a str,
+  severity: Severity,
+  range: JsonRange,
+  related: Vec<JsonRelatedLocation<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonRelatedLocation<'a> {
+  specifier: &'a str,
+  range: JsonRange,
+  message: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonRange {
+  start: JsonPosition,
+  end: JsonPosition,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPosition {
+  /// 0-based offset in bytes from the start of the file.
+  byte_offset: usize,
+  /// 1-based line number.
+  line: usize,
+  /// 1-based column in bytes.
+  utf8_column: usize,
+  /// 1-based column in UTF-16 code units, as used by LSP clients.
+  utf16_column: usize,
+}
+
+/// Converts the position, computing its columns from `source` if it's the
+/// text of the position's file.
+fn json_position(source: Option<&str>, position: &Position) -> JsonPosition {
+  let line = source.and_then(|source| line_before(source, position));
+  let (utf8_column, utf16_column) = match line {
+    Some(line) => (line.len() + 1, line.encode_utf16().count() + 1),
+    None => (position.column_index + 1, position.column_index + 1),
+  };
+  JsonPosition {
+    byte_offset: position.byte_index,
+    line: position.line_index + 1,
+    utf8_column,
+    utf16_column,
+  }
+}
+
+fn json_range(source: Option<&str>, range: &Range) -> JsonRange {
+  JsonRange {
+    start: json_position(source, &range.start),
+    end: json_position(source, &range.end),
+  }
+}
+
+fn json_related_location<'a>(
+  related: &'a LintRelatedLocation,
+  diagnostic_filename: &'a str,
+  source: &str,
+) -> JsonRelatedLocation<'a> {
+  // Locations in other files have no source text to compute columns from
+  let source = related.specifier.is_none().then_some(source);
+  JsonRelatedLocation {
+    specifier: related.filename(diagnostic_filename),
+    range: json_range(source, &related.range),
+    message: &related.message,
+  }
+}
+
+/// Converts the diagnostics of a file with the given source text to JSON.
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "diagnostics": [{
+///     "code": "no-var",
+///     "message": "`var` keyword is not allowed.",
+///     "hint": null,
+///     "filename": "file:///main.ts",
+///     "severity": "error",
+///     "range": {
+///       "start": { "byteOffset": 0, "line": 1, "utf8Column": 1, "utf16Column": 1 },
+///       "end": { "byteOffset": 6, "line": 1, "utf8Column": 7, "utf16Column": 7 }
+///     },
+///     "related": []
+///   }]
+/// }
+/// ```
+///
+/// The columns are computed from `source`, which must be the text the
+/// diagnostics were reported for. Each related location has the `specifier`
+/// of its file, which is the diagnostic's `filename` unless it's in another
+/// file. The columns of locations in other files are as reported.
+pub fn to_json(diagnostics: &[LintDiagnostic], source: &str) -> Value {
+  let output = JsonOutput {
+    version: JSON_SCHEMA_VERSION,
+    diagnostics: diagnostics
+      .iter()
+      .map(|diagnostic| JsonDiagnostic {
+        code: &diagnostic.code,
+        message: &diagnostic.message,
+        hint: diagnostic.hint.as_deref(),
+        filename: &diagnostic.filename,
+        severity: diagnostic.severity,
+        range: json_range(Some(source), &diagnostic.range),
+        related: diagnostic
+          .related
+          .iter()
+          .map(|related| {
+            json_related_location(related, &diagnostic.filename, source)
+          })
+          .collect(),
+      })
+      .collect(),
+  };
+  serde_json::to_value(output).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::no_var::NoVar;
+  use serde_json::json;
+
+  #[test]
+  fn converts_diagnostics_to_json() {
+    let source = "const s = \"😀é\"; var a = 1;\nvar b = 2;";
+    let diagnostics = crate::test_util::lint(&NoVar, source, "foo.ts");
+    let json = to_json(&diagnostics, source);
+
+    assert_eq!(json["version"], 1);
+    assert_eq!(
+      json["diagnostics"][0],
+      json!({
+        "code": "no-var",
+        "message": "`var` keyword is not allowed.",
+        "hint": null,
+        "filename": "foo.ts",
+        "severity": "error",
+        "range": {
+          // The emoji is four bytes and two UTF-16 code units long, and `é`
+          // is two bytes and one code unit long
+          "start": {
+            "byteOffset": 20,
+            "line": 1,
+            "utf8Column": 21,
+            "utf16Column": 18,
+          },
+          "end": {
+            "byteOffset": 30,
+            "line": 1,
+            "utf8Column": 31,
+            "utf16Column": 28,
+          },
+        },
+        "related": [],
+      })
+    );
+    assert_eq!(
+      json["diagnostics"][1]["range"]["start"],
+      json!({
+        "byteOffset": 31,
+        "line": 2,
+        "utf8Column": 1,
+        "utf16Column": 1,
+      })
+    );
+  }
+
+  #[test]
+  fn computes_columns_with_byte_order_mark_and_crlf() {
+    let source = "\u{FEFF}let a;\r\nvar b = 2;";
+    let diagnostics = crate::test_util::lint(&NoVar, source, "foo.ts");
+    let json = to_json(&diagnostics, source);
+
+    assert_eq!(
+      json["diagnostics"][0]["range"]["start"],
+      json!({
+        "byteOffset": 8,
+        "line": 2,
+        "utf8Column": 1,
+        "utf16Column": 1,
+      })
+    );
+  }
+
+  #[test]
+  fn converts_related_locations() {
+    let source = "let é = 1;\nvar b = 2;";
+    let mut diagnostic = crate::test_util::lint(&NoVar, source, "foo.ts")
+      .into_iter()
+      .next()
+      .unwrap();
+    // The range of `é`, which is two bytes and one character long
+    let declaration = Range {
+      start: Position {
+        line_index: 0,
+        column_index: 4,
+        byte_index: 4,
+      },
+      end: Position {
+        line_index: 0,
+        column_index: 5,
+        byte_index: 6,
+      },
+    };
+    diagnostic.related = vec![
+      LintRelatedLocation {
+        specifier: None,
+        range: declaration.clone(),
+        message: "Declared here".to_string(),
+      },
+      LintRelatedLocation {
+        specifier: Some("file:///other.ts".to_string()),
+        range: declaration,
+        message: "Also declared here".to_string(),
+      },
+    ];
+    let json = to_json(&[diagnostic], source);
+
+    assert_eq!(
+      json["diagnostics"][0]["related"],
+      json!([
+        {
+          "specifier": "foo.ts",
+          "range": {
+            "start": {
+              "byteOffset": 4,
+              "line": 1,
+              "utf8Column": 5,
+              "utf16Column": 5,
+            },
+            "end": {
+              "byteOffset": 6,
+              "line": 1,
+              "utf8Column": 7,
+              "utf16Column": 6,
+            },
+          },
+          "message": "Declared here",
+        },
+        {
+          "specifier": "file:///other.ts",
+          "range": {
+            "start": {
+              "byteOffset": 4,
+              "line": 1,
+              "utf8Column": 5,
+              "utf16Column": 5,
+            },
+            "end": {
+              "byteOffset": 6,
+              "line": 1,
+              "utf8Column": 6,
+              "utf16Column": 6,
+            },
+          },
+          "message": "Also declared here",
+        },
+      ])
+    );
+  }
+}
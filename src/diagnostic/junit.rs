@@ -0,0 +1,134 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to JUnit XML, which many CI systems can show.
+//!
+//! Each linted file becomes a `testsuite`, and each diagnostic a failing
+//! `testcase` in it.
+
+use super::LintDiagnostic;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Escapes the characters that can't appear as is in XML text and attribute
+/// values.
+fn escape_xml(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// Converts diagnostics, possibly of several files, to a JUnit XML report.
+///
+/// Files are sorted by name, and the diagnostics of each file by their
+/// position and code, so the output doesn't depend on the order of
+/// `diagnostics`. Lines and columns are 1-based.
+pub fn to_junit(diagnostics: &[LintDiagnostic]) -> String {
+  let mut files: BTreeMap<&str, Vec<&LintDiagnostic>> = BTreeMap::new();
+  for diagnostic in diagnostics {
+    files
+      .entry(&diagnostic.filename)
+      .or_default()
+      .push(diagnostic);
+  }
+
+  let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+  writeln!(
+    xml,
+    "<testsuites name=\"deno_lint\" tests=\"{0}\" failures=\"{0}\">",
+    diagnostics.len()
+  )
+  .unwrap();
+  for (filename, mut diagnostics) in files {
+    diagnostics.sort_by_key(|d| {
+      (
+        d.range.start.line_index,
+        d.range.start.column_index,
+        &d.code,
+      )
+    });
+    let filename = escape_xml(filename);
+    writeln!(
+      xml,
+      "  <testsuite name=\"{0}\" tests=\"{1}\" failures=\"{1}\">",
+      filename,
+      diagnostics.len()
+    )
+    .unwrap();
+    for diagnostic in diagnostics {
+      let line = diagnostic.range.start.line_index + 1;
+      let col = diagnostic.range.start.column_index + 1;
+      let code = escape_xml(&diagnostic.code);
+      let message = escape_xml(&diagnostic.message);
+      writeln!(
+        xml,
+        "    <testcase name=\"{code} at {line}:{col}\" classname=\"{filename}\">",
+      )
+      .unwrap();
+      write!(
+        xml,
+        "      <failure message=\"{message}\" type=\"{code}\">{filename}:{line}:{col}: {message}",
+      )
+      .unwrap();
+      if let Some(hint) = &diagnostic.hint {
+        write!(xml, "\nHint: {}", escape_xml(hint)).unwrap();
+      }
+      xml.push_str("</failure>\n    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n");
+  }
+  xml.push_str("</testsuites>\n");
+  xml
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::no_var::NoVar;
+
+  #[test]
+  fn converts_diagnostics_to_junit() {
+    let mut diagnostics =
+      crate::test_util::lint(&NoVar, "let a;\nvar b;", "b.ts");
+    let mut other = crate::test_util::lint(&NoVar, "var a; var c;", "a.ts");
+    other[0].hint = Some("Use `let` & \"const\" <instead>".to_string());
+    diagnostics.extend(other);
+
+    assert_eq!(
+      to_junit(&diagnostics),
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="deno_lint" tests="3" failures="3">
+  <testsuite name="a.ts" tests="2" failures="2">
+    <testcase name="no-var at 1:1" classname="a.ts">
+      <failure message="`var` keyword is not allowed." type="no-var">a.ts:1:1: `var` keyword is not allowed.
+Hint: Use `let` &amp; &quot;const&quot; &lt;instead&gt;</failure>
+    </testcase>
+    <testcase name="no-var at 1:8" classname="a.ts">
+      <failure message="`var` keyword is not allowed." type="no-var">a.ts:1:8: `var` keyword is not allowed.</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="b.ts" tests="1" failures="1">
+    <testcase name="no-var at 2:1" classname="b.ts">
+      <failure message="`var` keyword is not allowed." type="no-var">b.ts:2:1: `var` keyword is not allowed.</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+    );
+  }
+
+  #[test]
+  fn converts_no_diagnostics_to_junit() {
+    assert_eq!(
+      to_junit(&[]),
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"deno_lint\" tests=\"0\" failures=\"0\">\n</testsuites>\n"
+    );
+  }
+}
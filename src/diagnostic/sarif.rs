@@ -0,0 +1,370 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Conversion of diagnostics to a [SARIF 2.1.0] log, the format consumed by
+//! code scanning services such as GitHub's.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use super::{line_before, LintDiagnostic, Position, Range, Severity};
+use crate::rules::LintRule;
+use serde::Serialize;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The root object of a SARIF log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifLog {
+  #[serde(rename = "$schema")]
+  pub schema: &'static str,
+  pub version: &'static str,
+  pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+  pub tool: Tool,
+  /// How columns in regions are counted. Always `utf16CodeUnits`, which is
+  /// the SARIF default, but stated explicitly for consumers that don't
+  /// default to it.
+  pub column_kind: &'static str,
+  pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Tool {
+  pub driver: Driver,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+  pub name: &'static str,
+  pub version: &'static str,
+  pub information_uri: &'static str,
+  /// Metadata of every rule that was run, whether or not it reported
+  /// anything.
+  pub rules: Vec<ReportingDescriptor>,
+}
+
+/// Metadata of a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+  pub id: String,
+  pub help_uri: String,
+  pub default_configuration: ReportingConfiguration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportingConfiguration {
+  pub level: Level,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Level {
+  Warning,
+  Error,
+}
+
+impl From<Severity> for Level {
+  fn from(severity: Severity) -> Self {
+    match severity {
+      Severity::Warning => Level::Warning,
+      Severity::Error => Level::Error,
+    }
+  }
+}
+
+/// A diagnostic. Named so it doesn't clash with `std::result::Result`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+  pub rule_id: String,
+  /// Index of the rule in `Driver::rules`.
+  pub rule_index: usize,
+  pub level: Level,
+  pub message: Message,
+  pub locations: Vec<Location>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related_locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Message {
+  pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+  pub physical_location: PhysicalLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub message: Option<Message>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+  pub artifact_location: ArtifactLocation,
+  pub region: Region,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactLocation {
+  pub uri: String,
+}
+
+/// A range of a file, with 1-based lines and columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+  pub start_line: usize,
+  pub start_column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+}
+
+/// The 1-based column of the position in UTF-16 code units, which differs
+/// from the column of the position for lines with characters outside the
+/// Basic Multilingual Plane.
+fn utf16_column(source: &str, position: &Position) -> usize {
+  match line_before(source, position) {
+    Some(line) => line.encode_utf16().count() + 1,
+    None => position.column_index + 1,
+  }
+}
+
+fn region(range: &Range, source: Option<&str>) -> Region {
+  let column = |position: &Position| match source {
+    Some(source) => utf16_column(source, position),
+    None => position.column_index + 1,
+  };
+  Region {
+    start_line: range.start.line_index + 1,
+    start_column: column(&range.start),
+    end_line: range.end.line_index + 1,
+    end_column: column(&range.end),
+  }
+}
+
+fn location(
+  uri: &str,
+  range: &Range,
+  source: Option<&str>,
+  message: Option<String>,
+) -> Location {
+  Location {
+    physical_location: PhysicalLocation {
+      artifact_location: ArtifactLocation {
+        uri: uri.to_string(),
+      },
+      region: region(range, source),
+    },
+    message: message.map(|text| Message { text }),
+  }
+}
+
+fn rule_descriptor(code: &str) -> ReportingDescriptor {
+  ReportingDescriptor {
+    id: code.to_string(),
+    help_uri: format!("https://lint.deno.land/#{}", code),
+    default_configuration: ReportingConfiguration {
+      level: Severity::default().into(),
+    },
+  }
+}
+
+/// Converts the diagnostics of the file `filename` with the given source text
+/// to a SARIF log.
+///
+/// All of `rules` are listed in the log's rule metadata, so rules that didn't
+/// report anything are known too. Codes of diagnostics that don't belong to
+/// any of them are added after them. Hints are appended to the messages.
+///
+/// Columns are converted to UTF-16 code units using `source`. Related
+/// locations in other files are assumed to only contain characters that are
+/// one code unit long, as their source isn't available.
+pub fn to_sarif(
+  diagnostics: &[LintDiagnostic],
+  filename: &str,
+  source: &str,
+  rules: &[&dyn LintRule],
+) -> SarifLog {
+  let mut descriptors = rules
+    .iter()
+    .map(|rule| rule_descriptor(rule.code()))
+    .collect::<Vec<_>>();
+
+  let results = diagnostics
+    .iter()
+    .map(|diagnostic| {
+      let rule_index = descriptors
+        .iter()
+        .position(|descriptor| descriptor.id == diagnostic.code)
+        .unwrap_or_else(|| {
+          descriptors.push(rule_descriptor(&diagnostic.code));
+          descriptors.len() - 1
+        });
+      let text = match &diagnostic.hint {
+        Some(hint) => format!("{}\n\nHint: {}", diagnostic.message, hint),
+        None => diagnostic.message.clone(),
+      };
+      SarifResult {
+        rule_id: diagnostic.code.clone(),
+        rule_index,
+        level: diagnostic.severity.into(),
+        message: Message { text },
+        locations: vec![location(
+          filename,
+          &diagnostic.range,
+          Some(source),
+          None,
+        )],
+        related_locations: diagnostic
+          .related
+          .iter()
+          .map(|related| match &related.specifier {
+            Some(specifier) => location(
+              specifier,
+              &related.range,
+              None,
+              Some(related.message.clone()),
+            ),
+            None => location(
+              filename,
+              &related.range,
+              Some(source),
+              Some(related.message.clone()),
+            ),
+          })
+          .collect(),
+      }
+    })
+    .collect();
+
+  SarifLog {
+    schema: SCHEMA,
+    version: "2.1.0",
+    runs: vec![Run {
+      tool: Tool {
+        driver: Driver {
+          name: "deno_lint",
+          version: env!("CARGO_PKG_VERSION"),
+          information_uri: "https://lint.deno.land",
+          rules: descriptors,
+        },
+      },
+      column_kind: "utf16CodeUnits",
+      results,
+    }],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::linter::LinterBuilder;
+  use crate::rules::no_var::NoVar;
+  use crate::rules::use_isnan::UseIsNaN;
+  use serde_json::json;
+
+  #[test]
+  fn converts_diagnostics_to_sarif() {
+    let source = "const s = \"😀é\"; var a = 1;\nvar b = 2;";
+    let rules: Vec<&'static dyn LintRule> = vec![&NoVar, &UseIsNaN];
+    let (_, diagnostics) = LinterBuilder::default()
+      .rules(rules.clone())
+      .build()
+      .lint("file:///main.ts".to_string(), source.to_string())
+      .unwrap();
+    let log = to_sarif(&diagnostics, "file:///main.ts", source, &rules);
+    let json = serde_json::to_value(log).unwrap();
+
+    assert_eq!(json["version"], "2.1.0");
+    let run = &json["runs"][0];
+    // Rules without results are listed too
+    assert_eq!(
+      run["tool"]["driver"]["rules"],
+      json!([
+        {
+          "id": "no-var",
+          "helpUri": "https://lint.deno.land/#no-var",
+          "defaultConfiguration": { "level": "error" },
+        },
+        {
+          "id": "use-isnan",
+          "helpUri": "https://lint.deno.land/#use-isnan",
+          "defaultConfiguration": { "level": "error" },
+        },
+      ])
+    );
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "no-var");
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(
+      results[0]["message"]["text"],
+      "`var` keyword is not allowed."
+    );
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "file:///main.ts");
+    // The emoji is two UTF-16 code units and four bytes long
+    assert_eq!(
+      location["region"],
+      json!({
+        "startLine": 1,
+        "startColumn": 18,
+        "endLine": 1,
+        "endColumn": 28,
+      })
+    );
+    assert_eq!(
+      results[1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+      2
+    );
+  }
+
+  #[test]
+  fn adds_unknown_rules_and_related_locations() {
+    let mut diagnostic = crate::test_util::lint(&NoVar, "var a;", "foo.ts")
+      .into_iter()
+      .next()
+      .unwrap();
+    diagnostic.code = "some-plugin-rule".to_string();
+    diagnostic.severity = Severity::Warning;
+    diagnostic.hint = Some("Use `let` instead".to_string());
+    diagnostic.related.push(super::super::LintRelatedLocation {
+      specifier: Some("other.ts".to_string()),
+      range: diagnostic.range.clone(),
+      message: "Declared here".to_string(),
+    });
+    let log = to_sarif(&[diagnostic], "foo.ts", "var a;", &[&NoVar]);
+    let json = serde_json::to_value(log).unwrap();
+    let run = &json["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "some-plugin-rule");
+    assert_eq!(run["results"][0]["ruleIndex"], 1);
+    assert_eq!(run["results"][0]["level"], "warning");
+    assert_eq!(
+      run["results"][0]["message"]["text"],
+      "`var` keyword is not allowed.\n\nHint: Use `let` instead"
+    );
+    assert_eq!(
+      run["results"][0]["relatedLocations"][0],
+      json!({
+        "physicalLocation": {
+          "artifactLocation": { "uri": "other.ts" },
+          "region": {
+            "startLine": 1,
+            "startColumn": 1,
+            "endLine": 1,
+            "endColumn": 7,
+          },
+        },
+        "message": { "text": "Declared here" },
+      })
+    );
+  }
+}
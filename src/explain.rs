@@ -0,0 +1,166 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Records why a rule did or didn't report a diagnostic at a position of a
+//! file, for `Linter::explain`.
+
+use crate::diagnostic::LintDiagnostic;
+use deno_ast::view::{Node, NodeKind, NodeTrait};
+use deno_ast::{Diagnostic, SourcePos, SourceRange, SourceRanged};
+use derive_more::Display;
+
+/// The ignore directive that suppressed a diagnostic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SuppressedBy {
+  /// A `deno-lint-ignore-file` directive with the rule's code.
+  FileDirective,
+  /// A `deno-lint-ignore` directive with the rule's code, on the line with
+  /// the given 0-indexed line index.
+  LineDirective { line_index: usize },
+}
+
+/// A diagnostic that was reported by a rule but not returned by the linter.
+#[derive(Clone, Debug)]
+pub struct SuppressedDiagnostic {
+  pub diagnostic: LintDiagnostic,
+  pub suppressed_by: SuppressedBy,
+}
+
+/// Why a rule did or didn't report a diagnostic at a position.
+#[derive(Clone, Debug, Default)]
+pub struct RuleExplanation {
+  /// Whether the rule ran on the file. It doesn't if it isn't enabled, if
+  /// it doesn't apply to the file's media type, or if the whole file is
+  /// ignored.
+  pub rule_executed: bool,
+  /// Whether the whole file is skipped by a `deno-lint-ignore-file`
+  /// directive without codes.
+  pub file_ignored: bool,
+  /// Kinds of the nodes at the position that the rule's handler was called
+  /// for, from the outermost to the innermost. Empty for rules that don't
+  /// traverse the AST with a [`crate::handler::Handler`].
+  pub visited_nodes: Vec<NodeKind>,
+  /// Diagnostics of the rule at the position that the linter returned.
+  pub reported: Vec<LintDiagnostic>,
+  /// Diagnostics of the rule at the position that ignore directives
+  /// suppressed.
+  pub suppressed: Vec<SuppressedDiagnostic>,
+}
+
+/// Error that prevented `Linter::explain` from explaining a rule.
+#[derive(Debug, Display)]
+pub enum ExplainError {
+  #[display(fmt = "Failed to parse file: {}", _0)]
+  Parse(Diagnostic),
+  #[display(
+    fmt = "Line index {} is out of range, the file has {} lines",
+    line_index,
+    lines_count
+  )]
+  LineOutOfRange {
+    line_index: usize,
+    lines_count: usize,
+  },
+  #[display(
+    fmt = "Column index {} is out of range, line {} has {} characters",
+    column_index,
+    line_index,
+    line_length
+  )]
+  ColumnOutOfRange {
+    line_index: usize,
+    column_index: usize,
+    line_length: usize,
+  },
+}
+
+impl std::error::Error for ExplainError {}
+
+/// Collects the [`RuleExplanation`] of a rule while a file is linted.
+pub(crate) struct ExplainRecorder {
+  code: String,
+  position: SourcePos,
+  /// The position as a byte index into the file text, which is how
+  /// diagnostics store their range.
+  byte_index: usize,
+  /// Whether the explained rule is the one that's running.
+  active: bool,
+  explanation: RuleExplanation,
+}
+
+/// Whether `start..end` contains `position`. Empty ranges contain the
+/// position they're at.
+fn contains<T: PartialOrd>(start: T, end: T, position: T) -> bool {
+  start <= position && (position < end || start == end)
+}
+
+impl ExplainRecorder {
+  pub(crate) fn new(
+    code: &str,
+    position: SourcePos,
+    byte_index: usize,
+  ) -> Self {
+    Self {
+      code: code.to_string(),
+      position,
+      byte_index,
+      active: false,
+      explanation: RuleExplanation::default(),
+    }
+  }
+
+  fn is_at_position(&self, diagnostic: &LintDiagnostic) -> bool {
+    diagnostic.code == self.code
+      && contains(
+        diagnostic.range.start.byte_index,
+        diagnostic.range.end.byte_index,
+        self.byte_index,
+      )
+  }
+
+  pub(crate) fn file_ignored(&mut self) {
+    self.explanation.file_ignored = true;
+  }
+
+  /// Called before each rule runs.
+  pub(crate) fn start_rule(&mut self, code: &str) {
+    self.active = code == self.code;
+    if self.active {
+      self.explanation.rule_executed = true;
+    }
+  }
+
+  pub(crate) fn record_visit(&mut self, node: Node) {
+    let range: SourceRange = node.range();
+    if self.active && contains(range.start, range.end, self.position) {
+      self.explanation.visited_nodes.push(node.kind());
+    }
+  }
+
+  pub(crate) fn record_suppressed(
+    &mut self,
+    diagnostic: &LintDiagnostic,
+    suppressed_by: SuppressedBy,
+  ) {
+    if self.is_at_position(diagnostic) {
+      self.explanation.suppressed.push(SuppressedDiagnostic {
+        diagnostic: diagnostic.clone(),
+        suppressed_by,
+      });
+    }
+  }
+
+  pub(crate) fn finish(
+    self,
+    diagnostics: &[LintDiagnostic],
+  ) -> RuleExplanation {
+    let reported = diagnostics
+      .iter()
+      .filter(|diagnostic| self.is_at_position(diagnostic))
+      .cloned()
+      .collect();
+    RuleExplanation {
+      reported,
+      ..self.explanation
+    }
+  }
+}
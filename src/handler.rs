@@ -1,8 +1,63 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use crate::context::Context;
 use deno_ast::view as ast_view;
-use deno_ast::view::NodeTrait;
+use deno_ast::view::{Expr, Lit, Node, NodeTrait};
 
+/// A directive in the prologue of a script, module or function body, e.g.
+/// `"use strict"`.
+pub struct Directive<'a> {
+  /// The statement of the directive.
+  pub stmt: &'a ast_view::ExprStmt<'a>,
+  /// The string literal of the directive.
+  pub str: &'a ast_view::Str<'a>,
+}
+
+impl<'a> Directive<'a> {
+  fn from_expr_stmt(stmt: &'a ast_view::ExprStmt<'a>) -> Option<Self> {
+    // Parenthesized strings aren't directives
+    let Expr::Lit(Lit::Str(str)) = stmt.expr else {
+      return None;
+    };
+    let in_prologue_position = match stmt.parent() {
+      Node::Module(_) | Node::Script(_) => true,
+      Node::BlockStmt(block) => matches!(
+        block.parent(),
+        Node::Function(_)
+          | Node::ArrowExpr(_)
+          | Node::Constructor(_)
+          | Node::GetterProp(_)
+          | Node::SetterProp(_)
+      ),
+      _ => false,
+    };
+    // Only the leading string statements form the prologue
+    let is_string_stmt = |node: &Node| {
+      matches!(node, Node::ExprStmt(prev)
+        if matches!(prev.expr, Expr::Lit(Lit::Str(_))))
+    };
+    let in_prologue = in_prologue_position
+      && stmt.previous_siblings().iter().all(is_string_stmt);
+    in_prologue.then_some(Directive { stmt, str })
+  }
+
+  /// The text of the directive between its quotes. Escapes aren't processed,
+  /// since `"use\x20strict"` isn't a `use strict` directive.
+  pub fn raw_value(&self) -> &str {
+    match &self.str.inner.raw {
+      Some(raw) => &raw[1..raw.len() - 1],
+      None => &self.str.inner.value,
+    }
+  }
+
+  /// Whether this is a `"use strict"` directive.
+  pub fn is_use_strict(&self) -> bool {
+    self.raw_value() == "use strict"
+  }
+}
+
+/// Callbacks invoked for each node of the AST by [`Traverse::traverse`].
+/// Rules implement the callbacks for the nodes they check; the others do
+/// nothing by default.
 pub trait Handler {
   fn on_enter_node(&mut self, _n: ast_view::Node, _ctx: &mut Context) {}
   fn on_exit_node(&mut self, _n: ast_view::Node, _ctx: &mut Context) {}
@@ -49,6 +104,9 @@ pub trait Handler {
   fn debugger_stmt(&mut self, _n: &ast_view::DebuggerStmt, _ctx: &mut Context) {
   }
   fn decorator(&mut self, _n: &ast_view::Decorator, _ctx: &mut Context) {}
+  /// Called for the string statements at the start of scripts, modules and
+  /// function bodies, after [`Handler::expr_stmt`].
+  fn directive(&mut self, _n: &Directive, _ctx: &mut Context) {}
   fn do_while_stmt(&mut self, _n: &ast_view::DoWhileStmt, _ctx: &mut Context) {}
   fn empty_stmt(&mut self, _n: &ast_view::EmptyStmt, _ctx: &mut Context) {}
   fn export_all(&mut self, _n: &ast_view::ExportAll, _ctx: &mut Context) {}
@@ -524,6 +582,10 @@ pub trait Handler {
   fn yield_expr(&mut self, _n: &ast_view::YieldExpr, _ctx: &mut Context) {}
 }
 
+/// Visits a node and all of its descendants, calling the [`Handler`]
+/// callback of each one. Call [`Context::stop_traverse`] in a callback to skip
+/// the children of the node. No more children are visited once linting is
+/// cancelled, see [`Context::is_cancelled`].
 pub trait Traverse: Handler {
   fn traverse<'a, N>(&mut self, node: N, ctx: &mut Context)
   where
@@ -537,6 +599,9 @@ pub trait Traverse: Handler {
     // First, invoke a handler that does anything we want when _entering_ a node.
     self.on_enter_node(node, ctx);
 
+    #[cfg(feature = "profiling")]
+    ctx.record_visit(node);
+
     // Next, invoke a handler that is specific to the type of node.
     use deno_ast::view::Node::*;
     match node {
@@ -578,7 +643,12 @@ pub trait Traverse: Handler {
       ExportNamedSpecifier(n) => self.export_named_specifier(n, ctx),
       ExportNamespaceSpecifier(n) => self.export_namespace_specifier(n, ctx),
       ExprOrSpread(n) => self.expr_or_spread(n, ctx),
-      ExprStmt(n) => self.expr_stmt(n, ctx),
+      ExprStmt(n) => {
+        self.expr_stmt(n, ctx);
+        if let Some(directive) = Directive::from_expr_stmt(n) {
+          self.directive(&directive, ctx);
+        }
+      }
       FnDecl(n) => self.fn_decl(n, ctx),
       FnExpr(n) => self.fn_expr(n, ctx),
       ForInStmt(n) => self.for_in_stmt(n, ctx),
@@ -712,8 +782,8 @@ pub trait Traverse: Handler {
       YieldExpr(n) => self.yield_expr(n, ctx),
     };
 
-    // Walk the child nodes recursively.
-    if !ctx.should_stop_traverse() {
+    // Walk the child nodes recursively, unless linting was cancelled.
+    if !ctx.should_stop_traverse() && !ctx.is_cancelled() {
       for child in node.children() {
         self.traverse(child, ctx);
       }
@@ -725,3 +795,137 @@ pub trait Traverse: Handler {
 }
 
 impl<H: Handler> Traverse for H {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::diagnostic::{LintFix, LintFixChange};
+  use crate::rules::LintRule;
+  use crate::Program;
+  use deno_ast::SourceRanged;
+
+  // A rule that flags `"use strict"` in modules, which are always strict
+  #[derive(Debug)]
+  struct NoRedundantUseStrict;
+
+  const CODE: &str = "no-redundant-use-strict";
+  const MESSAGE: &str = "Modules are always in strict mode";
+  const HINT: &str = "Remove the directive";
+
+  impl LintRule for NoRedundantUseStrict {
+    fn code(&self) -> &'static str {
+      CODE
+    }
+
+    fn lint_program_with_ast_view(
+      &self,
+      context: &mut Context,
+      program: Program<'_>,
+    ) {
+      if matches!(program, Program::Module(_)) {
+        NoRedundantUseStrictHandler.traverse(program, context);
+      }
+    }
+
+    #[cfg(feature = "docs")]
+    fn docs(&self) -> &'static str {
+      ""
+    }
+  }
+
+  struct NoRedundantUseStrictHandler;
+
+  impl Handler for NoRedundantUseStrictHandler {
+    fn directive(&mut self, directive: &Directive, ctx: &mut Context) {
+      if directive.is_use_strict() {
+        ctx.add_diagnostic_with_fixes(
+          directive.stmt.range(),
+          CODE,
+          MESSAGE,
+          Some(HINT.to_string()),
+          vec![LintFix {
+            description: HINT.into(),
+            changes: vec![LintFixChange {
+              new_text: "".into(),
+              range: directive.stmt.range(),
+            }],
+          }],
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn directive_valid() {
+    assert_lint_ok! {
+      NoRedundantUseStrict,
+      r#""use strict";"#,
+      r#"function f() { "use strict"; }"#,
+      r#"export {}; foo(); "use strict";"#,
+      r#"export {}; ("use strict");"#,
+      r#"export {}; "use\x20strict";"#,
+      r#"export function f() { foo(); "use strict"; }"#,
+      r#"export function f() { if (a) { "use strict"; } }"#,
+      r#"export {}; class A { static { "use strict"; } }"#,
+    };
+  }
+
+  #[test]
+  fn directive_invalid() {
+    assert_lint_err! {
+      NoRedundantUseStrict,
+      "\"use strict\";\nexport {};": [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (HINT, "\nexport {};"),
+        }
+      ],
+      "'use asm'; 'use strict';\nexport {};": [
+        {
+          col: 11,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (HINT, "'use asm'; \nexport {};"),
+        }
+      ],
+      r#"export function f() { "use strict"; }"#: [
+        {
+          col: 22,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (HINT, "export function f() {  }"),
+        }
+      ],
+      r#"export const f = () => { "use strict"; };"#: [
+        {
+          col: 25,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (HINT, "export const f = () => {  };"),
+        }
+      ],
+      r#"export class A { constructor() { "use strict"; } get a() { "use strict"; } }"#: [
+        {
+          col: 33,
+          message: MESSAGE,
+          hint: HINT,
+        },
+        {
+          col: 59,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      r#"export default { set a(v) { "use strict"; } };"#: [
+        {
+          col: 28,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (HINT, "export default { set a(v) {  } };"),
+        }
+      ],
+    };
+  }
+}
@@ -24,6 +24,7 @@ impl DirectiveKind for File {}
 pub struct IgnoreDirective<T: DirectiveKind> {
   range: SourceRange,
   codes: HashMap<String, CodeStatus>,
+  reason: Option<String>,
   _marker: std::marker::PhantomData<T>,
 }
 
@@ -42,6 +43,12 @@ impl<T: DirectiveKind> IgnoreDirective<T> {
     &self.codes
   }
 
+  /// The text after `--` explaining why the rules are ignored, e.g.
+  /// `// deno-lint-ignore no-explicit-any -- untyped library`.
+  pub fn reason(&self) -> Option<&str> {
+    self.reason.as_deref()
+  }
+
   pub fn has_code(&self, code: &str) -> bool {
     self.codes.contains_key(code)
   }
@@ -91,6 +98,15 @@ pub fn parse_file_ignore_directives(
   ignore_global_directive: &str,
   program: ast_view::Program,
 ) -> Option<FileIgnoreDirective> {
+  file_leading_comments(program)
+    .find_map(|comment| parse_ignore_comment(ignore_global_directive, comment))
+}
+
+/// Returns the comments placed at the top of the file, i.e. before any
+/// statement or declaration.
+pub(crate) fn file_leading_comments(
+  program: ast_view::Program,
+) -> impl Iterator<Item = &Comment> {
   // We want to get a file's leading comments, even if they come after a
   // shebang. There are three cases:
   // 1. No shebang. The file's leading comments are the program's leading
@@ -112,13 +128,11 @@ pub fn parse_file_ignore_directives(
   };
 
   let comments = program.comment_container();
-  let mut initial_comments = match (has_shebang, first_item_range) {
+  match (has_shebang, first_item_range) {
     (false, _) => comments.leading_comments(program.start()),
     (true, Some(range)) => comments.leading_comments(range.start),
     (true, None) => comments.trailing_comments(program.end()),
-  };
-  initial_comments
-    .find_map(|comment| parse_ignore_comment(ignore_global_directive, comment))
+  }
 }
 
 fn parse_ignore_comment<T: DirectiveKind>(
@@ -137,6 +151,21 @@ fn parse_ignore_comment<T: DirectiveKind>(
         .strip_prefix(ignore_diagnostic_directive)
         .unwrap();
 
+      static IGNORE_COMMENT_REASON_RE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(^|\s)--(\s|$)").unwrap());
+
+      let (comment_text, reason) =
+        match IGNORE_COMMENT_REASON_RE.find(comment_text) {
+          Some(separator) => {
+            let reason = comment_text[separator.end()..].trim();
+            (
+              &comment_text[..separator.start()],
+              (!reason.is_empty()).then(|| reason.to_string()),
+            )
+          }
+          None => (comment_text, None),
+        };
+
       static IGNORE_COMMENT_CODE_RE: Lazy<Regex> =
         Lazy::new(|| Regex::new(r",\s*|\s").unwrap());
 
@@ -156,6 +185,7 @@ fn parse_ignore_comment<T: DirectiveKind>(
       return Some(IgnoreDirective::<T> {
         range: comment.range(),
         codes,
+        reason,
         _marker: std::marker::PhantomData,
       });
     }
@@ -225,6 +255,59 @@ object | undefined {}
     });
   }
 
+  #[test]
+  fn test_parse_ignore_reasons() {
+    let source_code = r#"
+// deno-lint-ignore no-explicit-any -- comes from an untyped library
+function foo(): any {}
+
+// deno-lint-ignore no-explicit-any, no-empty -- see #123
+function foo(): any {}
+
+// deno-lint-ignore no-explicit-any --
+function foo(): any {}
+
+// deno-lint-ignore no-explicit-any
+function foo(): any {}
+
+// deno-lint-ignore -- everything
+function foo(): any {}
+  "#;
+
+    test_util::parse_and_then(source_code, |program| {
+      let line_directives =
+        parse_line_ignore_directives("deno-lint-ignore", program);
+
+      assert_eq!(line_directives.len(), 5);
+      let d = line_directives.get(&1).unwrap();
+      assert_eq!(d.codes, code_map(["no-explicit-any"]));
+      assert_eq!(d.reason(), Some("comes from an untyped library"));
+      let d = line_directives.get(&4).unwrap();
+      assert_eq!(d.codes, code_map(["no-explicit-any", "no-empty"]));
+      assert_eq!(d.reason(), Some("see #123"));
+      let d = line_directives.get(&7).unwrap();
+      assert_eq!(d.codes, code_map(["no-explicit-any"]));
+      assert_eq!(d.reason(), None);
+      let d = line_directives.get(&10).unwrap();
+      assert_eq!(d.reason(), None);
+      let d = line_directives.get(&13).unwrap();
+      assert!(d.ignore_all());
+      assert_eq!(d.reason(), Some("everything"));
+    });
+
+    test_util::parse_and_then(
+      "// deno-lint-ignore-file foo -- generated code",
+      |program| {
+        let file_directive =
+          parse_file_ignore_directives("deno-lint-ignore-file", program)
+            .unwrap();
+
+        assert_eq!(file_directive.codes, code_map(["foo"]));
+        assert_eq!(file_directive.reason(), Some("generated code"));
+      },
+    );
+  }
+
   #[test]
   fn test_parse_global_ignore_directives() {
     test_util::parse_and_then("// deno-lint-ignore-file", |program| {
@@ -6,32 +6,41 @@
 #[macro_use]
 extern crate log;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 #[macro_use]
-mod test_util;
+pub mod test_util;
 
 pub mod ast_parser;
+pub mod config;
 pub mod context;
 mod control_flow;
 pub mod diagnostic;
-mod globals;
-mod handler;
+#[cfg(feature = "profiling")]
+pub mod explain;
+pub mod globals;
+pub mod handler;
 mod ignore_directives;
 mod js_regex;
 pub mod linter;
+pub mod rule_codes;
+mod rule_summaries;
 pub mod rules;
+mod sort_imports;
 pub mod swc_util;
+mod time;
 
 pub use deno_ast::view::Program;
 pub use deno_ast::view::ProgramRef;
 
 #[cfg(test)]
 mod lint_tests {
+  use crate::config::LintConfig;
   use crate::diagnostic::LintDiagnostic;
   use crate::linter::*;
   use crate::rules::{get_recommended_rules, LintRule};
   use crate::test_util::{assert_diagnostic, parse};
   use deno_ast::ParsedSource;
+  use std::path::{Path, PathBuf};
 
   fn lint(
     source: &str,
@@ -150,6 +159,24 @@ const fooBar: any = 42;
     assert_diagnostic(&diagnostics[1], "ban-unused-ignore", 4, 3, src);
   }
 
+  #[test]
+  fn ban_unused_ignore_with_reason() {
+    // The reason isn't parsed as rule codes, and each code is checked
+    // independently
+    let src = r#"
+ // deno-lint-ignore no-explicit-any, no-empty -- the type is unknown
+ function _bar(_p: any) {
+   // pass
+ }
+      "#;
+
+    let diagnostics = lint_recommended_rules(src);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 2, 1, src);
+    assert!(diagnostics[0].message.contains("no-empty"));
+  }
+
   #[test]
   fn ban_unused_ignore_not_report_unexecuted_rule() {
     use crate::rules::camelcase::Camelcase;
@@ -242,10 +269,1434 @@ const _foo = 42;
     assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 4, 1, src);
   }
 
+  fn lint_with_config(
+    source: &str,
+    rules: Vec<&'static dyn LintRule>,
+    config: LintConfig,
+  ) -> Vec<LintDiagnostic> {
+    let linter = LinterBuilder::default().rules(rules).config(config).build();
+
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), source.to_string())
+      .expect("Failed to lint");
+    diagnostics
+  }
+
+  #[test]
+  fn config_directive_merges_with_config() {
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+    let src = r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["atob"] } } }
+window.fetch();
+window.atob();
+      "#;
+    let mut config = LintConfig::default();
+    config.rules.insert(
+      "no-window-prefix".to_string(),
+      serde_json::json!({ "allow": ["fetch"] }),
+    );
+
+    // The directive takes precedence over the provided config
+    let diagnostics = lint_with_config(src, vec![&NoWindowPrefix], config);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-window-prefix", 3, 0, src);
+
+    // The directive only applies to the file it's written in
+    let mut config = LintConfig::default();
+    config.rules.insert(
+      "no-window-prefix".to_string(),
+      serde_json::json!({ "allow": ["fetch"] }),
+    );
+    let diagnostics = lint_with_config(
+      "window.fetch();\nwindow.atob();",
+      vec![&NoWindowPrefix],
+      config,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line_index, 1);
+  }
+
+  #[test]
+  fn builder_enables_and_disables_rules() {
+    use crate::rules::no_var::NoVar;
+    let lint_codes = |builder: LinterBuilder| {
+      let (_, diagnostics) = builder
+        .build()
+        .lint(
+          "lint_test.ts".to_string(),
+          "export var a = x === NaN; // TODO\nwindow.fetch();".to_string(),
+        )
+        .expect("Failed to lint");
+      let mut codes = diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect::<Vec<_>>();
+      codes.sort();
+      codes
+    };
+
+    assert_eq!(
+      lint_codes(LinterBuilder::default().enable_tag("recommended")),
+      vec!["no-var", "no-window-prefix", "use-isnan"]
+    );
+    // Tags can also be enabled in the config
+    assert_eq!(
+      lint_codes(LinterBuilder::default().disable("no-var").config(
+        LintConfig {
+          tags: vec!["recommended".to_string()],
+          ..Default::default()
+        }
+      )),
+      vec!["no-window-prefix", "use-isnan"]
+    );
+    // Enabled rules are added to the ones set with `rules`
+    assert_eq!(
+      lint_codes(
+        LinterBuilder::default()
+          .rules(vec![&NoVar])
+          .enable("ban-untagged-todo")
+      ),
+      vec!["ban-untagged-todo", "no-var"]
+    );
+    // Disabling takes precedence, regardless of the order
+    assert_eq!(
+      lint_codes(
+        LinterBuilder::default()
+          .disable("no-var")
+          .enable_tag("recommended")
+          .enable("no-var")
+          .disable("use-isnan")
+      ),
+      vec!["no-window-prefix"]
+    );
+  }
+
+  #[test]
+  fn builder_sets_rule_options() {
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+    let mut config = LintConfig::default();
+    config.rules.insert(
+      "no-window-prefix".to_string(),
+      serde_json::json!({ "allow": ["atob"] }),
+    );
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoWindowPrefix])
+      .config(config)
+      .with_options(
+        "no-window-prefix",
+        serde_json::json!({ "allow": ["fetch"] }),
+      )
+      .build();
+    let src = "window.fetch();\nwindow.atob();";
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-window-prefix", 2, 0, src);
+  }
+
+  #[test]
+  fn builder_validates_codes_and_tags() {
+    assert!(LinterBuilder::default()
+      .enable_tag("recommended")
+      .enable("no-var")
+      .disable("use-isnan")
+      .with_options("no-window-prefix", serde_json::json!({ "allow": [] }))
+      .try_build()
+      .is_ok());
+
+    let errors = LinterBuilder::default()
+      .enable("no-such-rule")
+      .enable_tag("no-such-tag")
+      .disable("another-unknown-rule")
+      .with_options("max-nested-callbacks", serde_json::json!({ "max": "3" }))
+      .try_build()
+      .err()
+      .unwrap();
+    assert_eq!(errors.len(), 4);
+    assert_eq!(
+      errors[0],
+      LinterBuilderError::UnknownRule("no-such-rule".to_string())
+    );
+    assert_eq!(
+      errors[1],
+      LinterBuilderError::UnknownRule("another-unknown-rule".to_string())
+    );
+    assert_eq!(errors[2].to_string(), "unknown tag \"no-such-tag\"");
+    assert!(matches!(
+      &errors[3],
+      LinterBuilderError::InvalidRuleOptions { code, .. } if code == "max-nested-callbacks"
+    ));
+
+    let no_fix_config = |code: &str| LintConfig {
+      no_fix: [code.to_string()].into_iter().collect(),
+      ..Default::default()
+    };
+    assert!(LinterBuilder::default()
+      .config(no_fix_config("no-var"))
+      .try_build()
+      .is_ok());
+    let errors = LinterBuilder::default()
+      .config(no_fix_config("no-such-rule"))
+      .try_build()
+      .err()
+      .unwrap();
+    assert_eq!(
+      errors,
+      vec![LinterBuilderError::UnknownNoFixRule(
+        "no-such-rule".to_string()
+      )]
+    );
+
+    let errors = LinterBuilder::default()
+      .config(LintConfig {
+        tags: vec!["no-such-tag".to_string()],
+        ..Default::default()
+      })
+      .try_build()
+      .err()
+      .unwrap();
+    assert_eq!(
+      errors,
+      vec![LinterBuilderError::UnknownTag("no-such-tag".to_string())]
+    );
+  }
+
+  #[test]
+  fn config_directive_ignored_with_no_external_config() {
+    use crate::rules::ban_invalid_config_comment::BanInvalidConfigComment;
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+    let config = LintConfig {
+      no_external_config: true,
+      ..Default::default()
+    };
+    let src = r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["fetch"] } } }
+window.fetch();
+      "#;
+    let diagnostics =
+      lint_with_config(src, vec![&NoWindowPrefix], config.clone());
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-window-prefix", 3, 0, src);
+
+    let diagnostics = lint_with_config(
+      "// deno-lint-config { \"rules\": ",
+      vec![&BanInvalidConfigComment],
+      config,
+    );
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn no_fix_rules_report_diagnostics_without_fixes() {
+    use crate::rules::no_useless_fragment::NoUselessFragment;
+    use crate::rules::use_isnan::UseIsNaN;
+    let src = "x === NaN;";
+    let config = LintConfig {
+      no_fix: ["use-isnan".to_string()].into_iter().collect(),
+      ..Default::default()
+    };
+    let diagnostics = lint_with_config(src, vec![&UseIsNaN], config.clone());
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "use-isnan", 1, 0, src);
+    assert!(diagnostics[0].fixes.is_empty());
+
+    // Other rules keep their fixes
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoUselessFragment])
+      .config(config)
+      .media_type(deno_ast::MediaType::Tsx)
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.tsx".to_string(), "<><Foo /></>".to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].fixes.len(), 1);
+  }
+
+  #[test]
+  fn severity_is_overridden_per_rule() {
+    use crate::diagnostic::Severity;
+    use crate::rules::no_var::NoVar;
+    use crate::rules::use_isnan::UseIsNaN;
+    let src = "var a = x === NaN;";
+    let config = LintConfig {
+      severity: [("no-var".to_string(), Severity::Warning)]
+        .into_iter()
+        .collect(),
+      ..Default::default()
+    };
+    let diagnostics = lint_with_config(src, vec![&NoVar, &UseIsNaN], config);
+    assert_eq!(diagnostics.len(), 2);
+    let no_var = diagnostics.iter().find(|d| d.code == "no-var").unwrap();
+    assert_eq!(no_var.severity, Severity::Warning);
+    let json = serde_json::to_value(no_var).unwrap();
+    assert_eq!(json["severity"], "warning");
+
+    // Rules without an override report errors
+    let use_isnan = diagnostics.iter().find(|d| d.code == "use-isnan").unwrap();
+    assert_eq!(use_isnan.severity, Severity::Error);
+  }
+
+  #[test]
+  fn message_templates_are_applied() {
+    use crate::config::MessageTemplate;
+    use crate::rules::no_var::NoVar;
+    use crate::rules::use_isnan::UseIsNaN;
+    let src = "var a = x === NaN;";
+    let config = LintConfig {
+      message_templates: [(
+        "no-var".to_string(),
+        MessageTemplate::parse("{message} (see wiki/{code})").unwrap(),
+      )]
+      .into_iter()
+      .collect(),
+      ..Default::default()
+    };
+    let diagnostics = lint_with_config(src, vec![&NoVar, &UseIsNaN], config);
+    assert_eq!(diagnostics.len(), 2);
+    let no_var = diagnostics.iter().find(|d| d.code == "no-var").unwrap();
+    assert_eq!(
+      no_var.message,
+      "`var` keyword is not allowed. (see wiki/no-var)"
+    );
+    assert_eq!(
+      no_var.untemplated_message(),
+      "`var` keyword is not allowed."
+    );
+    let json = serde_json::to_value(no_var).unwrap();
+    assert_eq!(json["originalMessage"], "`var` keyword is not allowed.");
+
+    // Rules without a template keep their message
+    let use_isnan = diagnostics.iter().find(|d| d.code == "use-isnan").unwrap();
+    assert!(use_isnan.original_message.is_none());
+    assert_eq!(use_isnan.untemplated_message(), use_isnan.message);
+  }
+
+  #[test]
+  fn message_overrides_are_applied() {
+    use crate::config::{MessageOverride, MessageTemplate};
+    use crate::rules::no_var::NoVar;
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+    let src = "var a = window.fetch;\nvar b;\nvar c;";
+    let config = LintConfig {
+      message_overrides: [
+        (
+          "no-var".to_string(),
+          MessageOverride {
+            message: Some("Use `let` or `const`".to_string()),
+            hint: None,
+          },
+        ),
+        (
+          "no-window-prefix".to_string(),
+          MessageOverride {
+            message: None,
+            hint: Some("Drop `window.`".to_string()),
+          },
+        ),
+      ]
+      .into_iter()
+      .collect(),
+      message_templates: [(
+        "no-var".to_string(),
+        MessageTemplate::parse("{message} ({code})").unwrap(),
+      )]
+      .into_iter()
+      .collect(),
+      ..Default::default()
+    };
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoVar, &NoWindowPrefix])
+      .config(config)
+      .max_diagnostics_per_rule(Some(2))
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .unwrap();
+    assert_eq!(diagnostics.len(), 4);
+
+    assert_diagnostic(&diagnostics[0], "no-var", 1, 0, src);
+    assert_eq!(diagnostics[0].message, "Use `let` or `const` (no-var)");
+    assert_eq!(
+      diagnostics[0].untemplated_message(),
+      "`var` keyword is not allowed."
+    );
+    let json = serde_json::to_value(&diagnostics[0]).unwrap();
+    assert_eq!(json["message"], "Use `let` or `const` (no-var)");
+    assert_eq!(json["originalMessage"], "`var` keyword is not allowed.");
+    assert_eq!(json["code"], "no-var");
+
+    // Only the hint is replaced
+    assert_diagnostic(&diagnostics[1], "no-window-prefix", 1, 8, src);
+    assert!(diagnostics[1].original_message.is_none());
+    assert_eq!(diagnostics[1].hint.as_deref(), Some("Drop `window.`"));
+    assert_eq!(
+      serde_json::to_value(&diagnostics[1]).unwrap()["hint"],
+      "Drop `window.`"
+    );
+
+    // The summary of the suppressed diagnostics isn't replaced
+    assert_diagnostic(&diagnostics[2], "no-var", 2, 0, src);
+    assert_eq!(diagnostics[2].message, "Use `let` or `const` (no-var)");
+    assert_diagnostic(&diagnostics[3], "no-var", 3, 0, src);
+    assert_eq!(
+      diagnostics[3].message,
+      "1 more occurrence of no-var suppressed"
+    );
+    assert!(diagnostics[3].original_message.is_none());
+  }
+
+  #[test]
+  fn rules_only_run_on_their_media_types() {
+    use crate::rules::ban_unused_ignore::BanUnusedIgnore;
+    use crate::rules::no_explicit_any::NoExplicitAny;
+    let lint_as = |media_type, src: &str| {
+      let linter = LinterBuilder::default()
+        .rules(vec![&NoExplicitAny, &BanUnusedIgnore])
+        .media_type(media_type)
+        .build();
+      let (_, diagnostics) = linter
+        .lint("lint_test".to_string(), src.to_string())
+        .expect("Failed to lint");
+      diagnostics
+    };
+
+    let src = "// deno-lint-ignore no-explicit-any\nconst a = 1;";
+    let diagnostics = lint_as(deno_ast::MediaType::TypeScript, src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "ban-unused-ignore", 1, 0, src);
+
+    // The ignore directive isn't reported as unused for a rule that didn't run
+    let diagnostics = lint_as(deno_ast::MediaType::JavaScript, src);
+    assert!(diagnostics.is_empty());
+
+    let diagnostics =
+      lint_as(deno_ast::MediaType::Tsx, "const a: any = <div />;");
+    assert_eq!(diagnostics.len(), 1);
+  }
+
+  #[test]
+  fn config_directive_does_not_enable_rules() {
+    let diagnostics = lint(
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": {} } }
+window.fetch();
+      "#,
+      vec![],
+    );
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn known_globals_are_defined() {
+    use crate::rules::no_undef::NoUndef;
+    let src = "myGlobal.run(); otherGlobal;";
+    let lint_with = |known_globals| {
+      let linter = LinterBuilder::default()
+        .rules(vec![&NoUndef])
+        .known_globals(known_globals)
+        .build();
+      let (_, diagnostics) = linter
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .expect("Failed to lint");
+      diagnostics
+    };
+
+    let diagnostics = lint_with(KnownGlobals::default());
+    assert_eq!(diagnostics.len(), 2);
+
+    let diagnostics = lint_with(KnownGlobals {
+      values: ["myGlobal".to_string()].into_iter().collect(),
+      types: ["otherGlobal".to_string()].into_iter().collect(),
+    });
+    // A global type doesn't define a value of the same name
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-undef", 1, 16, src);
+  }
+
+  #[test]
+  fn custom_is_global_predicate() {
+    use crate::rules::no_undef::NoUndef;
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+    let src = "window.fetch(); sandbox.run(); Array.isArray([]);";
+    let lint_with = |builder: LinterBuilder| {
+      let (_, diagnostics) = builder
+        .rules(vec![&NoWindowPrefix, &NoUndef])
+        .build()
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .expect("Failed to lint");
+      diagnostics
+    };
+
+    let diagnostics = lint_with(LinterBuilder::default());
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "no-window-prefix", 1, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-undef", 1, 16, src);
+
+    // An environment without `window`, which provides `sandbox` instead
+    let diagnostics = lint_with(LinterBuilder::default().is_global(|name| {
+      name != "window"
+        && (name == "sandbox"
+          || crate::globals::GLOBALS.iter().any(|(g, _)| *g == name))
+    }));
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-undef", 1, 0, src);
+
+    // Known globals exist regardless of the predicate
+    let diagnostics = lint_with(
+      LinterBuilder::default()
+        .is_global(|name| name == "Array")
+        .known_globals(KnownGlobals {
+          values: ["window".to_string(), "sandbox".to_string()]
+            .into_iter()
+            .collect(),
+          types: Default::default(),
+        }),
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-window-prefix", 1, 0, src);
+  }
+
+  #[test]
+  fn is_global_predicate_ignores_implicit_bindings() {
+    use crate::rules::no_useless_assignment_to_exports::NoUselessAssignmentToExports;
+    use crate::rules::prefer_rest_params::PreferRestParams;
+    let src =
+      "function f() { return arguments; } module.exports = f; export {};";
+    // `arguments` is part of the language, and the rule reports `module`
+    // precisely because it doesn't exist in ES modules
+    let (_, diagnostics) = LinterBuilder::default()
+      .is_global(|_| false)
+      .rules(vec![&PreferRestParams, &NoUselessAssignmentToExports])
+      .build()
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[0], "prefer-rest-params", 1, 22, src);
+    assert_diagnostic(
+      &diagnostics[1],
+      "no-useless-assignment-to-exports",
+      1,
+      35,
+      src,
+    );
+  }
+
   #[test]
   fn empty_file_with_ast() {
     let parsed_source = parse("");
     let diagnostics = lint_recommended_rules_with_ast(&parsed_source);
     assert!(diagnostics.is_empty());
   }
+
+  #[test]
+  fn lint_many_programs_with_ast() {
+    use crate::rules::no_var::NoVar;
+    let linter = LinterBuilder::default().rules(vec![&NoVar]).build();
+    let first = parse("var a;\nvar b;");
+    let second = parse("let a;\nvar b;");
+
+    assert_eq!(linter.lint_with_ast(&first).len(), 2);
+    assert_eq!(linter.lint_with_ast(&second).len(), 1);
+    // Linting again gives the same result
+    let diagnostics = linter.lint_with_ast(&first);
+    assert_eq!(diagnostics.len(), 2);
+    assert_diagnostic(&diagnostics[1], "no-var", 2, 0, "var a;\nvar b;");
+  }
+
+  #[test]
+  fn lint_and_fix_until_no_fixes_apply() {
+    use crate::rules::no_useless_assignment_via_increment::NoUselessAssignmentViaIncrement;
+    use crate::rules::no_var::NoVar;
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoVar, &NoUselessAssignmentViaIncrement])
+      .build();
+
+    // The fixes of the inner assignments overlap the outer ones, so they're
+    // only applied once the outer ones are
+    let (source, diagnostics) = linter
+      .lint_and_fix(
+        "lint_test.ts".to_string(),
+        "var a = 1;\nx = x + (y = y * (z = z - 1));\n".to_string(),
+      )
+      .unwrap();
+    assert_eq!(source, "var a = 1;\nx += (y *= (z -= 1));\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-var", 1, 0, &source);
+
+    let (source, diagnostics) = linter
+      .lint_and_fix("lint_test.ts".to_string(), "let a = 1;\n".to_string())
+      .unwrap();
+    assert_eq!(source, "let a = 1;\n");
+    assert!(diagnostics.is_empty());
+
+    assert!(linter
+      .lint_and_fix("lint_test.ts".to_string(), "let = ;".to_string())
+      .is_err());
+  }
+
+  #[test]
+  fn rule_timings() {
+    use crate::rules::no_var::NoVar;
+    use crate::rules::prefer_const::PreferConst;
+    let rules: Vec<&'static dyn LintRule> = vec![&NoVar, &PreferConst];
+
+    let linter = LinterBuilder::default().rules(rules.clone()).build();
+    linter.lint_with_ast(&parse("var a;"));
+    assert!(linter.rule_timings().is_empty());
+
+    let linter = LinterBuilder::default()
+      .rules(rules)
+      .with_timing(true)
+      .build();
+    linter.lint_with_ast(&parse("var a;"));
+    let first = linter.rule_timings();
+    let mut codes = first.keys().copied().collect::<Vec<_>>();
+    codes.sort_unstable();
+    assert_eq!(codes, vec!["no-var", "prefer-const"]);
+
+    // Timings add up over all linted files
+    linter.lint_with_ast(&parse("var b;"));
+    let second = linter.rule_timings();
+    assert_eq!(second.len(), 2);
+    assert!(second["no-var"] >= first["no-var"]);
+  }
+
+  #[test]
+  fn cancellation_flag() {
+    use crate::context::Context;
+    use crate::handler::{Handler, Traverse};
+    use crate::rules::no_var::NoVar;
+    use crate::Program;
+    use deno_ast::view as ast_view;
+    use deno_ast::SourceRanged;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Cancels linting at the first identifier it visits, as if the flag
+    /// was set by another thread at that point, and reports every
+    /// identifier.
+    #[derive(Debug)]
+    struct CancellingRule {
+      code: &'static str,
+      flag: Arc<AtomicBool>,
+      visits: Arc<AtomicUsize>,
+    }
+
+    impl LintRule for CancellingRule {
+      fn code(&self) -> &'static str {
+        self.code
+      }
+
+      fn priority(&self) -> u32 {
+        1
+      }
+
+      fn lint_program_with_ast_view(
+        &self,
+        context: &mut Context,
+        program: Program<'_>,
+      ) {
+        CancellingRuleHandler(self).traverse(program, context);
+      }
+
+      #[cfg(feature = "docs")]
+      fn docs(&self) -> &'static str {
+        ""
+      }
+    }
+
+    struct CancellingRuleHandler<'a>(&'a CancellingRule);
+
+    impl Handler for CancellingRuleHandler<'_> {
+      fn ident(&mut self, ident: &ast_view::Ident, ctx: &mut Context) {
+        self.0.visits.fetch_add(1, Ordering::Relaxed);
+        self.0.flag.store(true, Ordering::Relaxed);
+        ctx.add_diagnostic(ident.range(), self.0.code, "identifier");
+      }
+    }
+
+    let src = "var a; var b; var c;";
+    let flag = Arc::new(AtomicBool::new(false));
+    let visits = Arc::new(AtomicUsize::new(0));
+    let linter = LinterBuilder::default()
+      .rules(vec![&NoVar])
+      .plugin_rule(Arc::new(CancellingRule {
+        code: "a-cancelling-rule",
+        flag: flag.clone(),
+        visits: visits.clone(),
+      }))
+      .plugin_rule(Arc::new(CancellingRule {
+        code: "b-cancelling-rule",
+        flag: flag.clone(),
+        visits: visits.clone(),
+      }))
+      .cancellation_flag(flag.clone())
+      .build();
+    let lint = || {
+      linter
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .unwrap()
+        .1
+    };
+
+    // `no-var` finishes before the flag is set. The traversal of the
+    // interrupted rule stops right away and its diagnostics are dropped, and
+    // the next rule doesn't run.
+    let diagnostics = lint();
+    assert!(flag.load(Ordering::Relaxed));
+    assert_eq!(visits.load(Ordering::Relaxed), 1);
+    assert_eq!(diagnostics.len(), 3);
+    assert!(diagnostics.iter().all(|d| d.code == "no-var"));
+
+    // Nothing runs while the flag is set
+    let diagnostics = lint();
+    assert_eq!(visits.load(Ordering::Relaxed), 1);
+    assert!(diagnostics.is_empty());
+
+    // Clearing the flag allows linting again
+    flag.store(false, Ordering::Relaxed);
+    let diagnostics = lint();
+    assert_eq!(visits.load(Ordering::Relaxed), 2);
+    assert_eq!(diagnostics.len(), 3);
+  }
+
+  #[test]
+  fn sort_diagnostics() {
+    use crate::context::Context;
+    use crate::handler::{Handler, Traverse};
+    use crate::rules::no_var::NoVar;
+    use crate::Program;
+    use deno_ast::view as ast_view;
+    use deno_ast::SourceRanged;
+    use std::sync::Arc;
+
+    // Runs after `no-var` and reports the same range
+    #[derive(Debug)]
+    struct LateRule;
+
+    impl LintRule for LateRule {
+      fn code(&self) -> &'static str {
+        "a-late-rule"
+      }
+
+      fn priority(&self) -> u32 {
+        1
+      }
+
+      fn lint_program_with_ast_view(
+        &self,
+        context: &mut Context,
+        program: Program<'_>,
+      ) {
+        LateRuleHandler.traverse(program, context);
+      }
+
+      #[cfg(feature = "docs")]
+      fn docs(&self) -> &'static str {
+        ""
+      }
+    }
+
+    struct LateRuleHandler;
+
+    impl Handler for LateRuleHandler {
+      fn var_decl(&mut self, var_decl: &ast_view::VarDecl, ctx: &mut Context) {
+        ctx.add_diagnostic(var_decl.range(), "a-late-rule", "declaration");
+      }
+
+      fn ident(&mut self, ident: &ast_view::Ident, ctx: &mut Context) {
+        ctx.add_diagnostic(ident.range(), "a-late-rule", "identifier");
+      }
+    }
+
+    let src = "var a;";
+    let builder = LinterBuilder::default()
+      .rules(vec![&NoVar])
+      .plugin_rule(Arc::new(LateRule));
+    let lint = |builder: LinterBuilder| {
+      builder
+        .build()
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .unwrap()
+        .1
+    };
+
+    let diagnostics = lint(builder.clone());
+    assert_eq!(diagnostics.len(), 3);
+    assert_diagnostic(&diagnostics[0], "a-late-rule", 1, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-var", 1, 0, src);
+    assert_diagnostic(&diagnostics[2], "a-late-rule", 1, 4, src);
+
+    let diagnostics = lint(builder.sort_diagnostics(false));
+    assert_eq!(diagnostics.len(), 3);
+    assert_diagnostic(&diagnostics[0], "no-var", 1, 0, src);
+    assert_diagnostic(&diagnostics[1], "a-late-rule", 1, 0, src);
+    assert_diagnostic(&diagnostics[2], "a-late-rule", 1, 4, src);
+  }
+
+  #[test]
+  #[cfg(feature = "profiling")]
+  fn explain_rule_at_position() {
+    use crate::explain::SuppressedBy;
+    use crate::rules::no_var::NoVar;
+    use deno_ast::view::NodeKind;
+
+    let src = r#"// deno-lint-ignore no-var
+var a = 1;
+var b = 2;
+"#;
+    let linter = LinterBuilder::default().rules(vec![&NoVar]).build();
+    let explain = |code: &str, line_index: usize, column_index: usize| {
+      linter
+        .explain(
+          "lint_test.ts".to_string(),
+          src.to_string(),
+          code,
+          line_index,
+          column_index,
+        )
+        .unwrap()
+    };
+
+    // Suppressed by the ignore directive
+    let explanation = explain("no-var", 1, 4);
+    assert!(explanation.rule_executed);
+    assert!(!explanation.file_ignored);
+    assert_eq!(
+      explanation.visited_nodes,
+      vec![
+        NodeKind::Script,
+        NodeKind::VarDecl,
+        NodeKind::VarDeclarator,
+        NodeKind::BindingIdent,
+        NodeKind::Ident
+      ]
+    );
+    assert!(explanation.reported.is_empty());
+    assert_eq!(explanation.suppressed.len(), 1);
+    assert_eq!(
+      explanation.suppressed[0].suppressed_by,
+      SuppressedBy::LineDirective { line_index: 0 }
+    );
+    assert_diagnostic(
+      &explanation.suppressed[0].diagnostic,
+      "no-var",
+      2,
+      0,
+      src,
+    );
+
+    let explanation = explain("no-var", 2, 0);
+    assert_eq!(explanation.reported.len(), 1);
+    assert!(explanation.suppressed.is_empty());
+
+    let explanation = explain("prefer-const", 2, 0);
+    assert!(!explanation.rule_executed);
+    assert!(explanation.visited_nodes.is_empty());
+
+    let explanation = linter
+      .explain(
+        "lint_test.ts".to_string(),
+        format!("// deno-lint-ignore-file\n{}", src),
+        "no-var",
+        2,
+        0,
+      )
+      .unwrap();
+    assert!(explanation.file_ignored);
+    assert!(!explanation.rule_executed);
+  }
+
+  #[test]
+  #[cfg(feature = "profiling")]
+  fn explain_position_out_of_range() {
+    use crate::explain::ExplainError;
+    use crate::rules::no_var::NoVar;
+
+    let linter = LinterBuilder::default().rules(vec![&NoVar]).build();
+    let explain = |line_index: usize, column_index: usize| {
+      linter.explain(
+        "lint_test.ts".to_string(),
+        "var a;\nvar bc;".to_string(),
+        "no-var",
+        line_index,
+        column_index,
+      )
+    };
+
+    assert!(explain(1, 7).is_ok());
+    assert!(matches!(
+      explain(2, 0),
+      Err(ExplainError::LineOutOfRange {
+        line_index: 2,
+        lines_count: 2,
+      })
+    ));
+    assert!(matches!(
+      explain(1, 8),
+      Err(ExplainError::ColumnOutOfRange {
+        line_index: 1,
+        column_index: 8,
+        line_length: 7,
+      })
+    ));
+  }
+
+  #[test]
+  fn max_diagnostics_per_rule() {
+    use crate::rules::no_var::NoVar;
+    let src = r#"
+// deno-lint-ignore no-var
+var a;
+var b;
+var c;
+var d;
+var e;
+      "#;
+    let builder = LinterBuilder::default().rules(vec![&NoVar]);
+    let lint = |builder: LinterBuilder| {
+      builder
+        .build()
+        .lint("lint_test.ts".to_string(), src.to_string())
+        .unwrap()
+        .1
+    };
+
+    // The ignored diagnostic doesn't count
+    let diagnostics = lint(builder.clone().max_diagnostics_per_rule(Some(2)));
+    assert_eq!(diagnostics.len(), 3);
+    assert_diagnostic(&diagnostics[0], "no-var", 4, 0, src);
+    assert_diagnostic(&diagnostics[1], "no-var", 5, 0, src);
+    assert_diagnostic(&diagnostics[2], "no-var", 6, 0, src);
+    assert_eq!(
+      diagnostics[2].message,
+      "2 more occurrences of no-var suppressed"
+    );
+
+    assert_eq!(lint(builder.clone()).len(), 4);
+    assert_eq!(lint(builder.max_diagnostics_per_rule(Some(0))).len(), 4);
+  }
+
+  #[derive(Debug)]
+  struct DuplicateReports;
+
+  impl LintRule for DuplicateReports {
+    fn code(&self) -> &'static str {
+      "duplicate-reports"
+    }
+
+    fn lint_program_with_ast_view(
+      &self,
+      context: &mut crate::context::Context,
+      program: crate::Program<'_>,
+    ) {
+      use deno_ast::SourceRanged;
+      let range = program.range();
+      context.add_diagnostic_with_hint(range, self.code(), "first", "hint a");
+      context.add_diagnostic_with_hint(range, self.code(), "second", "hint b");
+      context.add_diagnostic(range, "other-code", "third");
+    }
+
+    #[cfg(feature = "docs")]
+    fn docs(&self) -> &'static str {
+      ""
+    }
+  }
+
+  #[test]
+  fn merge_duplicate_diagnostics() {
+    let src = "const a = 1;";
+    let diagnostics = lint(src, vec![&DuplicateReports]);
+    assert_eq!(diagnostics.len(), 3);
+
+    let linter = LinterBuilder::default()
+      .rules(vec![&DuplicateReports])
+      .merge_duplicate_diagnostics(true)
+      .build();
+    let (_, diagnostics) = linter
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .expect("Failed to lint");
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].code, "duplicate-reports");
+    assert_eq!(diagnostics[0].message, "first");
+    assert_eq!(diagnostics[0].hint.as_deref(), Some("hint a\nhint b"));
+    assert_eq!(diagnostics[1].code, "other-code");
+  }
+
+  #[derive(Debug)]
+  struct TextOf;
+
+  impl LintRule for TextOf {
+    fn code(&self) -> &'static str {
+      "text-of"
+    }
+
+    fn lint_program_with_ast_view(
+      &self,
+      context: &mut crate::context::Context,
+      program: crate::Program<'_>,
+    ) {
+      use deno_ast::view::NodeTrait;
+      use deno_ast::{SourceRange, SourceRanged};
+      let range = program.range();
+      let texts = [
+        context.text_of(range).to_string(),
+        context
+          .text_of(SourceRange::new(range.start, range.start))
+          .to_string(),
+        context.text_of(program.children()[0].range()).to_string(),
+      ];
+      for text in texts {
+        context.add_diagnostic(range, self.code(), text);
+      }
+    }
+
+    #[cfg(feature = "docs")]
+    fn docs(&self) -> &'static str {
+      ""
+    }
+  }
+
+  #[test]
+  fn context_text_of() {
+    let src = "const 日本 = \"🦕\";\nconst b = 42;";
+    let diagnostics = lint(src, vec![&TextOf]);
+    let texts = diagnostics
+      .iter()
+      .map(|d| d.message.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(texts, vec![src, "", "const 日本 = \"🦕\";"]);
+  }
+
+  /// A file system with the given files, and the directories they're in.
+  struct MemoryFileSystem(std::collections::HashMap<PathBuf, String>);
+
+  impl MemoryFileSystem {
+    fn new<'a>(files: impl IntoIterator<Item = (&'a str, String)>) -> Self {
+      Self(
+        files
+          .into_iter()
+          .map(|(path, src)| (PathBuf::from(path), src))
+          .collect(),
+      )
+    }
+  }
+
+  impl FileSystem for MemoryFileSystem {
+    fn read_file(&self, path: &Path) -> std::io::Result<String> {
+      self.0.get(path).cloned().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "not found")
+      })
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Option<Vec<PathBuf>>> {
+      if self.0.contains_key(path) {
+        return Ok(None);
+      }
+      let mut entries = self
+        .0
+        .keys()
+        .filter_map(|file| {
+          let rest = file.strip_prefix(path).ok()?;
+          Some(path.join(rest.components().next()?))
+        })
+        .collect::<Vec<_>>();
+      entries.sort();
+      entries.dedup();
+      if entries.is_empty() {
+        // Missing files are reported when they're read
+        return Ok(None);
+      }
+      Ok(Some(entries))
+    }
+  }
+
+  #[test]
+  fn lint_paths_with_shared_config() {
+    use crate::rules::no_window_prefix::NoWindowPrefix;
+
+    let file_system = MemoryFileSystem::new([
+      ("a.ts", "window.fetch();\nwindow.atob();".to_string()),
+      ("b.tsx", "const a = <div>{window.atob()}</div>;".to_string()),
+      ("c.ts", "const a = ;".to_string()),
+      ("vendor/d.ts", "window.atob();".to_string()),
+    ]);
+
+    let mut config = LintConfig::default();
+    config.rules.insert(
+      "no-window-prefix".to_string(),
+      serde_json::json!({ "allow": ["fetch"] }),
+    );
+    let builder = LinterBuilder::default()
+      .rules(vec![&NoWindowPrefix])
+      .config(config);
+    let paths = ["a.ts", "b.tsx", "c.ts", "vendor/d.ts", "missing.ts"]
+      .into_iter()
+      .map(PathBuf::from);
+    let results = lint_paths(
+      &builder,
+      paths,
+      &["vendor/**".to_string()],
+      &file_system,
+      None,
+    )
+    .unwrap();
+
+    let paths = results.iter().map(|r| r.path.as_path()).collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      vec![
+        Path::new("a.ts"),
+        Path::new("b.tsx"),
+        Path::new("c.ts"),
+        Path::new("missing.ts")
+      ]
+    );
+    assert_eq!(results[0].result.as_ref().unwrap().len(), 1);
+    assert_eq!(results[1].result.as_ref().unwrap().len(), 1);
+    assert!(matches!(results[2].result, Err(LintPathError::Parse(_))));
+    assert!(matches!(results[3].result, Err(LintPathError::Read(_))));
+
+    let invalid_pattern =
+      lint_paths(&builder, vec![], &["a/**[".to_string()], &file_system, None);
+    assert!(invalid_pattern.is_err());
+  }
+
+  #[test]
+  fn lint_paths_walks_directories() {
+    use crate::rules::no_var::NoVar;
+
+    let file_system = MemoryFileSystem::new([
+      ("src/b.ts", "var b;".to_string()),
+      ("src/a.js", "var a;".to_string()),
+      ("src/nested/c.tsx", "var c = <div />;".to_string()),
+      ("src/data.json", "{}".to_string()),
+      ("src/README.md", "var d;".to_string()),
+      ("src/vendor/e.ts", "var e;".to_string()),
+      ("script", "var f;".to_string()),
+    ]);
+    let builder = LinterBuilder::default().rules(vec![&NoVar]);
+    let paths = ["src", "script"].into_iter().map(PathBuf::from);
+    let results = lint_paths(
+      &builder,
+      paths,
+      &["**/vendor".to_string()],
+      &file_system,
+      None,
+    )
+    .unwrap();
+
+    // Only JavaScript and TypeScript files are linted in directories, but
+    // files given explicitly are linted whatever their extension
+    let paths = results.iter().map(|r| r.path.as_path()).collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      vec![
+        Path::new("src/a.js"),
+        Path::new("src/b.ts"),
+        Path::new("src/nested/c.tsx"),
+        Path::new("script"),
+      ]
+    );
+    for result in &results {
+      assert_eq!(result.result.as_ref().unwrap().len(), 1);
+    }
+  }
+
+  #[test]
+  fn lint_paths_walks_directories_on_disk() {
+    use crate::rules::no_var::NoVar;
+
+    let dir = std::env::temp_dir()
+      .join(format!("deno_lint_walk_test_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+    std::fs::write(dir.join("a.ts"), "var a;").unwrap();
+    std::fs::write(dir.join("nested/b.ts"), "var b;\nvar c;").unwrap();
+    let builder = LinterBuilder::default().rules(vec![&NoVar]);
+    let results =
+      lint_paths(&builder, vec![dir.clone()], &[], &StdFileSystem, None);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let results = results.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].path, dir.join("a.ts"));
+    assert_eq!(results[0].result.as_ref().unwrap().len(), 1);
+    assert_eq!(results[1].path, dir.join("nested/b.ts"));
+    assert_eq!(results[1].result.as_ref().unwrap().len(), 2);
+  }
+
+  #[test]
+  fn lint_paths_with_cache() {
+    use crate::rules::no_var::NoVar;
+
+    let builder = LinterBuilder::default().rules(vec![&NoVar]);
+    let mut cache = LintCache::new();
+    let file_system = MemoryFileSystem::new([
+      ("a.ts", "var a;".to_string()),
+      ("b.ts", "var b;".to_string()),
+      ("c.ts", "const c = ;".to_string()),
+    ]);
+    let paths = || ["a.ts", "b.ts", "c.ts"].into_iter().map(PathBuf::from);
+    let results =
+      lint_paths(&builder, paths(), &[], &file_system, Some(&mut cache))
+        .unwrap();
+    assert!(results.iter().all(|r| !r.cached));
+    // Files that fail to parse aren't cached
+    assert_eq!(cache.len(), 2);
+
+    // Only changed files are linted again
+    let file_system = MemoryFileSystem::new([
+      ("a.ts", "var a;".to_string()),
+      ("b.ts", "var b;\nvar c;".to_string()),
+      ("c.ts", "const c = ;".to_string()),
+    ]);
+    let results =
+      lint_paths(&builder, paths(), &[], &file_system, Some(&mut cache))
+        .unwrap();
+    let cached = results.iter().map(|r| r.cached).collect::<Vec<_>>();
+    assert_eq!(cached, vec![true, false, false]);
+    assert_eq!(results[0].result.as_ref().unwrap().len(), 1);
+    assert_eq!(results[1].result.as_ref().unwrap().len(), 2);
+
+    #[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+    {
+      let results = lint_paths_parallel(
+        &builder,
+        paths(),
+        &[],
+        &file_system,
+        Some(&mut cache),
+      )
+      .unwrap();
+      let cached = results.iter().map(|r| r.cached).collect::<Vec<_>>();
+      assert_eq!(cached, vec![true, true, false]);
+    }
+
+    cache.clear();
+    assert!(cache.is_empty());
+  }
+
+  #[test]
+  fn lint_source_uses_media_type_of_file_name() {
+    use crate::rules::no_var::NoVar;
+
+    let src = "var a = <div />;";
+    let diagnostics =
+      lint_source("a.tsx".to_string(), src.to_string(), vec![&NoVar]).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-var", 1, 0, src);
+    assert_eq!(diagnostics[0].filename, "a.tsx");
+
+    assert!(
+      lint_source("a.ts".to_string(), src.to_string(), vec![&NoVar]).is_err()
+    );
+  }
+
+  #[test]
+  #[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+  fn lint_paths_parallel_keeps_order() {
+    use crate::rules::no_var::NoVar;
+
+    // The file number is the number of `var`s, except that every fifth
+    // file doesn't parse
+    let names = (0..40).map(|i| format!("{}.ts", i)).collect::<Vec<_>>();
+    let file_system =
+      MemoryFileSystem::new(names.iter().enumerate().map(|(i, name)| {
+        if i % 5 == 4 {
+          (name.as_str(), "const a = ;".to_string())
+        } else {
+          (name.as_str(), "var a;\n".repeat(i))
+        }
+      }));
+
+    let builder = LinterBuilder::default().rules(vec![&NoVar]);
+    let paths = names.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let results =
+      lint_paths_parallel(&builder, paths.clone(), &[], &file_system, None)
+        .unwrap();
+
+    assert_eq!(
+      results.iter().map(|r| r.path.clone()).collect::<Vec<_>>(),
+      paths
+    );
+    for (i, result) in results.iter().enumerate() {
+      if i % 5 == 4 {
+        assert!(matches!(result.result, Err(LintPathError::Parse(_))));
+      } else {
+        assert_eq!(result.result.as_ref().unwrap().len(), i);
+      }
+    }
+
+    let sequential =
+      lint_paths(&builder, paths, &[], &file_system, None).unwrap();
+    let summary = |results: &[FileLintResult]| {
+      results
+        .iter()
+        .map(|r| {
+          r.result.as_ref().ok().map(|diagnostics| {
+            diagnostics
+              .iter()
+              .map(|d| (d.code.clone(), d.range.start.line_index))
+              .collect::<Vec<_>>()
+          })
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(summary(&results), summary(&sequential));
+
+    let empty =
+      lint_paths_parallel(&builder, vec![], &[], &file_system, None).unwrap();
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  #[should_panic(expected = "1 diagnostics expected, but got 2")]
+  fn assert_lint_err_reports_unexpected_diagnostics() {
+    use crate::rules::no_var::NoVar;
+
+    assert_lint_err! {
+      NoVar,
+      "var a; var b;": [
+        {
+          col: 0,
+          message: "`var` keyword is not allowed.",
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn plugin_rule_bans_identifier() {
+    use crate::context::Context;
+    use crate::handler::{Handler, Traverse};
+    use crate::rules::no_var::NoVar;
+    use crate::Program;
+    use deno_ast::view as ast_view;
+    use deno_ast::SourceRanged;
+    use std::sync::Arc;
+
+    // A rule as a downstream crate would define it
+    #[derive(Debug)]
+    struct BanFoo;
+
+    impl LintRule for BanFoo {
+      fn code(&self) -> &'static str {
+        "ban-foo"
+      }
+
+      fn lint_program_with_ast_view(
+        &self,
+        context: &mut Context,
+        program: Program<'_>,
+      ) {
+        BanFooHandler.traverse(program, context);
+      }
+
+      #[cfg(feature = "docs")]
+      fn docs(&self) -> &'static str {
+        "Disallows `foo`"
+      }
+    }
+
+    struct BanFooHandler;
+
+    impl Handler for BanFooHandler {
+      fn ident(&mut self, ident: &ast_view::Ident, ctx: &mut Context) {
+        if ident.sym() == "foo" {
+          ctx.add_diagnostic_with_hint(
+            ident.range(),
+            "ban-foo",
+            "`foo` is banned",
+            "Use `bar` instead",
+          );
+        }
+      }
+    }
+
+    let src = r#"
+var foo = 1;
+// deno-lint-ignore ban-foo
+foo();
+// deno-lint-ignore ban-foo
+bar();
+      "#;
+    let builder = LinterBuilder::default()
+      .rules(vec![&NoVar])
+      .plugin_rule(Arc::new(BanFoo));
+    let (_, diagnostics) = builder
+      .clone()
+      .build()
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .unwrap();
+    assert_eq!(diagnostics.len(), 3);
+    assert_diagnostic(&diagnostics[0], "no-var", 2, 0, src);
+    assert_diagnostic(&diagnostics[1], "ban-foo", 2, 4, src);
+    assert_eq!(diagnostics[1].hint.as_deref(), Some("Use `bar` instead"));
+    assert_diagnostic(&diagnostics[2], "ban-unused-ignore", 5, 0, src);
+
+    let (_, diagnostics) = builder
+      .disable("ban-foo")
+      .build()
+      .lint("lint_test.ts".to_string(), src.to_string())
+      .unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_diagnostic(&diagnostics[0], "no-var", 2, 0, src);
+
+    // Plugin codes are valid in `noFix`
+    assert!(LinterBuilder::default()
+      .plugin_rule(Arc::new(BanFoo))
+      .config(crate::config::LintConfig {
+        no_fix: ["ban-foo".to_string()].into_iter().collect(),
+        ..Default::default()
+      })
+      .try_build()
+      .is_ok());
+
+    let rule: Arc<dyn LintRule> = Arc::new(BanFoo);
+    assert_lint_ok! { rule, "bar();" };
+    assert_lint_err! {
+      rule,
+      "bar(foo);": [
+        {
+          col: 4,
+          message: "`foo` is banned",
+          hint: "Use `bar` instead",
+        }
+      ],
+    };
+
+    // Codes of builtin rules can't be reused
+    #[derive(Debug)]
+    struct FakeNoVar;
+
+    impl LintRule for FakeNoVar {
+      fn code(&self) -> &'static str {
+        "no-var"
+      }
+
+      fn lint_program_with_ast_view(
+        &self,
+        _context: &mut Context,
+        _program: Program<'_>,
+      ) {
+      }
+
+      #[cfg(feature = "docs")]
+      fn docs(&self) -> &'static str {
+        ""
+      }
+    }
+
+    let errors = LinterBuilder::default()
+      .plugin_rule(Arc::new(FakeNoVar))
+      .try_build()
+      .err()
+      .unwrap();
+    assert_eq!(
+      errors,
+      vec![LinterBuilderError::DuplicateRule("no-var".to_string())]
+    );
+  }
 }
@@ -1,47 +1,270 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use crate::ast_parser::parse_program;
+use crate::config::{parse_config_directive, LintConfig};
 use crate::context::Context;
 use crate::control_flow::ControlFlow;
-use crate::diagnostic::LintDiagnostic;
+use crate::diagnostic::{
+  apply_fixes, limit_diagnostics_per_rule, merge_duplicate_diagnostics,
+  LintDiagnostic,
+};
+#[cfg(feature = "profiling")]
+use crate::explain::{ExplainError, ExplainRecorder, RuleExplanation};
 use crate::ignore_directives::{
   parse_file_ignore_directives, parse_line_ignore_directives,
 };
-use crate::rules::{ban_unknown_rule_code::BanUnknownRuleCode, LintRule};
+use crate::rules::{
+  ban_unknown_rule_code::BanUnknownRuleCode, get_all_rules, LintRule,
+};
+use crate::time::Instant;
+use crate::Program;
 use deno_ast::Diagnostic;
 use deno_ast::MediaType;
 use deno_ast::ParsedSource;
 use deno_ast::Scope;
+use derive_more::Display;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_json::Value;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The maximum number of times [`Linter::lint_and_fix`] lints a file.
+const MAX_LINT_AND_FIX_PASSES: usize = 10;
+
+/// Globals declared outside of the linted file, e.g. found by scanning a
+/// project's `.d.ts` files.
+///
+/// They're known in addition to the builtin globals, so that rules like
+/// `no-undef` treat them as defined.
+#[derive(Clone, Debug, Default)]
+pub struct KnownGlobals {
+  /// Names of globals that exist at runtime, like `declare var foo: string`.
+  pub values: HashSet<String>,
+  /// Names of global types, like `interface Foo {}` in a global declaration
+  /// file.
+  pub types: HashSet<String>,
+}
+
+impl KnownGlobals {
+  pub fn has_value(&self, name: &str) -> bool {
+    self.values.contains(name)
+  }
 
-use std::time::Instant;
+  pub fn has_type(&self, name: &str) -> bool {
+    self.types.contains(name)
+  }
+}
 
+/// Decides whether a global with the given name exists, for identifiers that
+/// aren't declared in the linted file. See [`LinterBuilder::is_global`].
+pub type IsGlobal = dyn Fn(&str) -> bool + Send + Sync;
+
+#[derive(Clone)]
 pub struct LinterBuilder {
   ignore_file_directive: String,
   ignore_diagnostic_directive: String,
+  config_directive: String,
   media_type: MediaType,
   rules: Vec<&'static dyn LintRule>,
+  plugin_rules: Vec<Arc<dyn LintRule>>,
+  enabled_codes: Vec<String>,
+  enabled_tags: Vec<String>,
+  disabled_codes: Vec<String>,
+  rule_options: HashMap<String, Value>,
+  config: LintConfig,
+  merge_duplicate_diagnostics: bool,
+  sort_diagnostics: bool,
+  max_diagnostics_per_rule: Option<usize>,
+  known_globals: Arc<KnownGlobals>,
+  is_global: Option<Arc<IsGlobal>>,
+  timing: bool,
+  cancellation_flag: Option<Arc<AtomicBool>>,
+}
+
+/// A problem found by [`LinterBuilder::try_build`].
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum LinterBuilderError {
+  #[display(fmt = "unknown rule \"{}\"", _0)]
+  UnknownRule(String),
+  #[display(fmt = "unknown tag \"{}\"", _0)]
+  UnknownTag(String),
+  #[display(fmt = "invalid options for rule \"{}\": {}", code, message)]
+  InvalidRuleOptions { code: String, message: String },
+  #[display(fmt = "plugin rule \"{}\" has the code of another rule", _0)]
+  DuplicateRule(String),
+  #[display(fmt = "unknown rule \"{}\" in `noFix`", _0)]
+  UnknownNoFixRule(String),
 }
 
+impl std::error::Error for LinterBuilderError {}
+
 impl Default for LinterBuilder {
   fn default() -> Self {
     Self {
       ignore_file_directive: "deno-lint-ignore-file".to_string(),
       ignore_diagnostic_directive: "deno-lint-ignore".to_string(),
+      config_directive: "deno-lint-config".to_string(),
       media_type: MediaType::TypeScript,
       rules: Vec::new(),
+      plugin_rules: Vec::new(),
+      enabled_codes: Vec::new(),
+      enabled_tags: Vec::new(),
+      disabled_codes: Vec::new(),
+      rule_options: HashMap::new(),
+      config: LintConfig::default(),
+      merge_duplicate_diagnostics: false,
+      sort_diagnostics: true,
+      max_diagnostics_per_rule: None,
+      known_globals: Default::default(),
+      is_global: None,
+      timing: false,
+      cancellation_flag: None,
     }
   }
 }
 
 impl LinterBuilder {
+  /// Builds the linter. Unknown rule codes and tags passed to `enable`,
+  /// `enable_tag`, `disable` and `with_options` are ignored; use `try_build`
+  /// to have them reported instead.
   pub fn build(self) -> Linter {
+    let rules = self.selected_rules();
+    let plugin_rules = self.selected_plugin_rules(&rules);
+    let mut config = self.config;
+    config.rules.extend(self.rule_options);
     Linter::new(
       self.ignore_file_directive,
       self.ignore_diagnostic_directive,
+      self.config_directive,
       self.media_type,
-      self.rules,
+      rules,
+      plugin_rules,
+      config,
+      self.merge_duplicate_diagnostics,
+      self.sort_diagnostics,
+      self.max_diagnostics_per_rule,
+      self.known_globals,
+      self.is_global,
+      self.timing,
+      self.cancellation_flag,
     )
   }
 
+  /// Builds the linter after checking that every rule code and tag passed to
+  /// `enable`, `enable_tag`, `disable` and `with_options` exists, as well as
+  /// every tag in the `tags` config and every code in the `no_fix` config,
+  /// that the options passed to `with_options` are valid for their rule, and
+  /// that no plugin rule has the code of another rule.
+  pub fn try_build(self) -> Result<Linter, Vec<LinterBuilderError>> {
+    let mut all_rules = get_all_rules()
+      .into_iter()
+      .map(|rule| (rule.code(), rule as &dyn LintRule))
+      .collect::<HashMap<_, _>>();
+    let mut errors = Vec::new();
+    for rule in &self.plugin_rules {
+      if all_rules.insert(rule.code(), rule.as_ref()).is_some() {
+        errors.push(LinterBuilderError::DuplicateRule(rule.code().to_owned()));
+      }
+    }
+    let all_tags = all_rules
+      .values()
+      .flat_map(|rule| rule.tags().iter().copied())
+      .collect::<HashSet<_>>();
+
+    for code in self.enabled_codes.iter().chain(&self.disabled_codes) {
+      if !all_rules.contains_key(code.as_str()) {
+        errors.push(LinterBuilderError::UnknownRule(code.clone()));
+      }
+    }
+    for tag in self.enabled_tags.iter().chain(&self.config.tags) {
+      if !all_tags.contains(tag.as_str()) {
+        errors.push(LinterBuilderError::UnknownTag(tag.clone()));
+      }
+    }
+    let mut option_codes = self.rule_options.keys().collect::<Vec<_>>();
+    option_codes.sort_unstable();
+    for code in option_codes {
+      let Some(rule) = all_rules.get(code.as_str()) else {
+        errors.push(LinterBuilderError::UnknownRule(code.clone()));
+        continue;
+      };
+      if let Err(message) = rule.validate_options(&self.rule_options[code]) {
+        errors.push(LinterBuilderError::InvalidRuleOptions {
+          code: code.clone(),
+          message,
+        });
+      }
+    }
+    // Plugin rules aren't known to the config
+    errors.extend(
+      self
+        .config
+        .unknown_no_fix_codes()
+        .into_iter()
+        .filter(|code| !all_rules.contains_key(code))
+        .map(|code| LinterBuilderError::UnknownNoFixRule(code.to_string())),
+    );
+
+    if errors.is_empty() {
+      Ok(self.build())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// The rules set with `rules`, plus the enabled ones, minus the disabled
+  /// ones.
+  fn selected_rules(&self) -> Vec<&'static dyn LintRule> {
+    let mut codes = HashSet::new();
+    let enabled = get_all_rules().into_iter().filter(|rule| {
+      self.enabled_codes.iter().any(|code| code == rule.code())
+        || rule.tags().iter().any(|tag| {
+          self
+            .enabled_tags
+            .iter()
+            .chain(&self.config.tags)
+            .any(|enabled| enabled == tag)
+        })
+    });
+    self
+      .rules
+      .iter()
+      .copied()
+      .chain(enabled)
+      .filter(|rule| {
+        !self.disabled_codes.iter().any(|code| code == rule.code())
+      })
+      .filter(|rule| codes.insert(rule.code()))
+      .collect()
+  }
+
+  /// The plugin rules that aren't disabled and don't have the code of a
+  /// builtin rule.
+  fn selected_plugin_rules(
+    &self,
+    rules: &[&'static dyn LintRule],
+  ) -> Vec<Arc<dyn LintRule>> {
+    let mut codes = get_all_rules()
+      .into_iter()
+      .chain(rules.iter().copied())
+      .map(|rule| rule.code())
+      .collect::<HashSet<_>>();
+    self
+      .plugin_rules
+      .iter()
+      .filter(|rule| {
+        !self.disabled_codes.iter().any(|code| code == rule.code())
+      })
+      .filter(|rule| codes.insert(rule.code()))
+      .cloned()
+      .collect()
+  }
+
   /// Set name for directive that can be used to skip linting file.
   ///
   /// Defaults to "deno-lint-ignore-file".
@@ -58,6 +281,15 @@ impl LinterBuilder {
     self
   }
 
+  /// Set name for directive that can be used to configure rules for a single
+  /// file.
+  ///
+  /// Defaults to "deno-lint-config".
+  pub fn config_directive(mut self, directive: &str) -> Self {
+    self.config_directive = directive.to_owned();
+    self
+  }
+
   /// Set media type of a file to be linted.
   ///
   /// Defaults to `MediaType::TypeScript`
@@ -73,38 +305,221 @@ impl LinterBuilder {
     self.rules = rules;
     self
   }
+
+  /// Run a rule defined outside of this crate, in addition to the rules set
+  /// with `rules`. Like builtin rules, it can be disabled with `disable`,
+  /// configured with `with_options` and ignored with ignore directives.
+  ///
+  /// A plugin rule with the code of a builtin rule or of an earlier plugin
+  /// rule isn't run; `try_build` reports it instead.
+  pub fn plugin_rule(mut self, rule: Arc<dyn LintRule>) -> Self {
+    self.plugin_rules.push(rule);
+    self
+  }
+
+  /// Run the rule with the given code, in addition to the rules set with
+  /// `rules`.
+  pub fn enable(mut self, code: &str) -> Self {
+    self.enabled_codes.push(code.to_owned());
+    self
+  }
+
+  /// Run all rules with the given tag, e.g. "recommended", in addition to the
+  /// rules set with `rules`.
+  pub fn enable_tag(mut self, tag: &str) -> Self {
+    self.enabled_tags.push(tag.to_owned());
+    self
+  }
+
+  /// Don't run the rule with the given code, even if it's set with `rules`
+  /// or enabled by `enable` or `enable_tag`.
+  pub fn disable(mut self, code: &str) -> Self {
+    self.disabled_codes.push(code.to_owned());
+    self
+  }
+
+  /// Set the options of the rule with the given code. They replace the
+  /// options for that rule in the config set with `config`.
+  pub fn with_options(mut self, code: &str, options: Value) -> Self {
+    self.rule_options.insert(code.to_owned(), options);
+    self
+  }
+
+  /// Set configuration passed to the rules. The rules with the tags listed
+  /// in its `tags` are run like those enabled with `enable_tag`.
+  ///
+  /// Options embedded in a file's config directive are merged on top of it
+  /// for that file only, unless `no_external_config` is set. A config
+  /// directive can only provide options; it never changes which rules run,
+  /// which rules are listed in `no_fix`, nor the severity of rules.
+  ///
+  /// Defaults to empty config.
+  pub fn config(mut self, config: LintConfig) -> Self {
+    self.config = config;
+    self
+  }
+
+  /// Merge diagnostics reported by the same rule on the same range into a
+  /// single diagnostic, keeping the first message and all distinct hints.
+  ///
+  /// Defaults to `false`.
+  pub fn merge_duplicate_diagnostics(mut self, merge: bool) -> Self {
+    self.merge_duplicate_diagnostics = merge;
+    self
+  }
+
+  /// Sort the diagnostics of a file by their start position, then by rule
+  /// code. Diagnostics of the same rule at the same start are ordered by
+  /// their end position, and then by the order in which they were reported.
+  ///
+  /// Defaults to `true`. When `false`, diagnostics are only sorted by line,
+  /// so those on the same line keep the order in which rules reported them.
+  pub fn sort_diagnostics(mut self, sort: bool) -> Self {
+    self.sort_diagnostics = sort;
+    self
+  }
+
+  /// Report at most `max` diagnostics of each rule per file, followed by a
+  /// single diagnostic saying how many more were suppressed. Diagnostics
+  /// suppressed by ignore directives don't count towards the limit.
+  ///
+  /// Defaults to `None`, i.e. no limit. A limit of zero means no limit too.
+  pub fn max_diagnostics_per_rule(mut self, max: Option<usize>) -> Self {
+    self.max_diagnostics_per_rule = max;
+    self
+  }
+
+  /// Set globals declared outside of the linted files, which are known in
+  /// addition to the builtin ones.
+  ///
+  /// Defaults to no additional globals.
+  pub fn known_globals(mut self, known_globals: KnownGlobals) -> Self {
+    self.known_globals = Arc::new(known_globals);
+    self
+  }
+
+  /// Set the predicate that decides which globals exist in the environment
+  /// the linted files run in, e.g. to lint code for a sandbox that doesn't
+  /// provide `window`.
+  ///
+  /// Identifiers that aren't declared in a file are only treated as globals
+  /// by the rules if the predicate returns `true` for their name, or if
+  /// they're known globals. `no-undef` reports those it returns `false` for.
+  ///
+  /// Defaults to treating every undeclared identifier as a global, with
+  /// `no-undef` checking them against the builtin globals.
+  pub fn is_global(
+    mut self,
+    is_global: impl Fn(&str) -> bool + Send + Sync + 'static,
+  ) -> Self {
+    self.is_global = Some(Arc::new(is_global));
+    self
+  }
+
+  /// Measure the wall-clock time each rule spends linting, which
+  /// [`Linter::rule_timings`] returns.
+  ///
+  /// Defaults to `false`, in which case the clock isn't read at all.
+  pub fn with_timing(mut self, timing: bool) -> Self {
+    self.timing = timing;
+    self
+  }
+
+  /// A flag that cancels linting when set, e.g. by an editor when the file
+  /// changes again before linting finishes. It's checked before each rule
+  /// and at every node that rules visit with [`Traverse`], so linting returns
+  /// soon after the flag is set, from any thread.
+  ///
+  /// A cancelled lint returns partial results: the diagnostics of the rules
+  /// that finished before the flag was set, with ignore directives applied.
+  /// The rule that was running is dropped, since it may have seen only part
+  /// of the file, and the remaining rules aren't run. `lint_and_fix` returns
+  /// the source text as fixed by the passes before. Callers can check the
+  /// flag afterwards to tell whether the results are complete. The flag isn't
+  /// reset, so it must be cleared before linting again.
+  ///
+  /// [`Traverse`]: crate::handler::Traverse
+  pub fn cancellation_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+    self.cancellation_flag = Some(flag);
+    self
+  }
 }
 
 pub struct Linter {
   ignore_file_directive: String,
   ignore_diagnostic_directive: String,
+  config_directive: String,
   media_type: MediaType,
   rules: Vec<&'static dyn LintRule>,
+  plugin_rules: Vec<Arc<dyn LintRule>>,
+  config: LintConfig,
+  merge_duplicate_diagnostics: bool,
+  sort_diagnostics: bool,
+  max_diagnostics_per_rule: Option<usize>,
+  known_globals: Arc<KnownGlobals>,
+  is_global: Option<Arc<IsGlobal>>,
+  /// Time spent in each rule over all files linted so far, if timing is
+  /// enabled.
+  rule_timings: Option<Mutex<HashMap<&'static str, Duration>>>,
+  cancellation_flag: Option<Arc<AtomicBool>>,
 }
 
 impl Linter {
+  #[allow(clippy::too_many_arguments)]
   fn new(
     ignore_file_directive: String,
     ignore_diagnostic_directive: String,
+    config_directive: String,
     media_type: MediaType,
-    rules: Vec<&'static dyn LintRule>,
+    mut rules: Vec<&'static dyn LintRule>,
+    plugin_rules: Vec<Arc<dyn LintRule>>,
+    config: LintConfig,
+    merge_duplicate_diagnostics: bool,
+    sort_diagnostics: bool,
+    max_diagnostics_per_rule: Option<usize>,
+    known_globals: Arc<KnownGlobals>,
+    is_global: Option<Arc<IsGlobal>>,
+    timing: bool,
+    cancellation_flag: Option<Arc<AtomicBool>>,
   ) -> Self {
+    crate::rules::sort_rules_by_priority(&mut rules);
     Linter {
       ignore_file_directive,
       ignore_diagnostic_directive,
+      config_directive,
       media_type,
       rules,
+      plugin_rules,
+      config,
+      merge_duplicate_diagnostics,
+      sort_diagnostics,
+      max_diagnostics_per_rule,
+      known_globals,
+      is_global,
+      rule_timings: timing.then(Default::default),
+      cancellation_flag,
     }
   }
 
   pub fn lint(
-    mut self,
+    &self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<(ParsedSource, Vec<LintDiagnostic>), Diagnostic> {
+    self.lint_as(file_name, source_code, self.media_type)
+  }
+
+  /// Like [`Linter::lint`], but with the given media type instead of the one
+  /// set on the builder, so that one linter can lint files of any type.
+  fn lint_as(
+    &self,
     file_name: String,
     source_code: String,
+    media_type: MediaType,
   ) -> Result<(ParsedSource, Vec<LintDiagnostic>), Diagnostic> {
     let start = Instant::now();
 
-    let syntax = deno_ast::get_syntax(self.media_type);
+    let syntax = deno_ast::get_syntax(media_type);
     let parse_result = parse_program(&file_name, syntax, source_code);
 
     let end_parse_program = Instant::now();
@@ -114,34 +529,137 @@ impl Linter {
     );
 
     let parsed_source = parse_result?;
-    let diagnostics = self.lint_program(&parsed_source);
+    let diagnostics = self.lint_program(&parsed_source, media_type);
 
     let end = Instant::now();
     debug!("Linter::lint took {:#?}", end - start);
     Ok((parsed_source, diagnostics))
   }
 
+  /// Lints a program that was already parsed, e.g. by a tool that needs
+  /// the AST for other purposes too. The program isn't parsed again, nor is
+  /// its AST cloned, and the same linter can lint any number of programs.
   pub fn lint_with_ast(
-    mut self,
+    &self,
     parsed_source: &ParsedSource,
   ) -> Vec<LintDiagnostic> {
     let start = Instant::now();
-    let diagnostics = self.lint_program(parsed_source);
+    let diagnostics = self.lint_program(parsed_source, self.media_type);
     let end = Instant::now();
     debug!("Linter::lint_with_ast took {:#?}", end - start);
 
     diagnostics
   }
 
-  fn filter_diagnostics(&self, mut context: Context) -> Vec<LintDiagnostic> {
+  /// Lints the source text and applies the fixes of the diagnostics, then
+  /// lints and fixes the result again until no more fixes apply. Returns the
+  /// final source text and its diagnostics, i.e. those without a fix.
+  ///
+  /// Only the first fix of each diagnostic is applied, as with
+  /// [`apply_fixes`]. A fix that overlaps another one is skipped and applied
+  /// in the next pass if it's still reported then, so conflicting fixes are
+  /// resolved one after the other. The file is linted at most 10 times; if
+  /// fixes still apply after that, the returned diagnostics still have them.
+  ///
+  /// If fixes produce source text that doesn't parse, they are discarded and
+  /// the source text before them is returned along with its diagnostics. An
+  /// error is only returned if the original source text doesn't parse.
+  pub fn lint_and_fix(
+    &self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<(String, Vec<LintDiagnostic>), Diagnostic> {
+    let start = Instant::now();
+
+    let syntax = deno_ast::get_syntax(self.media_type);
+    let parsed_source = parse_program(&file_name, syntax, source_code.clone())?;
+    let mut diagnostics = self.lint_program(&parsed_source, self.media_type);
+    let mut source_code = source_code;
+    for _ in 1..MAX_LINT_AND_FIX_PASSES {
+      if self.is_cancelled() {
+        break;
+      }
+      let (fixed, report) = apply_fixes(&source_code, &diagnostics);
+      if report.applied.is_empty() {
+        break;
+      }
+      let Ok(parsed_source) = parse_program(&file_name, syntax, fixed.clone())
+      else {
+        break;
+      };
+      diagnostics = self.lint_program(&parsed_source, self.media_type);
+      source_code = fixed;
+    }
+
+    let end = Instant::now();
+    debug!("Linter::lint_and_fix took {:#?}", end - start);
+    Ok((source_code, diagnostics))
+  }
+
+  fn is_cancelled(&self) -> bool {
+    self.cancellation_flag.as_ref().map_or(false, |flag| {
+      flag.load(std::sync::atomic::Ordering::Relaxed)
+    })
+  }
+
+  fn filter_diagnostics(
+    &self,
+    context: &mut Context,
+    executed_rules: &[&dyn LintRule],
+  ) -> Vec<LintDiagnostic> {
     let start = Instant::now();
 
     let mut filtered_diagnostics = context.check_ignore_directive_usage();
     // Run `ban-unknown-rule-code`
     filtered_diagnostics.extend(context.ban_unknown_rule_code());
     // Run `ban-unused-ignore`
-    filtered_diagnostics.extend(context.ban_unused_ignore(&self.rules));
-    filtered_diagnostics.sort_by_key(|d| d.range.start.line_index);
+    filtered_diagnostics.extend(context.ban_unused_ignore(executed_rules));
+    if self.merge_duplicate_diagnostics {
+      filtered_diagnostics = merge_duplicate_diagnostics(filtered_diagnostics);
+    }
+    for diagnostic in &mut filtered_diagnostics {
+      // Keep reporting the diagnostics of `noFix` rules, just without fixes
+      if self.config.no_fix.contains(&diagnostic.code) {
+        diagnostic.fixes.clear();
+      }
+      if let Some(severity) = self.config.severity.get(&diagnostic.code) {
+        diagnostic.severity = *severity;
+      }
+      let message_override =
+        self.config.message_overrides.get(&diagnostic.code);
+      if let Some(hint) = message_override.and_then(|o| o.hint.as_ref()) {
+        diagnostic.hint = Some(hint.clone());
+      }
+      let mut message = message_override.and_then(|o| o.message.clone());
+      if let Some(template) =
+        self.config.message_templates.get(&diagnostic.code)
+      {
+        let reported = message.as_deref().unwrap_or(&diagnostic.message);
+        message = Some(template.render(reported, &diagnostic.code));
+      }
+      if let Some(message) = message {
+        diagnostic.original_message =
+          Some(std::mem::replace(&mut diagnostic.message, message));
+      }
+    }
+    if self.sort_diagnostics {
+      // `sort_by` is stable, so reports of the same rule on the same range
+      // keep their order
+      filtered_diagnostics.sort_by(|a, b| {
+        a.range
+          .start
+          .byte_index
+          .cmp(&b.range.start.byte_index)
+          .then_with(|| a.code.cmp(&b.code))
+          .then_with(|| a.range.end.byte_index.cmp(&b.range.end.byte_index))
+      });
+    } else {
+      filtered_diagnostics.sort_by_key(|d| d.range.start.line_index);
+    }
+    if let Some(max) = self.max_diagnostics_per_rule {
+      filtered_diagnostics =
+        limit_diagnostics_per_rule(filtered_diagnostics, max);
+    }
 
     let end = Instant::now();
     debug!("Linter::filter_diagnostics took {:#?}", end - start);
@@ -150,55 +668,558 @@ impl Linter {
   }
 
   fn lint_program(
-    &mut self,
+    &self,
     parsed_source: &ParsedSource,
+    media_type: MediaType,
   ) -> Vec<LintDiagnostic> {
     let start = Instant::now();
+    let diagnostics = parsed_source.with_view(|pg| {
+      match self.create_context(parsed_source, pg, media_type) {
+        Some(mut context) => self.run_rules(&mut context, pg),
+        None => vec![],
+      }
+    });
+
+    let end = Instant::now();
+    debug!("Linter::lint_module took {:#?}", end - start);
+
+    diagnostics
+  }
+
+  /// Creates the context for linting the program, or returns `None` if the
+  /// file is skipped.
+  fn create_context<'view>(
+    &self,
+    parsed_source: &ParsedSource,
+    pg: Program<'view>,
+    media_type: MediaType,
+  ) -> Option<Context<'view>> {
     let check_unknown_rules = self
       .rules
       .iter()
       .any(|a| a.code() == (BanUnknownRuleCode).code());
     let control_flow = ControlFlow::analyze(parsed_source);
-    let diagnostics = parsed_source.with_view(|pg| {
-      let file_ignore_directive =
-        parse_file_ignore_directives(&self.ignore_file_directive, pg);
+    let file_ignore_directive =
+      parse_file_ignore_directives(&self.ignore_file_directive, pg);
 
-      // If a global ignore directive that has no codes specified exists, we must skip linting on
-      // this file.
-      if matches!(file_ignore_directive, Some(ref file_ignore) if file_ignore.ignore_all())
-      {
-        return vec![];
-      }
+    // If a global ignore directive that has no codes specified exists, we must skip linting on
+    // this file.
+    if matches!(file_ignore_directive, Some(ref file_ignore) if file_ignore.ignore_all())
+    {
+      return None;
+    }
 
-      let line_ignore_directives =
-        parse_line_ignore_directives(&self.ignore_diagnostic_directive, pg);
+    let line_ignore_directives =
+      parse_line_ignore_directives(&self.ignore_diagnostic_directive, pg);
 
-      let scope = Scope::analyze(pg);
+    let mut config = self.config.clone();
+    let config_directive = if config.no_external_config {
+      None
+    } else {
+      parse_config_directive(&self.config_directive, pg)
+    };
+    if let Some(Ok(rules)) = config_directive.as_ref().map(|d| d.rules()) {
+      config.merge_rules(rules.clone());
+    }
 
-      let mut context = Context::new(
-        parsed_source.clone(),
-        self.media_type,
-        pg,
-        file_ignore_directive,
-        line_ignore_directives,
-        scope,
-        control_flow,
-        check_unknown_rules,
-      );
+    let scope = Scope::analyze(pg);
 
-      crate::rules::sort_rules_by_priority(&mut self.rules);
+    Some(Context::new(
+      parsed_source.clone(),
+      media_type,
+      pg,
+      file_ignore_directive,
+      line_ignore_directives,
+      config_directive,
+      config,
+      scope,
+      control_flow,
+      check_unknown_rules,
+      self.plugin_rules.iter().map(|rule| rule.code()).collect(),
+      self.known_globals.clone(),
+      self.is_global.clone(),
+      self.cancellation_flag.clone(),
+    ))
+  }
 
-      // Run builtin rules
-      for rule in self.rules.iter() {
-        rule.lint_program_with_ast_view(&mut context, pg);
+  /// Runs the rules on the program and returns the diagnostics that aren't
+  /// ignored.
+  fn run_rules<'view>(
+    &self,
+    context: &mut Context<'view>,
+    pg: Program<'view>,
+  ) -> Vec<LintDiagnostic> {
+    // Rules that don't apply to the file's media type aren't run at all
+    let media_type = context.media_type();
+    let mut executed_rules = self
+      .rules
+      .iter()
+      .map(|rule| *rule as &dyn LintRule)
+      .chain(self.plugin_rules.iter().map(|rule| rule.as_ref()))
+      .filter(|rule| {
+        rule
+          .media_types()
+          .map_or(true, |media_types| media_types.contains(&media_type))
+      })
+      .collect::<Vec<_>>();
+    // Builtin rules are already sorted
+    if !self.plugin_rules.is_empty() {
+      crate::rules::sort_rules_by_priority(&mut executed_rules);
+    }
+
+    // Run builtin and plugin rules
+    let mut rule_timings = self.rule_timings.as_ref().map(|_| HashMap::new());
+    let mut completed_rules = 0;
+    for rule in executed_rules.iter() {
+      if context.is_cancelled() {
+        break;
+      }
+      let diagnostic_count = context.diagnostics().len();
+      #[cfg(feature = "profiling")]
+      if let Some(recorder) = &mut context.explain {
+        recorder.start_rule(rule.code());
+      }
+      if let Some(rule_timings) = &mut rule_timings {
+        let start = Instant::now();
+        rule.lint_program_with_ast_view(context, pg);
+        let end = Instant::now();
+        *rule_timings.entry(rule.code()).or_default() += end - start;
+      } else {
+        rule.lint_program_with_ast_view(context, pg);
+      }
+      // The rule may have been interrupted and seen only part of the file
+      if context.is_cancelled() {
+        context.truncate_diagnostics(diagnostic_count);
+        break;
       }
+      completed_rules += 1;
+    }
+    // Ignore directives of rules that didn't run aren't unused
+    executed_rules.truncate(completed_rules);
+    if let (Some(total), Some(rule_timings)) =
+      (&self.rule_timings, rule_timings)
+    {
+      let mut total = total.lock().unwrap();
+      for (code, duration) in rule_timings {
+        *total.entry(code).or_default() += duration;
+      }
+    }
+
+    self.filter_diagnostics(context, &executed_rules)
+  }
+
+  /// The wall-clock time each rule spent in `lint_program_with_ast_view`,
+  /// summed over all files this linter has linted so far. Rules that weren't
+  /// run, e.g. because of the media type, aren't included.
+  ///
+  /// Empty unless timing was enabled with [`LinterBuilder::with_timing`].
+  /// On WebAssembly every duration is zero.
+  pub fn rule_timings(&self) -> HashMap<&'static str, Duration> {
+    self
+      .rule_timings
+      .as_ref()
+      .map(|timings| timings.lock().unwrap().clone())
+      .unwrap_or_default()
+  }
 
-      self.filter_diagnostics(context)
+  /// Lints the source text like [`Linter::lint`], and explains why the rule
+  /// with the given code did or didn't report a diagnostic at the 0-indexed
+  /// line and column.
+  ///
+  /// This is meant for debugging rules and ignore directives, and lints the
+  /// file with all rules of the linter. Returns an error if the file doesn't
+  /// parse or the position isn't in it.
+  #[cfg(feature = "profiling")]
+  pub fn explain(
+    &self,
+    file_name: String,
+    source_code: String,
+    code: &str,
+    line_index: usize,
+    column_index: usize,
+  ) -> Result<RuleExplanation, ExplainError> {
+    let syntax = deno_ast::get_syntax(self.media_type);
+    let parsed_source = parse_program(&file_name, syntax, source_code)
+      .map_err(ExplainError::Parse)?;
+    let text_info = parsed_source.text_info();
+    let lines_count = text_info.lines_count();
+    if line_index >= lines_count {
+      return Err(ExplainError::LineOutOfRange {
+        line_index,
+        lines_count,
+      });
+    }
+    // The column may be at the end of the line
+    let line_length = text_info.line_text(line_index).chars().count();
+    if column_index > line_length {
+      return Err(ExplainError::ColumnOutOfRange {
+        line_index,
+        column_index,
+        line_length,
+      });
+    }
+    let position = text_info.loc_to_source_pos(deno_ast::LineAndColumnIndex {
+      line_index,
+      column_index,
     });
+    let byte_index = position.as_byte_index(text_info.range().start);
+    let mut recorder = ExplainRecorder::new(code, position, byte_index);
 
-    let end = Instant::now();
-    debug!("Linter::lint_module took {:#?}", end - start);
+    let explanation = parsed_source.with_view(|pg| {
+      let Some(mut context) =
+        self.create_context(&parsed_source, pg, self.media_type)
+      else {
+        recorder.file_ignored();
+        return recorder.finish(&[]);
+      };
+      context.explain = Some(recorder);
+      let diagnostics = self.run_rules(&mut context, pg);
+      context.explain.take().unwrap().finish(&diagnostics)
+    });
+    Ok(explanation)
+  }
+}
 
-    diagnostics
+/// Error that prevented a file passed to [`lint_paths`] from being linted.
+#[derive(Debug, Display)]
+pub enum LintPathError {
+  #[display(fmt = "Failed to read file: {}", _0)]
+  Read(std::io::Error),
+  #[display(fmt = "Failed to read directory: {}", _0)]
+  ReadDir(std::io::Error),
+  #[display(fmt = "Failed to parse file: {}", _0)]
+  Parse(Diagnostic),
+}
+
+impl std::error::Error for LintPathError {}
+
+/// Result of linting a single file with [`lint_paths`].
+#[derive(Debug)]
+pub struct FileLintResult {
+  pub path: PathBuf,
+  pub result: Result<Vec<LintDiagnostic>, LintPathError>,
+  /// Whether the diagnostics were taken from the [`LintCache`] rather than
+  /// linting the file again.
+  pub cached: bool,
+}
+
+/// How [`lint_paths`] reads files and directories, so that it works with any
+/// runtime or file system.
+pub trait FileSystem {
+  fn read_file(&self, path: &Path) -> std::io::Result<String>;
+
+  /// The paths of the entries of the directory at `path`, or `None` if
+  /// `path` isn't a directory.
+  fn read_dir(&self, path: &Path) -> std::io::Result<Option<Vec<PathBuf>>>;
+}
+
+/// Reads files and directories with `std::fs`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdFileSystem;
+
+impl FileSystem for StdFileSystem {
+  fn read_file(&self, path: &Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+  }
+
+  fn read_dir(&self, path: &Path) -> std::io::Result<Option<Vec<PathBuf>>> {
+    if !std::fs::metadata(path)?.is_dir() {
+      return Ok(None);
+    }
+    std::fs::read_dir(path)?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<std::io::Result<Vec<_>>>()
+      .map(Some)
+  }
+}
+
+/// Diagnostics of files linted by [`lint_paths`], so that linting them again
+/// skips the files whose text hasn't changed.
+///
+/// The diagnostics depend on the rules and config too, so a cache must only
+/// be used with the [`LinterBuilder`] it was filled with.
+#[derive(Debug, Default)]
+pub struct LintCache {
+  entries: HashMap<PathBuf, (u64, Vec<LintDiagnostic>)>,
+}
+
+impl LintCache {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The number of files with cached diagnostics.
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.clear();
+  }
+
+  fn get(&self, path: &Path, hash: u64) -> Option<Vec<LintDiagnostic>> {
+    self
+      .entries
+      .get(path)
+      .filter(|(cached_hash, _)| *cached_hash == hash)
+      .map(|(_, diagnostics)| diagnostics.clone())
+  }
+
+  fn insert(
+    &mut self,
+    path: PathBuf,
+    hash: u64,
+    diagnostics: Vec<LintDiagnostic>,
+  ) {
+    self.entries.insert(path, (hash, diagnostics));
+  }
+}
+
+fn hash_source(source_code: &str) -> u64 {
+  use std::hash::{Hash, Hasher};
+  let mut hasher = std::collections::hash_map::DefaultHasher::new();
+  source_code.hash(&mut hasher);
+  hasher.finish()
+}
+
+fn exclude_set(exclude: &[String]) -> Result<GlobSet, globset::Error> {
+  let mut exclude_set = GlobSetBuilder::new();
+  for pattern in exclude {
+    exclude_set.add(Glob::new(pattern)?);
+  }
+  exclude_set.build()
+}
+
+/// Whether a file found by walking a directory is linted, i.e. it's
+/// JavaScript or TypeScript.
+fn is_lintable(path: &Path) -> bool {
+  matches!(
+    MediaType::from_path(path),
+    MediaType::JavaScript
+      | MediaType::Jsx
+      | MediaType::Mjs
+      | MediaType::Cjs
+      | MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Cts
+      | MediaType::Dts
+      | MediaType::Dmts
+      | MediaType::Dcts
+      | MediaType::Tsx
+  )
+}
+
+/// Collects the files to lint under `path` in the order they're found, with
+/// the entries of each directory sorted. Excluded paths are skipped, along
+/// with everything under them.
+fn walk(
+  path: PathBuf,
+  exclude_set: &GlobSet,
+  file_system: &impl FileSystem,
+  is_root: bool,
+  files: &mut Vec<Result<PathBuf, FileLintResult>>,
+) {
+  if exclude_set.is_match(&path) {
+    return;
+  }
+  match file_system.read_dir(&path) {
+    Ok(Some(mut entries)) => {
+      entries.sort();
+      for entry in entries {
+        walk(entry, exclude_set, file_system, false, files);
+      }
+    }
+    // Paths given explicitly are linted whatever their extension
+    Ok(None) if is_root || is_lintable(&path) => files.push(Ok(path)),
+    Ok(None) => {}
+    Err(err) => files.push(Err(FileLintResult {
+      path,
+      result: Err(LintPathError::ReadDir(err)),
+      cached: false,
+    })),
+  }
+}
+
+fn walk_paths(
+  paths: impl IntoIterator<Item = PathBuf>,
+  exclude: &[String],
+  file_system: &impl FileSystem,
+) -> Result<Vec<Result<PathBuf, FileLintResult>>, globset::Error> {
+  let exclude_set = exclude_set(exclude)?;
+  let mut files = Vec::new();
+  for path in paths {
+    walk(path, &exclude_set, file_system, true, &mut files);
   }
+  Ok(files)
+}
+
+fn lint_path(
+  linter: &Linter,
+  path: PathBuf,
+  file_system: &impl FileSystem,
+  cache: Option<&Mutex<&mut LintCache>>,
+) -> FileLintResult {
+  let source_code = match file_system.read_file(&path) {
+    Ok(source_code) => source_code,
+    Err(err) => {
+      return FileLintResult {
+        path,
+        result: Err(LintPathError::Read(err)),
+        cached: false,
+      }
+    }
+  };
+  let hash = cache.map(|_| hash_source(&source_code));
+  if let (Some(cache), Some(hash)) = (cache, hash) {
+    if let Some(diagnostics) = cache.lock().unwrap().get(&path, hash) {
+      return FileLintResult {
+        path,
+        result: Ok(diagnostics),
+        cached: true,
+      };
+    }
+  }
+
+  let result = linter
+    .lint_as(
+      path.to_string_lossy().to_string(),
+      source_code,
+      MediaType::from_path(&path),
+    )
+    .map(|(_, diagnostics)| diagnostics)
+    .map_err(LintPathError::Parse);
+  if let (Some(cache), Some(hash), Ok(diagnostics)) = (cache, hash, &result) {
+    cache
+      .lock()
+      .unwrap()
+      .insert(path.clone(), hash, diagnostics.clone());
+  }
+  FileLintResult {
+    path,
+    result,
+    cached: false,
+  }
+}
+
+/// Lints the given files, and the JavaScript and TypeScript files in the
+/// given directories, with one linter built from `builder`, so the rules and
+/// config are shared between all of them. The media type of each file is
+/// determined from its path, and the one set on `builder` is ignored.
+///
+/// Files and directories are read with `file_system`, e.g. [`StdFileSystem`],
+/// so that this works with any runtime or file system. Directories are walked
+/// recursively, with the entries of each one in sorted order. Paths matching
+/// one of the `exclude` glob patterns are skipped during the walk, along with
+/// everything under them. Returns an error if one of the patterns is invalid.
+///
+/// With a `cache`, files whose text is the same as when they were last
+/// linted with it get their diagnostics from the cache. A file that can't be
+/// read or parsed only fails its own result.
+pub fn lint_paths(
+  builder: &LinterBuilder,
+  paths: impl IntoIterator<Item = PathBuf>,
+  exclude: &[String],
+  file_system: &impl FileSystem,
+  cache: Option<&mut LintCache>,
+) -> Result<Vec<FileLintResult>, globset::Error> {
+  let files = walk_paths(paths, exclude, file_system)?;
+  let linter = builder.clone().build();
+  let cache = cache.map(Mutex::new);
+  let results = files
+    .into_iter()
+    .map(|file| match file {
+      Ok(path) => lint_path(&linter, path, file_system, cache.as_ref()),
+      Err(result) => result,
+    })
+    .collect();
+  Ok(results)
+}
+
+/// Lints a single file with the given rules on the current thread. The media
+/// type is determined from `file_name`.
+///
+/// This doesn't depend on threads or a clock, so unlike
+/// `lint_paths_parallel` it works when compiled to `wasm32-unknown-unknown`.
+pub fn lint_source(
+  file_name: String,
+  source_code: String,
+  rules: Vec<&'static dyn LintRule>,
+) -> Result<Vec<LintDiagnostic>, Diagnostic> {
+  let linter = LinterBuilder::default()
+    .media_type(MediaType::from_path(Path::new(&file_name)))
+    .rules(rules)
+    .build();
+  linter
+    .lint(file_name, source_code)
+    .map(|(_, diagnostics)| diagnostics)
+}
+
+/// Like [`lint_paths`], but lints the files on as many threads as there are
+/// available CPUs. The rules are shared between the threads, which is why
+/// [`LintRule`] requires `Send + Sync`.
+///
+/// The results are in the same order as [`lint_paths`] returns them,
+/// regardless of which file finishes first.
+///
+/// Not available on WebAssembly or with the `wasm` feature, since
+/// `wasm32-unknown-unknown` has no threads.
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+pub fn lint_paths_parallel(
+  builder: &LinterBuilder,
+  paths: impl IntoIterator<Item = PathBuf>,
+  exclude: &[String],
+  file_system: &(impl FileSystem + Sync),
+  cache: Option<&mut LintCache>,
+) -> Result<Vec<FileLintResult>, globset::Error> {
+  let files = walk_paths(paths, exclude, file_system)?;
+  let linter = builder.clone().build();
+  let cache = cache.map(Mutex::new);
+  let mut results = Vec::with_capacity(files.len());
+  results.resize_with(files.len(), || None);
+  let mut paths = Vec::with_capacity(files.len());
+  for (index, file) in files.into_iter().enumerate() {
+    match file {
+      Ok(path) => paths.push((index, path)),
+      Err(result) => results[index] = Some(result),
+    }
+  }
+  let thread_count = std::thread::available_parallelism()
+    .map_or(1, |count| count.get())
+    .min(paths.len());
+
+  // Each thread takes the next file that hasn't been linted yet
+  let next_index = AtomicUsize::new(0);
+  std::thread::scope(|scope| {
+    let handles = (0..thread_count)
+      .map(|_| {
+        scope.spawn(|| {
+          let mut linted = Vec::new();
+          loop {
+            let next = next_index.fetch_add(1, Ordering::Relaxed);
+            let Some((index, path)) = paths.get(next) else {
+              break;
+            };
+            let result =
+              lint_path(&linter, path.clone(), file_system, cache.as_ref());
+            linted.push((*index, result));
+          }
+          linted
+        })
+      })
+      .collect::<Vec<_>>();
+    for handle in handles {
+      let linted = handle
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+      for (index, result) in linted {
+        results[index] = Some(result);
+      }
+    }
+  });
+
+  Ok(results.into_iter().flatten().collect())
 }
@@ -0,0 +1,236 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Codes of all rules returned by [`crate::rules::get_all_rules`], so that
+//! they can be referenced without spelling out strings.
+
+use derive_more::Display;
+use std::str::FromStr;
+
+macro_rules! rule_codes {
+  ($($variant:ident => $code:literal,)*) => {
+    /// Codes of all rules, in alphabetical order.
+    pub const ALL_RULE_CODES: &[&str] = &[$($code),*];
+
+    /// The code of a rule.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum RuleCode {
+      $($variant,)*
+    }
+
+    impl RuleCode {
+      /// All rule codes, in alphabetical order.
+      pub const ALL: &'static [RuleCode] = &[$(RuleCode::$variant),*];
+
+      pub const fn as_str(self) -> &'static str {
+        match self {
+          $(RuleCode::$variant => $code,)*
+        }
+      }
+    }
+
+    impl FromStr for RuleCode {
+      type Err = UnknownRuleCode;
+
+      fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match code {
+          $($code => Ok(RuleCode::$variant),)*
+          _ => Err(UnknownRuleCode(code.to_string())),
+        }
+      }
+    }
+  };
+}
+
+// Keep in sync with `get_all_rules_raw` in `rules.rs`, which is checked by
+// the tests below.
+rule_codes! {
+  AdjacentOverloadSignatures => "adjacent-overload-signatures",
+  BanInvalidConfigComment => "ban-invalid-config-comment",
+  BanTsComment => "ban-ts-comment",
+  BanTypes => "ban-types",
+  BanUnknownRuleCode => "ban-unknown-rule-code",
+  BanUntaggedIgnore => "ban-untagged-ignore",
+  BanUntaggedTodo => "ban-untagged-todo",
+  BanUnusedIgnore => "ban-unused-ignore",
+  Camelcase => "camelcase",
+  ConstructorSuper => "constructor-super",
+  DefaultParamLast => "default-param-last",
+  Eqeqeq => "eqeqeq",
+  ExplicitFunctionReturnType => "explicit-function-return-type",
+  ExplicitModuleBoundaryTypes => "explicit-module-boundary-types",
+  ForDirection => "for-direction",
+  FreshHandlerExport => "fresh-handler-export",
+  FreshServerEventHandlers => "fresh-server-event-handlers",
+  FuncStyle => "func-style",
+  GetterReturn => "getter-return",
+  GuardForIn => "guard-for-in",
+  JsxNoUselessExpressionContainer => "jsx-no-useless-expression-container",
+  MaxNestedCallbacks => "max-nested-callbacks",
+  NoArrayConstructor => "no-array-constructor",
+  NoAsyncPromiseExecutor => "no-async-promise-executor",
+  NoAwaitInLoop => "no-await-in-loop",
+  NoAwaitInSyncFn => "no-await-in-sync-fn",
+  NoCaseDeclarations => "no-case-declarations",
+  NoClassAssign => "no-class-assign",
+  NoCompareNegZero => "no-compare-neg-zero",
+  NoCondAssign => "no-cond-assign",
+  NoConstAssign => "no-const-assign",
+  NoConstantCondition => "no-constant-condition",
+  NoControlRegex => "no-control-regex",
+  NoDebugger => "no-debugger",
+  NoDeleteVar => "no-delete-var",
+  NoDeprecatedDenoApi => "no-deprecated-deno-api",
+  NoDupeArgs => "no-dupe-args",
+  NoDupeClassMembers => "no-dupe-class-members",
+  NoDupeElseIf => "no-dupe-else-if",
+  NoDupeKeys => "no-dupe-keys",
+  NoDuplicateCase => "no-duplicate-case",
+  NoEmpty => "no-empty",
+  NoEmptyCharacterClass => "no-empty-character-class",
+  NoEmptyEnum => "no-empty-enum",
+  NoEmptyInterface => "no-empty-interface",
+  NoEmptyPattern => "no-empty-pattern",
+  NoEmptyStaticBlock => "no-empty-static-block",
+  NoEval => "no-eval",
+  NoExAssign => "no-ex-assign",
+  NoExplicitAny => "no-explicit-any",
+  NoExternalImport => "no-external-import",
+  NoExtraBooleanCast => "no-extra-boolean-cast",
+  NoExtraNonNullAssertion => "no-extra-non-null-assertion",
+  NoFallthrough => "no-fallthrough",
+  NoFuncAssign => "no-func-assign",
+  NoGlobalAssign => "no-global-assign",
+  NoGlobalthisWindowPrefix => "no-globalthis-window-prefix",
+  NoImplicitDeclareNamespaceExport => "no-implicit-declare-namespace-export",
+  NoImportAssign => "no-import-assign",
+  NoInferrableTypes => "no-inferrable-types",
+  NoInnerDeclarations => "no-inner-declarations",
+  NoInvalidRegexp => "no-invalid-regexp",
+  NoInvalidTripleSlashReference => "no-invalid-triple-slash-reference",
+  NoIrregularWhitespace => "no-irregular-whitespace",
+  NoLossOfPrecisionInBigintConversion => "no-loss-of-precision-in-bigint-conversion",
+  NoMisusedNew => "no-misused-new",
+  NoNamespace => "no-namespace",
+  NoNewSymbol => "no-new-symbol",
+  NoNonNullAssertedOptionalChain => "no-non-null-asserted-optional-chain",
+  NoNonNullAssertion => "no-non-null-assertion",
+  NoObjCalls => "no-obj-calls",
+  NoOctal => "no-octal",
+  NoPrototypeBuiltins => "no-prototype-builtins",
+  NoRedeclare => "no-redeclare",
+  NoRedundantUseStrict => "no-redundant-use-strict",
+  NoRegexSpaces => "no-regex-spaces",
+  NoReturnInFinallyThatSwallows => "no-return-in-finally-that-swallows",
+  NoSelfAssign => "no-self-assign",
+  NoSetterReturn => "no-setter-return",
+  NoShadowRestrictedNames => "no-shadow-restricted-names",
+  NoSparseArrays => "no-sparse-arrays",
+  NoSyncFnInAsyncFn => "no-sync-fn-in-async-fn",
+  NoThisAlias => "no-this-alias",
+  NoThisBeforeSuper => "no-this-before-super",
+  NoThrowLiteral => "no-throw-literal",
+  NoTopLevelAwait => "no-top-level-await",
+  NoUnassignedVars => "no-unassigned-vars",
+  NoUndef => "no-undef",
+  NoUnderscoreDangle => "no-underscore-dangle",
+  NoUnnecessaryAwaitOnNonPromise => "no-unnecessary-await-on-non-promise",
+  NoUnreachable => "no-unreachable",
+  NoUnreachableLoop => "no-unreachable-loop",
+  NoUnsafeArgumentSpread => "no-unsafe-argument-spread",
+  NoUnsafeFinally => "no-unsafe-finally",
+  NoUnsafeNegation => "no-unsafe-negation",
+  NoUnusedLabels => "no-unused-labels",
+  NoUnusedVars => "no-unused-vars",
+  NoUselessAssignmentBeforeOverwriteInObject => "no-useless-assignment-before-overwrite-in-object",
+  NoUselessAssignmentBeforeThrow => "no-useless-assignment-before-throw",
+  NoUselessAssignmentChain => "no-useless-assignment-chain",
+  NoUselessAssignmentInCatch => "no-useless-assignment-in-catch",
+  NoUselessAssignmentInFinally => "no-useless-assignment-in-finally",
+  NoUselessAssignmentInReturn => "no-useless-assignment-in-return",
+  NoUselessAssignmentOperator => "no-useless-assignment-operator",
+  NoUselessAssignmentPatternDefault => "no-useless-assignment-pattern-default",
+  NoUselessAssignmentToAccumulator => "no-useless-assignment-to-accumulator",
+  NoUselessAssignmentToConditionallyUnused => "no-useless-assignment-to-conditionally-unused",
+  NoUselessAssignmentToDestructured => "no-useless-assignment-to-destructured",
+  NoUselessAssignmentToDiscardedAwait => "no-useless-assignment-to-discarded-await",
+  NoUselessAssignmentToExports => "no-useless-assignment-to-exports",
+  NoUselessAssignmentToExportsDefault => "no-useless-assignment-to-exports-default",
+  NoUselessAssignmentToImportedNamespaceProperty => "no-useless-assignment-to-imported-namespace-property",
+  NoUselessAssignmentToLoopVariable => "no-useless-assignment-to-loop-variable",
+  NoUselessAssignmentToOuterInClosure => "no-useless-assignment-to-outer-in-closure",
+  NoUselessAssignmentToOverwrittenDefaultExport => "no-useless-assignment-to-overwritten-default-export",
+  NoUselessAssignmentToParameterDefault => "no-useless-assignment-to-parameter-default",
+  NoUselessAssignmentToSameValue => "no-useless-assignment-to-same-value",
+  NoUselessAssignmentToTempInSwap => "no-useless-assignment-to-temp-in-swap",
+  NoUselessAssignmentToThisInArrow => "no-useless-assignment-to-this-in-arrow",
+  NoUselessAssignmentViaIncrement => "no-useless-assignment-via-increment",
+  NoUselessComputedInJsx => "no-useless-computed-in-jsx",
+  NoUselessErrorCapture => "no-useless-error-capture",
+  NoUselessFragment => "no-useless-fragment",
+  NoUselessRenameDefault => "no-useless-rename-default",
+  NoUselessSpreadInJsx => "no-useless-spread-in-jsx",
+  NoUselessStringRaw => "no-useless-string-raw",
+  NoVar => "no-var",
+  NoWindowPrefix => "no-window-prefix",
+  NoWith => "no-with",
+  PreferArrayFlat => "prefer-array-flat",
+  PreferAsConst => "prefer-as-const",
+  PreferAscii => "prefer-ascii",
+  PreferConst => "prefer-const",
+  PreferIncludes => "prefer-includes",
+  PreferNamespaceKeyword => "prefer-namespace-keyword",
+  PreferObjectSpread => "prefer-object-spread",
+  PreferPrimordials => "prefer-primordials",
+  PreferRestParams => "prefer-rest-params",
+  PreferSpreadInNew => "prefer-spread-in-new",
+  PreferStringStartsEndsWith => "prefer-string-starts-ends-with",
+  RequireAwait => "require-await",
+  RequireIgnoreReason => "require-ignore-reason",
+  RequireYield => "require-yield",
+  SingleVarDeclarator => "single-var-declarator",
+  SortImports => "sort-imports",
+  TripleSlashReference => "triple-slash-reference",
+  UseIsNaN => "use-isnan",
+  ValidTypeof => "valid-typeof",
+}
+
+impl std::fmt::Display for RuleCode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// The error of parsing a [`RuleCode`] from a string that isn't the code of
+/// any rule.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display(fmt = "unknown rule code \"{}\"", _0)]
+pub struct UnknownRuleCode(pub String);
+
+impl std::error::Error for UnknownRuleCode {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rules::get_all_rules;
+
+  #[test]
+  fn rule_codes_match_registry() {
+    let registry = get_all_rules()
+      .into_iter()
+      .map(|rule| rule.code())
+      .collect::<Vec<_>>();
+    assert_eq!(RuleCode::ALL.len(), registry.len());
+    assert_eq!(ALL_RULE_CODES, registry.as_slice());
+    for (rule_code, code) in RuleCode::ALL.iter().zip(ALL_RULE_CODES) {
+      assert_eq!(rule_code.as_str(), *code);
+    }
+  }
+
+  #[test]
+  fn rule_code_from_str() {
+    assert_eq!("no-var".parse::<RuleCode>(), Ok(RuleCode::NoVar));
+    assert_eq!(RuleCode::UseIsNaN.to_string(), "use-isnan");
+    let err = "no-such-rule".parse::<RuleCode>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown rule code \"no-such-rule\"");
+  }
+}
@@ -0,0 +1,170 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+//! One-line summaries of all rules, taken from the first paragraph of their
+//! docs, so that they're available without the `docs` feature.
+
+macro_rules! rule_summaries {
+  ($($code:literal => $summary:literal,)*) => {
+    /// Returns the summary of the rule with the given code, or `None` if
+    /// there's no such builtin rule.
+    pub(crate) fn rule_summary(code: &str) -> Option<&'static str> {
+      match code {
+        $($code => Some($summary),)*
+        _ => None,
+      }
+    }
+  };
+}
+
+// Keep in sync with the first paragraph of `docs/rules/*.md`, which is checked
+// by the tests in `rules.rs` when the `docs` feature is enabled.
+rule_summaries! {
+  "adjacent-overload-signatures" => "Requires overload signatures to be adjacent to each other.",
+  "ban-invalid-config-comment" => "Warns the usage of malformed config comments",
+  "ban-ts-comment" => "Disallows the use of Typescript directives without a comment.",
+  "ban-types" => "Bans the use of primitive wrapper objects (e.g. `String` the object is a wrapper of `string` the primitive) in addition to the non-explicit `Function` type and the misunderstood `Object` type.",
+  "ban-unknown-rule-code" => "Warns the usage of unknown rule codes in ignore directives",
+  "ban-untagged-ignore" => "Requires `deno-lint-ignore` to be annotated with one or more rule names.",
+  "ban-untagged-todo" => "Requires TODOs to be annotated with either a user tag (`@user`) or an issue reference (`#issue`).",
+  "ban-unused-ignore" => "Warns unused ignore directives",
+  "camelcase" => "Enforces the use of camelCase in variable names",
+  "constructor-super" => "Verifies the correct usage of constructors and calls to `super()`.",
+  "default-param-last" => "Enforces default parameter(s) to be last in the function signature.",
+  "eqeqeq" => "Enforces the use of type-safe equality operators `===` and `!==` instead of the more error prone `==` and `!=` operators.",
+  "explicit-function-return-type" => "Requires all functions to have explicit return types.",
+  "explicit-module-boundary-types" => "Requires all module exports to have fully typed declarations",
+  "for-direction" => "Requires `for` loop control variables to increment in the correct direction",
+  "fresh-handler-export" => "Checks correct naming for named fresh middleware export",
+  "fresh-server-event-handlers" => "Disallows event handlers in fresh server components",
+  "func-style" => "Enforces a consistent style for top-level functions",
+  "getter-return" => "Requires all property getter functions to return a value",
+  "guard-for-in" => "Require `for-in` loops to include an `if` statement",
+  "jsx-no-useless-expression-container" => "Disallows wrapping plain strings in JSX expression containers",
+  "max-nested-callbacks" => "Enforces a maximum depth of nested callbacks",
+  "no-array-constructor" => "Enforce conventional usage of array construction",
+  "no-async-promise-executor" => "Requires that async promise executor functions are not used",
+  "no-await-in-loop" => "Requires `await` is not used in a for loop body",
+  "no-await-in-sync-fn" => "Disallow `await` keyword inside a non-async function",
+  "no-case-declarations" => "Requires lexical declarations (`let`, `const`, `function` and `class`) in switch `case` or `default` clauses to be scoped with brackets.",
+  "no-class-assign" => "Disallows modifying variables of class declarations",
+  "no-compare-neg-zero" => "Disallows comparing against negative zero (`-0`).",
+  "no-cond-assign" => "Disallows the use of the assignment operator, `=`, in conditional statements.",
+  "no-const-assign" => "Disallows modifying a variable declared as `const`.",
+  "no-constant-condition" => "Disallows the use of a constant expression in conditional test",
+  "no-control-regex" => "Disallows the use ascii control characters in regular expressions",
+  "no-debugger" => "Disallows the use of the `debugger` statement",
+  "no-delete-var" => "Disallows the deletion of variables",
+  "no-deprecated-deno-api" => "Warns the usage of the deprecated Deno APIs",
+  "no-dupe-args" => "Disallows using an argument name more than once in a function signature",
+  "no-dupe-class-members" => "Disallows using a class member function name more than once",
+  "no-dupe-else-if" => "Disallows using the same condition twice in an `if`/`else if` statement",
+  "no-dupe-keys" => "Disallows duplicate keys in object literals.",
+  "no-duplicate-case" => "Disallows using the same case clause in a switch statement more than once",
+  "no-empty" => "Disallows the use of empty block statements.",
+  "no-empty-character-class" => "Disallows using the empty character class in a regular expression",
+  "no-empty-enum" => "Disallows the declaration of an empty enum",
+  "no-empty-interface" => "Disallows the declaration of an empty interface",
+  "no-empty-pattern" => "Disallows the use of empty patterns in destructuring",
+  "no-empty-static-block" => "Disallows empty static initialization blocks",
+  "no-eval" => "Disallows the use of `eval`",
+  "no-ex-assign" => "Disallows the reassignment of exception parameters",
+  "no-explicit-any" => "Disallows use of the `any` type",
+  "no-external-import" => "Disallows the use of external imports",
+  "no-extra-boolean-cast" => "Disallows unnecessary boolean casts",
+  "no-extra-non-null-assertion" => "Disallows unnecessary non-null assertions",
+  "no-fallthrough" => "Disallows the implicit fallthrough of case statements",
+  "no-func-assign" => "Disallows the overwriting/reassignment of an existing function",
+  "no-global-assign" => "Disallows assignment to native Javascript objects",
+  "no-globalthis-window-prefix" => "Disallows the use of Web APIs via `globalThis.window` or `self.window`.",
+  "no-implicit-declare-namespace-export" => "Disallows the use of implicit exports in [\"ambient\" namespaces].",
+  "no-import-assign" => "Disallows reassignment of imported module bindings",
+  "no-inferrable-types" => "Disallows easily inferrable types",
+  "no-inner-declarations" => "Disallows variable or function definitions in nested blocks",
+  "no-invalid-regexp" => "Disallows specifying invalid regular expressions in RegExp constructors",
+  "no-invalid-triple-slash-reference" => "Warns the wrong usage of triple-slash reference directives.",
+  "no-irregular-whitespace" => "Disallows the use of non-space or non-tab whitespace characters",
+  "no-loss-of-precision-in-bigint-conversion" => "Disallows passing number literals to `BigInt` that aren't safe integers",
+  "no-misused-new" => "Disallows defining `constructor`s for interfaces or `new` for classes",
+  "no-namespace" => "Disallows the use of `namespace` and `module` keywords in TypeScript code.",
+  "no-new-symbol" => "Disallows the use of `new` operators with built-in `Symbol`s",
+  "no-non-null-asserted-optional-chain" => "",
+  "no-non-null-assertion" => "",
+  "no-obj-calls" => "Disallows calling built-in global objects like functions",
+  "no-octal" => "Disallows expressing octal numbers via numeric literals beginning with `0`",
+  "no-prototype-builtins" => "Disallows the use of `Object.prototype` builtins directly",
+  "no-redeclare" => "Disallows redeclaration of variables, functions, parameters with the same name.",
+  "no-redundant-use-strict" => "Disallows `\"use strict\"` directives in code that's already strict",
+  "no-regex-spaces" => "Disallows multiple spaces in regular expression literals.",
+  "no-return-in-finally-that-swallows" => "Disallows returning from a `finally` block when an exception may be pending",
+  "no-self-assign" => "Disallows self assignments",
+  "no-setter-return" => "Disallows returning values from setters.",
+  "no-shadow-restricted-names" => "Disallows shadowing of restricted names.",
+  "no-sparse-arrays" => "Disallows sparse arrays",
+  "no-sync-fn-in-async-fn" => "Disallow sync function inside async function",
+  "no-this-alias" => "Disallows assigning variables to `this`.",
+  "no-this-before-super" => "Disallows use of `this` or `super` before calling `super()` in constructors.",
+  "no-throw-literal" => "",
+  "no-top-level-await" => "Disallows the use of top level await expressions.",
+  "no-unassigned-vars" => "Disallows reading `let` or `var` variables that are never assigned",
+  "no-undef" => "",
+  "no-underscore-dangle" => "Disallows dangling underscores in identifiers",
+  "no-unnecessary-await-on-non-promise" => "Disallows `await` on values that are never promises.",
+  "no-unreachable" => "Disallows the unreachable code after the control flow statements.",
+  "no-unreachable-loop" => "Disallows loops that can run at most once",
+  "no-unsafe-argument-spread" => "Disallows spreading object literals into function arguments.",
+  "no-unsafe-finally" => "Disallows the use of control flow statements within `finally` blocks.",
+  "no-unsafe-negation" => "Disallows the usage of negation operator `!` as the left operand of relational operators.",
+  "no-unused-labels" => "Disallows unused labels.",
+  "no-unused-vars" => "Enforces all variables used at least once.",
+  "no-useless-assignment-before-overwrite-in-object" => "Disallows object literal properties whose value is computed by a call and then overwritten by a later property with the same key.",
+  "no-useless-assignment-before-throw" => "Disallows assigning a variable right before throwing when the value can never be used.",
+  "no-useless-assignment-chain" => "Disallows assigning to a local variable in the middle of an assignment chain when the variable is never read",
+  "no-useless-assignment-in-catch" => "Disallows assigning a variable in a catch block when the value is never read afterwards.",
+  "no-useless-assignment-in-finally" => "Disallows assigning a variable in a `finally` block when the value can never be read.",
+  "no-useless-assignment-in-return" => "Disallows assigning a variable only to return it right away.",
+  "no-useless-assignment-operator" => "Disallows compound assignments that don't change the value.",
+  "no-useless-assignment-pattern-default" => "Disallows destructuring default values that can never be used.",
+  "no-useless-assignment-to-accumulator" => "Disallows `reduce` callbacks that assign to the accumulator without returning it",
+  "no-useless-assignment-to-conditionally-unused" => "Disallows assigning a variable in only one branch of an `if` statement when the value is never read.",
+  "no-useless-assignment-to-destructured" => "Disallows destructuring a value into a variable that's overwritten before it's read",
+  "no-useless-assignment-to-discarded-await" => "Disallows assigning an awaited value to a variable that's never used",
+  "no-useless-assignment-to-exports" => "Disallows assigning to CommonJS `module.exports` and `exports`",
+  "no-useless-assignment-to-exports-default" => "Disallows declaring a variable only to export it as default",
+  "no-useless-assignment-to-imported-namespace-property" => "Disallows assigning to nested properties of a namespace import",
+  "no-useless-assignment-to-loop-variable" => "Disallows assigning to a loop variable when the value is never read",
+  "no-useless-assignment-to-outer-in-closure" => "Disallows assigning to an outer variable in an immediately invoked function when the value is never read",
+  "no-useless-assignment-to-overwritten-default-export" => "Disallows reassigning a variable after exporting its value with `export default`.",
+  "no-useless-assignment-to-parameter-default" => "Disallows parameter defaults that reference later parameters",
+  "no-useless-assignment-to-same-value" => "Disallows conditionally assigning a variable its own value.",
+  "no-useless-assignment-to-temp-in-swap" => "Disallows swapping two variables through a temporary variable",
+  "no-useless-assignment-to-this-in-arrow" => "Disallows assigning to properties of `this` in arrow functions where `this` isn't an object",
+  "no-useless-assignment-via-increment" => "Enforces compound assignment operators over assigning the result of an operation on the target to itself",
+  "no-useless-computed-in-jsx" => "Disallows template literals without expressions in JSX expression containers.",
+  "no-useless-error-capture" => "Requires errors thrown from a `catch` block to keep the caught error as their cause",
+  "no-useless-fragment" => "Disallows unnecessary JSX fragments",
+  "no-useless-rename-default" => "Disallows importing or exporting `default` by name when a default import or export can be used instead",
+  "no-useless-spread-in-jsx" => "Disallows spreading object literals into JSX attributes.",
+  "no-useless-string-raw" => "Disallows `String.raw` on templates that don't contain any backslashes",
+  "no-var" => "Enforces the use of block scoped variables over more error prone function scoped variables. Block scoped variables are defined using `const` and `let` keywords.",
+  "no-window-prefix" => "Disallows the use of Web APIs via the `window` object.",
+  "no-with" => "Disallows the usage of `with` statements.",
+  "prefer-array-flat" => "Recommends `.flat()` and `.flatMap()` over flattening arrays with `.concat()`",
+  "prefer-as-const" => "Recommends using const assertion (`as const`) over explicitly specifying literal types or using type assertion.",
+  "prefer-ascii" => "Ensures that the code is fully written in ASCII characters.",
+  "prefer-const" => "Recommends declaring variables with [`const`] over [`let`].",
+  "prefer-includes" => "Prefers `.includes()` over comparing the result of `.indexOf()`",
+  "prefer-namespace-keyword" => "Recommends the use of `namespace` keyword over `module` keyword when declaring TypeScript module.",
+  "prefer-object-spread" => "Suggests using object spread instead of `Object.assign` with an empty object as the target",
+  "prefer-primordials" => "Suggests using frozen intrinsics from `primordials` rather than the default globals.",
+  "prefer-rest-params" => "Suggests using rest parameters instead of the `arguments` object",
+  "prefer-spread-in-new" => "Suggests calling constructors with `new` instead of `Reflect.construct` when the arguments are an array literal",
+  "prefer-string-starts-ends-with" => "Prefers `startsWith()` and `endsWith()` over other ways of checking the start or end of a string",
+  "require-await" => "Disallows async functions that have no await expression",
+  "require-ignore-reason" => "Requires ignore directives to explain why the rules are ignored.",
+  "require-yield" => "Disallows generator functions that have no `yield`.",
+  "single-var-declarator" => "",
+  "sort-imports" => "Requires the imports at the start of a file to be sorted",
+  "triple-slash-reference" => "",
+  "use-isnan" => "Disallows comparisons to `NaN`.",
+  "valid-typeof" => "Restricts the use of the `typeof` operator to a specific set of string literals.",
+}
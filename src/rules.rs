@@ -2,10 +2,13 @@
 use crate::context::Context;
 use crate::Program;
 use crate::ProgramRef;
+use deno_ast::MediaType;
+use serde_json::Value;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
 pub mod adjacent_overload_signatures;
+pub mod ban_invalid_config_comment;
 pub mod ban_ts_comment;
 pub mod ban_types;
 pub mod ban_unknown_rule_code;
@@ -21,8 +24,11 @@ pub mod explicit_module_boundary_types;
 pub mod for_direction;
 pub mod fresh_handler_export;
 pub mod fresh_server_event_handlers;
+pub mod func_style;
 pub mod getter_return;
 pub mod guard_for_in;
+pub mod jsx_no_useless_expression_container;
+pub mod max_nested_callbacks;
 pub mod no_array_constructor;
 pub mod no_async_promise_executor;
 pub mod no_await_in_loop;
@@ -48,6 +54,7 @@ pub mod no_empty_character_class;
 pub mod no_empty_enum;
 pub mod no_empty_interface;
 pub mod no_empty_pattern;
+pub mod no_empty_static_block;
 pub mod no_eval;
 pub mod no_ex_assign;
 pub mod no_explicit_any;
@@ -57,6 +64,7 @@ pub mod no_extra_non_null_assertion;
 pub mod no_fallthrough;
 pub mod no_func_assign;
 pub mod no_global_assign;
+pub mod no_globalthis_window_prefix;
 pub mod no_implicit_declare_namespace_export;
 pub mod no_import_assign;
 pub mod no_inferrable_types;
@@ -64,6 +72,7 @@ pub mod no_inner_declarations;
 pub mod no_invalid_regexp;
 pub mod no_invalid_triple_slash_reference;
 pub mod no_irregular_whitespace;
+pub mod no_loss_of_precision_in_bigint_conversion;
 pub mod no_misused_new;
 pub mod no_namespace;
 pub mod no_new_symbol;
@@ -73,7 +82,9 @@ pub mod no_obj_calls;
 pub mod no_octal;
 pub mod no_prototype_builtins;
 pub mod no_redeclare;
+pub mod no_redundant_use_strict;
 pub mod no_regex_spaces;
+pub mod no_return_in_finally_that_swallows;
 pub mod no_self_assign;
 pub mod no_setter_return;
 pub mod no_shadow_restricted_names;
@@ -83,23 +94,65 @@ pub mod no_this_alias;
 pub mod no_this_before_super;
 pub mod no_throw_literal;
 pub mod no_top_level_await;
+pub mod no_unassigned_vars;
 pub mod no_undef;
+pub mod no_underscore_dangle;
+pub mod no_unnecessary_await_on_non_promise;
 pub mod no_unreachable;
+pub mod no_unreachable_loop;
+pub mod no_unsafe_argument_spread;
 pub mod no_unsafe_finally;
 pub mod no_unsafe_negation;
 pub mod no_unused_labels;
 pub mod no_unused_vars;
+pub mod no_useless_assignment_before_overwrite_in_object;
+pub mod no_useless_assignment_before_throw;
+pub mod no_useless_assignment_chain;
+pub mod no_useless_assignment_in_catch;
+pub mod no_useless_assignment_in_finally;
+pub mod no_useless_assignment_in_return;
+pub mod no_useless_assignment_operator;
+pub mod no_useless_assignment_pattern_default;
+pub mod no_useless_assignment_to_accumulator;
+pub mod no_useless_assignment_to_conditionally_unused;
+pub mod no_useless_assignment_to_destructured;
+pub mod no_useless_assignment_to_discarded_await;
+pub mod no_useless_assignment_to_exports;
+pub mod no_useless_assignment_to_exports_default;
+pub mod no_useless_assignment_to_imported_namespace_property;
+pub mod no_useless_assignment_to_loop_variable;
+pub mod no_useless_assignment_to_outer_in_closure;
+pub mod no_useless_assignment_to_overwritten_default_export;
+pub mod no_useless_assignment_to_parameter_default;
+pub mod no_useless_assignment_to_same_value;
+pub mod no_useless_assignment_to_temp_in_swap;
+pub mod no_useless_assignment_to_this_in_arrow;
+pub mod no_useless_assignment_via_increment;
+pub mod no_useless_computed_in_jsx;
+pub mod no_useless_error_capture;
+pub mod no_useless_fragment;
+pub mod no_useless_rename_default;
+pub mod no_useless_spread_in_jsx;
+pub mod no_useless_string_raw;
 pub mod no_var;
 pub mod no_window_prefix;
 pub mod no_with;
+pub mod prefer_array_flat;
 pub mod prefer_as_const;
 pub mod prefer_ascii;
 pub mod prefer_const;
+pub mod prefer_includes;
 pub mod prefer_namespace_keyword;
+pub mod prefer_object_spread;
 pub mod prefer_primordials;
+pub mod prefer_rest_params;
+pub mod prefer_spread_in_new;
+pub mod prefer_string_starts_ends_with;
 pub mod require_await;
+pub mod require_ignore_reason;
 pub mod require_yield;
 pub mod single_var_declarator;
+pub mod sort_imports;
 pub mod triple_slash_reference;
 pub mod use_isnan;
 pub mod valid_typeof;
@@ -126,6 +179,13 @@ pub trait LintRule: std::fmt::Debug + Send + Sync {
   #[cfg(feature = "docs")]
   fn docs(&self) -> &'static str;
 
+  /// Returns a one-line summary of what this rule is for, i.e. the first
+  /// paragraph of `docs`. Unlike `docs`, this is available without the `docs`
+  /// feature. Builtin rules always have one.
+  fn doc_short(&self) -> Option<&'static str> {
+    crate::rule_summaries::rule_summary(self.code())
+  }
+
   /// The lower the return value is, the earlier this rule will be run.
   ///
   /// By default it is 0. Some rules might want to defer being run to the end
@@ -133,8 +193,38 @@ pub trait LintRule: std::fmt::Debug + Send + Sync {
   fn priority(&self) -> u32 {
     0
   }
+
+  /// Returns the media types of the files this rule applies to. The linter
+  /// doesn't run the rule on any other files.
+  ///
+  /// By default it is `None`, meaning that the rule applies to all files.
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    None
+  }
+
+  /// Checks that the given options can be used to configure this rule,
+  /// returning a description of the problem otherwise.
+  ///
+  /// By default any options are accepted, which suits rules that don't read
+  /// any. Configurable rules should check them with
+  /// `crate::config::validate_rule_options`.
+  fn validate_options(&self, _options: &Value) -> Result<(), String> {
+    Ok(())
+  }
 }
 
+/// Media types of TypeScript files, for rules that only check TypeScript
+/// syntax.
+pub const TYPESCRIPT_MEDIA_TYPES: &[MediaType] = &[
+  MediaType::TypeScript,
+  MediaType::Mts,
+  MediaType::Cts,
+  MediaType::Dts,
+  MediaType::Dmts,
+  MediaType::Dcts,
+  MediaType::Tsx,
+];
+
 /// TODO(@magurotuna): remove this after all rules get to use ast_view
 pub fn program_ref(program: Program) -> ProgramRef {
   match program {
@@ -148,10 +238,41 @@ pub fn get_all_rules() -> Vec<&'static dyn LintRule> {
 }
 
 pub fn get_recommended_rules() -> Vec<&'static dyn LintRule> {
+  get_rules_by_tag("recommended")
+}
+
+/// Returns the rule with the given code, or `None` if there's no such rule.
+pub fn get_rule_by_code(code: &str) -> Option<&'static dyn LintRule> {
   get_all_rules_raw()
     .into_iter()
-    .filter(|r| r.tags().contains(&"recommended"))
-    .collect()
+    .find(|rule| rule.code() == code)
+}
+
+/// Returns the rules that have the given tag, sorted alphabetically by code.
+pub fn get_rules_by_tag(tag: &str) -> Vec<&'static dyn LintRule> {
+  let mut rules = get_all_rules_raw()
+    .into_iter()
+    .filter(|rule| rule.tags().contains(&tag))
+    .collect::<Vec<_>>();
+  rules.sort_by_key(|rule| rule.code());
+  rules
+}
+
+/// Returns the distinct tags of all rules, sorted alphabetically.
+pub fn categories() -> Vec<&'static str> {
+  let mut tags = get_all_rules_raw()
+    .into_iter()
+    .flat_map(|rule| rule.tags().iter().copied())
+    .collect::<Vec<_>>();
+  tags.sort_unstable();
+  tags.dedup();
+  tags
+}
+
+/// Returns the codes of all rules, sorted alphabetically. The same codes are
+/// available as [`crate::rule_codes::ALL_RULE_CODES`].
+pub fn all_rule_codes() -> Vec<&'static str> {
+  crate::rule_codes::ALL_RULE_CODES.to_vec()
 }
 
 /// Returns a list of rules after filtering.
@@ -211,7 +332,7 @@ pub fn get_filtered_rules(
 }
 
 /// Sort lint rules by priority and alphabetically.
-pub(crate) fn sort_rules_by_priority(rules: &mut [&'static dyn LintRule]) {
+pub(crate) fn sort_rules_by_priority(rules: &mut [&dyn LintRule]) {
   rules.sort_by(|rule1, rule2| {
     let priority_cmp = rule1.priority().cmp(&rule2.priority());
 
@@ -223,9 +344,11 @@ pub(crate) fn sort_rules_by_priority(rules: &mut [&'static dyn LintRule]) {
   });
 }
 
+// New rules also need a code in `crate::rule_codes`.
 fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
   vec![
     &adjacent_overload_signatures::AdjacentOverloadSignatures,
+    &ban_invalid_config_comment::BanInvalidConfigComment,
     &ban_ts_comment::BanTsComment,
     &ban_types::BanTypes,
     &ban_unknown_rule_code::BanUnknownRuleCode,
@@ -241,8 +364,11 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &for_direction::ForDirection,
     &fresh_handler_export::FreshHandlerExport,
     &fresh_server_event_handlers::FreshServerEventHandlers,
+    &func_style::FuncStyle,
     &getter_return::GetterReturn,
     &guard_for_in::GuardForIn,
+    &jsx_no_useless_expression_container::JsxNoUselessExpressionContainer,
+    &max_nested_callbacks::MaxNestedCallbacks,
     &no_array_constructor::NoArrayConstructor,
     &no_async_promise_executor::NoAsyncPromiseExecutor,
     &no_await_in_loop::NoAwaitInLoop,
@@ -267,6 +393,7 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &no_empty_enum::NoEmptyEnum,
     &no_empty_interface::NoEmptyInterface,
     &no_empty_pattern::NoEmptyPattern,
+    &no_empty_static_block::NoEmptyStaticBlock,
     &no_eval::NoEval,
     &no_ex_assign::NoExAssign,
     &no_explicit_any::NoExplicitAny,
@@ -276,6 +403,7 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &no_fallthrough::NoFallthrough,
     &no_func_assign::NoFuncAssign,
     &no_global_assign::NoGlobalAssign,
+    &no_globalthis_window_prefix::NoGlobalthisWindowPrefix,
     &no_implicit_declare_namespace_export::NoImplicitDeclareNamespaceExport,
     &no_import_assign::NoImportAssign,
     &no_inferrable_types::NoInferrableTypes,
@@ -283,6 +411,7 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &no_invalid_regexp::NoInvalidRegexp,
     &no_invalid_triple_slash_reference::NoInvalidTripleSlashReference,
     &no_irregular_whitespace::NoIrregularWhitespace,
+    &no_loss_of_precision_in_bigint_conversion::NoLossOfPrecisionInBigintConversion,
     &no_misused_new::NoMisusedNew,
     &no_namespace::NoNamespace,
     &no_new_symbol::NoNewSymbol,
@@ -292,7 +421,9 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &no_octal::NoOctal,
     &no_prototype_builtins::NoPrototypeBuiltins,
     &no_redeclare::NoRedeclare,
+    &no_redundant_use_strict::NoRedundantUseStrict,
     &no_regex_spaces::NoRegexSpaces,
+    &no_return_in_finally_that_swallows::NoReturnInFinallyThatSwallows,
     &no_self_assign::NoSelfAssign,
     &no_setter_return::NoSetterReturn,
     &no_shadow_restricted_names::NoShadowRestrictedNames,
@@ -302,23 +433,65 @@ fn get_all_rules_raw() -> Vec<&'static dyn LintRule> {
     &no_this_before_super::NoThisBeforeSuper,
     &no_throw_literal::NoThrowLiteral,
     &no_top_level_await::NoTopLevelAwait,
+    &no_unassigned_vars::NoUnassignedVars,
     &no_undef::NoUndef,
+    &no_underscore_dangle::NoUnderscoreDangle,
+    &no_unnecessary_await_on_non_promise::NoUnnecessaryAwaitOnNonPromise,
     &no_unreachable::NoUnreachable,
+    &no_unreachable_loop::NoUnreachableLoop,
+    &no_unsafe_argument_spread::NoUnsafeArgumentSpread,
     &no_unsafe_finally::NoUnsafeFinally,
     &no_unsafe_negation::NoUnsafeNegation,
     &no_unused_labels::NoUnusedLabels,
     &no_unused_vars::NoUnusedVars,
+    &no_useless_assignment_before_overwrite_in_object::NoUselessAssignmentBeforeOverwriteInObject,
+    &no_useless_assignment_before_throw::NoUselessAssignmentBeforeThrow,
+    &no_useless_assignment_chain::NoUselessAssignmentChain,
+    &no_useless_assignment_in_catch::NoUselessAssignmentInCatch,
+    &no_useless_assignment_in_finally::NoUselessAssignmentInFinally,
+    &no_useless_assignment_in_return::NoUselessAssignmentInReturn,
+    &no_useless_assignment_operator::NoUselessAssignmentOperator,
+    &no_useless_assignment_pattern_default::NoUselessAssignmentPatternDefault,
+    &no_useless_assignment_to_accumulator::NoUselessAssignmentToAccumulator,
+    &no_useless_assignment_to_conditionally_unused::NoUselessAssignmentToConditionallyUnused,
+    &no_useless_assignment_to_destructured::NoUselessAssignmentToDestructured,
+    &no_useless_assignment_to_discarded_await::NoUselessAssignmentToDiscardedAwait,
+    &no_useless_assignment_to_exports::NoUselessAssignmentToExports,
+    &no_useless_assignment_to_exports_default::NoUselessAssignmentToExportsDefault,
+    &no_useless_assignment_to_imported_namespace_property::NoUselessAssignmentToImportedNamespaceProperty,
+    &no_useless_assignment_to_loop_variable::NoUselessAssignmentToLoopVariable,
+    &no_useless_assignment_to_outer_in_closure::NoUselessAssignmentToOuterInClosure,
+    &no_useless_assignment_to_overwritten_default_export::NoUselessAssignmentToOverwrittenDefaultExport,
+    &no_useless_assignment_to_parameter_default::NoUselessAssignmentToParameterDefault,
+    &no_useless_assignment_to_same_value::NoUselessAssignmentToSameValue,
+    &no_useless_assignment_to_temp_in_swap::NoUselessAssignmentToTempInSwap,
+    &no_useless_assignment_to_this_in_arrow::NoUselessAssignmentToThisInArrow,
+    &no_useless_assignment_via_increment::NoUselessAssignmentViaIncrement,
+    &no_useless_computed_in_jsx::NoUselessComputedInJsx,
+    &no_useless_error_capture::NoUselessErrorCapture,
+    &no_useless_fragment::NoUselessFragment,
+    &no_useless_rename_default::NoUselessRenameDefault,
+    &no_useless_spread_in_jsx::NoUselessSpreadInJsx,
+    &no_useless_string_raw::NoUselessStringRaw,
     &no_var::NoVar,
     &no_window_prefix::NoWindowPrefix,
     &no_with::NoWith,
+    &prefer_array_flat::PreferArrayFlat,
     &prefer_as_const::PreferAsConst,
     &prefer_ascii::PreferAscii,
     &prefer_const::PreferConst,
+    &prefer_includes::PreferIncludes,
     &prefer_namespace_keyword::PreferNamespaceKeyword,
+    &prefer_object_spread::PreferObjectSpread,
     &prefer_primordials::PreferPrimordials,
+    &prefer_rest_params::PreferRestParams,
+    &prefer_spread_in_new::PreferSpreadInNew,
+    &prefer_string_starts_ends_with::PreferStringStartsEndsWith,
     &require_await::RequireAwait,
+    &require_ignore_reason::RequireIgnoreReason,
     &require_yield::RequireYield,
     &single_var_declarator::SingleVarDeclarator,
+    &sort_imports::SortImports,
     &triple_slash_reference::TripleSlashReference,
     &use_isnan::UseIsNaN,
     &valid_typeof::ValidTypeof,
@@ -417,6 +590,74 @@ mod tests {
     assert_eq!(rules[1].code(), "no-throw-literal");
   }
 
+  #[test]
+  fn test_get_rules_by_tag() {
+    let codes = |rules: Vec<&'static dyn LintRule>| {
+      rules
+        .into_iter()
+        .map(|rule| rule.code())
+        .collect::<Vec<_>>()
+    };
+    let recommended = codes(get_rules_by_tag("recommended"));
+    assert!(!recommended.is_empty());
+    assert!(recommended.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(recommended.contains(&"no-var"));
+    assert!(!recommended.contains(&"ban-untagged-todo"));
+    assert_eq!(codes(get_recommended_rules()), recommended);
+
+    assert!(get_rules_by_tag("no-such-tag").is_empty());
+  }
+
+  #[test]
+  fn test_all_rule_codes() {
+    let codes = all_rule_codes();
+    assert_eq!(codes.len(), get_all_rules().len());
+    assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(codes.contains(&"ban-untagged-todo"));
+  }
+
+  #[test]
+  fn all_rules_have_summary() {
+    for rule in get_all_rules() {
+      assert!(rule.doc_short().is_some(), "{} has no summary", rule.code());
+    }
+    assert_eq!(
+      get_rule_by_code("no-var").unwrap().doc_short(),
+      Some("Enforces the use of block scoped variables over more error prone function scoped variables. Block scoped variables are defined using `const` and `let` keywords.")
+    );
+  }
+
+  #[cfg(feature = "docs")]
+  #[test]
+  fn summaries_match_docs() {
+    for rule in get_all_rules() {
+      let first_paragraph = rule.docs().split("\n\n").next().unwrap();
+      let summary = first_paragraph
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ");
+      assert_eq!(rule.doc_short(), Some(summary.as_str()), "{}", rule.code());
+    }
+  }
+
+  #[test]
+  fn categories_are_tags_of_rules() {
+    let categories = categories();
+    assert!(categories.contains(&"recommended"));
+    let mut sorted = categories.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(categories, sorted);
+  }
+
+  #[test]
+  fn get_rule_by_code_finds_rule() {
+    let rule = get_rule_by_code("no-var").unwrap();
+    assert_eq!(rule.code(), "no-var");
+    assert!(get_rule_by_code("no-such-rule").is_none());
+  }
+
   #[test]
   fn ensure_lint_rules_are_sharable_across_threads() {
     use std::thread::spawn;
@@ -0,0 +1,101 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::Program;
+
+#[derive(Debug)]
+pub struct BanInvalidConfigComment;
+
+const CODE: &str = "ban-invalid-config-comment";
+const HINT: &str = "The directive must be followed by a JSON object like `{ \"rules\": { \"rule-name\": {} } }`";
+
+impl LintRule for BanInvalidConfigComment {
+  fn tags(&self) -> &'static [&'static str] {
+    &["recommended"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    _program: Program<'_>,
+  ) {
+    let maybe_error = context.config_directive().and_then(|directive| {
+      directive
+        .rules()
+        .err()
+        .map(|err| (directive.range(), err.to_string()))
+    });
+
+    if let Some((range, err)) = maybe_error {
+      context.add_diagnostic_with_hint(
+        range,
+        CODE,
+        format!("Invalid config comment: {}", err),
+        HINT,
+      );
+    }
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/ban_invalid_config_comment.md")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ban_invalid_config_comment_valid() {
+    assert_lint_ok! {
+      BanInvalidConfigComment,
+      r#"// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["fetch"] } } }"#,
+      r#"// deno-lint-config {}"#,
+      r#"
+/* deno-lint-config {
+  "rules": {}
+} */
+"#,
+      // Not placed at the top of the file
+      r#"
+const a = 42;
+// deno-lint-config { "rules": "#,
+    };
+  }
+
+  #[test]
+  fn ban_invalid_config_comment_invalid() {
+    assert_lint_err! {
+      BanInvalidConfigComment,
+      r#"// deno-lint-config { "rules": "#: [
+        {
+          col: 0,
+          message: "Invalid config comment: EOF while parsing a value at line 1 column 10",
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "eqeqeq": [] } }
+const a = 42;
+"#: [
+        {
+          line: 2,
+          col: 0,
+          message: "Invalid config comment: options for \"eqeqeq\" must be an object",
+          hint: HINT,
+        }
+      ],
+      r#"// deno-lint-config { "foo": {} }"#: [
+        {
+          col: 0,
+          message: "Invalid config comment: unknown field `foo`, expected `rules` at line 1 column 7",
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -1,8 +1,9 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
 use deno_ast::view::TsEntityName;
+use deno_ast::MediaType;
 use deno_ast::{view as ast_view, SourceRanged};
 use if_chain::if_chain;
 use std::convert::TryFrom;
@@ -81,6 +82,10 @@ impl LintRule for BanTypes {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
@@ -106,7 +111,7 @@ impl Handler for BanTypesHandler {
     if_chain! {
       if let TsEntityName::Ident(ident) = &ts_type_ref.type_name;
       if ident.ctxt() == ctx.unresolved_ctxt();
-      if ctx.scope().is_global(&ident.to_id());
+      if ctx.is_global(&ident.to_id());
       if let Ok(banned_type) = BannedType::try_from(ident.sym().as_ref());
       then {
         ctx.add_diagnostic_with_hint(
@@ -1,5 +1,6 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::config::validate_rule_options;
 use crate::handler::{Handler, Traverse};
 use crate::swc_util::StringRepr;
 
@@ -7,6 +8,8 @@ use deno_ast::view::{Node, NodeKind, NodeTrait};
 use deno_ast::{view as ast_view, SourceRange, SourceRanged};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
+use serde::Deserialize;
+use serde_json::Value;
 use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug)]
@@ -14,6 +17,62 @@ pub struct Camelcase;
 
 const CODE: &str = "camelcase";
 
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct CamelcaseOptions {
+  /// Don't check names bound by destructuring that are the same as the
+  /// property they come from, e.g. `const { snake_case } = obj;`
+  ignore_destructuring: bool,
+  /// Don't check names bound by imports.
+  ignore_imports: bool,
+  allow_leading_underscore: bool,
+  allow_trailing_underscore: bool,
+  /// Names that are allowed even if they aren't in camel case.
+  allow: Vec<String>,
+}
+
+impl Default for CamelcaseOptions {
+  fn default() -> Self {
+    Self {
+      ignore_destructuring: false,
+      ignore_imports: false,
+      allow_leading_underscore: true,
+      allow_trailing_underscore: true,
+      allow: Vec::new(),
+    }
+  }
+}
+
+impl CamelcaseOptions {
+  /// Check if the name starts or ends with underscores that aren't allowed.
+  /// Names consisting only of underscores, like `_`, are always allowed.
+  fn has_disallowed_underscores(&self, ident_name: &str) -> bool {
+    !ident_name.trim_matches('_').is_empty()
+      && ((!self.allow_leading_underscore && ident_name.starts_with('_'))
+        || (!self.allow_trailing_underscore && ident_name.ends_with('_')))
+  }
+
+  fn is_violation(&self, ident: &IdentToCheck) -> bool {
+    let ident_name = ident.get_ident_name();
+    if self.allow.iter().any(|allowed| allowed == ident_name) {
+      return false;
+    }
+    if self.ignore_destructuring {
+      if let IdentToCheck::ObjectPat {
+        key_name,
+        value_name,
+        ..
+      } = ident
+      {
+        if value_name.as_ref().map_or(true, |value| value == key_name) {
+          return false;
+        }
+      }
+    }
+    is_underscored(ident_name) || self.has_disallowed_underscores(ident_name)
+  }
+}
+
 impl LintRule for Camelcase {
   fn tags(&self) -> &'static [&'static str] {
     &[]
@@ -28,11 +87,18 @@ impl LintRule for Camelcase {
     context: &mut Context,
     program: ast_view::Program,
   ) {
-    let mut handler = CamelcaseHandler::default();
+    let mut handler = CamelcaseHandler {
+      options: context.rule_options(CODE),
+      ..Default::default()
+    };
     handler.traverse(program, context);
     handler.report_errors(context);
   }
 
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<CamelcaseOptions>(options)
+  }
+
   #[cfg(feature = "docs")]
   fn docs(&self) -> &'static str {
     include_str!("../../docs/rules/camelcase.md")
@@ -338,6 +404,7 @@ impl IdentToCheck {
 
 #[derive(Default)]
 struct CamelcaseHandler {
+  options: CamelcaseOptions,
   /// Accumulated errors to report
   errors: BTreeMap<SourceRange, IdentToCheck>,
   /// Already visited identifiers
@@ -348,19 +415,20 @@ impl CamelcaseHandler {
   /// Report accumulated errors, consuming `self`.
   fn report_errors(self, ctx: &mut Context) {
     for (range, error_ident) in self.errors {
-      ctx.add_diagnostic_with_hint(
-        range,
-        CODE,
-        error_ident.to_message(),
-        error_ident.to_hint(),
-      );
+      let name = error_ident.get_ident_name();
+      let hint = if is_underscored(name) {
+        error_ident.to_hint()
+      } else {
+        format!("Remove the leading or trailing underscores from `{}`", name)
+      };
+      ctx.add_diagnostic_with_hint(range, CODE, error_ident.to_message(), hint);
     }
   }
 
   /// Check if this ident is underscored only when it's not yet visited.
   fn check_ident<S: SourceRanged>(&mut self, range: &S, ident: IdentToCheck) {
     let range = range.range();
-    if self.visited.insert(range) && is_underscored(ident.get_ident_name()) {
+    if self.visited.insert(range) && self.options.is_violation(&ident) {
       self.errors.insert(range, ident);
     }
   }
@@ -602,6 +670,9 @@ impl Handler for CamelcaseHandler {
     import_named_specifier: &ast_view::ImportNamedSpecifier,
     _ctx: &mut Context,
   ) {
+    if self.options.ignore_imports {
+      return;
+    }
     let ast_view::ImportNamedSpecifier {
       local, imported, ..
     } = import_named_specifier;
@@ -624,6 +695,9 @@ impl Handler for CamelcaseHandler {
     import_default_specifier: &ast_view::ImportDefaultSpecifier,
     _ctx: &mut Context,
   ) {
+    if self.options.ignore_imports {
+      return;
+    }
     let ast_view::ImportDefaultSpecifier { local, .. } =
       import_default_specifier;
     self.check_ident(local, IdentToCheck::variable(local.inner));
@@ -634,6 +708,9 @@ impl Handler for CamelcaseHandler {
     import_star_as_specifier: &ast_view::ImportStarAsSpecifier,
     _ctx: &mut Context,
   ) {
+    if self.options.ignore_imports {
+      return;
+    }
     let ast_view::ImportStarAsSpecifier { local, .. } =
       import_star_as_specifier;
     self.check_ident(local, IdentToCheck::variable(local.inner));
@@ -1246,4 +1323,56 @@ mod tests {
           ],
     };
   }
+
+  #[test]
+  fn camelcase_options_valid() {
+    assert_lint_ok! {
+      Camelcase,
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"ignoreDestructuring\": true } } }\nfunction foo({ no_camelcased }) {}",
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"ignoreDestructuring\": true } } }\nfunction foo({ no_camelcased = 1 }) {}",
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"ignoreImports\": true } } }\nimport snake_cased, { a as b_c } from 'mod';",
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"ignoreImports\": true } } }\nimport * as snake_cased from 'mod';",
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"allow\": [\"snake_case\"] } } }\nconst snake_case = 1;",
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"allowLeadingUnderscore\": false } } }\nconst foo_ = 1; const _ = 2;",
+    };
+  }
+
+  #[test]
+  fn camelcase_options_invalid() {
+    assert_lint_err! {
+      Camelcase,
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"ignoreDestructuring\": true } } }\nfunction foo({ isCamelcased: no_camelcased }) {}": [
+        {
+          line: 3,
+          col: 29,
+          message: "Identifier 'no_camelcased' is not in camel case.",
+          hint: "Consider renaming `no_camelcased` to `noCamelcased`",
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"allow\": [\"snake_case\"] } } }\nconst other_snake = 1;": [
+        {
+          line: 3,
+          col: 6,
+          message: "Identifier 'other_snake' is not in camel case.",
+          hint: "Consider renaming `other_snake` to `otherSnake`",
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"allowLeadingUnderscore\": false } } }\nconst _private = 1;": [
+        {
+          line: 3,
+          col: 6,
+          message: "Identifier '_private' is not in camel case.",
+          hint: "Remove the leading or trailing underscores from `_private`",
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"camelcase\": { \"allowTrailingUnderscore\": false } } }\nfunction foo(bar_) {}": [
+        {
+          line: 3,
+          col: 13,
+          message: "Identifier 'bar_' is not in camel case.",
+          hint: "Remove the leading or trailing underscores from `bar_`",
+        }
+      ],
+    };
+  }
 }
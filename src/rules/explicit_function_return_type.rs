@@ -1,5 +1,5 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
 use deno_ast::{view as ast_view, MediaType, SourceRanged};
@@ -27,15 +27,15 @@ impl LintRule for ExplicitFunctionReturnType {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
     program: Program,
   ) {
-    // ignore js(x) files
-    if matches!(context.media_type(), MediaType::JavaScript | MediaType::Jsx) {
-      return;
-    }
     ExplicitFunctionReturnTypeHandler.traverse(program, context);
   }
 
@@ -1,5 +1,5 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::handler::{Handler, Traverse};
 
 use deno_ast::{view as ast_view, MediaType, SourceRange, SourceRanged};
@@ -33,15 +33,15 @@ impl LintRule for ExplicitModuleBoundaryTypes {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
     program: ast_view::Program,
   ) {
-    // ignore js(x) files
-    if matches!(context.media_type(), MediaType::JavaScript | MediaType::Jsx) {
-      return;
-    }
     ExplicitModuleBoundaryTypesHandler.traverse(program, context);
   }
 
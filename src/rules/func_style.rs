@@ -0,0 +1,495 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::find_lhs_ids;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{self as ast_view, BlockStmtOrExpr, Expr, NodeTrait, Pat};
+use deno_ast::{SourcePos, SourceRange, SourceRanged};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct FuncStyle;
+
+const CODE: &str = "func-style";
+const EXPRESSION_FIX_DESC: &str = "Convert to a function expression";
+const DECLARATION_FIX_DESC: &str = "Convert to a function declaration";
+
+#[derive(Display)]
+enum FuncStyleMessage {
+  #[display(fmt = "Expected a function expression")]
+  Expression,
+  #[display(fmt = "Expected a function declaration")]
+  Declaration,
+}
+
+#[derive(Display)]
+enum FuncStyleHint {
+  #[display(fmt = "Use `const {} = function () {{}}` instead", _0)]
+  Expression(String),
+  #[display(fmt = "Use `function {}() {{}}` instead", _0)]
+  Declaration(String),
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum Style {
+  #[default]
+  Expression,
+  Declaration,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct FuncStyleOptions {
+  /// The preferred style of top-level functions.
+  style: Style,
+  /// If set, `const f = () => {}` is allowed when declarations are preferred.
+  allow_arrow_functions: bool,
+}
+
+impl LintRule for FuncStyle {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: FuncStyleOptions = context.rule_options(CODE);
+    let mut handler = FuncStyleHandler {
+      options,
+      fn_decls: Vec::new(),
+      first_usages: HashMap::new(),
+      reassigned: HashSet::new(),
+    };
+    handler.traverse(program, context);
+
+    for (id, range, fix) in handler.fn_decls {
+      // Function declarations are hoisted while expressions aren't, so
+      // converting is only safe if the function isn't used before it's defined,
+      // and a `const` can't be reassigned
+      let used_before = handler
+        .first_usages
+        .get(&id)
+        .map_or(false, |pos| *pos < range.start);
+      let fixes = if used_before || handler.reassigned.contains(&id) {
+        vec![]
+      } else {
+        vec![fix]
+      };
+      context.add_diagnostic_with_fixes(
+        range,
+        CODE,
+        FuncStyleMessage::Expression,
+        Some(FuncStyleHint::Expression(id.0.to_string()).to_string()),
+        fixes,
+      );
+    }
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<FuncStyleOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/func_style.md")
+  }
+}
+
+struct FuncStyleHandler {
+  options: FuncStyleOptions,
+  /// Top-level function declarations to report once all usages are known,
+  /// along with the fix converting them.
+  fn_decls: Vec<(Id, SourceRange, LintFix)>,
+  /// Position of the first usage of each identifier.
+  first_usages: HashMap<Id, SourcePos>,
+  /// Identifiers that are assigned to.
+  reassigned: HashSet<Id>,
+}
+
+fn is_top_level(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  match node.parent() {
+    Some(Node::Module(_) | Node::Script(_)) => true,
+    Some(Node::ExportDecl(export_decl)) => {
+      matches!(export_decl.parent(), Node::Module(_))
+    }
+    _ => false,
+  }
+}
+
+/// Checks if `this` or `arguments` is used in the node, not counting the
+/// ones that belong to nested non-arrow functions or classes.
+fn uses_this_or_arguments(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  node.children().into_iter().any(|child| match child {
+    Node::ThisExpr(_) => true,
+    Node::Ident(ident) if ident.sym() == "arguments" => true,
+    Node::Function(_) | Node::Class(_) => false,
+    _ => uses_this_or_arguments(child),
+  })
+}
+
+fn single_change(range: SourceRange, new_text: String) -> Vec<LintFixChange> {
+  vec![LintFixChange {
+    new_text: new_text.into(),
+    range,
+  }]
+}
+
+impl FuncStyleHandler {
+  fn check_var_declarator(
+    &mut self,
+    var_decl: &ast_view::VarDecl,
+    declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    let Pat::Ident(binding) = declarator.name else { return };
+    let name = binding.id.sym().to_string();
+    let new_text = match declarator.init {
+      Some(Expr::Arrow(_)) if self.options.allow_arrow_functions => return,
+      Some(Expr::Arrow(arrow)) => {
+        if uses_this_or_arguments(arrow.as_node()) {
+          None
+        } else {
+          arrow_to_declaration(arrow, &name, ctx)
+        }
+      }
+      Some(Expr::Fn(fn_expr)) => {
+        if fn_expr.ident.is_some() {
+          None
+        } else {
+          fn_expr_to_declaration(fn_expr, &name, ctx)
+        }
+      }
+      _ => return,
+    };
+
+    // The declaration can only replace the whole statement if nothing else is
+    // declared in it, and nothing would be lost by dropping the type.
+    let fixes = match new_text {
+      Some(new_text)
+        if var_decl.decls.len() == 1 && binding.type_ann.is_none() =>
+      {
+        vec![LintFix {
+          description: DECLARATION_FIX_DESC.into(),
+          changes: single_change(var_decl.range(), new_text),
+        }]
+      }
+      _ => vec![],
+    };
+    ctx.add_diagnostic_with_fixes(
+      declarator.range(),
+      CODE,
+      FuncStyleMessage::Declaration,
+      Some(FuncStyleHint::Declaration(name).to_string()),
+      fixes,
+    );
+  }
+}
+
+fn arrow_to_declaration(
+  arrow: &ast_view::ArrowExpr,
+  name: &str,
+  ctx: &Context,
+) -> Option<String> {
+  let head = ctx
+    .text_of(SourceRange::new(arrow.start(), arrow.body.start()))
+    .trim_end()
+    .strip_suffix("=>")?
+    .trim();
+  let (is_async, signature) = match head.strip_prefix("async") {
+    Some(rest) if arrow.inner.is_async => ("async ", rest.trim_start()),
+    _ => ("", head),
+  };
+  let signature = if signature.starts_with('(') || signature.starts_with('<') {
+    signature.to_string()
+  } else {
+    // A single parameter without parentheses, e.g. `a => a`
+    format!("({})", signature)
+  };
+  let body = match arrow.body {
+    BlockStmtOrExpr::BlockStmt(block) => ctx.text_of(block.range()).to_string(),
+    BlockStmtOrExpr::Expr(expr) => {
+      format!("{{ return {}; }}", ctx.text_of(expr.range()))
+    }
+  };
+  Some(format!(
+    "{}function {}{} {}",
+    is_async, name, signature, body
+  ))
+}
+
+fn fn_expr_to_declaration(
+  fn_expr: &ast_view::FnExpr,
+  name: &str,
+  ctx: &Context,
+) -> Option<String> {
+  let text = ctx.text_of(fn_expr.range());
+  let keyword_end = text.find("function")? + "function".len();
+  let (keyword, rest) = text.split_at(keyword_end);
+  let (keyword, rest) = match rest.trim_start().strip_prefix('*') {
+    Some(rest) => (format!("{}*", keyword), rest),
+    None => (keyword.to_string(), rest),
+  };
+  Some(format!("{} {}{}", keyword, name, rest.trim_start()))
+}
+
+impl Handler for FuncStyleHandler {
+  fn fn_decl(&mut self, fn_decl: &ast_view::FnDecl, _ctx: &mut Context) {
+    if self.options.style != Style::Expression
+      || fn_decl.declare()
+      || fn_decl.function.body.is_none()
+      || !is_top_level(fn_decl.as_node())
+    {
+      return;
+    }
+
+    let name = fn_decl.ident.sym();
+    let range = fn_decl.range();
+    let start = fn_decl.function.start();
+    let fix = LintFix {
+      description: EXPRESSION_FIX_DESC.into(),
+      changes: vec![
+        LintFixChange {
+          new_text: format!("const {} = ", name).into(),
+          range: SourceRange::new(start, start),
+        },
+        LintFixChange {
+          new_text: "".into(),
+          range: fn_decl.ident.range(),
+        },
+        LintFixChange {
+          new_text: ";".into(),
+          range: SourceRange::new(range.end, range.end),
+        },
+      ],
+    };
+    self
+      .fn_decls
+      .push((fn_decl.ident.inner.to_id(), range, fix));
+  }
+
+  fn var_decl(&mut self, var_decl: &ast_view::VarDecl, ctx: &mut Context) {
+    if self.options.style != Style::Declaration
+      || var_decl.inner.declare
+      || var_decl.decl_kind() != ast_view::VarDeclKind::Const
+      || !is_top_level(var_decl.as_node())
+    {
+      return;
+    }
+
+    for declarator in &var_decl.decls {
+      self.check_var_declarator(var_decl, declarator, ctx);
+    }
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    let ids: Vec<Id> = find_lhs_ids(&assign_expr.inner.left);
+    self.reassigned.extend(ids);
+  }
+
+  fn update_expr(
+    &mut self,
+    update_expr: &ast_view::UpdateExpr,
+    _ctx: &mut Context,
+  ) {
+    if let Expr::Ident(ident) = update_expr.arg {
+      self.reassigned.insert(ident.inner.to_id());
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    // Skip the name of function declarations themselves
+    if let ast_view::Node::FnDecl(fn_decl) = ident.parent() {
+      if fn_decl.ident.range() == ident.range() {
+        return;
+      }
+    }
+    self
+      .first_usages
+      .entry(ident.inner.to_id())
+      .or_insert_with(|| ident.start());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn func_style_valid() {
+    assert_lint_ok! {
+      FuncStyle,
+      "const foo = function () {};",
+      "const foo = () => {};",
+      "export const foo = async function () {};",
+
+      // Only top-level functions are checked
+      "{ function foo() {} }",
+      "const foo = () => { function bar() {} };",
+      "namespace N { function foo() {} }",
+
+      // Overloads and ambient declarations have no body to convert
+      "declare function foo(): void;",
+
+      r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+function foo() {}
+export function bar() {}
+let baz = () => {};
+const qux = 42;
+      "#,
+      r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration", "allowArrowFunctions": true } } }
+const foo = () => {};
+      "#,
+    };
+  }
+
+  #[test]
+  fn func_style_invalid() {
+    assert_lint_err! {
+      FuncStyle,
+      "function foo(a) { return a; }": [
+        {
+          col: 0,
+          message: FuncStyleMessage::Expression,
+          hint: FuncStyleHint::Expression("foo".to_string()),
+          fix: (
+            EXPRESSION_FIX_DESC,
+            "const foo = function (a) { return a; };",
+          ),
+        }
+      ],
+      "export async function* foo<T>(a: T): AsyncGenerator<T> { yield a; }": [
+        {
+          col: 7,
+          message: FuncStyleMessage::Expression,
+          hint: FuncStyleHint::Expression("foo".to_string()),
+          fix: (
+            EXPRESSION_FIX_DESC,
+            "export const foo = async function* <T>(a: T): AsyncGenerator<T> { yield a; };",
+          ),
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+const foo = (a) => a + 1;
+const bar = async <T,>(a: T): Promise<T> => { return a; };
+export const baz = a => ({ a });
+const qux = function* () { yield 1; };
+      "#: [
+        {
+          line: 3,
+          col: 6,
+          message: FuncStyleMessage::Declaration,
+          hint: FuncStyleHint::Declaration("foo".to_string()),
+          fix: (
+            DECLARATION_FIX_DESC,
+            r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+function foo(a) { return a + 1; }
+const bar = async <T,>(a: T): Promise<T> => { return a; };
+export const baz = a => ({ a });
+const qux = function* () { yield 1; };
+      "#,
+          ),
+        },
+        {
+          line: 4,
+          col: 6,
+          message: FuncStyleMessage::Declaration,
+          hint: FuncStyleHint::Declaration("bar".to_string()),
+          fix: (
+            DECLARATION_FIX_DESC,
+            r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+const foo = (a) => a + 1;
+async function bar<T,>(a: T): Promise<T> { return a; }
+export const baz = a => ({ a });
+const qux = function* () { yield 1; };
+      "#,
+          ),
+        },
+        {
+          line: 5,
+          col: 13,
+          message: FuncStyleMessage::Declaration,
+          hint: FuncStyleHint::Declaration("baz".to_string()),
+          fix: (
+            DECLARATION_FIX_DESC,
+            r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+const foo = (a) => a + 1;
+const bar = async <T,>(a: T): Promise<T> => { return a; };
+export function baz(a) { return ({ a }); }
+const qux = function* () { yield 1; };
+      "#,
+          ),
+        },
+        {
+          line: 6,
+          col: 6,
+          message: FuncStyleMessage::Declaration,
+          hint: FuncStyleHint::Declaration("qux".to_string()),
+          fix: (
+            DECLARATION_FIX_DESC,
+            r#"
+// deno-lint-config { "rules": { "func-style": { "style": "declaration" } } }
+const foo = (a) => a + 1;
+const bar = async <T,>(a: T): Promise<T> => { return a; };
+export const baz = a => ({ a });
+function* qux() { yield 1; }
+      "#,
+          ),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn func_style_invalid_without_fix() {
+    for src in [
+      "foo(); function foo() {}",
+      "function foo() {} foo = null;",
+      "function foo() {} [foo] = [null];",
+      "function foo() {} foo++;",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&FuncStyle, src, "func_style_test.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+
+    for src in [
+      "const foo = () => this;",
+      "const foo = () => arguments[0];",
+      "const foo = function bar() {};",
+      "const foo: Handler = () => {};",
+      "const foo = () => {}, bar = 42;",
+    ] {
+      let src = format!(
+        "// deno-lint-config {{ \"rules\": {{ \"func-style\": {{ \"style\": \"declaration\" }} }} }}\n{}",
+        src
+      );
+      let diagnostics =
+        crate::test_util::lint(&FuncStyle, &src, "func_style_test.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
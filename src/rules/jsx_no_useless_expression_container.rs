@@ -0,0 +1,180 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::{to_jsx_attribute_value, to_jsx_text};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, JSXExpr, Lit};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct JsxNoUselessExpressionContainer;
+
+const CODE: &str = "jsx-no-useless-expression-container";
+const HINT: &str = "Remove the curly braces around the string";
+const FIX_DESC: &str = "Remove curly braces";
+
+#[derive(Display)]
+enum JsxNoUselessExpressionContainerMessage {
+  #[display(
+    fmt = "String attribute values don't need to be wrapped in `{{}}`"
+  )]
+  Attribute,
+  #[display(fmt = "String children don't need to be wrapped in `{{}}`")]
+  Child,
+}
+
+impl LintRule for JsxNoUselessExpressionContainer {
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsx"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    JsxNoUselessExpressionContainerHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/jsx_no_useless_expression_container.md")
+  }
+}
+
+struct JsxNoUselessExpressionContainerHandler;
+
+impl Handler for JsxNoUselessExpressionContainerHandler {
+  fn jsx_expr_container(
+    &mut self,
+    container: &ast_view::JSXExprContainer,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let JSXExpr::Expr(Expr::Lit(Lit::Str(str))) = container.expr else {
+      return;
+    };
+    let value = str.value();
+
+    let (message, new_text) = match container.parent() {
+      Node::JSXAttr(_) => (
+        JsxNoUselessExpressionContainerMessage::Attribute,
+        to_jsx_attribute_value(value),
+      ),
+      Node::JSXElement(_) | Node::JSXFragment(_) => (
+        JsxNoUselessExpressionContainerMessage::Child,
+        to_jsx_text(value),
+      ),
+      _ => return,
+    };
+    // The braces are needed if the string can't be written without them
+    let Some(new_text) = new_text else { return };
+
+    ctx.add_diagnostic_with_fixes(
+      container.range(),
+      CODE,
+      message,
+      Some(HINT.to_string()),
+      vec![LintFix {
+        description: FIX_DESC.into(),
+        changes: vec![LintFixChange {
+          new_text: new_text.into(),
+          range: container.range(),
+        }],
+      }],
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn jsx_no_useless_expression_container_valid() {
+    assert_lint_ok! {
+      JsxNoUselessExpressionContainer,
+      filename: "foo.tsx",
+      r#"<Foo bar="hello" />"#,
+      "<Foo bar={hello} />",
+      "<Foo bar={`hello`} />",
+      "<Foo bar={42} />",
+      "<div>text</div>",
+      "<div>{text}</div>",
+      "<div>{/* comment */}</div>",
+
+      // Strings that can't be written without the braces
+      r#"<Foo bar={"it's \"quoted\""} />"#,
+      r#"<Foo bar={"a &amp; b"} />"#,
+      r#"<Foo bar={"a\nb"} />"#,
+      r#"<div>{" "}</div>"#,
+      r#"<div>foo{" "}<b>bar</b></div>"#,
+      r#"<div>{"{"}</div>"#,
+      r#"<div>{"a < b"}</div>"#,
+      r#"<div>{"a & b"}</div>"#,
+      r#"<div>{"a\nb"}</div>"#,
+    };
+  }
+
+  #[test]
+  fn jsx_no_useless_expression_container_invalid() {
+    assert_lint_err! {
+      JsxNoUselessExpressionContainer,
+      filename: "foo.tsx",
+      r#"<Foo bar={"hello"} />"#: [
+        {
+          col: 9,
+          message: JsxNoUselessExpressionContainerMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="hello" />"#),
+        }
+      ],
+      r#"<Foo bar={'hello'} />"#: [
+        {
+          col: 9,
+          message: JsxNoUselessExpressionContainerMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="hello" />"#),
+        }
+      ],
+      r#"<Foo bar={'say "hi"'} />"#: [
+        {
+          col: 9,
+          message: JsxNoUselessExpressionContainerMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar='say "hi"' />"#),
+        }
+      ],
+      r#"<Foo bar={"{braces}"} />"#: [
+        {
+          col: 9,
+          message: JsxNoUselessExpressionContainerMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="{braces}" />"#),
+        }
+      ],
+      r#"<div>{"text"}</div>"#: [
+        {
+          col: 5,
+          message: JsxNoUselessExpressionContainerMessage::Child,
+          hint: HINT,
+          fix: (FIX_DESC, "<div>text</div>"),
+        }
+      ],
+      r#"<>{"it's fine"}</>"#: [
+        {
+          col: 2,
+          message: JsxNoUselessExpressionContainerMessage::Child,
+          hint: HINT,
+          fix: (FIX_DESC, "<>it's fine</>"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,302 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, NodeTrait};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct MaxNestedCallbacks;
+
+const CODE: &str = "max-nested-callbacks";
+const HINT: &str =
+  "Extract some of the callbacks into named functions, or use `async`/`await` instead";
+
+#[derive(Display)]
+enum MaxNestedCallbacksMessage {
+  #[display(
+    fmt = "Too many nested callbacks ({}). Maximum allowed is {}",
+    _0,
+    _1
+  )]
+  TooMany(usize, usize),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct MaxNestedCallbacksOptions {
+  /// Maximum depth of nested callbacks.
+  max: usize,
+  /// If set, named functions that aren't callbacks themselves start counting
+  /// from zero again.
+  reset_on_named_functions: bool,
+}
+
+impl Default for MaxNestedCallbacksOptions {
+  fn default() -> Self {
+    Self {
+      max: 3,
+      reset_on_named_functions: false,
+    }
+  }
+}
+
+impl LintRule for MaxNestedCallbacks {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: MaxNestedCallbacksOptions = context.rule_options(CODE);
+    MaxNestedCallbacksHandler {
+      options,
+      depth: 0,
+      frames: Vec::new(),
+    }
+    .traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<MaxNestedCallbacksOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/max_nested_callbacks.md")
+  }
+}
+
+enum Frame {
+  Callback {
+    /// Whether a callback nested in this one has exceeded the maximum depth.
+    offending_inside: bool,
+  },
+  NamedFunction {
+    saved_depth: usize,
+  },
+}
+
+struct MaxNestedCallbacksHandler {
+  options: MaxNestedCallbacksOptions,
+  depth: usize,
+  frames: Vec<Frame>,
+}
+
+/// Checks if the function is passed as an argument to a call, e.g.
+/// `foo(() => {})` or `new Foo(function () {})`.
+fn is_callback(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  match node.parent() {
+    Some(Node::ExprOrSpread(arg)) => matches!(
+      arg.parent(),
+      Node::CallExpr(_) | Node::NewExpr(_) | Node::OptCall(_)
+    ),
+    _ => false,
+  }
+}
+
+fn is_named_function(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  match node {
+    Node::FnDecl(_) => true,
+    Node::FnExpr(fn_expr) => fn_expr.ident.is_some(),
+    _ => false,
+  }
+}
+
+impl Handler for MaxNestedCallbacksHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    use deno_ast::view::Node;
+    if !matches!(node, Node::ArrowExpr(_) | Node::FnExpr(_) | Node::FnDecl(_)) {
+      return;
+    }
+
+    if is_callback(node) {
+      self.depth += 1;
+      self.frames.push(Frame::Callback {
+        offending_inside: false,
+      });
+    } else if self.options.reset_on_named_functions && is_named_function(node) {
+      self.frames.push(Frame::NamedFunction {
+        saved_depth: self.depth,
+      });
+      self.depth = 0;
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, ctx: &mut Context) {
+    use deno_ast::view::Node;
+    if !matches!(node, Node::ArrowExpr(_) | Node::FnExpr(_) | Node::FnDecl(_)) {
+      return;
+    }
+
+    if is_callback(node) {
+      let offending_inside = match self.frames.pop() {
+        Some(Frame::Callback { offending_inside }) => offending_inside,
+        _ => unreachable!("unbalanced callback frames"),
+      };
+      let offending = self.depth > self.options.max;
+      // Only the innermost callback of an offending chain is reported
+      if offending && !offending_inside {
+        ctx.add_diagnostic_with_hint(
+          node.range(),
+          CODE,
+          MaxNestedCallbacksMessage::TooMany(self.depth, self.options.max),
+          HINT,
+        );
+      }
+      if let Some(Frame::Callback {
+        offending_inside: parent,
+      }) = self.frames.last_mut()
+      {
+        *parent |= offending || offending_inside;
+      }
+      self.depth -= 1;
+    } else if self.options.reset_on_named_functions && is_named_function(node) {
+      match self.frames.pop() {
+        Some(Frame::NamedFunction { saved_depth }) => self.depth = saved_depth,
+        _ => unreachable!("unbalanced named function frames"),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn max_nested_callbacks_valid() {
+    assert_lint_ok! {
+      MaxNestedCallbacks,
+      "foo(() => { bar(() => { baz(() => {}); }); });",
+      "foo(function () { bar(function () { baz(function () {}); }); });",
+      "new Foo(() => { bar(() => { baz?.(() => {}); }); });",
+
+      // Functions that aren't callbacks don't count
+      r#"
+foo(() => {
+  const a = () => {
+    bar(() => {
+      baz(() => {});
+    });
+  };
+});
+      "#,
+      "foo(() => { bar(() => { baz(() => { const f = () => () => () => {}; }); }); });",
+      "foo([() => {}, () => {}], () => { bar(() => { baz(() => {}); }); });",
+
+      // Depth configured via a config comment
+      r#"
+// deno-lint-config { "rules": { "max-nested-callbacks": { "max": 4 } } }
+a(() => { b(() => { c(() => { d(() => {}); }); }); });
+      "#,
+
+      // Named functions reset the depth if configured
+      r#"
+// deno-lint-config { "rules": { "max-nested-callbacks": { "resetOnNamedFunctions": true } } }
+a(() => {
+  b(() => {
+    function named() {
+      c(() => { d(() => { e(() => {}); }); });
+    }
+  });
+});
+      "#,
+    };
+  }
+
+  #[test]
+  fn max_nested_callbacks_invalid() {
+    assert_lint_err! {
+      MaxNestedCallbacks,
+      "a(() => { b(() => { c(() => { d(() => {}); }); }); });": [
+        {
+          col: 32,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        }
+      ],
+      "a(function () { b(function () { c(function () { d(function () {}); }); }); });": [
+        {
+          col: 50,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        }
+      ],
+
+      // Only the innermost offending callback is reported
+      "a(() => { b(() => { c(() => { d(() => { e(() => {}); }); }); }); });": [
+        {
+          col: 42,
+          message: MaxNestedCallbacksMessage::TooMany(5, 3),
+          hint: HINT,
+        }
+      ],
+      "a(() => { b(() => { c(() => { d(() => {}); e(() => {}); }); }); });": [
+        {
+          col: 32,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        },
+        {
+          col: 45,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        }
+      ],
+
+      // Named functions don't reset the depth by default
+      r#"
+a(() => {
+  b(() => {
+    function named() {
+      c(() => { d(() => {}); });
+    }
+  });
+});
+      "#: [
+        {
+          line: 5,
+          col: 18,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "max-nested-callbacks": { "max": 1 } } }
+a(() => { b(() => {}); });
+      "#: [
+        {
+          line: 3,
+          col: 12,
+          message: MaxNestedCallbacksMessage::TooMany(2, 1),
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "max-nested-callbacks": { "resetOnNamedFunctions": true } } }
+a(() => {
+  function named() {
+    b(() => { c(() => { d(() => { e(() => {}); }); }); });
+  }
+});
+      "#: [
+        {
+          line: 5,
+          col: 36,
+          message: MaxNestedCallbacksMessage::TooMany(4, 3),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -37,7 +37,7 @@ struct NoConsoleHandler;
 
 impl Handler for NoConsoleHandler {
   fn ident(&mut self, id: &Ident, ctx: &mut Context) {
-    if id.sym().as_ref() == "console" && ctx.scope().is_global(&id.to_id()) {
+    if id.sym().as_ref() == "console" && ctx.is_global(&id.to_id()) {
       ctx.add_diagnostic(id.range(), CODE, MESSAGE);
     }
   }
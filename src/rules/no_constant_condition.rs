@@ -1,13 +1,19 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::program_ref;
 use super::{Context, LintRule};
+use crate::config::validate_rule_options;
 use crate::Program;
 use crate::ProgramRef;
-use deno_ast::swc::ast::{BinaryOp, CondExpr, Expr, IfStmt, Lit, UnaryOp};
+use deno_ast::swc::ast::{
+  BinaryOp, CondExpr, DoWhileStmt, Expr, ForStmt, IfStmt, Lit, UnaryOp,
+  WhileStmt,
+};
 use deno_ast::swc::visit::{noop_visit_type, VisitAll, VisitAllWith};
 use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
 use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
 
 #[derive(Debug)]
 pub struct NoConstantCondition;
@@ -28,6 +34,14 @@ enum NoConstantConditionHint {
   Remove,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NoConstantConditionOptions {
+  /// Whether loop conditions are checked as well. Off by default since
+  /// `while (true) {}` loops are a common and intentional pattern.
+  check_loops: bool,
+}
+
 impl LintRule for NoConstantCondition {
   fn tags(&self) -> &'static [&'static str] {
     &["recommended"]
@@ -42,14 +56,20 @@ impl LintRule for NoConstantCondition {
     context: &mut Context<'view>,
     program: Program<'view>,
   ) {
+    let options: NoConstantConditionOptions = context.rule_options(CODE);
     let program = program_ref(program);
-    let mut visitor = NoConstantConditionVisitor::new(context);
+    let mut visitor =
+      NoConstantConditionVisitor::new(context, options.check_loops);
     match program {
       ProgramRef::Module(m) => m.visit_all_with(&mut visitor),
       ProgramRef::Script(s) => s.visit_all_with(&mut visitor),
     }
   }
 
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoConstantConditionOptions>(options)
+  }
+
   #[cfg(feature = "docs")]
   fn docs(&self) -> &'static str {
     include_str!("../../docs/rules/no_constant_condition.md")
@@ -58,11 +78,15 @@ impl LintRule for NoConstantCondition {
 
 struct NoConstantConditionVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  check_loops: bool,
 }
 
 impl<'c, 'view: 'c> NoConstantConditionVisitor<'c, 'view> {
-  fn new(context: &'c mut Context<'view>) -> Self {
-    Self { context }
+  fn new(context: &'c mut Context<'view>, check_loops: bool) -> Self {
+    Self {
+      context,
+      check_loops,
+    }
   }
 
   fn add_diagnostic(&mut self, range: SourceRange) {
@@ -193,37 +217,25 @@ impl<'c, 'view> VisitAll for NoConstantConditionVisitor<'c, 'view> {
     self.report(&if_stmt.test);
   }
 
-  /* TODO(bartlomieju): temporarly disabled because
-    deno_std uses while (true) {} loops
-  fn visit_while_stmt(
-    &mut self,
-    while_stmt: &WhileStmt,
-
-  ) {
-    self.report(&while_stmt.test)
-    while_stmt.visit_children_with(self);
+  fn visit_while_stmt(&mut self, while_stmt: &WhileStmt) {
+    if self.check_loops {
+      self.report(&while_stmt.test);
+    }
   }
 
-  fn visit_do_while_stmt(
-    &mut self,
-    do_while_stmt: &DoWhileStmt,
-
-  ) {
-    self.report(&do_while_stmt.test)
-    do_while_stmt.visit_children_with(self);
+  fn visit_do_while_stmt(&mut self, do_while_stmt: &DoWhileStmt) {
+    if self.check_loops {
+      self.report(&do_while_stmt.test);
+    }
   }
 
-  fn visit_for_stmt(
-    &mut self,
-    for_stmt: &ForStmt,
-
-  ) {
-    if let Some(cond) = for_stmt.test.as_ref() {
-      self.report(cond)
+  fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
+    if self.check_loops {
+      if let Some(test) = for_stmt.test.as_ref() {
+        self.report(test);
+      }
     }
-    for_stmt.visit_children_with(self);
   }
-  */
 }
 
 #[cfg(test)]
@@ -263,6 +275,23 @@ mod tests {
       r#"while(tag`${a}`) {}"#,
       "while(`\\\n${a}`) {}",
 
+      // loops aren't checked unless `checkLoops` is set
+      r#"while(true);"#,
+      r#"for(;true;);"#,
+      r#"do{}while(true)"#,
+      r#"function* foo(){while(true){yield 'foo';}}"#,
+      r#"function* foo(){for(;true;){yield 'foo';}}"#,
+      r#"function* foo(){do{yield 'foo';}while(true)}"#,
+      r#"function* foo() {for (; yield; ) {}}"#,
+      r#"function* foo() {for (; ; yield) {}}"#,
+      r#"
+// deno-lint-config { "rules": { "no-constant-condition": { "checkLoops": true } } }
+while (x) {}
+for (;x < 10;) {}
+for (;;) {}
+do {} while (x)
+      "#,
+
       // typeof conditions
       r#"if(typeof x === 'undefined'){}"#,
       r#"if(`${typeof x}` === 'undefined'){}"#,
@@ -326,6 +355,20 @@ mod tests {
       // nested
       r#"if (foo) { if (bar) {} }"#,
       r#"foo ? bar ? 1 : 2 : 3"#,
+
+      // loops aren't checked unless `checkLoops` is set
+      r#"while(true);"#,
+      r#"for(;true;);"#,
+      r#"do{}while(true)"#,
+      r#"function* foo(){while(true){yield 'foo';}}"#,
+      r#"function* foo(){for(;true;){yield 'foo';}}"#,
+      r#"function* foo(){do{yield 'foo';}while(true)}"#,
+      r#"function* foo(){while (true) { while(true) {yield;}}}"#,
+      r#"function* foo() {for (; yield; ) {}}"#,
+      r#"function* foo() {for (; ; yield) {}}"#,
+      r#"function* foo() {while (true) {function* foo() {yield;}yield;}}"#,
+      r#"function* foo() { for (let x = yield; x < 10; x++) {yield;}yield;}"#,
+      r#"function* foo() { for (let x = yield; ; x++) { yield; }}"#,
     };
   }
 
@@ -729,6 +772,57 @@ mod tests {
     };
   }
 
+  #[test]
+  fn no_constant_condition_check_loops_invalid() {
+    assert_lint_err! {
+      NoConstantCondition,
+      r#"
+// deno-lint-config { "rules": { "no-constant-condition": { "checkLoops": true } } }
+while (true) {}
+      "#: [
+        {
+          line: 3,
+          col: 7,
+          message: NoConstantConditionMessage::Unexpected,
+          hint: NoConstantConditionHint::Remove,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-constant-condition": { "checkLoops": true } } }
+for (;1;) {}
+      "#: [
+        {
+          line: 3,
+          col: 6,
+          message: NoConstantConditionMessage::Unexpected,
+          hint: NoConstantConditionHint::Remove,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-constant-condition": { "checkLoops": true } } }
+do {} while (x || true)
+      "#: [
+        {
+          line: 3,
+          col: 13,
+          message: NoConstantConditionMessage::Unexpected,
+          hint: NoConstantConditionHint::Remove,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-constant-condition": { "checkLoops": true } } }
+while (a) { while ([]) {} }
+      "#: [
+        {
+          line: 3,
+          col: 19,
+          message: NoConstantConditionMessage::Unexpected,
+          hint: NoConstantConditionHint::Remove,
+        }
+      ],
+    };
+  }
+
   // TODO(humancalico) make these tests pass
   #[test]
   #[ignore]
@@ -749,20 +843,6 @@ mod tests {
       r#"if(true && abc==='str' || def ==='str'){}"#,
       r#"if(true && typeof abc==='string'){}"#,
 
-      // TODO(humancalico) add a configuration option for { checkLoops: false }
-      // https://eslint.org/docs/rules/no-constant-condition#checkloops
-      r#"while(true);"#,
-      r#"for(;true;);"#,
-      r#"do{}while(true)"#,
-      r#"function* foo(){while(true){yield 'foo';}}"#,
-      r#"function* foo(){for(;true;){yield 'foo';}}"#,
-      r#"function* foo(){do{yield 'foo';}while(true)}"#,
-      r#"function* foo(){while (true) { while(true) {yield;}}}"#,
-      r#"function* foo() {for (; yield; ) {}}"#,
-      r#"function* foo() {for (; ; yield) {}}"#,
-      r#"function* foo() {while (true) {function* foo() {yield;}yield;}}"#,
-      r#"function* foo() { for (let x = yield; x < 10; x++) {yield;}yield;}"#,
-      r#"function* foo() { for (let x = yield; ; x++) { yield; }}"#,
     };
   }
 
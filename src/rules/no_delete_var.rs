@@ -19,7 +19,9 @@ enum NoDeleteVarMessage {
 
 #[derive(Display)]
 enum NoDeleteVarHint {
-  #[display(fmt = "Remove the deletion statement")]
+  #[display(
+    fmt = "`delete` only works on object properties, so remove the deletion statement"
+  )]
   Remove,
 }
 
@@ -54,7 +56,16 @@ impl Handler for NoDeleteVarHandler {
       return;
     }
 
-    if let Expr::Ident(_) = unary_expr.arg {
+    // Undeclared globals are properties of the global object, which can be
+    // deleted
+    let mut arg = &unary_expr.arg;
+    while let Expr::Paren(paren) = arg {
+      arg = &paren.expr;
+    }
+    let Expr::Ident(ident) = arg else {
+      return;
+    };
+    if !ctx.scope().is_global(&ident.to_id()) {
       ctx.add_diagnostic_with_hint(
         unary_expr.range(),
         CODE,
@@ -69,6 +80,17 @@ impl Handler for NoDeleteVarHandler {
 mod tests {
   use super::*;
 
+  #[test]
+  fn no_delete_var_valid() {
+    assert_lint_ok! {
+      NoDeleteVar,
+      "const obj = { a: 1 }; delete obj.a;",
+      "const obj = { a: 1 }; delete obj['a'];",
+      "delete someGlobal;",
+      "delete globalThis.someGlobal;",
+    };
+  }
+
   #[test]
   fn no_delete_var_invalid() {
     assert_lint_err! {
@@ -80,6 +102,20 @@ mod tests {
           hint: NoDeleteVarHint::Remove,
         }
       ],
+      "function f(param) { delete param; }": [
+        {
+          col: 20,
+          message: NoDeleteVarMessage::Unexpected,
+          hint: NoDeleteVarHint::Remove,
+        }
+      ],
+      "let a; delete (a);": [
+        {
+          col: 7,
+          message: NoDeleteVarMessage::Unexpected,
+          hint: NoDeleteVarHint::Remove,
+        }
+      ],
     }
   }
 }
@@ -80,7 +80,7 @@ impl TryFrom<(&str, &str)> for DeprecatedApi {
   /// Converts the given member expression (made up of `obj_symbol` and `prop_symbol`) into
   /// `DeprecatedApi` if it's one of deprecated APIs.
   /// Note that this conversion does not take shadowing into account, so use this after calling
-  /// `is_global` method on the scope analyzer.
+  /// `is_global_ident` method on the context.
   fn try_from(
     (obj_symbol, prop_symbol): (&str, &str),
   ) -> Result<Self, Self::Error> {
@@ -183,7 +183,7 @@ impl Handler for NoDeprecatedDenoApiHandler {
     use deno_ast::view::Expr;
     if_chain! {
       if let Expr::Ident(obj) = &member_expr.obj;
-      if ctx.scope().is_global(&obj.inner.to_id());
+      if ctx.is_global_ident(obj);
       let obj_symbol: &str = obj.sym();
       if let Some(prop_symbol) = extract_symbol(&member_expr.prop);
       if let Ok(deprecated_api) = DeprecatedApi::try_from((obj_symbol, prop_symbol));
@@ -207,7 +207,7 @@ impl Handler for NoDeprecatedDenoApiHandler {
       if let ast_view::TsEntityName::Ident(ident) = qualified_name.left;
       if ident.sym() == "Deno";
       if qualified_name.right.sym() == "File";
-      if ctx.scope().is_global(&ident.inner.to_id());
+      if ctx.is_global_ident(ident);
       then {
         let deprecated_api = DeprecatedApi::File;
         ctx.add_diagnostic_with_hint(
@@ -3,13 +3,15 @@ use super::program_ref;
 use super::{Context, LintRule};
 use crate::Program;
 use crate::ProgramRef;
-use deno_ast::swc::ast::{Expr, SwitchStmt};
+use deno_ast::swc::ast::{BigInt, Expr, Number, Str, SwitchStmt};
+use deno_ast::swc::common::util::take::Take;
 use deno_ast::swc::utils::drop_span;
-use deno_ast::swc::visit::noop_visit_type;
-use deno_ast::swc::visit::{VisitAll, VisitAllWith};
+use deno_ast::swc::visit::{noop_visit_mut_type, noop_visit_type};
+use deno_ast::swc::visit::{VisitAll, VisitAllWith, VisitMut, VisitMutWith};
 use deno_ast::SourceRangedForSpanned;
 use derive_more::Display;
-use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct NoDuplicateCase;
@@ -24,8 +26,11 @@ enum NoDuplicateCaseMessage {
 
 #[derive(Display)]
 enum NoDuplicateCaseHint {
-  #[display(fmt = "Remove or rename the duplicate case clause")]
-  RemoveOrRename,
+  #[display(
+    fmt = "Remove or rename the duplicate case clause, the same value is already tested on line {}",
+    _0
+  )]
+  RemoveOrRename(usize),
 }
 
 impl LintRule for NoDuplicateCase {
@@ -66,23 +71,63 @@ impl<'c, 'view> NoDuplicateCaseVisitor<'c, 'view> {
   }
 }
 
+/// Removes the parts of an expression that don't affect its value, i.e.
+/// parentheses and the raw text of literals, so that e.g. `("a")` and `'a'`
+/// compare equal.
+struct Normalizer;
+
+impl VisitMut for Normalizer {
+  noop_visit_mut_type!();
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    while let Expr::Paren(paren) = expr {
+      *expr = *paren.expr.take();
+    }
+    expr.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_str(&mut self, str: &mut Str) {
+    str.raw = None;
+  }
+
+  fn visit_mut_number(&mut self, number: &mut Number) {
+    number.raw = None;
+  }
+
+  fn visit_mut_big_int(&mut self, big_int: &mut BigInt) {
+    big_int.raw = None;
+  }
+}
+
+fn normalize(expr: &Expr) -> Box<Expr> {
+  let mut expr = drop_span(Box::new(expr.clone()));
+  expr.visit_mut_with(&mut Normalizer);
+  expr
+}
+
 impl<'c, 'view> VisitAll for NoDuplicateCaseVisitor<'c, 'view> {
   noop_visit_type!();
 
   fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
-    // Check if there are duplicates by comparing span dropped expressions
-    let mut seen: HashSet<Box<Expr>> = HashSet::new();
+    // Check if there are duplicates by comparing normalized expressions,
+    // remembering the line on which each one is tested first
+    let mut seen: HashMap<Box<Expr>, usize> = HashMap::new();
 
     for case in &switch_stmt.cases {
       if let Some(test) = &case.test {
-        let span_dropped_test = drop_span(test.clone());
-        if !seen.insert(span_dropped_test) {
-          self.context.add_diagnostic_with_hint(
-            case.range(),
-            CODE,
-            NoDuplicateCaseMessage::Unexpected,
-            NoDuplicateCaseHint::RemoveOrRename,
-          );
+        let line = self.context.text_info().line_index(case.start()) + 1;
+        match seen.entry(normalize(test)) {
+          Entry::Occupied(first) => {
+            self.context.add_diagnostic_with_hint(
+              case.range(),
+              CODE,
+              NoDuplicateCaseMessage::Unexpected,
+              NoDuplicateCaseHint::RemoveOrRename(*first.get()),
+            );
+          }
+          Entry::Vacant(entry) => {
+            entry.insert(line);
+          }
         }
       }
     }
@@ -154,96 +199,129 @@ switch (someText) {
           col: 4,
           line: 8,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(4),
         }
       ],
       "var a = 1; switch (a) {case 1: break; case 1: break; case 2: break; default: break;}": [
         {
           col: 38,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = '1'; switch (a) {case '1': break; case '1': break; case '2': break; default: break;}": [
         {
           col: 42,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, one = 1; switch (a) {case one: break; case one: break; case 2: break; default: break;}": [
         {
           col: 49,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, p = {p: {p1: 1, p2: 1}}; switch (a) {case p.p.p1: break; case p.p.p1: break; default: break;}": [
         {
           col: 68,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f = function(b) { return b ? { p1: 1 } : { p1: 2 }; }; switch (a) {case f(true).p1: break; case f(true).p1: break; default: break;}": [
         {
           col: 102,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f = function(s) { return { p1: s } }; switch (a) {case f(a + 1).p1: break; case f(a + 1).p1: break; default: break;}": [
         {
           col: 86,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f = function(s) { return { p1: s } }; switch (a) {case f(a === 1 ? 2 : 3).p1: break; case f(a === 1 ? 2 : 3).p1: break; default: break;}": [
         {
           col: 96,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f1 = function() { return { p1: 1 } }; switch (a) {case f1().p1: break; case f1().p1: break; default: break;}": [
         {
           col: 82,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = [1, 2]; switch(a.toString()){case ([1, 2]).toString():break; case ([1, 2]).toString():break; default:break;}": [
         {
           col: 69,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "switch (a) { case a: case a: }": [
         {
           col: 21,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "switch (a) { case a: break; case b: break; case a: break; case c: break; case a: break; }": [
         {
           col: 43,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         },
         {
           col: 73,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f = function(s) { return { p1: s } }; switch (a) {case f(a + 1).p1: break; case f(a+1).p1: break; default: break;}": [
         {
           col: 86,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
+        }
+      ],
+
+      // Parentheses and the way literals are written don't matter
+      r#"switch (a) { case "a": break; case 'a': break; }"#: [
+        {
+          col: 30,
+          message: NoDuplicateCaseMessage::Unexpected,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
+        }
+      ],
+      "switch (a) { case 1: break; case (1): break; case 1.0: break; case 0x1: break; }": [
+        {
+          col: 28,
+          message: NoDuplicateCaseMessage::Unexpected,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
+        },
+        {
+          col: 45,
+          message: NoDuplicateCaseMessage::Unexpected,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
+        },
+        {
+          col: 62,
+          message: NoDuplicateCaseMessage::Unexpected,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
+        }
+      ],
+      "switch (a) { case (b.c): break; case b.c: break; }": [
+        {
+          col: 32,
+          message: NoDuplicateCaseMessage::Unexpected,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
 
@@ -265,14 +343,14 @@ switch (a) {
           line: 7,
           col: 6,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(5),
         }
       ],
       "var a = 1, p = {p: {p1: 1, p2: 1}}; switch (a) {case p.p.p1: break; case p. p // comment\n .p1: break; default: break;}": [
         {
           col: 68,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, p = {p: {p1: 1, p2: 1}}; switch (a) {case p .p\n/* comment */\n.p1: break; case p.p.p1: break; default: break;}": [
@@ -280,7 +358,7 @@ switch (a) {
           line: 3,
           col: 12,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, p = {p: {p1: 1, p2: 1}}; switch (a) {case p .p\n/* comment */\n.p1: break; case p. p // comment\n .p1: break; default: break;}": [
@@ -288,7 +366,7 @@ switch (a) {
           line: 3,
           col: 12,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, p = {p: {p1: 1, p2: 1}}; switch (a) {case p.p.p1: break; case p. p // comment\n .p1: break; case p .p\n/* comment */\n.p1: break; default: break;}": [
@@ -296,13 +374,13 @@ switch (a) {
           line: 1,
           col: 68,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         },
         {
           line: 2,
           col: 13,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
       "var a = 1, f = function(s) { return { p1: s } }; switch (a) {case f(\na + 1 // comment\n).p1: break; case f(a+1)\n.p1: break; default: break;}": [
@@ -310,7 +388,7 @@ switch (a) {
           line: 3,
           col: 13,
           message: NoDuplicateCaseMessage::Unexpected,
-          hint: NoDuplicateCaseHint::RemoveOrRename,
+          hint: NoDuplicateCaseHint::RemoveOrRename(1),
         }
       ],
     };
@@ -1,8 +1,9 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
 use deno_ast::view::TsInterfaceDecl;
+use deno_ast::MediaType;
 use deno_ast::SourceRanged;
 use derive_more::Display;
 
@@ -40,6 +41,10 @@ impl LintRule for NoEmptyInterface {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
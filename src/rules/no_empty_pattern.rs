@@ -118,6 +118,9 @@ mod tests {
       "function foo({a = {}}) {}",
       "function foo({a = []}) {}",
       "var [a] = foo",
+      "({a = {}} = foo)",
+      "[a = []] = foo",
+      "const foo = ({a = {}}) => {}",
       "async function startFileServerAsLibrary({}: FileServerCfg = {}): Promise<void>",
     };
   }
@@ -171,6 +174,36 @@ mod tests {
         message: MESSAGE,
         hint: HINT,
       }],
+      "const foo = ({}) => {}": [{
+        col: 13,
+        message: MESSAGE,
+        hint: HINT,
+      }],
+      "({} = foo)": [{
+        col: 1,
+        message: MESSAGE,
+        hint: HINT,
+      }],
+      "[] = foo": [{
+        col: 0,
+        message: MESSAGE,
+        hint: HINT,
+      }],
+      "({a: {}} = foo)": [{
+        col: 5,
+        message: MESSAGE,
+        hint: HINT,
+      }],
+      "for (const {} of foo) {}": [{
+        col: 11,
+        message: MESSAGE,
+        hint: HINT,
+      }],
+      "try {} catch ({}) {}": [{
+        col: 14,
+        message: MESSAGE,
+        hint: HINT,
+      }],
     }
   }
 }
@@ -0,0 +1,232 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view as ast_view;
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct NoEmptyStaticBlock;
+
+const CODE: &str = "no-empty-static-block";
+const MESSAGE: &str = "Empty static initialization block";
+const HINT: &str = "Remove the block, or add code to it";
+const FIX_DESC: &str = "Remove the empty static block";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NoEmptyStaticBlockOptions {
+  /// Don't report static blocks that contain comments.
+  allow_comments: bool,
+}
+
+impl LintRule for NoEmptyStaticBlock {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: NoEmptyStaticBlockOptions = context.rule_options(CODE);
+    NoEmptyStaticBlockHandler { options }.traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoEmptyStaticBlockOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_empty_static_block.md")
+  }
+}
+
+struct NoEmptyStaticBlockHandler {
+  options: NoEmptyStaticBlockOptions,
+}
+
+/// Removes the block along with its line if nothing else is on it, or else
+/// with the whitespace after it, so that the next class member takes its
+/// place.
+fn remove_fix(range: SourceRange, ctx: &Context) -> LintFix {
+  let file = ctx.text_info().range();
+  let before =
+    ctx.text_of(SourceRange::new(file.start.as_source_pos(), range.start));
+  let after = ctx.text_of(SourceRange::new(range.end, file.end));
+  let line_before = before.trim_end_matches([' ', '\t']);
+  let line_after = after.trim_start_matches([' ', '\t']);
+  let starts_line = line_before.is_empty() || line_before.ends_with('\n');
+  let newline = if line_after.starts_with('\n') {
+    Some(1)
+  } else if line_after.starts_with("\r\n") {
+    Some(2)
+  } else {
+    None
+  };
+  let range = if let (true, Some(newline)) = (starts_line, newline) {
+    let indent = before.len() - line_before.len();
+    let trailing = after.len() - line_after.len();
+    SourceRange::new(range.start - indent, range.end + trailing + newline)
+  } else {
+    let whitespace = after.len() - after.trim_start().len();
+    SourceRange::new(range.start, range.end + whitespace)
+  };
+  LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: "".into(),
+      range,
+    }],
+  }
+}
+
+/// Checks if removing the block could join the members around it into one,
+/// e.g. `a = 1` and `[b] = 2` into `a = 1[b] = 2`. That's the case when the
+/// next member starts with a character that can continue an expression, and
+/// the previous member may be a field without a semicolon.
+fn may_join_members(range: SourceRange, ctx: &Context) -> bool {
+  let file = ctx.text_info().range();
+  let before =
+    ctx.text_of(SourceRange::new(file.start.as_source_pos(), range.start));
+  let after = ctx.text_of(SourceRange::new(range.end, file.end));
+  after.trim_start().starts_with(['[', '(', '`', '*'])
+    && !before.trim_end().ends_with([';', '{'])
+}
+
+impl Handler for NoEmptyStaticBlockHandler {
+  fn static_block(
+    &mut self,
+    static_block: &ast_view::StaticBlock,
+    ctx: &mut Context,
+  ) {
+    if !static_block.body.stmts.is_empty() {
+      return;
+    }
+    let range = static_block.range();
+    let has_comments = ctx
+      .all_comments()
+      .any(|comment| range.contains(&comment.range()));
+    if has_comments && self.options.allow_comments {
+      return;
+    }
+
+    // Removing the block would remove its comments too
+    let fixes = if has_comments || may_join_members(range, ctx) {
+      vec![]
+    } else {
+      vec![remove_fix(range, ctx)]
+    };
+    ctx.add_diagnostic_with_fixes(
+      range,
+      CODE,
+      MESSAGE,
+      Some(HINT.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_empty_static_block_valid() {
+    assert_lint_ok! {
+      NoEmptyStaticBlock,
+      "class A { static { init(); } }",
+      "class A { static { let a; } }",
+      "class A { static x = {}; }",
+      "class A { static m() {} }",
+      r#"
+// deno-lint-config { "rules": { "no-empty-static-block": { "allowComments": true } } }
+class A {
+  static {
+    // Nothing to initialize yet
+  }
+  static { /* reserved */ }
+}
+      "#,
+    };
+  }
+
+  #[test]
+  fn no_empty_static_block_invalid() {
+    assert_lint_err! {
+      NoEmptyStaticBlock,
+      "class A { static {} }": [
+        {
+          col: 10,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "class A { }"),
+        }
+      ],
+      "class A {\n  static {}\n  static x = 1;\n  static {\n  }\n}": [
+        {
+          line: 2,
+          col: 2,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "class A {\n  static x = 1;\n  static {\n  }\n}"),
+        },
+        {
+          line: 4,
+          col: 2,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "class A {\n  static {}\n  static x = 1;\n}"),
+        }
+      ],
+      "class A {\r\n  static {}\r\n}": [
+        {
+          line: 2,
+          col: 2,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "class A {\r\n}"),
+        }
+      ],
+      "const A = class { static {} m() {} };": [
+        {
+          col: 18,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "const A = class { m() {} };"),
+        }
+      ],
+      "class A { a = 1;\nstatic {}\n[b] = 2 }": [
+        {
+          line: 2,
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "class A { a = 1;\n[b] = 2 }"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_empty_static_block_invalid_without_fix() {
+    for src in [
+      // Comments are kept
+      "class A { static { /* TODO */ } }",
+      // `a = 1\n[b] = 2` would be parsed as `a = 1[b] = 2`
+      "class A { a = 1\nstatic {}\n[b] = 2 }",
+      "class A { a = b\nstatic {}\n*m() {} }",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&NoEmptyStaticBlock, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
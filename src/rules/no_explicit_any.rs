@@ -1,9 +1,10 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
 use deno_ast::swc::ast::TsKeywordTypeKind::TsAnyKeyword;
 use deno_ast::view::TsKeywordType;
+use deno_ast::MediaType;
 use deno_ast::SourceRanged;
 
 #[derive(Debug)]
@@ -22,6 +23,10 @@ impl LintRule for NoExplicitAny {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
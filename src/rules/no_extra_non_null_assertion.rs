@@ -1,6 +1,7 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use super::TYPESCRIPT_MEDIA_TYPES;
 use crate::handler::Handler;
 use crate::handler::Traverse;
 use crate::Program;
@@ -9,6 +10,7 @@ use deno_ast::view::Expr;
 use deno_ast::view::OptChainBase;
 use deno_ast::view::OptChainExpr;
 use deno_ast::view::TsNonNullExpr;
+use deno_ast::MediaType;
 use deno_ast::SourceRange;
 use deno_ast::SourceRanged;
 use derive_more::Display;
@@ -39,6 +41,10 @@ impl LintRule for NoExtraNonNullAssertion {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view(
     &self,
     context: &mut Context,
@@ -1,6 +1,7 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::program_ref;
 use super::{Context, LintRule};
+use crate::config::validate_rule_options;
 use crate::Program;
 use crate::ProgramRef;
 use crate::{globals::GLOBALS, swc_util::find_lhs_ids};
@@ -12,6 +13,9 @@ use deno_ast::swc::{
 use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
 use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct NoGlobalAssign;
@@ -30,6 +34,15 @@ enum NoGlobalAssignHint {
   Remove,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct NoGlobalAssignOptions {
+  /// Additional globals that must not be assigned to.
+  globals: HashSet<String>,
+  /// Globals that are allowed to be assigned to.
+  exceptions: HashSet<String>,
+}
+
 impl LintRule for NoGlobalAssign {
   fn tags(&self) -> &'static [&'static str] {
     &["recommended"]
@@ -45,13 +58,18 @@ impl LintRule for NoGlobalAssign {
     program: Program<'view>,
   ) {
     let program = program_ref(program);
-    let mut visitor = NoGlobalAssignVisitor::new(context);
+    let options: NoGlobalAssignOptions = context.rule_options(CODE);
+    let mut visitor = NoGlobalAssignVisitor::new(context, options);
     match program {
       ProgramRef::Module(m) => m.visit_with(&mut visitor),
       ProgramRef::Script(s) => s.visit_with(&mut visitor),
     }
   }
 
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoGlobalAssignOptions>(options)
+  }
+
   #[cfg(feature = "docs")]
   fn docs(&self) -> &'static str {
     include_str!("../../docs/rules/no_global_assign.md")
@@ -60,11 +78,26 @@ impl LintRule for NoGlobalAssign {
 
 struct NoGlobalAssignVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  options: NoGlobalAssignOptions,
 }
 
 impl<'c, 'view> NoGlobalAssignVisitor<'c, 'view> {
-  fn new(context: &'c mut Context<'view>) -> Self {
-    Self { context }
+  fn new(
+    context: &'c mut Context<'view>,
+    options: NoGlobalAssignOptions,
+  ) -> Self {
+    Self { context, options }
+  }
+
+  fn is_read_only_global(&self, name: &str) -> bool {
+    if self.options.exceptions.contains(name) {
+      return false;
+    }
+    // Globals that can be overwritten don't need to be reported
+    self.options.globals.contains(name)
+      || GLOBALS
+        .iter()
+        .any(|(global, writable)| *global == name && !writable)
   }
 
   fn check(&mut self, range: SourceRange, id: Id) {
@@ -72,23 +105,17 @@ impl<'c, 'view> NoGlobalAssignVisitor<'c, 'view> {
       return;
     }
 
-    if self.context.scope().var(&id).is_some() {
+    if !self.context.is_global(&id) {
       return;
     }
 
-    // We only care about globals.
-    let maybe_global = GLOBALS.iter().find(|(name, _)| name == &&*id.0);
-
-    if let Some(global) = maybe_global {
-      // If global can be overwritten then don't need to report anything
-      if !global.1 {
-        self.context.add_diagnostic_with_hint(
-          range,
-          CODE,
-          NoGlobalAssignMessage::NotAllowed,
-          NoGlobalAssignHint::Remove,
-        );
-      }
+    if self.is_read_only_global(&id.0) {
+      self.context.add_diagnostic_with_hint(
+        range,
+        CODE,
+        NoGlobalAssignMessage::NotAllowed,
+        NoGlobalAssignHint::Remove,
+      );
     }
   }
 }
@@ -102,6 +129,7 @@ impl<'c, 'view> Visit for NoGlobalAssignVisitor<'c, 'view> {
     for ident in idents {
       self.check(ident.range(), ident.to_id());
     }
+    e.right.visit_with(self);
   }
 
   fn visit_update_expr(&mut self, e: &UpdateExpr) {
@@ -127,6 +155,12 @@ mod tests {
       "require = 0;",
       "onmessage = function () {};",
       "let Array = 0; Array = 42;",
+      "Object.foo = 1;",
+      "undefined.foo = 1;",
+      r#"
+// deno-lint-config { "rules": { "no-global-assign": { "exceptions": ["Object"] } } }
+Object = {};
+      "#,
       r#"
 let Boolean = true;
 function foo() {
@@ -173,6 +207,52 @@ function foo() {
           hint: NoGlobalAssignHint::Remove,
         }
       ],
+      "undefined = 1;": [
+        {
+          col: 0,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
+      "window = null;": [
+        {
+          col: 0,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
+      "Object += 'foo';": [
+        {
+          col: 0,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
+      "--NaN;": [
+        {
+          col: 0,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
+      "foo = Object = {};": [
+        {
+          col: 6,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-global-assign": { "globals": ["myGlobal"] } } }
+myGlobal = 1;
+      "#: [
+        {
+          line: 3,
+          col: 0,
+          message: NoGlobalAssignMessage::NotAllowed,
+          hint: NoGlobalAssignHint::Remove,
+        }
+      ],
       r#"
 function foo() {
   let Boolean = false;
@@ -0,0 +1,182 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::no_window_prefix::{extract_symbol, NoWindowPrefixOptions};
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+use if_chain::if_chain;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct NoGlobalthisWindowPrefix;
+
+const CODE: &str = "no-globalthis-window-prefix";
+const MESSAGE: &str = "For compatibility between the Window context and the Web Workers, calling Web APIs via `window` is disallowed";
+const FIX_DESC: &str = "Remove the `window` prefix";
+
+#[derive(Display)]
+enum NoGlobalthisWindowPrefixHint {
+  #[display(fmt = "Instead, call this API via `{}.{}`", _0, _1)]
+  Direct(String, String),
+}
+
+impl LintRule for NoGlobalthisWindowPrefix {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: NoWindowPrefixOptions = context.rule_options(CODE);
+    NoGlobalthisWindowPrefixHandler { options }.traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoWindowPrefixOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_globalthis_window_prefix.md")
+  }
+}
+
+struct NoGlobalthisWindowPrefixHandler {
+  options: NoWindowPrefixOptions,
+}
+
+impl Handler for NoGlobalthisWindowPrefixHandler {
+  fn member_expr(
+    &mut self,
+    member_expr: &ast_view::MemberExpr,
+    ctx: &mut Context,
+  ) {
+    if_chain! {
+      if let Expr::Member(window_expr) = &member_expr.obj;
+      if let Expr::Ident(global) = &window_expr.obj;
+      if matches!(global.sym().as_ref(), "globalThis" | "self");
+      if ctx.is_global_ident(global);
+      if extract_symbol(window_expr).as_deref() == Some("window");
+      if let Some(prop_symbol) = extract_symbol(member_expr);
+      if self.options.is_denied(&prop_symbol);
+      then {
+        let global_symbol = global.sym().to_string();
+        let fix = LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![LintFixChange {
+            new_text: global_symbol.clone().into(),
+            range: window_expr.range(),
+          }],
+        };
+        ctx.add_diagnostic_with_fixes(
+          member_expr.range(),
+          CODE,
+          MESSAGE,
+          Some(
+            NoGlobalthisWindowPrefixHint::Direct(
+              global_symbol,
+              prop_symbol.to_string(),
+            )
+            .to_string(),
+          ),
+          vec![fix],
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_globalthis_window_prefix_valid() {
+    assert_lint_ok! {
+      NoGlobalthisWindowPrefix,
+      "globalThis.fetch();",
+      "self.fetch();",
+      "window.fetch();",
+      "globalThis.window;",
+      "globalThis.window.alert();",
+      "globalThis.window.location.href;",
+      "foo.globalThis.window.fetch();",
+      "foo.self.window.fetch();",
+      "const globalThis = {}; globalThis.window.fetch();",
+      "function f(self) { self.window.fetch(); }",
+      r#"const f = "fetch"; globalThis.window[f]();"#,
+      "\n// deno-lint-config { \"rules\": { \"no-globalthis-window-prefix\": { \"allow\": [\"fetch\"] } } }\nglobalThis.window.fetch();",
+    };
+  }
+
+  #[test]
+  fn no_globalthis_window_prefix_invalid() {
+    assert_lint_err! {
+      NoGlobalthisWindowPrefix,
+      "globalThis.window.fetch();": [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "globalThis", "fetch"),
+          fix: (FIX_DESC, "globalThis.fetch();"),
+        }
+      ],
+      "self.window.fetch();": [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "self", "fetch"),
+          fix: (FIX_DESC, "self.fetch();"),
+        }
+      ],
+      r#"globalThis.window["fetch"]();"#: [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "globalThis", "fetch"),
+          fix: (FIX_DESC, r#"globalThis["fetch"]();"#),
+        }
+      ],
+      r#"globalThis["window"].atob("");"#: [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "globalThis", "atob"),
+          fix: (FIX_DESC, r#"globalThis.atob("");"#),
+        }
+      ],
+      r#"self["win" + "dow"][`\u0066etch`]();"#: [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "self", "fetch"),
+          fix: (FIX_DESC, r#"self[`\u0066etch`]();"#),
+        }
+      ],
+      "const res = await globalThis.window.fetch(url);": [
+        {
+          col: 18,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "globalThis", "fetch"),
+          fix: (FIX_DESC, "const res = await globalThis.fetch(url);"),
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"no-globalthis-window-prefix\": { \"deny\": [\"myGlobal\"] } } }\nself.window.myGlobal;": [
+        {
+          line: 3,
+          col: 0,
+          message: MESSAGE,
+          hint: variant!(NoGlobalthisWindowPrefixHint, Direct, "self", "myGlobal"),
+          fix: (FIX_DESC, "\n// deno-lint-config { \"rules\": { \"no-globalthis-window-prefix\": { \"deny\": [\"myGlobal\"] } } }\nself.myGlobal;"),
+        }
+      ],
+    };
+  }
+}
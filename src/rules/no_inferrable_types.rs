@@ -1,6 +1,6 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::program_ref;
-use super::{Context, LintRule};
+use super::{Context, LintRule, TYPESCRIPT_MEDIA_TYPES};
 use crate::Program;
 use crate::ProgramRef;
 use deno_ast::swc::ast::{
@@ -10,6 +10,7 @@ use deno_ast::swc::ast::{
 };
 use deno_ast::swc::ast::{Callee, PropName};
 use deno_ast::swc::visit::{VisitAll, VisitAllWith};
+use deno_ast::MediaType;
 use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
 use derive_more::Display;
@@ -40,6 +41,10 @@ impl LintRule for NoInferrableTypes {
     CODE
   }
 
+  fn media_types(&self) -> Option<&'static [MediaType]> {
+    Some(TYPESCRIPT_MEDIA_TYPES)
+  }
+
   fn lint_program_with_ast_view<'view>(
     &self,
     context: &mut Context<'view>,
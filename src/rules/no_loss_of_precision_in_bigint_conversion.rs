@@ -0,0 +1,404 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::UnaryOp;
+use deno_ast::view::{self as ast_view, Callee, Expr, Lit, Node};
+use deno_ast::{SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoLossOfPrecisionInBigintConversion;
+
+const CODE: &str = "no-loss-of-precision-in-bigint-conversion";
+const FIX_DESC: &str = "Use a bigint literal";
+
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+#[derive(Display)]
+enum NoLossOfPrecisionInBigintConversionMessage {
+  #[display(fmt = "`BigInt` throws on the non-integer number `{}`", _0)]
+  NonInteger(String),
+  #[display(
+    fmt = "`{}` loses precision before it's converted to a bigint",
+    _0
+  )]
+  Unsafe(String),
+}
+
+#[derive(Display)]
+enum NoLossOfPrecisionInBigintConversionHint {
+  #[display(fmt = "Pass an integer, e.g. by rounding the number first")]
+  NonInteger,
+  #[display(fmt = "Use the bigint literal `{}` instead", _0)]
+  BigintLiteral(String),
+  #[display(fmt = "Use a bigint literal instead")]
+  Unsafe,
+}
+
+impl LintRule for NoLossOfPrecisionInBigintConversion {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoLossOfPrecisionInBigintConversionHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_loss_of_precision_in_bigint_conversion.md"
+    )
+  }
+}
+
+struct NoLossOfPrecisionInBigintConversionHandler;
+
+/// Returns the number literal passed to the call, possibly negated, along
+/// with the text of the argument.
+fn number_arg(
+  call_expr: &ast_view::CallExpr,
+  ctx: &Context,
+) -> Option<(f64, String)> {
+  let [arg] = call_expr.args.as_slice() else {
+    return None;
+  };
+  if arg.spread().is_some() {
+    return None;
+  }
+  let (num, negated) = match arg.expr {
+    Expr::Lit(Lit::Num(num)) => (num, false),
+    Expr::Unary(unary) if unary.op() == UnaryOp::Minus => match unary.arg {
+      Expr::Lit(Lit::Num(num)) => (num, true),
+      _ => return None,
+    },
+    _ => return None,
+  };
+  let value = if negated { -num.value() } else { num.value() };
+  Some((value, ctx.text_of(arg.expr.range()).to_string()))
+}
+
+/// Returns the bigint literal with the same digits as the number literal, if
+/// there's one. Decimal literals with a fraction or an exponent and legacy
+/// octal literals have none.
+fn bigint_literal(number: &str) -> Option<String> {
+  let (sign, digits) = match number.strip_prefix('-') {
+    Some(digits) => ("-", digits),
+    None => ("", number),
+  };
+  let lower = digits.to_ascii_lowercase();
+  let has_bigint_form = if lower.starts_with("0x")
+    || lower.starts_with("0o")
+    || lower.starts_with("0b")
+  {
+    true
+  } else {
+    !digits.starts_with('0')
+      && digits.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+  };
+  has_bigint_form.then(|| format!("{}{}n", sign, digits))
+}
+
+/// Returns the decimal digits of the integer that the number literal is
+/// written as, without its sign, or `None` if it's written with a fraction.
+fn written_integer(number: &str) -> Option<String> {
+  let number = number
+    .trim_start_matches('-')
+    .replace('_', "")
+    .to_ascii_lowercase();
+  let (radix, digits) = if let Some(digits) = number.strip_prefix("0x") {
+    (16, digits)
+  } else if let Some(digits) = number.strip_prefix("0o") {
+    (8, digits)
+  } else if let Some(digits) = number.strip_prefix("0b") {
+    (2, digits)
+  } else if number.len() > 1
+    && number.starts_with('0')
+    && number.bytes().all(|b| (b'0'..=b'7').contains(&b))
+  {
+    // Legacy octal literal
+    (8, &number[1..])
+  } else {
+    return written_decimal_integer(&number);
+  };
+
+  // Little-endian decimal digits
+  let mut decimal = vec![0u32];
+  for digit in digits.chars() {
+    let mut carry = digit.to_digit(radix)?;
+    for d in &mut decimal {
+      let n = *d * radix + carry;
+      *d = n % 10;
+      carry = n / 10;
+    }
+    while carry > 0 {
+      decimal.push(carry % 10);
+      carry /= 10;
+    }
+  }
+  let digits = decimal
+    .iter()
+    .rev()
+    .map(|d| char::from_digit(*d, 10).unwrap())
+    .collect::<String>();
+  Some(trim_leading_zeros(&digits))
+}
+
+fn written_decimal_integer(number: &str) -> Option<String> {
+  let (mantissa, exponent) = match number.split_once('e') {
+    Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+    None => (number, 0),
+  };
+  let (int_part, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+  let mut digits = format!("{}{}", int_part, fraction);
+  let exponent = exponent - fraction.len() as i64;
+  if exponent >= 0 {
+    digits.extend(std::iter::repeat('0').take(exponent as usize));
+  } else {
+    let fraction_start = digits
+      .len()
+      .saturating_sub(exponent.unsigned_abs() as usize);
+    if digits[fraction_start..].bytes().any(|b| b != b'0') {
+      return None;
+    }
+    digits.truncate(fraction_start);
+  }
+  Some(trim_leading_zeros(&digits))
+}
+
+fn trim_leading_zeros(digits: &str) -> String {
+  match digits.trim_start_matches('0') {
+    "" => "0".to_string(),
+    digits => digits.to_string(),
+  }
+}
+
+impl Handler for NoLossOfPrecisionInBigintConversionHandler {
+  fn call_expr(&mut self, call_expr: &ast_view::CallExpr, ctx: &mut Context) {
+    let Callee::Expr(Expr::Ident(callee)) = call_expr.callee else {
+      return;
+    };
+    if callee.sym() != "BigInt" || !ctx.is_global(&callee.to_id()) {
+      return;
+    }
+    let Some((value, number)) = number_arg(call_expr, ctx) else {
+      return;
+    };
+
+    if value.fract() != 0.0 || !value.is_finite() {
+      ctx.add_diagnostic_with_hint(
+        call_expr.range(),
+        CODE,
+        NoLossOfPrecisionInBigintConversionMessage::NonInteger(number),
+        NoLossOfPrecisionInBigintConversionHint::NonInteger,
+      );
+      return;
+    }
+    // Larger numbers are reported only if they differ from the integer
+    // that's written, e.g. `1e21` is exact
+    if value.abs() <= MAX_SAFE_INTEGER
+      || written_integer(&number) == Some(format!("{:.0}", value.abs()))
+    {
+      return;
+    }
+
+    let message =
+      NoLossOfPrecisionInBigintConversionMessage::Unsafe(number.clone());
+    // Comments in the call would be lost
+    let has_comments = ctx
+      .all_comments()
+      .any(|comment| call_expr.range().contains(&comment.range()));
+    match bigint_literal(&number).filter(|_| !has_comments) {
+      Some(literal) => {
+        // E.g. `-1n.toString()` would negate the string
+        let needs_parens = value < 0.0
+          && matches!(
+            call_expr.parent(),
+            Node::MemberExpr(_)
+              | Node::BinExpr(_)
+              | Node::UnaryExpr(_)
+              | Node::OptChainExpr(_)
+              | Node::TaggedTpl(_)
+          );
+        let new_text = if needs_parens {
+          format!("({})", literal)
+        } else {
+          literal.clone()
+        };
+        let fix = LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![LintFixChange {
+            new_text: new_text.into(),
+            range: call_expr.range(),
+          }],
+        };
+        ctx.add_diagnostic_with_fixes(
+          call_expr.range(),
+          CODE,
+          message,
+          Some(
+            NoLossOfPrecisionInBigintConversionHint::BigintLiteral(literal)
+              .to_string(),
+          ),
+          vec![fix],
+        );
+      }
+      None => {
+        ctx.add_diagnostic_with_hint(
+          call_expr.range(),
+          CODE,
+          message,
+          NoLossOfPrecisionInBigintConversionHint::Unsafe,
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_loss_of_precision_in_bigint_conversion_valid() {
+    assert_lint_ok! {
+      NoLossOfPrecisionInBigintConversion,
+      "BigInt(1);",
+      "BigInt(-1);",
+      "BigInt(1.0);",
+      "BigInt(9007199254740991);",
+      "BigInt(-9007199254740991);",
+      "BigInt(0x1fffffffffffff);",
+      "BigInt(2 ** 53);",
+      "BigInt(9007199254740992);",
+      "BigInt(9007199254740994);",
+      "BigInt(-9007199254740994);",
+      "BigInt(0x20000000000000);",
+      "BigInt(0o400000000000000000);",
+      "BigInt(1e21);",
+      "BigInt(1_000e18);",
+      "BigInt(9007199254740994.0);",
+      "BigInt(90071992547409940e-1);",
+      "BigInt('9007199254740993');",
+      "BigInt(x);",
+      "BigInt(1.5, 2.5);",
+      "BigInt(...[1.5]);",
+      "9007199254740993n;",
+      "foo(1.5);",
+      "Number(9007199254740993);",
+      "const BigInt = (n: number) => n; BigInt(1.5);",
+      "function f(BigInt) { BigInt(9007199254740993); }",
+    };
+  }
+
+  #[test]
+  fn no_loss_of_precision_in_bigint_conversion_invalid() {
+    assert_lint_err! {
+      NoLossOfPrecisionInBigintConversion,
+      "BigInt(1.5);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, NonInteger, "1.5"),
+          hint: NoLossOfPrecisionInBigintConversionHint::NonInteger,
+        }
+      ],
+      "BigInt(-0.1);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, NonInteger, "-0.1"),
+          hint: NoLossOfPrecisionInBigintConversionHint::NonInteger,
+        }
+      ],
+      "BigInt(1e-3);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, NonInteger, "1e-3"),
+          hint: NoLossOfPrecisionInBigintConversionHint::NonInteger,
+        }
+      ],
+      "const a = BigInt(9007199254740993);": [
+        {
+          col: 10,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "9007199254740993"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "9007199254740993n"),
+          fix: (FIX_DESC, "const a = 9007199254740993n;"),
+        }
+      ],
+      "BigInt(-9_007_199_254_740_993);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "-9_007_199_254_740_993"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "-9_007_199_254_740_993n"),
+          fix: (FIX_DESC, "-9_007_199_254_740_993n;"),
+        }
+      ],
+      "BigInt(-9007199254740993).toString();": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "-9007199254740993"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "-9007199254740993n"),
+          fix: (FIX_DESC, "(-9007199254740993n).toString();"),
+        }
+      ],
+      "BigInt(-9007199254740993) ** 2n;": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "-9007199254740993"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "-9007199254740993n"),
+          fix: (FIX_DESC, "(-9007199254740993n) ** 2n;"),
+        }
+      ],
+      "BigInt(0x20000000000001);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "0x20000000000001"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "0x20000000000001n"),
+          fix: (FIX_DESC, "0x20000000000001n;"),
+        }
+      ],
+      "BigInt(1.00000000000000001e21);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "1.00000000000000001e21"),
+          hint: NoLossOfPrecisionInBigintConversionHint::Unsafe,
+        }
+      ],
+      "BigInt(9007199254740994.5);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "9007199254740994.5"),
+          hint: NoLossOfPrecisionInBigintConversionHint::Unsafe,
+        }
+      ],
+      "BigInt(0b100000000000000000000000000000000000000000000000000001);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "0b100000000000000000000000000000000000000000000000000001"),
+          hint: variant!(NoLossOfPrecisionInBigintConversionHint, BigintLiteral, "0b100000000000000000000000000000000000000000000000000001n"),
+          fix: (FIX_DESC, "0b100000000000000000000000000000000000000000000000000001n;"),
+        }
+      ],
+      "BigInt(9007199254740993.0);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "9007199254740993.0"),
+          hint: NoLossOfPrecisionInBigintConversionHint::Unsafe,
+        }
+      ],
+      "BigInt(9007199254740993 /* two */);": [
+        {
+          col: 0,
+          message: variant!(NoLossOfPrecisionInBigintConversionMessage, Unsafe, "9007199254740993"),
+          hint: NoLossOfPrecisionInBigintConversionHint::Unsafe,
+        }
+      ],
+    };
+  }
+}
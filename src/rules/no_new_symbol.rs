@@ -42,7 +42,7 @@ impl Handler for NoNewSymbolHandler {
     if_chain! {
       if let Expr::Ident(ident) = new_expr.callee;
       if *ident.sym() == *"Symbol";
-      if ctx.scope().var(&ident.to_id()).is_none();
+      if ctx.is_global(&ident.to_id());
       then {
         ctx.add_diagnostic(new_expr.range(), CODE, MESSAGE);
       }
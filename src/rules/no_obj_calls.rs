@@ -43,7 +43,7 @@ fn check_callee(callee: &Ident, range: SourceRange, ctx: &mut Context) {
   if matches!(
     callee.sym().as_ref(),
     "Math" | "JSON" | "Reflect" | "Atomics"
-  ) && ctx.scope().var(&callee.to_id()).is_none()
+  ) && ctx.is_global(&callee.to_id())
   {
     ctx.add_diagnostic(
       range,
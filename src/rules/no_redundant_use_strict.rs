@@ -0,0 +1,241 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Directive, Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{Node, NodeTrait};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoRedundantUseStrict;
+
+const CODE: &str = "no-redundant-use-strict";
+const HINT: &str = "Remove the directive";
+const FIX_DESC: &str = "Remove the `\"use strict\"` directive";
+
+#[derive(Display)]
+enum NoRedundantUseStrictMessage {
+  #[display(
+    fmt = "`\"use strict\"` is redundant in modules, which are always strict"
+  )]
+  Module,
+  #[display(
+    fmt = "`\"use strict\"` is redundant in classes, which are always strict"
+  )]
+  Class,
+  #[display(
+    fmt = "`\"use strict\"` is redundant, since an enclosing scope is already strict"
+  )]
+  Enclosing,
+  #[display(fmt = "`\"use strict\"` is already declared in this prologue")]
+  Duplicate,
+}
+
+impl LintRule for NoRedundantUseStrict {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoRedundantUseStrictHandler {
+      is_module: matches!(program, Program::Module(_)),
+      strict_bodies: Vec::new(),
+    };
+    handler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_redundant_use_strict.md")
+  }
+}
+
+struct NoRedundantUseStrictHandler {
+  is_module: bool,
+  /// Ranges of the scripts and function bodies that have a `"use strict"`
+  /// directive. Their prologues are visited before anything nested in them.
+  strict_bodies: Vec<SourceRange>,
+}
+
+impl NoRedundantUseStrictHandler {
+  fn redundancy(
+    &self,
+    directive: &Directive,
+    body: SourceRange,
+  ) -> Option<NoRedundantUseStrictMessage> {
+    if self.is_module {
+      Some(NoRedundantUseStrictMessage::Module)
+    } else if directive
+      .stmt
+      .ancestors()
+      .any(|ancestor| matches!(ancestor, Node::Class(_)))
+    {
+      Some(NoRedundantUseStrictMessage::Class)
+    } else if self.strict_bodies.contains(&body) {
+      Some(NoRedundantUseStrictMessage::Duplicate)
+    } else if self
+      .strict_bodies
+      .iter()
+      .any(|strict| strict.contains(&body))
+    {
+      Some(NoRedundantUseStrictMessage::Enclosing)
+    } else {
+      None
+    }
+  }
+}
+
+/// Removes the directive along with the whitespace after it, so that the
+/// next statement takes its place.
+fn remove_fix(directive: &Directive, ctx: &Context) -> LintFix {
+  let stmt = directive.stmt.range();
+  let rest =
+    ctx.text_of(SourceRange::new(stmt.end, ctx.text_info().range().end));
+  let whitespace = rest.len() - rest.trim_start().len();
+  LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: "".into(),
+      range: SourceRange::new(stmt.start, stmt.end + whitespace),
+    }],
+  }
+}
+
+impl Handler for NoRedundantUseStrictHandler {
+  fn directive(&mut self, directive: &Directive, ctx: &mut Context) {
+    if !directive.is_use_strict() {
+      return;
+    }
+    let body = directive.stmt.parent().range();
+    match self.redundancy(directive, body) {
+      Some(message) => {
+        let fix = remove_fix(directive, ctx);
+        ctx.add_diagnostic_with_fixes(
+          directive.stmt.range(),
+          CODE,
+          message,
+          Some(HINT.to_string()),
+          vec![fix],
+        );
+      }
+      None => self.strict_bodies.push(body),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_redundant_use_strict_valid() {
+    assert_lint_ok! {
+      NoRedundantUseStrict,
+      r#""use strict";"#,
+      r#""use strict"; function f() {}"#,
+      r#"function f() { "use strict"; }"#,
+      r#"function f() { "use strict"; } function g() { "use strict"; }"#,
+      r#"const f = () => { "use strict"; };"#,
+      r#"function f() { "use strict"; } (function () { "use strict"; })();"#,
+      r#"foo(); "use strict"; function f() { "use strict"; }"#,
+      r#"'use\x20strict'; function f() { "use strict"; }"#,
+      r#"function f() { foo(); "use strict"; }"#,
+      r#"export {}; ("use strict");"#,
+      r#"export function f() { "use asm"; }"#,
+    };
+  }
+
+  #[test]
+  fn no_redundant_use_strict_invalid() {
+    assert_lint_err! {
+      NoRedundantUseStrict,
+      "\"use strict\";\nimport a from \"./a.ts\";": [
+        {
+          col: 0,
+          message: NoRedundantUseStrictMessage::Module,
+          hint: HINT,
+          fix: (FIX_DESC, "import a from \"./a.ts\";"),
+        }
+      ],
+      "export function f() {\n  \"use strict\";\n  return 1;\n}": [
+        {
+          line: 2,
+          col: 2,
+          message: NoRedundantUseStrictMessage::Module,
+          hint: HINT,
+          fix: (FIX_DESC, "export function f() {\n  return 1;\n}"),
+        }
+      ],
+      r#"class A { m() { "use strict"; } }"#: [
+        {
+          col: 16,
+          message: NoRedundantUseStrictMessage::Class,
+          hint: HINT,
+          fix: (FIX_DESC, "class A { m() { } }"),
+        }
+      ],
+      r#"class A { constructor() { "use strict"; } }"#: [
+        {
+          col: 26,
+          message: NoRedundantUseStrictMessage::Class,
+          hint: HINT,
+          fix: (FIX_DESC, "class A { constructor() { } }"),
+        }
+      ],
+      r#"class A { m = () => { "use strict"; }; }"#: [
+        {
+          col: 22,
+          message: NoRedundantUseStrictMessage::Class,
+          hint: HINT,
+          fix: (FIX_DESC, "class A { m = () => { }; }"),
+        }
+      ],
+      r#""use strict"; function f() { "use strict"; }"#: [
+        {
+          col: 29,
+          message: NoRedundantUseStrictMessage::Enclosing,
+          hint: HINT,
+          fix: (FIX_DESC, r#""use strict"; function f() { }"#),
+        }
+      ],
+      r#"function f() { "use strict"; return () => { "use strict"; }; }"#: [
+        {
+          col: 44,
+          message: NoRedundantUseStrictMessage::Enclosing,
+          hint: HINT,
+          fix: (FIX_DESC, r#"function f() { "use strict"; return () => { }; }"#),
+        }
+      ],
+      r#"const o = { get a() { "use strict"; return function () { "use strict"; }; } };"#: [
+        {
+          col: 57,
+          message: NoRedundantUseStrictMessage::Enclosing,
+          hint: HINT,
+          fix: (FIX_DESC, r#"const o = { get a() { "use strict"; return function () { }; } };"#),
+        }
+      ],
+      r#""use strict"; 'use strict'; foo();"#: [
+        {
+          col: 14,
+          message: NoRedundantUseStrictMessage::Duplicate,
+          hint: HINT,
+          fix: (FIX_DESC, r#""use strict"; foo();"#),
+        }
+      ],
+      "\"use strict\";\n\"use strict\"; // again\nfoo();": [
+        {
+          line: 2,
+          col: 0,
+          message: NoRedundantUseStrictMessage::Duplicate,
+          hint: HINT,
+          fix: (FIX_DESC, "\"use strict\";\n// again\nfoo();"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,225 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view as ast_view;
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoReturnInFinallyThatSwallows;
+
+const CODE: &str = "no-return-in-finally-that-swallows";
+const HINT: &str = "The exception is silently discarded when the `finally` block returns. Move the `return` after the `try` statement, or rethrow the exception";
+
+#[derive(Display)]
+enum NoReturnInFinallyThatSwallowsMessage {
+  #[display(
+    fmt = "`return` in `finally` swallows exceptions thrown in the `try` block"
+  )]
+  Try,
+  #[display(
+    fmt = "`return` in `finally` swallows exceptions thrown in the `catch` block"
+  )]
+  Catch,
+}
+
+impl LintRule for NoReturnInFinallyThatSwallows {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoReturnInFinallyThatSwallowsHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_return_in_finally_that_swallows.md")
+  }
+}
+
+struct NoReturnInFinallyThatSwallowsHandler;
+
+impl Handler for NoReturnInFinallyThatSwallowsHandler {
+  fn try_stmt(&mut self, try_stmt: &ast_view::TryStmt, ctx: &mut Context) {
+    let Some(finalizer) = try_stmt.finalizer else {
+      return;
+    };
+
+    // An exception thrown in the `try` block is handled by the `catch`
+    // clause if there's one, so only the `catch` block can throw past it
+    let (block, message) = match try_stmt.handler {
+      Some(handler) => {
+        (handler.body, NoReturnInFinallyThatSwallowsMessage::Catch)
+      }
+      None => (try_stmt.block, NoReturnInFinallyThatSwallowsMessage::Try),
+    };
+    if !BlockVisitor::visit(block, ctx).can_throw {
+      return;
+    }
+
+    for range in BlockVisitor::visit(finalizer, ctx).returns {
+      ctx.add_diagnostic_with_hint(range, CODE, message.to_string(), HINT);
+    }
+  }
+}
+
+/// Collects the `return` statements of a block and whether it contains
+/// anything that could throw, i.e. calls, `new`, `await` or `throw`. Nested
+/// functions are skipped since their bodies don't run as part of the block.
+#[derive(Default)]
+struct BlockVisitor {
+  returns: Vec<SourceRange>,
+  can_throw: bool,
+}
+
+impl BlockVisitor {
+  fn visit(block: &ast_view::BlockStmt, ctx: &mut Context) -> Self {
+    let mut visitor = Self::default();
+    for stmt in &block.stmts {
+      visitor.traverse(*stmt, ctx);
+    }
+    visitor
+  }
+}
+
+impl Handler for BlockVisitor {
+  fn function(&mut self, _n: &ast_view::Function, ctx: &mut Context) {
+    ctx.stop_traverse();
+  }
+
+  fn arrow_expr(&mut self, _n: &ast_view::ArrowExpr, ctx: &mut Context) {
+    ctx.stop_traverse();
+  }
+
+  fn constructor(&mut self, _n: &ast_view::Constructor, ctx: &mut Context) {
+    ctx.stop_traverse();
+  }
+
+  fn getter_prop(&mut self, _n: &ast_view::GetterProp, ctx: &mut Context) {
+    ctx.stop_traverse();
+  }
+
+  fn setter_prop(&mut self, _n: &ast_view::SetterProp, ctx: &mut Context) {
+    ctx.stop_traverse();
+  }
+
+  fn return_stmt(
+    &mut self,
+    return_stmt: &ast_view::ReturnStmt,
+    _ctx: &mut Context,
+  ) {
+    self.returns.push(return_stmt.range());
+  }
+
+  fn call_expr(&mut self, _n: &ast_view::CallExpr, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+
+  fn opt_call(&mut self, _n: &ast_view::OptCall, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+
+  fn new_expr(&mut self, _n: &ast_view::NewExpr, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+
+  fn tagged_tpl(&mut self, _n: &ast_view::TaggedTpl, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+
+  fn await_expr(&mut self, _n: &ast_view::AwaitExpr, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+
+  fn throw_stmt(&mut self, _n: &ast_view::ThrowStmt, _ctx: &mut Context) {
+    self.can_throw = true;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_return_in_finally_that_swallows_valid() {
+    assert_lint_ok! {
+      NoReturnInFinallyThatSwallows,
+      "function f() { try { foo(); } finally { cleanup(); } }",
+      "function f() { try { foo(); } finally { cleanup(); } return 1; }",
+      "function f() { try { x = 1; } finally { return x; } }",
+      "function f() { try { const g = () => foo(); } finally { return 1; } }",
+      "function f() { try { foo(); } catch { return 2; } finally { return 1; } }",
+      "function f() { try { foo(); } catch (e) { log = e; } finally { return 1; } }",
+      "function f() { try { foo(); } finally { const g = () => { return 1; }; } }",
+      "function f() { try { foo(); } finally { function g() { return 1; } } }",
+      "function f() { try { foo(); } catch (e) { throw e; } }",
+    };
+  }
+
+  #[test]
+  fn no_return_in_finally_that_swallows_invalid() {
+    assert_lint_err! {
+      NoReturnInFinallyThatSwallows,
+      "function f() { try { foo(); } finally { return 1; } }": [
+        {
+          col: 40,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        }
+      ],
+      "function f() { try { throw new Error(); } finally { return; } }": [
+        {
+          col: 52,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        }
+      ],
+      "async function f() { try { await p; } finally { if (x) { return 1; } } }": [
+        {
+          col: 57,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        }
+      ],
+      "function f() { try { a?.(); } finally { return 1; } }": [
+        {
+          col: 40,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        }
+      ],
+      "function f() { try { foo(); } catch (e) { throw e; } finally { return 1; } }": [
+        {
+          col: 63,
+          message: NoReturnInFinallyThatSwallowsMessage::Catch,
+          hint: HINT,
+        }
+      ],
+      "function f() { try { x = 1; } catch (e) { log(e); } finally { cleanup(); return 1; } }": [
+        {
+          col: 73,
+          message: NoReturnInFinallyThatSwallowsMessage::Catch,
+          hint: HINT,
+        }
+      ],
+      "function f() { try { foo(); } finally { if (a) return 1; else return 2; } }": [
+        {
+          col: 47,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        },
+        {
+          col: 62,
+          message: NoReturnInFinallyThatSwallowsMessage::Try,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -90,7 +90,7 @@ impl Handler for NoSyncFnInAsyncFnHandler {
     use deno_ast::view::Expr;
     if_chain! {
       if let Expr::Ident(obj) = &member_expr.obj;
-      if ctx.scope().is_global(&obj.inner.to_id());
+      if ctx.is_global_ident(obj);
       let obj_symbol: &str = obj.sym();
       if obj_symbol == "Deno";
       if let Some(prop_symbol) = extract_symbol(&member_expr.prop);
@@ -0,0 +1,267 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::{Id, VarDeclKind};
+use deno_ast::view::{self as ast_view, NodeTrait};
+use deno_ast::{MediaType, SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUnassignedVars;
+
+const CODE: &str = "no-unassigned-vars";
+
+#[derive(Display)]
+enum NoUnassignedVarsMessage {
+  #[display(
+    fmt = "`{}` is never assigned, so it's always `undefined` when read",
+    _0
+  )]
+  NeverAssigned(String),
+}
+
+#[derive(Display)]
+enum NoUnassignedVarsHint {
+  #[display(
+    fmt = "Give `{}` an initializer, or declare it as `const {} = undefined` if that's intended",
+    _0,
+    _0
+  )]
+  Initialize(String),
+}
+
+impl LintRule for NoUnassignedVars {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    if context.media_type() == MediaType::Dts {
+      return;
+    }
+
+    let mut handler = NoUnassignedVarsHandler::default();
+    handler.traverse(program, context);
+
+    for (id, range) in handler.candidates {
+      let usage = handler.usages.get(&id).copied().unwrap_or_default();
+      if usage.writes == 0 && usage.reads > 0 {
+        let name = id.0.to_string();
+        context.add_diagnostic_with_hint(
+          range,
+          CODE,
+          NoUnassignedVarsMessage::NeverAssigned(name.clone()),
+          NoUnassignedVarsHint::Initialize(name),
+        );
+      }
+    }
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_unassigned_vars.md")
+  }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Usage {
+  reads: usize,
+  writes: usize,
+}
+
+#[derive(Default)]
+struct NoUnassignedVarsHandler {
+  /// `let` and `var` declarations without an initializer, in source order.
+  candidates: Vec<(Id, SourceRange)>,
+  usages: HashMap<Id, Usage>,
+}
+
+/// Checks if the declaration is ambient, i.e. it doesn't exist at runtime.
+fn is_ambient(var_decl: &ast_view::VarDecl) -> bool {
+  var_decl.inner.declare
+    || var_decl.ancestors().any(|ancestor| {
+      matches!(ancestor, ast_view::Node::TsModuleDecl(m) if m.inner.declare)
+    })
+}
+
+enum IdentUsage {
+  Read,
+  Write,
+  Declaration,
+}
+
+fn classify(ident: &ast_view::Ident) -> IdentUsage {
+  use deno_ast::view::Node;
+  match ident.parent() {
+    Node::BindingIdent(binding) => match binding.parent() {
+      // A redeclaring `var` can assign the variable, e.g. `var a = 1` after
+      // `var a`
+      Node::VarDeclarator(declarator) => {
+        let is_for_in_or_of = matches!(
+          declarator.parent().parent(),
+          Some(Node::ForInStmt(_) | Node::ForOfStmt(_))
+        );
+        if declarator.init.is_some() || is_for_in_or_of {
+          IdentUsage::Write
+        } else {
+          IdentUsage::Declaration
+        }
+      }
+      // Any other binding position of an already declared variable is an
+      // assignment target, e.g. `[a] = arr` or `for (a of arr)`
+      _ => IdentUsage::Write,
+    },
+    Node::AssignExpr(assign) if assign.left.range() == ident.range() => {
+      IdentUsage::Write
+    }
+    Node::UpdateExpr(_) => IdentUsage::Write,
+    Node::AssignPatProp(prop) if prop.key.range() == ident.range() => {
+      IdentUsage::Write
+    }
+    _ => IdentUsage::Read,
+  }
+}
+
+impl Handler for NoUnassignedVarsHandler {
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    use deno_ast::view::{Node, Pat};
+
+    if declarator.init.is_some() {
+      return;
+    }
+    let var_decl = match declarator.parent() {
+      Node::VarDecl(var_decl) => var_decl,
+      _ => return,
+    };
+    if var_decl.decl_kind() == VarDeclKind::Const || is_ambient(var_decl) {
+      return;
+    }
+    // Variables declared in `for (let x of xs)` or `for (var x in obj)` are
+    // assigned on every iteration
+    if matches!(var_decl.parent(), Node::ForInStmt(_) | Node::ForOfStmt(_)) {
+      return;
+    }
+
+    if let Pat::Ident(binding) = &declarator.name {
+      self
+        .candidates
+        .push((binding.id.inner.to_id(), declarator.range()));
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let usage = match classify(ident) {
+      IdentUsage::Declaration => return,
+      usage => usage,
+    };
+    let entry = self.usages.entry(ident.inner.to_id()).or_default();
+    match usage {
+      IdentUsage::Read => entry.reads += 1,
+      IdentUsage::Write => entry.writes += 1,
+      IdentUsage::Declaration => unreachable!(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_unassigned_vars_valid() {
+    assert_lint_ok! {
+      NoUnassignedVars,
+      "let a = 1; console.log(a);",
+      "let a; a = 1; console.log(a);",
+      "var a; a = 1; console.log(a);",
+      "let a; a += 1; console.log(a);",
+      "let a; a++; console.log(a);",
+      "let a; [a] = [1]; console.log(a);",
+      "let a; ({ a } = { a: 1 }); console.log(a);",
+      "let a; ({ b: a } = { b: 1 }); console.log(a);",
+      "let a; [a = 1] = []; console.log(a);",
+      "let a; for (a of [1, 2]) { console.log(a); }",
+      "let a; for (a in { b: 1 }) { console.log(a); }",
+      "let a; function f() { a = 1; } f(); console.log(a);",
+      "console.log(a); var a; a = 1;",
+      "for (let a of [1, 2]) { console.log(a); }",
+      "for (var a in { b: 1 }) { console.log(a); }",
+      // Never read at all; `no-unused-vars` takes care of this
+      "let a;",
+      // Ambient declarations
+      "declare let a: number; console.log(a);",
+      "declare var a: number; console.log(a);",
+      "declare module 'foo' { let a: number; }",
+      "declare namespace Foo { var a: number; }",
+      "declare global { var a: number; } console.log(a);",
+      "var a; var a = 1; console.log(a);",
+      "var a; for (var a of xs) {} console.log(a);",
+    };
+
+    assert_lint_ok! {
+      NoUnassignedVars,
+      filename: "file:///foo.d.ts",
+      "let a: number; export { a };",
+    };
+  }
+
+  #[test]
+  fn no_unassigned_vars_invalid() {
+    assert_lint_err! {
+      NoUnassignedVars,
+      "let a; console.log(a);": [
+        {
+          col: 4,
+          message: NoUnassignedVarsMessage::NeverAssigned("a".to_string()),
+          hint: NoUnassignedVarsHint::Initialize("a".to_string()),
+        }
+      ],
+      "var a; if (a) {}": [
+        {
+          col: 4,
+          message: NoUnassignedVarsMessage::NeverAssigned("a".to_string()),
+          hint: NoUnassignedVarsHint::Initialize("a".to_string()),
+        }
+      ],
+      "let a, b = 1; console.log(a, b);": [
+        {
+          col: 4,
+          message: NoUnassignedVarsMessage::NeverAssigned("a".to_string()),
+          hint: NoUnassignedVarsHint::Initialize("a".to_string()),
+        }
+      ],
+      "let a: string; const b = { a };": [
+        {
+          col: 4,
+          message: NoUnassignedVarsMessage::NeverAssigned("a".to_string()),
+          hint: NoUnassignedVarsHint::Initialize("a".to_string()),
+        }
+      ],
+      r#"
+let a;
+function f() {
+  let a = 1;
+  return a;
+}
+console.log(a);
+      "#: [
+        {
+          line: 2,
+          col: 4,
+          message: NoUnassignedVarsMessage::NeverAssigned("a".to_string()),
+          hint: NoUnassignedVarsHint::Initialize("a".to_string()),
+        }
+      ],
+    };
+  }
+}
@@ -1,7 +1,6 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::program_ref;
 use super::{Context, LintRule};
-use crate::globals::GLOBALS;
 use crate::Program;
 use crate::ProgramRef;
 use deno_ast::swc::{
@@ -66,7 +65,7 @@ impl<'c, 'view> NoUndefVisitor<'c, 'view> {
     }
 
     // Globals
-    if GLOBALS.iter().any(|(name, _)| name == &&*ident.sym) {
+    if self.context.global_exists(&ident.sym) {
       return;
     }
 
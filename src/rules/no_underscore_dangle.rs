@@ -0,0 +1,291 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, MemberProp, ObjectPatProp, Pat};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct NoUnderscoreDangle;
+
+const CODE: &str = "no-underscore-dangle";
+
+#[derive(Display)]
+enum NoUnderscoreDangleMessage {
+  #[display(fmt = "Unexpected dangling '_' in `{}`", _0)]
+  Dangling(String),
+}
+
+#[derive(Display)]
+enum NoUnderscoreDangleHint {
+  #[display(fmt = "Rename it to `{}`", _0)]
+  Rename(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NoUnderscoreDangleOptions {
+  /// Names that are allowed to have dangling underscores.
+  allow: Vec<String>,
+  /// Don't check properties in member expressions, e.g. `obj._foo`.
+  allow_in_member_expressions: bool,
+  /// Don't check properties of `this`, e.g. `this._foo`.
+  allow_after_this: bool,
+  allow_function_params: bool,
+}
+
+impl Default for NoUnderscoreDangleOptions {
+  fn default() -> Self {
+    Self {
+      allow: Vec::new(),
+      allow_in_member_expressions: false,
+      allow_after_this: false,
+      allow_function_params: true,
+    }
+  }
+}
+
+impl LintRule for NoUnderscoreDangle {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUnderscoreDangleHandler {
+      options: context.rule_options(CODE),
+    };
+    handler.traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoUnderscoreDangleOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_underscore_dangle.md")
+  }
+}
+
+/// Check if the name starts or ends with an underscore. Names consisting
+/// only of underscores, like `_`, are conventionally used for unused values
+/// and aren't dangling. `__proto__` is left to `no-proto`.
+fn has_dangling_underscore(name: &str) -> bool {
+  (name.starts_with('_') || name.ends_with('_'))
+    && !name.trim_matches('_').is_empty()
+    && name != "__proto__"
+}
+
+struct NoUnderscoreDangleHandler {
+  options: NoUnderscoreDangleOptions,
+}
+
+impl NoUnderscoreDangleHandler {
+  fn check_name(&self, range: SourceRange, name: &str, ctx: &mut Context) {
+    if !has_dangling_underscore(name)
+      || self.options.allow.iter().any(|allowed| allowed == name)
+    {
+      return;
+    }
+    ctx.add_diagnostic_with_hint(
+      range,
+      CODE,
+      NoUnderscoreDangleMessage::Dangling(name.to_string()),
+      NoUnderscoreDangleHint::Rename(name.trim_matches('_').to_string()),
+    );
+  }
+
+  fn check_ident(&self, ident: &ast_view::Ident, ctx: &mut Context) {
+    self.check_name(ident.range(), ident.sym(), ctx);
+  }
+
+  /// Checks the identifiers that a pattern binds.
+  fn check_pat(&self, pat: &Pat, ctx: &mut Context) {
+    match pat {
+      Pat::Ident(binding) => self.check_ident(binding.id, ctx),
+      Pat::Array(array) => {
+        for elem in array.elems.iter().flatten() {
+          self.check_pat(elem, ctx);
+        }
+      }
+      Pat::Object(object) => {
+        for prop in &object.props {
+          match prop {
+            ObjectPatProp::KeyValue(key_value) => {
+              self.check_pat(&key_value.value, ctx)
+            }
+            ObjectPatProp::Assign(assign) => self.check_ident(assign.key, ctx),
+            ObjectPatProp::Rest(rest) => self.check_pat(&rest.arg, ctx),
+          }
+        }
+      }
+      Pat::Assign(assign) => self.check_pat(&assign.left, ctx),
+      Pat::Rest(rest) => self.check_pat(&rest.arg, ctx),
+      Pat::Invalid(_) | Pat::Expr(_) => {}
+    }
+  }
+}
+
+impl Handler for NoUnderscoreDangleHandler {
+  fn var_declarator(
+    &mut self,
+    var_declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    self.check_pat(&var_declarator.name, ctx);
+  }
+
+  fn fn_decl(&mut self, fn_decl: &ast_view::FnDecl, ctx: &mut Context) {
+    self.check_ident(fn_decl.ident, ctx);
+  }
+
+  fn fn_expr(&mut self, fn_expr: &ast_view::FnExpr, ctx: &mut Context) {
+    if let Some(ident) = fn_expr.ident {
+      self.check_ident(ident, ctx);
+    }
+  }
+
+  fn function(&mut self, function: &ast_view::Function, ctx: &mut Context) {
+    if !self.options.allow_function_params {
+      for param in &function.params {
+        self.check_pat(&param.pat, ctx);
+      }
+    }
+  }
+
+  fn arrow_expr(
+    &mut self,
+    arrow_expr: &ast_view::ArrowExpr,
+    ctx: &mut Context,
+  ) {
+    if !self.options.allow_function_params {
+      for param in &arrow_expr.params {
+        self.check_pat(param, ctx);
+      }
+    }
+  }
+
+  fn member_expr(
+    &mut self,
+    member_expr: &ast_view::MemberExpr,
+    ctx: &mut Context,
+  ) {
+    if self.options.allow_in_member_expressions {
+      return;
+    }
+    if self.options.allow_after_this && matches!(member_expr.obj, Expr::This(_))
+    {
+      return;
+    }
+    if let MemberProp::Ident(ident) = &member_expr.prop {
+      self.check_ident(ident, ctx);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_underscore_dangle_valid() {
+    assert_lint_ok! {
+      NoUnderscoreDangle,
+      "const foo = 1;",
+      "const foo_bar = 1;",
+      "const _ = 1; const __ = 2;",
+      "foo(_bar);",
+      "function foo(_bar) {}",
+      "const f = (_bar) => {};",
+      "obj.__proto__;",
+      "obj['_foo'];",
+      "\n// deno-lint-config { \"rules\": { \"no-underscore-dangle\": { \"allow\": [\"_id\"] } } }\nconst _id = obj._id;",
+      "\n// deno-lint-config { \"rules\": { \"no-underscore-dangle\": { \"allowInMemberExpressions\": true } } }\nobj._foo; this._bar;",
+      "\n// deno-lint-config { \"rules\": { \"no-underscore-dangle\": { \"allowAfterThis\": true } } }\nthis._foo;",
+    };
+  }
+
+  #[test]
+  fn no_underscore_dangle_invalid() {
+    assert_lint_err! {
+      NoUnderscoreDangle,
+      "const _foo = 1;": [
+        {
+          col: 6,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "let { a: foo_, ...__rest } = obj;": [
+        {
+          col: 9,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "foo_"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        },
+        {
+          col: 18,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "__rest"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "rest"),
+        }
+      ],
+      "function _foo() {}": [
+        {
+          col: 9,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "const f = function _foo() {};": [
+        {
+          col: 19,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "obj._foo;": [
+        {
+          col: 4,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "this._foo;": [
+        {
+          col: 5,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"no-underscore-dangle\": { \"allowAfterThis\": true } } }\nobj._foo;": [
+        {
+          line: 3,
+          col: 4,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_foo"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "foo"),
+        }
+      ],
+      "\n// deno-lint-config { \"rules\": { \"no-underscore-dangle\": { \"allowFunctionParams\": false } } }\nconst f = (a, _b) => {}; function g({ c_ }) {}": [
+        {
+          line: 3,
+          col: 14,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "_b"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "b"),
+        },
+        {
+          line: 3,
+          col: 38,
+          message: variant!(NoUnderscoreDangleMessage, Dangling, "c_"),
+          hint: variant!(NoUnderscoreDangleHint, Rename, "c"),
+        }
+      ],
+    };
+  }
+}
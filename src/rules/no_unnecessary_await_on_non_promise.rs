@@ -0,0 +1,196 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::StringRepr;
+use crate::Program;
+use deno_ast::swc::ast::{Prop, PropOrSpread};
+use deno_ast::view::{self as ast_view, Expr, ObjectLit};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUnnecessaryAwaitOnNonPromise;
+
+const CODE: &str = "no-unnecessary-await-on-non-promise";
+const HINT: &str = "Remove the `await`, the value can be used directly";
+const FIX_DESC: &str = "Remove `await`";
+
+#[derive(Display)]
+enum NoUnnecessaryAwaitOnNonPromiseMessage {
+  #[display(fmt = "Unnecessary `await` on a value that is never a promise")]
+  Unnecessary,
+}
+
+impl LintRule for NoUnnecessaryAwaitOnNonPromise {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUnnecessaryAwaitOnNonPromiseHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_unnecessary_await_on_non_promise.md")
+  }
+}
+
+struct NoUnnecessaryAwaitOnNonPromiseHandler;
+
+/// Checks if the object literal may have a `then` property, in which case
+/// awaiting it calls that.
+fn may_be_thenable(object: &ObjectLit) -> bool {
+  object.inner.props.iter().any(|prop| match prop {
+    PropOrSpread::Spread(_) => true,
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::Shorthand(ident) => &*ident.sym == "then",
+      // Computed keys that aren't literals might be `then`
+      prop => prop.string_repr().map_or(true, |key| key == "then"),
+    },
+  })
+}
+
+/// Checks if the expression is syntactically known to never be thenable.
+fn is_non_thenable(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(_) | Expr::Tpl(_) | Expr::Array(_) => true,
+    Expr::Object(object) => !may_be_thenable(object),
+    Expr::Paren(paren) => is_non_thenable(&paren.expr),
+    _ => false,
+  }
+}
+
+impl Handler for NoUnnecessaryAwaitOnNonPromiseHandler {
+  fn await_expr(
+    &mut self,
+    await_expr: &ast_view::AwaitExpr,
+    ctx: &mut Context,
+  ) {
+    if !is_non_thenable(&await_expr.arg) {
+      return;
+    }
+
+    let arg = ctx.text_of(await_expr.arg.range());
+    // An object literal could otherwise be parsed as a block
+    let new_text = if matches!(await_expr.arg, Expr::Object(_)) {
+      format!("({})", arg)
+    } else {
+      arg.to_string()
+    };
+    ctx.add_diagnostic_with_fixes(
+      await_expr.range(),
+      CODE,
+      NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+      Some(HINT.to_string()),
+      vec![LintFix {
+        description: FIX_DESC.into(),
+        changes: vec![LintFixChange {
+          new_text: new_text.into(),
+          range: await_expr.range(),
+        }],
+      }],
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_unnecessary_await_on_non_promise_valid() {
+    assert_lint_ok! {
+      NoUnnecessaryAwaitOnNonPromise,
+      "async function f() { await foo(); }",
+      "async function f() { await promise; }",
+      "async function f() { await Promise.resolve(5); }",
+      "async function f() { await tag`x`; }",
+      "async function f() { await new Foo(); }",
+
+      // Object literals that may have a `then` property
+      "async function f() { await { then() {} }; }",
+      "async function f() { await { then: (resolve) => resolve(1) }; }",
+      "async function f() { await { 'then': foo }; }",
+      "async function f() { await { then }; }",
+      "async function f() { await { [key]: foo }; }",
+      "async function f() { await { ...other }; }",
+    };
+  }
+
+  #[test]
+  fn no_unnecessary_await_on_non_promise_invalid() {
+    assert_lint_err! {
+      NoUnnecessaryAwaitOnNonPromise,
+      "async function f() { await 5; }": [
+        {
+          col: 21,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() { 5; }"),
+        }
+      ],
+      r#"async function f() { return await "x"; }"#: [
+        {
+          col: 28,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, r#"async function f() { return "x"; }"#),
+        }
+      ],
+      "async function f() { const a = await [1, 2]; }": [
+        {
+          col: 31,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() { const a = [1, 2]; }"),
+        }
+      ],
+      "async function f() { await {}; }": [
+        {
+          col: 21,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() { ({}); }"),
+        }
+      ],
+      "const f = async () => await { a: 1, b };": [
+        {
+          col: 22,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "const f = async () => ({ a: 1, b });"),
+        }
+      ],
+      "async function f() { await (null); }": [
+        {
+          col: 21,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() { (null); }"),
+        }
+      ],
+      "async function f() { foo(await `text`); }": [
+        {
+          col: 25,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() { foo(`text`); }"),
+        }
+      ],
+      "await true;": [
+        {
+          col: 0,
+          message: NoUnnecessaryAwaitOnNonPromiseMessage::Unnecessary,
+          hint: HINT,
+          fix: (FIX_DESC, "true;"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,428 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::atoms::JsWord;
+use deno_ast::view::{self as ast_view, NodeTrait, Stmt};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct NoUnreachableLoop;
+
+const CODE: &str = "no-unreachable-loop";
+const HINT: &str =
+  "Use an `if` statement instead, or make sure the loop can continue";
+
+#[derive(Display)]
+enum NoUnreachableLoopMessage {
+  #[display(
+    fmt = "This loop runs at most once because its body always exits before the next iteration"
+  )]
+  Unexpected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum LoopKind {
+  For,
+  ForIn,
+  ForOf,
+  While,
+  DoWhile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NoUnreachableLoopOptions {
+  /// Kinds of loops that aren't checked.
+  ignore: HashSet<LoopKind>,
+}
+
+impl LintRule for NoUnreachableLoop {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: NoUnreachableLoopOptions = context.rule_options(CODE);
+    NoUnreachableLoopHandler { options }.traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoUnreachableLoopOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_unreachable_loop.md")
+  }
+}
+
+struct NoUnreachableLoopHandler {
+  options: NoUnreachableLoopOptions,
+}
+
+impl NoUnreachableLoopHandler {
+  fn check_loop(
+    &self,
+    node: ast_view::Node,
+    kind: LoopKind,
+    body: &Stmt,
+    ctx: &mut Context,
+  ) {
+    if self.options.ignore.contains(&kind) {
+      return;
+    }
+
+    let mut analysis = LoopBodyAnalysis {
+      targets: vec![Target {
+        labels: labels_of(node),
+        kind: TargetKind::Loop,
+      }],
+      broken: HashSet::new(),
+      loops: false,
+    };
+    let completes = analysis.stmt(body);
+    if !completes && !analysis.loops {
+      ctx.add_diagnostic_with_hint(
+        node.range(),
+        CODE,
+        NoUnreachableLoopMessage::Unexpected,
+        HINT,
+      );
+    }
+  }
+}
+
+/// The labels of a statement, e.g. `a` and `b` in `a: b: while (x) {}`.
+fn labels_of(node: ast_view::Node) -> Vec<JsWord> {
+  let mut labels = Vec::new();
+  let mut parent = node.parent();
+  while let Some(ast_view::Node::LabeledStmt(labeled)) = parent {
+    labels.push(labeled.label.sym().clone());
+    parent = Some(labeled.parent());
+  }
+  labels
+}
+
+#[derive(PartialEq)]
+enum TargetKind {
+  Loop,
+  Switch,
+  Labeled,
+}
+
+/// A statement that `break` or `continue` can jump to.
+struct Target {
+  labels: Vec<JsWord>,
+  kind: TargetKind,
+}
+
+/// Finds out whether the body of a loop can get to the next iteration, by
+/// completing normally or with a `continue` of the loop.
+///
+/// The existing control flow analysis doesn't tell `break` and `continue`
+/// apart, so the body is walked here instead. Anything that isn't understood
+/// is assumed to complete normally.
+struct LoopBodyAnalysis {
+  /// Statements that can be jumped to, from the checked loop inwards.
+  targets: Vec<Target>,
+  /// Indexes of the targets that a `break` jumps out of.
+  broken: HashSet<usize>,
+  /// Whether a `continue` of the checked loop was found.
+  loops: bool,
+}
+
+impl LoopBodyAnalysis {
+  /// Returns whether the statements can complete normally.
+  fn stmts(&mut self, stmts: &[Stmt]) -> bool {
+    // Statements after one that never completes are unreachable
+    stmts.iter().all(|stmt| self.stmt(stmt))
+  }
+
+  /// Returns whether the statement can complete normally.
+  fn stmt(&mut self, stmt: &Stmt) -> bool {
+    match stmt {
+      Stmt::Block(block) => self.stmts(&block.stmts),
+      Stmt::Return(_) | Stmt::Throw(_) => false,
+      Stmt::Break(break_stmt) => {
+        self.jump(break_stmt.label, false);
+        false
+      }
+      Stmt::Continue(continue_stmt) => {
+        self.jump(continue_stmt.label, true);
+        false
+      }
+      Stmt::If(if_stmt) => {
+        let cons = self.stmt(&if_stmt.cons);
+        let alt = if_stmt.alt.map_or(true, |alt| self.stmt(&alt));
+        cons || alt
+      }
+      Stmt::With(with_stmt) => self.stmt(&with_stmt.body),
+      Stmt::Labeled(labeled) => {
+        let mut labels = vec![labeled.label.sym().clone()];
+        let mut body = labeled.body;
+        while let Stmt::Labeled(labeled) = body {
+          labels.push(labeled.label.sym().clone());
+          body = labeled.body;
+        }
+        match loop_body(&body) {
+          Some(loop_body) => self.nested_loop(labels, &loop_body),
+          None => self.with_target(labels, TargetKind::Labeled, |analysis| {
+            analysis.stmt(&body)
+          }),
+        }
+      }
+      Stmt::Switch(switch_stmt) => {
+        self.with_target(Vec::new(), TargetKind::Switch, |analysis| {
+          // Any case can be jumped to, so each one starts out reachable
+          let mut completes = true;
+          for case in &switch_stmt.cases {
+            completes = analysis.stmts(&case.cons);
+          }
+          let has_default =
+            switch_stmt.cases.iter().any(|case| case.test.is_none());
+          completes || !has_default
+        })
+      }
+      Stmt::Try(try_stmt) => {
+        let block = self.stmts(&try_stmt.block.stmts);
+        // Any statement of the `try` block may throw
+        let mut completes = match try_stmt.handler {
+          Some(handler) => {
+            let handler = self.stmts(&handler.body.stmts);
+            block || handler
+          }
+          None => block,
+        };
+        if let Some(finalizer) = try_stmt.finalizer {
+          completes &= self.stmts(&finalizer.stmts);
+        }
+        completes
+      }
+      _ => match loop_body(stmt) {
+        Some(loop_body) => self.nested_loop(Vec::new(), &loop_body),
+        None => true,
+      },
+    }
+  }
+
+  fn nested_loop(&mut self, labels: Vec<JsWord>, body: &Stmt) -> bool {
+    self.with_target(labels, TargetKind::Loop, |analysis| {
+      analysis.stmt(body);
+      // The loop may run zero times
+      true
+    })
+  }
+
+  fn with_target(
+    &mut self,
+    labels: Vec<JsWord>,
+    kind: TargetKind,
+    op: impl FnOnce(&mut Self) -> bool,
+  ) -> bool {
+    self.targets.push(Target { labels, kind });
+    let completes = op(self);
+    let index = self.targets.len() - 1;
+    self.targets.pop();
+    // A `break` out of the statement completes it normally
+    self.broken.remove(&index) || completes
+  }
+
+  fn jump(&mut self, label: Option<&ast_view::Ident>, is_continue: bool) {
+    let target = self.targets.iter().rposition(|target| match label {
+      Some(label) => target.labels.contains(label.sym()),
+      None => {
+        target.kind == TargetKind::Loop
+          || (!is_continue && target.kind == TargetKind::Switch)
+      }
+    });
+    match target {
+      Some(0) => self.loops |= is_continue,
+      Some(index) if !is_continue => {
+        self.broken.insert(index);
+      }
+      // A `continue` of a nested loop stays inside the checked loop, and
+      // any other jump leaves it
+      _ => {}
+    }
+  }
+}
+
+fn loop_body<'a>(stmt: &Stmt<'a>) -> Option<Stmt<'a>> {
+  match stmt {
+    Stmt::For(for_stmt) => Some(for_stmt.body),
+    Stmt::ForIn(for_in_stmt) => Some(for_in_stmt.body),
+    Stmt::ForOf(for_of_stmt) => Some(for_of_stmt.body),
+    Stmt::While(while_stmt) => Some(while_stmt.body),
+    Stmt::DoWhile(do_while_stmt) => Some(do_while_stmt.body),
+    _ => None,
+  }
+}
+
+impl Handler for NoUnreachableLoopHandler {
+  fn for_stmt(&mut self, for_stmt: &ast_view::ForStmt, ctx: &mut Context) {
+    self.check_loop(for_stmt.as_node(), LoopKind::For, &for_stmt.body, ctx);
+  }
+
+  fn for_in_stmt(
+    &mut self,
+    for_in_stmt: &ast_view::ForInStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_loop(
+      for_in_stmt.as_node(),
+      LoopKind::ForIn,
+      &for_in_stmt.body,
+      ctx,
+    );
+  }
+
+  fn for_of_stmt(
+    &mut self,
+    for_of_stmt: &ast_view::ForOfStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_loop(
+      for_of_stmt.as_node(),
+      LoopKind::ForOf,
+      &for_of_stmt.body,
+      ctx,
+    );
+  }
+
+  fn while_stmt(
+    &mut self,
+    while_stmt: &ast_view::WhileStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_loop(
+      while_stmt.as_node(),
+      LoopKind::While,
+      &while_stmt.body,
+      ctx,
+    );
+  }
+
+  fn do_while_stmt(
+    &mut self,
+    do_while_stmt: &ast_view::DoWhileStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_loop(
+      do_while_stmt.as_node(),
+      LoopKind::DoWhile,
+      &do_while_stmt.body,
+      ctx,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_unreachable_loop_valid() {
+    assert_lint_ok! {
+      NoUnreachableLoop,
+      "while (a) { foo(); }",
+      "while (a) { if (b) { return; } }",
+      "while (a) { if (b) { break; } else { foo(); } }",
+      "for (const x of xs) { if (x) { continue; } return x; }",
+      "for (;;) { try { return foo(); } catch { } }",
+      "for (;;) { switch (a) { case 1: return; } }",
+      "for (;;) { switch (a) { case 1: break; default: return; } }",
+      "do { if (a) continue; break; } while (b);",
+      "for (;;) { if (a) { continue; } else { foo(); } break; }",
+      "outer: for (;;) { inner: { break inner; } }",
+      "for (;;) { for (;;) { if (a) { break; } } }",
+      "for (;;) { const f = () => { return; }; }",
+      "for (;;) { function f() { return; } }",
+
+      // The option ignores the loop kind
+      r#"
+// deno-lint-config { "rules": { "no-unreachable-loop": { "ignore": ["forOf", "while"] } } }
+for (const x of xs) { return x; }
+while (a) { break; }
+      "#,
+    };
+  }
+
+  #[test]
+  fn no_unreachable_loop_invalid() {
+    assert_lint_err! {
+      NoUnreachableLoop,
+      "while (a) { foo(); break; }": [
+        {
+          col: 0,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "function f(xs) { for (const x of xs) { return x; } }": [
+        {
+          col: 17,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "for (const key in obj) { if (a) { return 1; } else { throw e; } }": [
+        {
+          col: 0,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "do { switch (a) { case 1: return; default: throw e; } } while (b);": [
+        {
+          col: 0,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      // The outer loop is assumed to complete since inner loops may not run
+      "outer: for (;;) { for (;;) { break outer; } }": [
+        {
+          col: 18,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "outer: for (;;) { for (;;) { continue outer; } }": [
+        {
+          col: 18,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "for (;;) { try { return; } finally { foo(); } }": [
+        {
+          col: 0,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-unreachable-loop": { "ignore": ["forOf"] } } }
+while (a) { break; }
+      "#: [
+        {
+          line: 3,
+          col: 0,
+          message: NoUnreachableLoopMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,181 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::StringRepr;
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, Prop, PropName, PropOrSpread};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUnsafeArgumentSpread;
+
+const CODE: &str = "no-unsafe-argument-spread";
+const HINT: &str =
+  "Objects aren't iterable, pass the object itself or spread an array instead";
+
+#[derive(Display)]
+enum NoUnsafeArgumentSpreadMessage {
+  #[display(fmt = "Object literals can't be spread into function arguments")]
+  ObjectLiteral,
+}
+
+impl LintRule for NoUnsafeArgumentSpread {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program,
+  ) {
+    NoUnsafeArgumentSpreadHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_unsafe_argument_spread.md")
+  }
+}
+
+struct NoUnsafeArgumentSpreadHandler;
+
+fn unwrap_parens<'a>(expr: &Expr<'a>) -> Expr<'a> {
+  match expr {
+    Expr::Paren(paren_expr) => unwrap_parens(&paren_expr.expr),
+    _ => *expr,
+  }
+}
+
+/// Checks if the object literal may define `Symbol.iterator`, which makes it
+/// iterable. A spread may copy it, a computed key may be it, and
+/// `__proto__` may inherit it.
+fn may_be_iterable(object: &ast_view::ObjectLit) -> bool {
+  object.props.iter().any(|prop| match prop {
+    PropOrSpread::Spread(_) => true,
+    PropOrSpread::Prop(Prop::KeyValue(key_value)) => {
+      matches!(key_value.key, PropName::Computed(_))
+        || key_value.key.string_repr().as_deref() == Some("__proto__")
+    }
+    PropOrSpread::Prop(Prop::Getter(getter)) => {
+      matches!(getter.key, PropName::Computed(_))
+    }
+    PropOrSpread::Prop(Prop::Method(method)) => {
+      matches!(method.key, PropName::Computed(_))
+    }
+    // Setters don't define a value to iterate with
+    PropOrSpread::Prop(
+      Prop::Setter(_) | Prop::Shorthand(_) | Prop::Assign(_),
+    ) => false,
+  })
+}
+
+impl Handler for NoUnsafeArgumentSpreadHandler {
+  fn expr_or_spread(
+    &mut self,
+    expr_or_spread: &ast_view::ExprOrSpread,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    if expr_or_spread.inner.spread.is_none()
+      || !matches!(
+        expr_or_spread.parent(),
+        Node::CallExpr(_) | Node::NewExpr(_) | Node::OptCall(_)
+      )
+    {
+      return;
+    }
+
+    // Spreading an object literal that isn't iterable throws a `TypeError` at
+    // runtime
+    if let Expr::Object(object) = unwrap_parens(&expr_or_spread.expr) {
+      if may_be_iterable(object) {
+        return;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        expr_or_spread.range(),
+        CODE,
+        NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+        HINT,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_unsafe_argument_spread_valid() {
+    assert_lint_ok! {
+      NoUnsafeArgumentSpread,
+      "foo(...args);",
+      "foo(...[1, 2]);",
+      "foo(...'abc');",
+      "foo({ a: 1 });",
+      "foo({ ...obj });",
+      "new Foo(...args);",
+      "const a = [...{ length: 0 }];",
+      "const a = { ...{ a: 1 } };",
+
+      // These may be iterable
+      "foo(...({ ...obj }));",
+      "foo(...{ [Symbol.iterator]: function* () {} });",
+      "foo(...{ *[Symbol.iterator]() {} });",
+      "foo(...{ [key]: value });",
+      "foo(...{ __proto__: [1, 2] });",
+    };
+  }
+
+  #[test]
+  fn no_unsafe_argument_spread_invalid() {
+    assert_lint_err! {
+      NoUnsafeArgumentSpread,
+      "foo(...{});": [
+        {
+          col: 4,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+      "foo(a, ...{ b: 1 }, c);": [
+        {
+          col: 7,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+      "foo(...({ a, 'b': 1, get c() { return 1; }, d() {} }));": [
+        {
+          col: 4,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+      "new Foo(...{ a: 1 });": [
+        {
+          col: 8,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+      "foo?.(...{ a: 1 });": [
+        {
+          col: 6,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+      "obj.method(...{ a: 1 });": [
+        {
+          col: 11,
+          message: NoUnsafeArgumentSpreadMessage::ObjectLiteral,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -1,8 +1,9 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
-use deno_ast::{view as ast_view, SourceRanged};
+use deno_ast::{view as ast_view, SourceRange, SourceRanged};
 use derive_more::Display;
 use if_chain::if_chain;
 
@@ -10,6 +11,7 @@ use if_chain::if_chain;
 pub struct NoUnusedLabels;
 
 const CODE: &str = "no-unused-labels";
+const FIX_DESC: &str = "Remove the label";
 
 #[derive(Display)]
 enum NoUnusedLabelsMessage {
@@ -99,10 +101,27 @@ impl Handler for NoUnusedLabelsHandler {
       if let Some(label) = self.labels.pop();
       if !label.used;
       then {
-        ctx.add_diagnostic(
+        // Only `LABEL:` and whitespace is removed, comments are kept
+        let label_range =
+          SourceRange::new(labeled_stmt.start(), labeled_stmt.body.start());
+        let label_text = format!("{}:", label.name);
+        let fixes = if ctx.text_of(label_range).trim_end() == label_text {
+          vec![LintFix {
+            description: FIX_DESC.into(),
+            changes: vec![LintFixChange {
+              new_text: "".into(),
+              range: label_range,
+            }],
+          }]
+        } else {
+          vec![]
+        };
+        ctx.add_diagnostic_with_fixes(
           labeled_stmt.range(),
           CODE,
           NoUnusedLabelsMessage::Unused(label.name),
+          None,
+          fixes,
         );
       }
     }
@@ -134,32 +153,76 @@ mod tests {
         {
           col: 0,
           message: variant!(NoUnusedLabelsMessage, Unused, "LABEL"),
+          fix: (FIX_DESC, "var a = 0;"),
         }
       ],
       "LABEL: if (something) { a(); }": [
         {
           col: 0,
           message: variant!(NoUnusedLabelsMessage, Unused, "LABEL"),
+          fix: (FIX_DESC, "if (something) { a(); }"),
         }
       ],
       "LABEL: for (let i = 0; i < 5; i++) { a(); b(); }": [
         {
           col: 0,
           message: variant!(NoUnusedLabelsMessage, Unused, "LABEL"),
+          fix: (FIX_DESC, "for (let i = 0; i < 5; i++) { a(); b(); }"),
         }
       ],
       "A: for (var i = 0; i < 10; ++i) { B: break A; }": [
         {
           col: 34,
           message: variant!(NoUnusedLabelsMessage, Unused, "B"),
+          fix: (FIX_DESC, "A: for (var i = 0; i < 10; ++i) { break A; }"),
         }
       ],
       "A: { let A = 0; console.log(A); }": [
         {
           col: 0,
           message: variant!(NoUnusedLabelsMessage, Unused, "A"),
+          fix: (FIX_DESC, "{ let A = 0; console.log(A); }"),
+        }
+      ],
+      "A: { A: for (;;) { break A; } }": [
+        {
+          col: 0,
+          message: variant!(NoUnusedLabelsMessage, Unused, "A"),
+          fix: (FIX_DESC, "{ A: for (;;) { break A; } }"),
+        }
+      ],
+      "A: B: for (;;) { break B; }": [
+        {
+          col: 0,
+          message: variant!(NoUnusedLabelsMessage, Unused, "A"),
+          fix: (FIX_DESC, "B: for (;;) { break B; }"),
+        }
+      ],
+      r#"
+LABEL:
+while (true) {
+  a();
+}
+      "#: [
+        {
+          line: 2,
+          col: 0,
+          message: variant!(NoUnusedLabelsMessage, Unused, "LABEL"),
+          fix: (FIX_DESC, r#"
+while (true) {
+  a();
+}
+      "#),
         }
       ],
     };
   }
+
+  #[test]
+  fn no_unused_label_invalid_without_fix() {
+    let src = "LABEL: /* keep */ for (;;) {}";
+    let diagnostics = crate::test_util::lint(&NoUnusedLabels, src, "foo.ts");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes.is_empty());
+  }
 }
@@ -0,0 +1,175 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::StringRepr;
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, Expr, OptChainBase, Prop, PropName, PropOrSpread,
+};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentBeforeOverwriteInObject;
+
+const CODE: &str = "no-useless-assignment-before-overwrite-in-object";
+const HINT: &str = "The call still runs, but its result is discarded. Remove the property, or make the call a separate statement if its side effects are needed";
+
+#[derive(Display)]
+enum NoUselessAssignmentBeforeOverwriteInObjectMessage {
+  #[display(
+    fmt = "The value of '{}' is computed by a call, but overwritten by a later property with the same key",
+    _0
+  )]
+  Overwritten(String),
+}
+
+impl LintRule for NoUselessAssignmentBeforeOverwriteInObject {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentBeforeOverwriteInObjectHandler
+      .traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_before_overwrite_in_object.md"
+    )
+  }
+}
+
+struct NoUselessAssignmentBeforeOverwriteInObjectHandler;
+
+/// The key that the property defines, if it's known statically.
+fn key_of(prop: &Prop) -> Option<String> {
+  let key: &PropName = match prop {
+    Prop::Shorthand(ident) => return Some(ident.sym().to_string()),
+    Prop::KeyValue(key_value) => &key_value.key,
+    Prop::Getter(getter) => &getter.key,
+    Prop::Setter(setter) => &setter.key,
+    Prop::Method(method) => &method.key,
+    Prop::Assign(_) => return None,
+  };
+  key.string_repr()
+}
+
+/// Whether the value is a call, whose side effects run even though the value
+/// is discarded, e.g. `load()`, `new Cache()` or `await fetch(url)`.
+fn is_call(expr: &Expr) -> bool {
+  match expr {
+    Expr::Call(_) | Expr::New(_) | Expr::TaggedTpl(_) => true,
+    Expr::OptChain(opt_chain) => {
+      matches!(opt_chain.base, OptChainBase::Call(_))
+    }
+    Expr::Paren(paren) => is_call(&paren.expr),
+    Expr::Await(await_expr) => is_call(&await_expr.arg),
+    _ => false,
+  }
+}
+
+impl Handler for NoUselessAssignmentBeforeOverwriteInObjectHandler {
+  fn object_lit(&mut self, obj_lit: &ast_view::ObjectLit, ctx: &mut Context) {
+    let props = obj_lit
+      .props
+      .iter()
+      .map(|prop| match prop {
+        PropOrSpread::Prop(prop) => key_of(prop).map(|key| (*prop, key)),
+        // A spread may or may not set any key
+        PropOrSpread::Spread(_) => None,
+      })
+      .collect::<Vec<_>>();
+    let mut last_index_of_key = HashMap::new();
+    for (index, prop) in props.iter().enumerate() {
+      if let Some((_, key)) = prop {
+        last_index_of_key.insert(key.as_str(), index);
+      }
+    }
+
+    for (index, prop) in props.iter().enumerate() {
+      let Some((Prop::KeyValue(key_value), key)) = prop else {
+        continue;
+      };
+      if last_index_of_key[key.as_str()] == index || !is_call(&key_value.value)
+      {
+        continue;
+      }
+      ctx.add_diagnostic_with_hint(
+        key_value.range(),
+        CODE,
+        NoUselessAssignmentBeforeOverwriteInObjectMessage::Overwritten(
+          key.clone(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_before_overwrite_in_object_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentBeforeOverwriteInObject,
+      "const o = { a: load(), b: load() };",
+      "const o = { a: 1, a: load() };",
+      "const o = { a: b, a: c };",
+      "const o = { a: load(), ...rest };",
+      "const o = { a: load(), [a]: 1 };",
+      "const o = { [a]: load(), [a]: 1 };",
+      "const o = { a: () => load(), a: 1 };",
+      "const o = { a: load(), b: { a: 1 } };",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_before_overwrite_in_object_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentBeforeOverwriteInObject,
+      "const o = { a: load(), a: 1 };": [
+        {
+          col: 12,
+          message: variant!(NoUselessAssignmentBeforeOverwriteInObjectMessage, Overwritten, "a"),
+          hint: HINT,
+        }
+      ],
+      "const o = { 'a': new Cache(), b, a };": [
+        {
+          col: 12,
+          message: variant!(NoUselessAssignmentBeforeOverwriteInObjectMessage, Overwritten, "a"),
+          hint: HINT,
+        }
+      ],
+      "async () => ({ 1: (await fetch(url)), ...rest, [1]: f?.(), 1: 2 });": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentBeforeOverwriteInObjectMessage, Overwritten, "1"),
+          hint: HINT,
+        },
+        {
+          col: 47,
+          message: variant!(NoUselessAssignmentBeforeOverwriteInObjectMessage, Overwritten, "1"),
+          hint: HINT,
+        }
+      ],
+      "const o = { a: tag`x`, get a() { return 1; } };": [
+        {
+          col: 12,
+          message: variant!(NoUselessAssignmentBeforeOverwriteInObjectMessage, Overwritten, "a"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
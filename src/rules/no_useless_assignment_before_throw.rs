@@ -0,0 +1,293 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::FunctionLike;
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::is_side_effect_free;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, Pat, PatOrExpr, Stmt, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentBeforeThrow;
+
+const CODE: &str = "no-useless-assignment-before-throw";
+const HINT: &str = "Remove the assignment";
+
+#[derive(Display)]
+enum NoUselessAssignmentBeforeThrowMessage {
+  #[display(
+    fmt = "`{}` is assigned right before throwing, so the value is never used",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentBeforeThrow {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentBeforeThrowHandler {
+      functions: vec![Function::default()],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_before_throw.md")
+  }
+}
+
+/// An assignment to a variable directly followed by a `throw`, e.g.
+/// `x = 1; throw new Error();`.
+struct Candidate {
+  id: Id,
+  /// The function the statements are in.
+  function: usize,
+  assign_stmt: SourceRange,
+  throw_stmt: SourceRange,
+}
+
+#[derive(Default)]
+struct Function {
+  /// Zero is the top level.
+  id: usize,
+  /// How many `try` statements of this function the visitor is in.
+  try_depth: usize,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentBeforeThrowHandler {
+  /// Stack of the functions being visited.
+  functions: Vec<Function>,
+  function_count: usize,
+  /// The function each `let` or `var` binding is declared in.
+  declarations: HashMap<Id, usize>,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentBeforeThrowHandler {
+  fn check_stmts(&mut self, stmts: &[Stmt], ctx: &Context) {
+    let function = self.functions.last().unwrap();
+    // A `catch` or `finally` block could still read the variable
+    if function.try_depth > 0 {
+      return;
+    }
+
+    for pair in stmts.windows(2) {
+      let (Stmt::Expr(expr_stmt), Stmt::Throw(throw_stmt)) =
+        (&pair[0], &pair[1])
+      else {
+        continue;
+      };
+      let Expr::Assign(assign_expr) = expr_stmt.expr else {
+        continue;
+      };
+      let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+        continue;
+      };
+      if assign_expr.op() != AssignOp::Assign
+        || !is_side_effect_free(&assign_expr.right, ctx.scope())
+      {
+        continue;
+      }
+
+      self.candidates.push(Candidate {
+        id: target.id.inner.to_id(),
+        function: function.id,
+        assign_stmt: expr_stmt.range(),
+        throw_stmt: throw_stmt.range(),
+      });
+    }
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      // The binding must be local to the function, so that no closure can
+      // observe the assignment, and the thrown value must not use it
+      let is_local =
+        self.declarations.get(&candidate.id) == Some(&candidate.function);
+      let is_unobserved =
+        self.occurrences[&candidate.id]
+          .iter()
+          .all(|(function, range)| {
+            *function == candidate.function
+              && !candidate.throw_stmt.contains(range)
+          });
+      if !is_local || !is_unobserved {
+        continue;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        candidate.assign_stmt,
+        CODE,
+        NoUselessAssignmentBeforeThrowMessage::Useless(
+          candidate.id.0.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+impl Handler for NoUselessAssignmentBeforeThrowHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(Function {
+        id: self.function_count,
+        try_depth: 0,
+      });
+    } else if let ast_view::Node::TryStmt(_) = node {
+      self.functions.last_mut().unwrap().try_depth += 1;
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    } else if let ast_view::Node::TryStmt(_) = node {
+      self.functions.last_mut().unwrap().try_depth -= 1;
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = self.functions.last().unwrap().id;
+    self
+      .occurrences
+      .entry(ident.inner.to_id())
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let function = self.functions.last().unwrap().id;
+    // Top-level `var`s are properties of the global object
+    let is_local = match declarator.parent() {
+      Node::VarDecl(var_decl) => match var_decl.decl_kind() {
+        VarDeclKind::Let => true,
+        VarDeclKind::Var => function != 0,
+        VarDeclKind::Const => false,
+      },
+      _ => false,
+    };
+    if is_local {
+      self.declarations.insert(binding.id.inner.to_id(), function);
+    }
+  }
+
+  fn block_stmt(
+    &mut self,
+    block_stmt: &ast_view::BlockStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_stmts(&block_stmt.stmts, ctx);
+  }
+
+  fn switch_case(
+    &mut self,
+    switch_case: &ast_view::SwitchCase,
+    ctx: &mut Context,
+  ) {
+    self.check_stmts(&switch_case.cons, ctx);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_before_throw_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentBeforeThrow,
+      "function f() { throw new Error(); }",
+      "function f() { let x; x = 1; foo(); throw new Error(); }",
+      "function f() { let x; x += 1; throw new Error(); }",
+      "function f() { let x; x = 1; throw new Error(x); }",
+      "function f(x) { x = 1; throw new Error(); }",
+
+      // The value has side effects
+      "function f() { let x; x = compute(); throw new Error(); }",
+      "function f() { let x; x = a.b; throw new Error(); }",
+      "function f() { let x; x = -a; throw new Error(); }",
+      "function f() { let x; x = { [key]: 1 }; throw new Error(); }",
+      "function f() { let x; x = [...items]; throw new Error(); }",
+      "function f() { let x; x = `${a}`; throw new Error(); }",
+      "function f() { let x; x = undeclared; throw new Error(); }",
+
+      // The assignment may be observed elsewhere
+      "function f() { let x; const g = () => x; x = 1; throw new Error(); }",
+      "function f() { let x; try { x = 1; throw new Error(); } catch { use(x); } }",
+      "function f() { let x; try { if (a) { x = 1; throw e; } } finally { use(x); } }",
+      "let x; function f() { x = 1; throw new Error(); }",
+      "var x; { x = 1; throw new Error(); }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_before_throw_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentBeforeThrow,
+      "function f() { let x; x = 1; throw new Error(); }": [
+        {
+          col: 22,
+          message: variant!(NoUselessAssignmentBeforeThrowMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f(a) { let x = 0; if (a) { x = [a, '!']; throw new Error(); } return x; }": [
+        {
+          col: 36,
+          message: variant!(NoUselessAssignmentBeforeThrowMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "const f = (a) => { let x; switch (a) { case 1: x = { a, b: [1] }; throw a; } };": [
+        {
+          col: 47,
+          message: variant!(NoUselessAssignmentBeforeThrowMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "{ let x; x = !x; throw new Error(); }": [
+        {
+          col: 9,
+          message: variant!(NoUselessAssignmentBeforeThrowMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      // A nested function's `try` doesn't protect the outer function
+      "function f() { let x; const g = () => { try {} catch {} }; x = 1; throw e; }": [
+        {
+          col: 59,
+          message: variant!(NoUselessAssignmentBeforeThrowMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
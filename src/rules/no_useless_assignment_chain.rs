@@ -0,0 +1,273 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, NodeTrait, Pat, PatOrExpr, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentChain;
+
+const CODE: &str = "no-useless-assignment-chain";
+
+#[derive(Display)]
+enum NoUselessAssignmentChainMessage {
+  #[display(
+    fmt = "`{}` is assigned in an assignment chain but never read",
+    _0
+  )]
+  Unused(String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentChainHint {
+  #[display(fmt = "Remove `{} =` from the chain", _0)]
+  Remove(String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentChainFix {
+  #[display(fmt = "Remove the assignment to `{}`", _0)]
+  Remove(String),
+}
+
+impl LintRule for NoUselessAssignmentChain {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentChainHandler::default();
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_chain.md")
+  }
+}
+
+/// An assignment in the middle of a chain, like `b = c` in `a = b = c`.
+struct Candidate {
+  id: Id,
+  assign_expr: SourceRange,
+  /// The part of the assignment to remove, i.e. `b = `.
+  target_and_operator: SourceRange,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentChainHandler {
+  /// Variables that can't be read outside of the file.
+  local_bindings: HashSet<Id>,
+  /// Ranges of identifiers that only write to their variable, i.e. binding
+  /// names of declarations and targets of plain assignments.
+  writes: HashSet<SourceRange>,
+  /// Ranges of all occurrences of each identifier.
+  occurrences: HashMap<Id, Vec<SourceRange>>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentChainHandler {
+  fn is_read(&self, id: &Id) -> bool {
+    self.occurrences.get(id).map_or(false, |ranges| {
+      ranges.iter().any(|r| !self.writes.contains(r))
+    })
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in &self.candidates {
+      if !self.local_bindings.contains(&candidate.id)
+        || self.is_read(&candidate.id)
+      {
+        continue;
+      }
+
+      let name = candidate.id.0.to_string();
+      let has_comments = ctx.all_comments().any(|comment| {
+        candidate.target_and_operator.contains(&comment.range())
+      });
+      let fixes = if has_comments {
+        vec![]
+      } else {
+        vec![LintFix {
+          description: NoUselessAssignmentChainFix::Remove(name.clone())
+            .to_string()
+            .into(),
+          changes: vec![LintFixChange {
+            new_text: "".into(),
+            range: candidate.target_and_operator,
+          }],
+        }]
+      };
+      ctx.add_diagnostic_with_fixes(
+        candidate.assign_expr,
+        CODE,
+        NoUselessAssignmentChainMessage::Unused(name.clone()),
+        Some(NoUselessAssignmentChainHint::Remove(name).to_string()),
+        fixes,
+      );
+    }
+  }
+}
+
+/// The target of a plain assignment to a variable, like `a` in `a = 1`.
+fn plain_assignment_target<'a>(
+  assign_expr: &ast_view::AssignExpr<'a>,
+) -> Option<&'a ast_view::BindingIdent<'a>> {
+  match assign_expr.left {
+    PatOrExpr::Pat(Pat::Ident(target))
+      if assign_expr.op() == AssignOp::Assign =>
+    {
+      Some(target)
+    }
+    _ => None,
+  }
+}
+
+impl Handler for NoUselessAssignmentChainHandler {
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    self
+      .occurrences
+      .entry(ident.inner.to_id())
+      .or_default()
+      .push(ident.range());
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    self.writes.insert(binding.id.range());
+
+    let Node::VarDecl(var_decl) = declarator.parent() else {
+      return;
+    };
+    if var_decl.decl_kind() == VarDeclKind::Const {
+      return;
+    }
+    // Top-level variables of scripts are shared with other scripts, and
+    // exported ones can be read by other modules
+    let is_local = ctx.enclosing_function(declarator.as_node()).is_some()
+      || (matches!(ctx.program(), Program::Module(_))
+        && !matches!(var_decl.parent(), Node::ExportDecl(_)));
+    if is_local {
+      self.local_bindings.insert(binding.id.inner.to_id());
+    }
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    if let Some(target) = plain_assignment_target(assign_expr) {
+      self.writes.insert(target.id.range());
+    }
+
+    if assign_expr.op() != AssignOp::Assign {
+      return;
+    }
+    let Expr::Assign(inner) = assign_expr.right else {
+      return;
+    };
+    let Some(target) = plain_assignment_target(inner) else {
+      return;
+    };
+    self.candidates.push(Candidate {
+      id: target.id.inner.to_id(),
+      assign_expr: inner.range(),
+      target_and_operator: SourceRange::new(inner.start(), inner.right.start()),
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_chain_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentChain,
+      "function f() { let a, b; a = b = 1; return a + b; }",
+      "function f() { let a, b; a = b = 1; g(() => b); return a; }",
+      "function f() { let a, b; a = b = 1; b += 1; return a; }",
+      "function f() { let a, b; a = b = 1; b++; return a; }",
+      "function f() { let a, b; a = b = 1; return { a, b }; }",
+      "function f(b) { let a; a = b = 1; return a; }",
+      "function f() { let a; a = this.b = 1; return a; }",
+      "function f() { let a, b; a = b += 1; return a; }",
+      "function f() { let a; a = b = 1; return a; }",
+      "let a, b; a = b = 1;",
+      "export let b; let a; a = b = 1;",
+      "let a, b; a = b = 1; export { b };",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_chain_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentChain,
+      "function f() { let a, b; a = b = 1; return a; }": [
+        {
+          col: 29,
+          message: variant!(NoUselessAssignmentChainMessage, Unused, "b"),
+          hint: variant!(NoUselessAssignmentChainHint, Remove, "b"),
+          fix: (
+            "Remove the assignment to `b`",
+            "function f() { let a, b; a = 1; return a; }"
+          ),
+        }
+      ],
+      "function f() { var a, b, c; a = b = c = g(); use(a, c); }": [
+        {
+          col: 32,
+          message: variant!(NoUselessAssignmentChainMessage, Unused, "b"),
+          hint: variant!(NoUselessAssignmentChainHint, Remove, "b"),
+          fix: (
+            "Remove the assignment to `b`",
+            "function f() { var a, b, c; a = c = g(); use(a, c); }"
+          ),
+        }
+      ],
+      "const f = () => { let b; obj.a = b = 1; b = 2; };": [
+        {
+          col: 33,
+          message: variant!(NoUselessAssignmentChainMessage, Unused, "b"),
+          hint: variant!(NoUselessAssignmentChainHint, Remove, "b"),
+          fix: (
+            "Remove the assignment to `b`",
+            "const f = () => { let b; obj.a = 1; b = 2; };"
+          ),
+        }
+      ],
+      "import { x } from './x.ts'; let a, b; a = b = x; export { a };": [
+        {
+          col: 42,
+          message: variant!(NoUselessAssignmentChainMessage, Unused, "b"),
+          hint: variant!(NoUselessAssignmentChainHint, Remove, "b"),
+          fix: (
+            "Remove the assignment to `b`",
+            "import { x } from './x.ts'; let a, b; a = x; export { a };"
+          ),
+        }
+      ],
+    };
+  }
+}
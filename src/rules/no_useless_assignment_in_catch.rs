@@ -0,0 +1,246 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::{is_loop, FunctionLike};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::is_side_effect_free;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, NodeTrait, Pat, PatOrExpr, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use if_chain::if_chain;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentInCatch;
+
+const CODE: &str = "no-useless-assignment-in-catch";
+const HINT: &str = "Remove the assignment, or use the assigned value";
+
+#[derive(Display)]
+enum NoUselessAssignmentInCatchMessage {
+  #[display(
+    fmt = "`{}` is assigned in a catch block but never read afterwards",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentInCatch {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentInCatchHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_in_catch.md")
+  }
+}
+
+struct NoUselessAssignmentInCatchHandler;
+
+/// Where a node is relative to the catch clause being analyzed.
+#[derive(Clone, Copy, Default)]
+struct Position {
+  /// Inside a function nested in the catch clause.
+  in_function: bool,
+  /// Inside a loop nested in the catch clause.
+  in_loop: bool,
+  /// Inside another catch clause nested in the catch clause.
+  in_catch: bool,
+}
+
+/// The variables declared by a catch clause, and how they're used in it.
+#[derive(Default)]
+struct CatchClauseAnalysis {
+  /// The catch parameter and `let` bindings declared in the clause.
+  bindings: HashSet<Id>,
+  /// Each occurrence of an identifier, and whether it's in a nested function.
+  occurrences: Vec<(Id, SourceRange, bool)>,
+  /// Statements assigning a side effect free value to a variable.
+  assignments: Vec<(Id, SourceRange)>,
+}
+
+impl CatchClauseAnalysis {
+  fn visit(&mut self, node: ast_view::Node, position: Position, ctx: &Context) {
+    use deno_ast::view::Node;
+    match node {
+      Node::Ident(ident) => {
+        self.occurrences.push((
+          ident.inner.to_id(),
+          ident.range(),
+          position.in_function,
+        ));
+      }
+      Node::VarDeclarator(declarator) => {
+        let is_let = matches!(
+          declarator.parent(),
+          Node::VarDecl(var_decl) if var_decl.decl_kind() == VarDeclKind::Let
+        );
+        if let Pat::Ident(binding) = declarator.name {
+          if is_let && !position.in_function && !position.in_catch {
+            self.bindings.insert(binding.id.inner.to_id());
+          }
+        }
+      }
+      Node::ExprStmt(expr_stmt) => {
+        if_chain! {
+          if !position.in_function && !position.in_loop;
+          if let Expr::Assign(assign_expr) = expr_stmt.expr;
+          if assign_expr.op() == AssignOp::Assign;
+          if let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left;
+          if is_side_effect_free(&assign_expr.right, ctx.scope());
+          then {
+            self
+              .assignments
+              .push((target.id.inner.to_id(), expr_stmt.range()));
+          }
+        }
+      }
+      _ => {}
+    }
+
+    for child in node.children() {
+      let position = Position {
+        in_function: position.in_function
+          || FunctionLike::from_node(child).is_some(),
+        in_loop: position.in_loop || is_loop(child),
+        in_catch: position.in_catch || matches!(child, Node::CatchClause(_)),
+      };
+      self.visit(child, position, ctx);
+    }
+  }
+
+  /// Assignments to the clause's variables that nothing can read afterwards.
+  fn useless_assignments(&self) -> impl Iterator<Item = &(Id, SourceRange)> {
+    self.assignments.iter().filter(|(id, assign_stmt)| {
+      // A closure could read the variable at any time
+      self.bindings.contains(id)
+        && self.occurrences.iter().all(|(other, range, in_function)| {
+          other != id || (!in_function && range.start < assign_stmt.end)
+        })
+    })
+  }
+}
+
+impl Handler for NoUselessAssignmentInCatchHandler {
+  fn catch_clause(
+    &mut self,
+    catch_clause: &ast_view::CatchClause,
+    ctx: &mut Context,
+  ) {
+    let mut analysis = CatchClauseAnalysis::default();
+    if let Some(Pat::Ident(param)) = catch_clause.param {
+      analysis.bindings.insert(param.id.inner.to_id());
+    }
+    analysis.visit(catch_clause.body.as_node(), Position::default(), ctx);
+
+    for (id, assign_stmt) in analysis.useless_assignments() {
+      ctx.add_diagnostic_with_hint(
+        *assign_stmt,
+        CODE,
+        NoUselessAssignmentInCatchMessage::Useless(id.0.to_string()),
+        HINT,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_in_catch_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentInCatch,
+      "try {} catch (e) { log(e); }",
+      "try {} catch (e) { e = normalize(e); log(e); }",
+      "try {} catch (e) { let msg; msg = 'failed'; log(msg); }",
+      "try {} catch (e) { let msg; msg += '!'; }",
+
+      // The value has side effects
+      "try {} catch (e) { let msg; msg = format(e); }",
+      "try {} catch (e) { e = e.cause; }",
+      "try {} catch (e) { let msg; msg = `${e}`; }",
+      "try {} catch (e) { let msg; msg = { undeclared }; }",
+
+      // The variable isn't local to the catch clause
+      "let msg; try {} catch (e) { msg = 'failed'; } log(msg);",
+      "function f() { let msg; try {} catch (e) { msg = 'failed'; } return msg; }",
+
+      // The variable may be read later
+      "try {} catch (e) { let msg; const log = () => msg; msg = 'failed'; log(); }",
+      "try {} catch (e) { let msg; while (a) { use(msg); msg = 'failed'; } }",
+      "try {} catch (e) { let msg; if (a) { msg = 'a'; } else { msg = 'b'; } log(msg); }",
+      "try {} catch (e) { let msg; try { msg = 'a'; throw e; } finally { log(msg); } }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_in_catch_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentInCatch,
+      "try {} catch (e) { let msg; msg = 'failed'; }": [
+        {
+          col: 28,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "msg"),
+          hint: HINT,
+        }
+      ],
+      "try {} catch (e) { log(e); e = null; }": [
+        {
+          col: 27,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "e"),
+          hint: HINT,
+        }
+      ],
+      "try {} catch (e) { let msg = ''; log(msg); if (a) { msg = { e }; } }": [
+        {
+          col: 52,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "msg"),
+          hint: HINT,
+        }
+      ],
+      r#"
+try {
+  run();
+} catch (err) {
+  let handled = false;
+  report(err, handled);
+  handled = true;
+}
+      "#: [
+        {
+          line: 7,
+          col: 2,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "handled"),
+          hint: HINT,
+        }
+      ],
+      // Variables of nested catch clauses are checked by those clauses
+      "try {} catch (e) { try {} catch (f) { let x; x = 1; } e = 1; }": [
+        {
+          col: 45,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "x"),
+          hint: HINT,
+        },
+        {
+          col: 54,
+          message: variant!(NoUselessAssignmentInCatchMessage, Useless, "e"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
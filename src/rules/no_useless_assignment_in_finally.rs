@@ -0,0 +1,334 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::FunctionLike;
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::is_side_effect_free;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, NodeTrait, Pat, PatOrExpr, Stmt,
+  VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentInFinally;
+
+const CODE: &str = "no-useless-assignment-in-finally";
+const HINT: &str =
+  "Remove the assignment, or move it to where the value is used";
+
+#[derive(Display)]
+enum NoUselessAssignmentInFinallyMessage {
+  #[display(
+    fmt = "`{}` is assigned in a `finally` block, but the value is never read afterwards",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentInFinally {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentInFinallyHandler {
+      functions: vec![0],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_in_finally.md")
+  }
+}
+
+/// An assignment to a variable that's a statement of a `finally` block, e.g.
+/// `x = 1;` in `try {} finally { x = 1; }`.
+struct Candidate {
+  id: Id,
+  /// The function the statements are in.
+  function: usize,
+  assign_stmt: SourceRange,
+  target: SourceRange,
+  /// Code after the assignment that may run after it, up to the end of the
+  /// function if the `try` statement can complete.
+  after: SourceRange,
+  /// The outermost loop around the `try` statement in the same function,
+  /// which could run any of its code again after the assignment.
+  outer_loop: Option<SourceRange>,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentInFinallyHandler {
+  /// Stack of the ids of the functions being visited. Zero is the top level.
+  functions: Vec<usize>,
+  function_count: usize,
+  /// The function each `let` or `var` binding is declared in.
+  declarations: HashMap<Id, usize>,
+  /// Bindings that are exported, and so may be read by other modules.
+  exported: HashSet<Id>,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentInFinallyHandler {
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      let is_local = self.declarations.get(&candidate.id)
+        == Some(&candidate.function)
+        && !self.exported.contains(&candidate.id);
+      // Closures may read the variable at any time
+      let is_unobserved =
+        self.occurrences[&candidate.id]
+          .iter()
+          .all(|(function, range)| {
+            *function == candidate.function
+              && (*range == candidate.target
+                || (!candidate.after.contains(range)
+                  && !candidate
+                    .outer_loop
+                    .map_or(false, |outer_loop| outer_loop.contains(range))))
+          });
+      if !is_local || !is_unobserved {
+        continue;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        candidate.assign_stmt,
+        CODE,
+        NoUselessAssignmentInFinallyMessage::Useless(
+          candidate.id.0.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+/// Information about the statements around a `try` statement, up to the
+/// function it's in.
+struct Surroundings {
+  outer_loop: Option<SourceRange>,
+  /// Whether a `break` or `continue` could leave the `try` statement, or an
+  /// outer `try` statement could run more code before the function returns.
+  has_other_exits: bool,
+  /// The end of the function or program.
+  end: deno_ast::SourcePos,
+}
+
+fn surroundings(try_stmt: &ast_view::TryStmt) -> Surroundings {
+  use deno_ast::view::Node;
+  let mut result = Surroundings {
+    outer_loop: None,
+    has_other_exits: false,
+    end: try_stmt.program().range().end,
+  };
+  for ancestor in try_stmt.ancestors() {
+    if let Some(function) = FunctionLike::from_node(ancestor) {
+      result.end = function.as_node().range().end;
+      break;
+    }
+    match ancestor {
+      Node::ForStmt(_)
+      | Node::ForInStmt(_)
+      | Node::ForOfStmt(_)
+      | Node::WhileStmt(_)
+      | Node::DoWhileStmt(_) => {
+        result.outer_loop = Some(ancestor.range());
+        result.has_other_exits = true;
+      }
+      Node::SwitchStmt(_) | Node::LabeledStmt(_) | Node::TryStmt(_) => {
+        result.has_other_exits = true;
+      }
+      _ => {}
+    }
+  }
+  result
+}
+
+impl Handler for NoUselessAssignmentInFinallyHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(self.function_count);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = *self.functions.last().unwrap();
+    let id = ident.inner.to_id();
+    if matches!(ident.parent(), ast_view::Node::ExportNamedSpecifier(_)) {
+      self.exported.insert(id.clone());
+    }
+    self
+      .occurrences
+      .entry(id)
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let function = *self.functions.last().unwrap();
+    // Top-level `var`s are properties of the global object
+    let is_local = match declarator.parent() {
+      Node::VarDecl(var_decl) => {
+        if matches!(var_decl.parent(), Node::ExportDecl(_)) {
+          self.exported.insert(binding.id.inner.to_id());
+        }
+        match var_decl.decl_kind() {
+          VarDeclKind::Let => true,
+          VarDeclKind::Var => function != 0,
+          VarDeclKind::Const => false,
+        }
+      }
+      _ => false,
+    };
+    if is_local {
+      self.declarations.insert(binding.id.inner.to_id(), function);
+    }
+  }
+
+  fn try_stmt(&mut self, try_stmt: &ast_view::TryStmt, ctx: &mut Context) {
+    let Some(finalizer) = try_stmt.finalizer else {
+      return;
+    };
+    let function = *self.functions.last().unwrap();
+    let surroundings = surroundings(try_stmt);
+    // When the `try` statement always returns or throws, nothing after it
+    // runs
+    let completes = surroundings.has_other_exits
+      || ctx
+        .control_flow()
+        .meta(try_stmt.start())
+        .map_or(true, |meta| meta.continues_execution());
+
+    for stmt in &finalizer.stmts {
+      let Stmt::Expr(expr_stmt) = stmt else {
+        continue;
+      };
+      let Expr::Assign(assign_expr) = expr_stmt.expr else {
+        continue;
+      };
+      let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+        continue;
+      };
+      if assign_expr.op() != AssignOp::Assign
+        || !is_side_effect_free(&assign_expr.right, ctx.scope())
+      {
+        continue;
+      }
+
+      let end = if completes {
+        surroundings.end
+      } else {
+        finalizer.end()
+      };
+      self.candidates.push(Candidate {
+        id: target.id.inner.to_id(),
+        function,
+        assign_stmt: expr_stmt.range(),
+        target: target.id.range(),
+        after: SourceRange::new(expr_stmt.end(), end),
+        outer_loop: surroundings.outer_loop,
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_in_finally_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentInFinally,
+      "function f() { let done = false; try { work(); } finally { done = true; } return done; }",
+      "function f() { let x; try { work(); } finally { x = 1; use(x); } }",
+      "function f() { let x; try { work(); } finally { x = compute(); } }",
+      "function f() { let x; try { work(); } finally { x = `${x}`; } }",
+      "function f() { let x; try { work(); } finally { x = undeclared; } }",
+      "function f() { let x; try { work(); } finally { x += 1; } }",
+      "function f(x) { try { work(); } finally { x = 1; } }",
+      "let x; function f() { try { work(); } finally { x = 1; } }",
+      "var x; try { work(); } finally { x = 1; }",
+      "function f() { let x; const g = () => x; try { work(); } finally { x = 1; } g(); }",
+      "function f() { let x = 0; while (c) { use(x); try { work(); } finally { x = 1; } } }",
+      "function f() { let x; for (;;) { try { break; } finally { x = 1; } } return x; }",
+      "function f() { let x; try { try { return 1; } finally { x = 2; } } finally { use(x); } }",
+      "function f() { let x; try { if (c) return; } finally { x = 1; } return x; }",
+
+      // Importers read exported bindings
+      "export let x = 0; try { a(); } finally { x = 2; }",
+      "let x = 0; export { x }; try { a(); } finally { x = 2; }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_in_finally_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentInFinally,
+      "function f() { let busy = true; try { work(); } finally { busy = false; } }": [
+        {
+          col: 58,
+          message: variant!(NoUselessAssignmentInFinallyMessage, Useless, "busy"),
+          hint: HINT,
+        }
+      ],
+      "let x = 0; try { a(); } finally { x = 2; } export {};": [
+        {
+          col: 34,
+          message: variant!(NoUselessAssignmentInFinallyMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f() { let x = 0; try { return x; } finally { x = 1; } use(x); }": [
+        {
+          col: 54,
+          message: variant!(NoUselessAssignmentInFinallyMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f() { let x = 0; use(x); while (c) { try { work(); } finally { x = 1; } } }": [
+        {
+          col: 72,
+          message: variant!(NoUselessAssignmentInFinallyMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "const f = () => { let state = 'idle'; try { run(); } catch { state = 'failed'; } finally { state = 'done'; } };": [
+        {
+          col: 91,
+          message: variant!(NoUselessAssignmentInFinallyMessage, Useless, "state"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,377 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::FunctionLike;
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::is_side_effect_free;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, Pat, PatOrExpr, Stmt, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentInReturn;
+
+const CODE: &str = "no-useless-assignment-in-return";
+const HINT: &str = "Return the assigned value directly";
+const FIX_DESC: &str = "Return the value directly";
+
+#[derive(Display)]
+enum NoUselessAssignmentInReturnMessage {
+  #[display(fmt = "`{}` is assigned only to be returned right away", _0)]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentInReturn {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentInReturnHandler::default();
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_in_return.md")
+  }
+}
+
+/// An assignment to a variable directly followed by returning that variable,
+/// e.g. `x = foo; return x;`.
+struct Candidate {
+  id: Id,
+  /// The function the statements are in.
+  function: usize,
+  assign_stmt: SourceRange,
+  value: SourceRange,
+  return_stmt: SourceRange,
+}
+
+struct Declaration {
+  /// The function the binding is declared in.
+  function: usize,
+  /// The declaration statement, if it only declares this binding and can be
+  /// removed once the binding is unused.
+  removable_stmt: Option<SourceRange>,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentInReturnHandler {
+  /// Stack of the functions being visited. Zero is the top level.
+  functions: Vec<usize>,
+  function_count: usize,
+  /// Where each `let` or `var` binding is declared.
+  declarations: HashMap<Id, Declaration>,
+  /// How often each identifier occurs, including its declaration.
+  occurrences: HashMap<Id, usize>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentInReturnHandler {
+  fn current_function(&self) -> usize {
+    self.functions.last().copied().unwrap_or(0)
+  }
+
+  fn check_stmts(&mut self, stmts: &[Stmt]) {
+    // Only an assignment directly before the `return` statement
+    for pair in stmts.windows(2) {
+      let (Stmt::Expr(expr_stmt), Stmt::Return(return_stmt)) =
+        (&pair[0], &pair[1])
+      else {
+        continue;
+      };
+      let Expr::Assign(assign_expr) = expr_stmt.expr else {
+        continue;
+      };
+      let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+        continue;
+      };
+      let Some(Expr::Ident(returned)) = return_stmt.arg else {
+        continue;
+      };
+      let id = target.id.inner.to_id();
+      if assign_expr.op() != AssignOp::Assign || returned.inner.to_id() != id {
+        continue;
+      }
+
+      self.candidates.push(Candidate {
+        id,
+        function: self.current_function(),
+        assign_stmt: expr_stmt.range(),
+        value: assign_expr.right.range(),
+        return_stmt: return_stmt.range(),
+      });
+    }
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      // The binding must be local to the function, so that no closure can
+      // observe the assignment, and only be used by the declaration, the
+      // assignment and the return. Occurrences are counted by binding, so
+      // other bindings with the same name don't matter
+      let Some(declaration) = self.declarations.get(&candidate.id) else {
+        continue;
+      };
+      if declaration.function != candidate.function
+        || self.occurrences.get(&candidate.id) != Some(&3)
+      {
+        continue;
+      }
+
+      let fixes = fix(&candidate, declaration, ctx).into_iter().collect();
+
+      ctx.add_diagnostic_with_fixes(
+        candidate.assign_stmt,
+        CODE,
+        NoUselessAssignmentInReturnMessage::Useless(candidate.id.0.to_string()),
+        Some(HINT.to_string()),
+        fixes,
+      );
+    }
+  }
+}
+
+/// Returns the value directly, and removes the declaration, which is unused
+/// afterwards. There's no fix if the declaration can't be removed, or if
+/// comments would be dropped.
+fn fix(
+  candidate: &Candidate,
+  declaration: &Declaration,
+  ctx: &Context,
+) -> Option<LintFix> {
+  let declaration_stmt = declaration.removable_stmt?;
+  let between = SourceRange::new(
+    candidate.assign_stmt.end(),
+    candidate.return_stmt.start(),
+  );
+  if !ctx.text_of(between).trim().is_empty()
+    || ctx
+      .all_comments()
+      .any(|comment| declaration_stmt.contains(&comment.range()))
+  {
+    return None;
+  }
+
+  // Remove the whitespace after the declaration too, so that the next
+  // statement takes its place
+  let rest = ctx.text_of(SourceRange::new(
+    declaration_stmt.end,
+    ctx.text_info().range().end,
+  ));
+  let whitespace = rest.len() - rest.trim_start().len();
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![
+      LintFixChange {
+        new_text: "".into(),
+        range: SourceRange::new(
+          declaration_stmt.start,
+          declaration_stmt.end + whitespace,
+        ),
+      },
+      LintFixChange {
+        new_text: format!("return {};", ctx.text_of(candidate.value)).into(),
+        range: SourceRange::new(
+          candidate.assign_stmt.start(),
+          candidate.return_stmt.end(),
+        ),
+      },
+    ],
+  })
+}
+
+impl Handler for NoUselessAssignmentInReturnHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(self.function_count);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    *self.occurrences.entry(ident.inner.to_id()).or_default() += 1;
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let Node::VarDecl(var_decl) = declarator.parent() else {
+      return;
+    };
+    if !matches!(var_decl.decl_kind(), VarDeclKind::Let | VarDeclKind::Var) {
+      return;
+    }
+    // A statement of its own, e.g. not the head of a `for` loop
+    let is_removable = var_decl.decls.len() == 1
+      && matches!(var_decl.parent(), Node::BlockStmt(_) | Node::SwitchCase(_))
+      && declarator
+        .init
+        .map_or(true, |init| is_side_effect_free(&init, ctx.scope()));
+    self.declarations.insert(
+      binding.id.inner.to_id(),
+      Declaration {
+        function: self.current_function(),
+        removable_stmt: is_removable.then(|| var_decl.range()),
+      },
+    );
+  }
+
+  fn block_stmt(
+    &mut self,
+    block_stmt: &ast_view::BlockStmt,
+    _ctx: &mut Context,
+  ) {
+    self.check_stmts(&block_stmt.stmts);
+  }
+
+  fn switch_case(
+    &mut self,
+    switch_case: &ast_view::SwitchCase,
+    _ctx: &mut Context,
+  ) {
+    self.check_stmts(&switch_case.cons);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_in_return_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentInReturn,
+      "function f() { return foo(); }",
+      "function f() { let x; x = foo(); bar(x); return x; }",
+      "function f() { let x; x = foo(); bar(); return y; }",
+      "function f() { let x; x = foo(); bar(); return x; }",
+      "function f() { let x; x += foo(); return x; }",
+      "function f() { let x = 1; if (a) { x = 2; } return x; }",
+      "function f() { let x; x = foo(); return x + 1; }",
+      "function f(x) { x = foo(); return x; }",
+
+      // The assignment isn't directly before the `return`
+      "function f() { let x; x = foo(); ; return x; }",
+      "function f() { let x; x = foo(); { return x; } }",
+
+      // The binding is also read elsewhere
+      "function f() { let x; if (a) { x = foo(); return x; } return x; }",
+      "function f() { let x; const g = () => x; x = foo(); return x; }",
+
+      // The binding isn't local to the function
+      "let x; function f() { x = foo(); return x; }",
+      "function f() { let x; return () => { x = foo(); return x; }; }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_in_return_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentInReturn,
+      "function f() { let x; x = foo(); return x; }": [
+        {
+          col: 22,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "function f() { return foo(); }"),
+        }
+      ],
+      "function f() { var x = 1; x = a + b; return x; }": [
+        {
+          col: 26,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "function f() { return a + b; }"),
+        }
+      ],
+      "const f = () => { let x; if (a) { x = 1; return x; } };": [
+        {
+          col: 34,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "const f = () => { if (a) { return 1; } };"),
+        }
+      ],
+      "function f(a) { let x; switch (a) { case 1: x = foo(); return x; } }": [
+        {
+          col: 44,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "function f(a) { switch (a) { case 1: return foo(); } }"),
+        }
+      ],
+      // Other bindings named `x` don't count as occurrences
+      "function f() { let x; { let x = 1; use(x); } x = foo(); return x; }": [
+        {
+          col: 45,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "function f() { { let x = 1; use(x); } return foo(); }"),
+        }
+      ],
+      r#"
+class A {
+  get value() {
+    let result;
+    result = this.compute();
+    return result;
+  }
+}
+      "#: [
+        {
+          line: 5,
+          col: 4,
+          message: variant!(NoUselessAssignmentInReturnMessage, Useless, "result"),
+          hint: HINT,
+          fix: (FIX_DESC, r#"
+class A {
+  get value() {
+    return this.compute();
+  }
+}
+      "#),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_in_return_invalid_without_fix() {
+    for src in [
+      "function f() { let x; x = foo(); /* keep */ return x; }",
+      "function f() { let x /* keep */; x = foo(); return x; }",
+      // The declaration can't be removed
+      "function f() { let x = init(); x = foo(); return x; }",
+      "function f() { let x, y; x = foo(); return x; }",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&NoUselessAssignmentInReturn, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
@@ -0,0 +1,298 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, Lit, Pat, PatOrExpr, TsKeywordTypeKind,
+  TsType, UnaryOp,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentOperator;
+
+const CODE: &str = "no-useless-assignment-operator";
+const HINT: &str = "Remove the assignment, or use the intended operand";
+
+#[derive(Display)]
+enum NoUselessAssignmentOperatorMessage {
+  #[display(fmt = "`{} {}` doesn't change the value", _0, _1)]
+  NoOp(String, String),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NoUselessAssignmentOperatorOptions {
+  /// Report `+= 0` for any target. By default it's only reported for
+  /// variables known to be numbers, since `+= 0` appends "0" to strings.
+  assume_numeric: bool,
+}
+
+impl LintRule for NoUselessAssignmentOperator {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let options: NoUselessAssignmentOperatorOptions =
+      context.rule_options(CODE);
+    let mut handler = NoUselessAssignmentOperatorHandler {
+      options,
+      numeric_bindings: HashSet::new(),
+      candidates: Vec::new(),
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoUselessAssignmentOperatorOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_operator.md")
+  }
+}
+
+struct Candidate {
+  range: SourceRange,
+  op: AssignOp,
+  operand: &'static str,
+  /// Variable that must be known to be a number for the assignment to be
+  /// reported.
+  numeric_binding: Option<Id>,
+}
+
+struct NoUselessAssignmentOperatorHandler {
+  options: NoUselessAssignmentOperatorOptions,
+  /// Variables declared with a `number` type or initialized with a number.
+  numeric_bindings: HashSet<Id>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentOperatorHandler {
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      if let Some(id) = &candidate.numeric_binding {
+        if !self.numeric_bindings.contains(id) {
+          continue;
+        }
+      }
+      ctx.add_diagnostic_with_hint(
+        candidate.range,
+        CODE,
+        NoUselessAssignmentOperatorMessage::NoOp(
+          candidate.op.to_string(),
+          candidate.operand.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+/// The operand that leaves the value unchanged for the operator, if any.
+fn identity_operand(op: AssignOp) -> Option<(f64, &'static str)> {
+  use AssignOp::*;
+  match op {
+    AddAssign | SubAssign | BitOrAssign => Some((0.0, "0")),
+    MulAssign | DivAssign => Some((1.0, "1")),
+    BitAndAssign => Some((-1.0, "-1")),
+    _ => None,
+  }
+}
+
+/// The value of a number literal, or a negated one.
+fn number_value(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Lit(Lit::Num(num)) => Some(num.value()),
+    Expr::Unary(unary) if unary.op() == UnaryOp::Minus => {
+      number_value(&unary.arg).map(|value| -value)
+    }
+    Expr::Paren(paren) => number_value(&paren.expr),
+    _ => None,
+  }
+}
+
+fn is_number_type(ts_type: &TsType) -> bool {
+  matches!(
+    ts_type,
+    TsType::TsKeywordType(keyword)
+      if keyword.keyword_kind() == TsKeywordTypeKind::TsNumberKeyword
+  )
+}
+
+impl Handler for NoUselessAssignmentOperatorHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    let op = assign_expr.op();
+    let Some((identity, operand)) = identity_operand(op) else {
+      return;
+    };
+    if number_value(&assign_expr.right) != Some(identity) {
+      return;
+    }
+
+    // `+= 0` on a string appends "0", so the target must be a number
+    let numeric_binding =
+      if op == AssignOp::AddAssign && !self.options.assume_numeric {
+        match assign_expr.left {
+          PatOrExpr::Pat(Pat::Ident(binding)) => Some(binding.id.inner.to_id()),
+          PatOrExpr::Expr(Expr::Ident(ident)) => Some(ident.inner.to_id()),
+          _ => return,
+        }
+      } else {
+        None
+      };
+
+    self.candidates.push(Candidate {
+      range: assign_expr.range(),
+      op,
+      operand,
+      numeric_binding,
+    });
+  }
+
+  fn binding_ident(
+    &mut self,
+    binding: &ast_view::BindingIdent,
+    _ctx: &mut Context,
+  ) {
+    if binding
+      .type_ann
+      .map_or(false, |ann| is_number_type(&ann.type_ann))
+    {
+      self.numeric_bindings.insert(binding.id.inner.to_id());
+    }
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    if let (Pat::Ident(binding), Some(init)) =
+      (declarator.name, declarator.init)
+    {
+      if number_value(&init).is_some() {
+        self.numeric_bindings.insert(binding.id.inner.to_id());
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_operator_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentOperator,
+      "x += 1;",
+      "x -= 1;",
+      "x *= 2;",
+      "x /= 0;",
+      "x %= 1;",
+      "x &= 0;",
+      "x |= 1;",
+      "x = 0;",
+      "x ||= 0;",
+      "x -= y;",
+
+      // Shifts and `^=` with 0 convert values to integers, e.g. `x >>>= 0`
+      // turns -1 into 4294967295
+      "x >>>= 0;",
+      "x >>= 0;",
+      "x <<= 0;",
+      "x ^= 0;",
+
+      // `+= 0` appends "0" to strings
+      "x += 0;",
+      "obj.x += 0;",
+      "let x = ''; x += 0;",
+      "let x: string = ''; x += 0;",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_operator_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentOperator,
+      "x -= 0;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "-=", "0"),
+          hint: HINT,
+        }
+      ],
+      "obj.x *= 1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "*=", "1"),
+          hint: HINT,
+        }
+      ],
+      "x /= 1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "/=", "1"),
+          hint: HINT,
+        }
+      ],
+      "x |= 0;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "|=", "0"),
+          hint: HINT,
+        }
+      ],
+      "x &= -1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "&=", "-1"),
+          hint: HINT,
+        }
+      ],
+      "let x = 5; x += 0;": [
+        {
+          col: 11,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "+=", "0"),
+          hint: HINT,
+        }
+      ],
+      "function f(n: number) { n += 0; }": [
+        {
+          col: 24,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "+=", "0"),
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-useless-assignment-operator": { "assumeNumeric": true } } }
+obj.x += 0;
+      "#: [
+        {
+          line: 3,
+          col: 0,
+          message: variant!(NoUselessAssignmentOperatorMessage, NoOp, "+=", "0"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
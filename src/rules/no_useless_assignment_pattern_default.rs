@@ -0,0 +1,308 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, Expr, ObjectPatProp, Pat, Prop, PropName, PropOrSpread,
+  UnaryOp,
+};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentPatternDefault;
+
+const CODE: &str = "no-useless-assignment-pattern-default";
+const HINT: &str = "Remove the default value";
+
+#[derive(Display)]
+enum NoUselessAssignmentPatternDefaultMessage {
+  #[display(
+    fmt = "The default value of `{}` is never used because the initializer always provides it",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentPatternDefault {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentPatternDefaultHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_pattern_default.md")
+  }
+}
+
+struct NoUselessAssignmentPatternDefaultHandler;
+
+/// Checks if the expression always evaluates to something other than
+/// `undefined`, which is when a default value would apply.
+fn is_defined(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(_)
+    | Expr::Tpl(_)
+    | Expr::Object(_)
+    | Expr::Array(_)
+    | Expr::Fn(_)
+    | Expr::Arrow(_)
+    | Expr::Class(_) => true,
+    Expr::Unary(unary) => unary.op() != UnaryOp::Void,
+    Expr::Paren(paren) => is_defined(&paren.expr),
+    _ => false,
+  }
+}
+
+fn static_prop_name(prop_name: &PropName) -> Option<String> {
+  match prop_name {
+    PropName::Ident(ident) => Some(ident.sym().to_string()),
+    PropName::Str(str) => Some(str.value().to_string()),
+    PropName::Num(num) => Some(num.value().to_string()),
+    PropName::Computed(_) | PropName::BigInt(_) => None,
+  }
+}
+
+/// The value an object literal has for a key, if it's known statically.
+fn object_value<'a>(
+  object: &'a ast_view::ObjectLit<'a>,
+  key: &str,
+) -> Option<PropValue<'a>> {
+  // The last property with the key wins, unless a spread comes after it
+  for prop in object.props.iter().rev() {
+    let PropOrSpread::Prop(prop) = prop else {
+      return None;
+    };
+    let (name, value) = match prop {
+      Prop::KeyValue(key_value) => {
+        (&key_value.key, PropValue::Expr(key_value.value))
+      }
+      Prop::Method(method) => (&method.key, PropValue::Defined),
+      Prop::Getter(getter) => (&getter.key, PropValue::Unknown),
+      Prop::Setter(setter) => (&setter.key, PropValue::Unknown),
+      Prop::Shorthand(ident) => {
+        if ident.sym().as_ref() == key {
+          return Some(PropValue::Unknown);
+        }
+        continue;
+      }
+      Prop::Assign(_) => return None,
+    };
+    match static_prop_name(name) {
+      Some(name) if name == key => return Some(value),
+      Some(_) => {}
+      None => return None,
+    }
+  }
+  None
+}
+
+/// What's known about the value a pattern is matched against.
+#[derive(Clone, Copy)]
+enum PropValue<'a> {
+  Expr(Expr<'a>),
+  /// A value that's never `undefined`, like a method.
+  Defined,
+  Unknown,
+}
+
+impl<'a> PropValue<'a> {
+  fn is_defined(&self) -> bool {
+    match self {
+      PropValue::Expr(expr) => is_defined(expr),
+      PropValue::Defined => true,
+      PropValue::Unknown => false,
+    }
+  }
+}
+
+fn check_pattern(pat: &Pat, value: PropValue, ctx: &mut Context) {
+  match pat {
+    Pat::Assign(assign_pat) => {
+      if value.is_defined() {
+        report(&assign_pat.left, &assign_pat.right, ctx);
+      }
+      check_pattern(&assign_pat.left, value, ctx);
+    }
+    Pat::Object(object_pat) => {
+      let PropValue::Expr(Expr::Object(object)) = value else {
+        return;
+      };
+      for prop in &object_pat.props {
+        match prop {
+          ObjectPatProp::Assign(assign_prop) => {
+            let Some(default) = &assign_prop.value else {
+              continue;
+            };
+            let key = assign_prop.key.sym();
+            if matches!(object_value(object, key), Some(value) if value.is_defined())
+            {
+              report_default(key, default, ctx);
+            }
+          }
+          ObjectPatProp::KeyValue(key_value) => {
+            let Some(key) = static_prop_name(&key_value.key) else {
+              continue;
+            };
+            if let Some(value) = object_value(object, &key) {
+              check_pattern(&key_value.value, value, ctx);
+            }
+          }
+          ObjectPatProp::Rest(_) => {}
+        }
+      }
+    }
+    Pat::Array(array_pat) => {
+      let PropValue::Expr(Expr::Array(array)) = value else {
+        return;
+      };
+      for (elem_pat, elem) in array_pat.elems.iter().zip(&array.elems) {
+        let Some(elem) = elem else {
+          continue;
+        };
+        // A spread shifts the elements after it
+        if elem.spread().is_some() {
+          return;
+        }
+        if let Some(elem_pat) = elem_pat {
+          check_pattern(elem_pat, PropValue::Expr(elem.expr), ctx);
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+fn report(left: &Pat, default: &Expr, ctx: &mut Context) {
+  let name = match left {
+    Pat::Ident(binding) => binding.id.sym().to_string(),
+    _ => ctx.text_of(left.range()).to_string(),
+  };
+  report_default(&name, default, ctx);
+}
+
+fn report_default(name: &str, default: &Expr, ctx: &mut Context) {
+  ctx.add_diagnostic_with_hint(
+    default.range(),
+    CODE,
+    NoUselessAssignmentPatternDefaultMessage::Useless(name.to_string()),
+    HINT,
+  );
+}
+
+impl Handler for NoUselessAssignmentPatternDefaultHandler {
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    let Some(init) = declarator.init else {
+      return;
+    };
+    if matches!(declarator.name, Pat::Object(_) | Pat::Array(_)) {
+      check_pattern(&declarator.name, PropValue::Expr(init), ctx);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_pattern_default_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentPatternDefault,
+      "const { a = 1 } = obj;",
+      "const { a = 1 } = {};",
+      "const { a = 1 } = { b: 2 };",
+      "const { a } = { a: 2 };",
+      "const [a = 1] = arr;",
+      "const [a = 1] = [];",
+      "const [a, b = 2] = [3];",
+      "const [a = 1] = [, 2];",
+      "const [, a = 1] = [...arr, 2];",
+
+      // The value may be `undefined`
+      "const { a = 1 } = { a: undefined };",
+      "const { a = 1 } = { a: b };",
+      "const { a = 1 } = { a: foo() };",
+      "const { a = 1 } = { a: void 0 };",
+      "const { a = 1 } = { get a() { return b; } };",
+      "const { a = 1 } = { set a(v) {} };",
+      "const { a = 1 } = { a };",
+      "const [a = 1] = [b];",
+
+      // The property may be overridden
+      "const { a = 1 } = { a: 2, ...obj };",
+      "const { a = 1 } = { a: 2, [key]: b };",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_pattern_default_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentPatternDefault,
+      "const { a = 1 } = { a: 2 };": [
+        {
+          col: 12,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "a"),
+          hint: HINT,
+        }
+      ],
+      "const { a: b = 1 } = { a: 'x' };": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "b"),
+          hint: HINT,
+        }
+      ],
+      "const { 'a': b = 1, c = 2 } = { ...obj, a: null, c() {} };": [
+        {
+          col: 17,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "b"),
+          hint: HINT,
+        },
+        {
+          col: 24,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "c"),
+          hint: HINT,
+        }
+      ],
+      "let [a = 1, b = 2] = [0, , 3];": [
+        {
+          col: 9,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "a"),
+          hint: HINT,
+        }
+      ],
+      "const { a: { b = 1 } = {} } = { a: { b: 2 } };": [
+        {
+          col: 17,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "b"),
+          hint: HINT,
+        },
+        {
+          col: 23,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "{ b = 1 }"),
+          hint: HINT,
+        }
+      ],
+      "var { a = 1 } = { a: 1, a: `${b}` };": [
+        {
+          col: 10,
+          message: variant!(NoUselessAssignmentPatternDefaultMessage, Useless, "a"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,236 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::StringRepr;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, BlockStmtOrExpr, Callee, Expr, MemberProp, NodeTrait, Pat,
+  PatOrExpr,
+};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToAccumulator;
+
+const CODE: &str = "no-useless-assignment-to-accumulator";
+
+#[derive(Display)]
+enum NoUselessAssignmentToAccumulatorMessage {
+  #[display(
+    fmt = "The `{}` callback assigns to the accumulator `{}` but doesn't always return it",
+    _0,
+    _1
+  )]
+  MissingReturn(String, String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentToAccumulatorHint {
+  #[display(
+    fmt = "Return the accumulator at the end of the callback, e.g. `return {};`",
+    _0
+  )]
+  Return(String),
+}
+
+impl LintRule for NoUselessAssignmentToAccumulator {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentToAccumulatorHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_accumulator.md")
+  }
+}
+
+/// What a callback does with its accumulator, not counting nested functions.
+#[derive(Default)]
+struct AccumulatorUsage {
+  assigned: bool,
+  /// Whether the callback has a `return;` without a value.
+  has_empty_return: bool,
+}
+
+fn collect_usage(node: ast_view::Node, id: &Id, usage: &mut AccumulatorUsage) {
+  use deno_ast::view::Node;
+  match node {
+    Node::Function(_) | Node::ArrowExpr(_) | Node::Class(_) => return,
+    Node::AssignExpr(assign_expr) => {
+      // Compound assignments have their target as an expression
+      let target = match assign_expr.left {
+        PatOrExpr::Pat(Pat::Ident(target)) => Some(target.id),
+        PatOrExpr::Pat(Pat::Expr(Expr::Ident(target)))
+        | PatOrExpr::Expr(Expr::Ident(target)) => Some(target),
+        _ => None,
+      };
+      if let Some(target) = target {
+        usage.assigned |= target.inner.to_id() == *id;
+      }
+    }
+    Node::UpdateExpr(update_expr) => {
+      if let Expr::Ident(target) = update_expr.arg {
+        usage.assigned |= target.inner.to_id() == *id;
+      }
+    }
+    Node::ReturnStmt(return_stmt) => {
+      usage.has_empty_return |= return_stmt.arg.is_none();
+    }
+    _ => {}
+  }
+  for child in node.children() {
+    collect_usage(child, id, usage);
+  }
+}
+
+struct NoUselessAssignmentToAccumulatorHandler;
+
+impl Handler for NoUselessAssignmentToAccumulatorHandler {
+  fn call_expr(&mut self, call_expr: &ast_view::CallExpr, ctx: &mut Context) {
+    let Callee::Expr(Expr::Member(callee)) = &call_expr.callee else {
+      return;
+    };
+    let method = match &callee.prop {
+      MemberProp::Ident(ident) => ident.sym().to_string(),
+      MemberProp::Computed(computed) => match computed.expr.string_repr() {
+        Some(method) => method,
+        None => return,
+      },
+      MemberProp::PrivateName(_) => return,
+    };
+    if method != "reduce" && method != "reduceRight" {
+      return;
+    }
+    let Some(callback) = call_expr.args.first() else {
+      return;
+    };
+    if callback.spread().is_some() {
+      return;
+    }
+
+    // Arrow functions with an expression body always return it
+    let (accumulator, body, callback_range) = match &callback.expr {
+      Expr::Arrow(arrow_expr) => match arrow_expr.body {
+        BlockStmtOrExpr::BlockStmt(body) => {
+          (arrow_expr.params.first(), body, arrow_expr.range())
+        }
+        BlockStmtOrExpr::Expr(_) => return,
+      },
+      Expr::Fn(fn_expr) => match fn_expr.function.body {
+        Some(body) => (
+          fn_expr.function.params.first().map(|param| &param.pat),
+          body,
+          fn_expr.range(),
+        ),
+        None => return,
+      },
+      _ => return,
+    };
+    let Some(Pat::Ident(accumulator)) = accumulator else {
+      return;
+    };
+
+    let id = accumulator.id.inner.to_id();
+    let mut usage = AccumulatorUsage::default();
+    for stmt in &body.stmts {
+      collect_usage(stmt.as_node(), &id, &mut usage);
+    }
+    if !usage.assigned {
+      return;
+    }
+
+    let falls_through = ctx
+      .control_flow()
+      .meta(body.range().start)
+      .map_or(false, |meta| meta.continues_execution());
+    if falls_through || usage.has_empty_return {
+      let name = accumulator.id.sym().to_string();
+      ctx.add_diagnostic_with_hint(
+        callback_range,
+        CODE,
+        NoUselessAssignmentToAccumulatorMessage::MissingReturn(
+          method,
+          name.clone(),
+        ),
+        NoUselessAssignmentToAccumulatorHint::Return(name),
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_accumulator_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToAccumulator,
+      "arr.reduce((acc, x) => { acc = acc + x; return acc; }, 0);",
+      "arr.reduce((acc, x) => acc = acc + x, 0);",
+      "arr.reduce((acc, x) => acc + x, 0);",
+      "arr.reduce(function (acc, x) { acc += x; return acc; }, 0);",
+      "arr.reduce((acc, x) => { if (x) { acc = x; return acc; } return acc; });",
+      "arr.reduce((acc, x) => { if (x) { acc = x; } return acc; });",
+      "arr.reduce((acc, x) => { acc = f(acc, x); return g(acc); });",
+      "arr.reduce((acc, x) => { acc.push(x); }, []);",
+      "arr.reduce((acc, x) => { const f = () => { acc = x; }; f(); return acc; });",
+      "arr.reduce((acc, x) => { if (!x) throw new Error(); acc = x; return acc; });",
+      "arr.map((acc, x) => { acc = x; });",
+      "arr.reduce(callback, 0);",
+      "arr.reduce(({ total }, x) => { total = x; }, {});",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_accumulator_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToAccumulator,
+      "arr.reduce((acc, x) => { acc = acc + x; }, 0);": [
+        {
+          col: 11,
+          message: variant!(NoUselessAssignmentToAccumulatorMessage, MissingReturn, "reduce", "acc"),
+          hint: variant!(NoUselessAssignmentToAccumulatorHint, Return, "acc"),
+        }
+      ],
+      "arr.reduceRight(function (sum, x) { sum += x; }, 0);": [
+        {
+          col: 16,
+          message: variant!(NoUselessAssignmentToAccumulatorMessage, MissingReturn, "reduceRight", "sum"),
+          hint: variant!(NoUselessAssignmentToAccumulatorHint, Return, "sum"),
+        }
+      ],
+      "arr.reduce((acc, x) => { if (x) { acc = x; return acc; } });": [
+        {
+          col: 11,
+          message: variant!(NoUselessAssignmentToAccumulatorMessage, MissingReturn, "reduce", "acc"),
+          hint: variant!(NoUselessAssignmentToAccumulatorHint, Return, "acc"),
+        }
+      ],
+      "arr.reduce((acc, x) => { acc++; if (x) return; return acc; }, 0);": [
+        {
+          col: 11,
+          message: variant!(NoUselessAssignmentToAccumulatorMessage, MissingReturn, "reduce", "acc"),
+          hint: variant!(NoUselessAssignmentToAccumulatorHint, Return, "acc"),
+        }
+      ],
+      r#"arr["reduce"]((acc, x) => { for (const y of x) { acc = acc + y; } }, 0);"#: [
+        {
+          col: 14,
+          message: variant!(NoUselessAssignmentToAccumulatorMessage, MissingReturn, "reduce", "acc"),
+          hint: variant!(NoUselessAssignmentToAccumulatorHint, Return, "acc"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,364 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::FunctionLike;
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::is_side_effect_free;
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Expr, Node, NodeTrait, Pat, PatOrExpr, Stmt,
+  VarDeclKind,
+};
+use deno_ast::{SourcePos, SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToConditionallyUnused;
+
+const CODE: &str = "no-useless-assignment-to-conditionally-unused";
+const HINT: &str = "Remove the assignment, or use the value";
+
+#[derive(Display)]
+enum NoUselessAssignmentToConditionallyUnusedMessage {
+  #[display(
+    fmt = "`{}` is only assigned in this branch, but the value is never read",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentToConditionallyUnused {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToConditionallyUnusedHandler {
+      functions: vec![0],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_to_conditionally_unused.md"
+    )
+  }
+}
+
+/// An assignment statement directly in one branch of an `if` statement, e.g.
+/// `if (a) { x = 1; }`.
+struct Candidate {
+  id: Id,
+  /// The function the `if` statement is in.
+  function: usize,
+  assign_stmt: SourceRange,
+  /// The start of the first branch, after the condition.
+  branches_start: SourcePos,
+  /// The branch that the assignment isn't in, if any.
+  other_branch: Option<SourceRange>,
+  /// The body of the nearest loop around the `if` statement, which may run
+  /// the statements before it again.
+  loop_body: Option<SourceRange>,
+}
+
+struct Declaration {
+  function: usize,
+  range: SourceRange,
+  kind: VarDeclKind,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToConditionallyUnusedHandler {
+  /// Stack of the ids of the functions being visited. Zero is the top level.
+  functions: Vec<usize>,
+  function_count: usize,
+  /// Where each `let` or `var` binding is declared.
+  declarations: HashMap<Id, Declaration>,
+  /// Bindings that are exported, and so may be read by other modules.
+  exported: HashSet<Id>,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+  /// Ranges of identifiers that are only written to, by `x = ...` or a
+  /// declaration.
+  writes: HashSet<SourceRange>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentToConditionallyUnusedHandler {
+  fn check_branch(
+    &mut self,
+    branch: &Stmt,
+    other_branch: Option<&Stmt>,
+    if_stmt: &ast_view::IfStmt,
+    ctx: &Context,
+  ) {
+    let stmts = match branch {
+      Stmt::Block(block_stmt) => &block_stmt.stmts[..],
+      _ => std::slice::from_ref(branch),
+    };
+    let loop_body = ctx.enclosing_loop(if_stmt.as_node()).and_then(loop_body);
+    for stmt in stmts {
+      let Stmt::Expr(expr_stmt) = stmt else {
+        continue;
+      };
+      let Expr::Assign(assign_expr) = expr_stmt.expr else {
+        continue;
+      };
+      let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+        continue;
+      };
+      if assign_expr.op() != AssignOp::Assign
+        || !is_side_effect_free(&assign_expr.right, ctx.scope())
+      {
+        continue;
+      }
+
+      self.candidates.push(Candidate {
+        id: target.id.inner.to_id(),
+        function: *self.functions.last().unwrap(),
+        assign_stmt: expr_stmt.range(),
+        branches_start: if_stmt.cons.start(),
+        other_branch: other_branch.map(|other_branch| other_branch.range()),
+        loop_body,
+      });
+    }
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      let Some(declaration) = self.declarations.get(&candidate.id) else {
+        continue;
+      };
+      // The binding must be local to the function, so that no closure or
+      // other module can observe the assignment
+      if declaration.function != candidate.function
+        || self.exported.contains(&candidate.id)
+      {
+        continue;
+      }
+      // In a loop, reads before the `if` statement may happen after the
+      // assignment, unless the binding is fresh in each iteration
+      if let Some(loop_body) = candidate.loop_body {
+        if declaration.kind == VarDeclKind::Var
+          || !loop_body.contains(&declaration.range)
+        {
+          continue;
+        }
+      }
+
+      // Reads in the condition or before the `if` statement get an older
+      // value. Any later read counts, whether or not the assigned value can
+      // reach it, since control flow isn't followed
+      let occurrences = &self.occurrences[&candidate.id];
+      let is_unread = occurrences.iter().all(|(function, range)| {
+        *function == candidate.function
+          && (self.writes.contains(range)
+            || range.end <= candidate.branches_start)
+      });
+      let is_assigned_in_other_branch =
+        candidate.other_branch.map_or(false, |other_branch| {
+          occurrences
+            .iter()
+            .any(|(_, range)| other_branch.contains(range))
+        });
+      if !is_unread || is_assigned_in_other_branch {
+        continue;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        candidate.assign_stmt,
+        CODE,
+        NoUselessAssignmentToConditionallyUnusedMessage::Useless(
+          candidate.id.0.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+fn loop_body(node: Node) -> Option<SourceRange> {
+  let body = match node {
+    Node::ForStmt(for_stmt) => for_stmt.body,
+    Node::ForInStmt(for_in) => for_in.body,
+    Node::ForOfStmt(for_of) => for_of.body,
+    Node::WhileStmt(while_stmt) => while_stmt.body,
+    Node::DoWhileStmt(do_while) => do_while.body,
+    _ => return None,
+  };
+  Some(body.range())
+}
+
+impl Handler for NoUselessAssignmentToConditionallyUnusedHandler {
+  fn on_enter_node(&mut self, node: Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(self.function_count);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = *self.functions.last().unwrap();
+    let id = ident.inner.to_id();
+    if matches!(ident.parent(), Node::ExportNamedSpecifier(_)) {
+      self.exported.insert(id.clone());
+    }
+    self
+      .occurrences
+      .entry(id)
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    if let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left {
+      if assign_expr.op() == AssignOp::Assign {
+        self.writes.insert(target.id.range());
+      }
+    }
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let Node::VarDecl(var_decl) = declarator.parent() else {
+      return;
+    };
+    let function = *self.functions.last().unwrap();
+    let id = binding.id.inner.to_id();
+    self.writes.insert(binding.id.range());
+    if matches!(var_decl.parent(), Node::ExportDecl(_)) {
+      self.exported.insert(id.clone());
+    }
+    // Top-level `var`s are properties of the global object
+    let kind = var_decl.decl_kind();
+    if kind == VarDeclKind::Let || kind == VarDeclKind::Var && function != 0 {
+      self.declarations.insert(
+        id,
+        Declaration {
+          function,
+          range: var_decl.range(),
+          kind,
+        },
+      );
+    }
+  }
+
+  fn if_stmt(&mut self, if_stmt: &ast_view::IfStmt, ctx: &mut Context) {
+    self.check_branch(&if_stmt.cons, if_stmt.alt.as_ref(), if_stmt, ctx);
+    if let Some(alt) = &if_stmt.alt {
+      self.check_branch(alt, Some(&if_stmt.cons), if_stmt, ctx);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_conditionally_unused_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToConditionallyUnused,
+      "function f(a) { let x = 0; if (a) { x = 1; } return x; }",
+      "function f(a) { let x = 0; if (a) { x = 1; use(x); } }",
+      "function f(a) { let x; if (a) { x = 1; } else { use(x); } }",
+      "function f(a) { let x; if (a) { x += 1; } }",
+      "function f(a) { let x; if (a) x = 1; else x = 2; }",
+      "function f(a) { let x; if (a) { x = 1; } else { x = 2; } }",
+      "function f(a) { let x; if (a) { x = { b: 1 }; } return `${x}`; }",
+
+      // The value has side effects
+      "function f(a) { let x; if (a) { x = compute(); } }",
+      "function f(a) { let x; if (a) { x = a.b; } }",
+      "function f(a) { let x; if (a) { x = `${a}!`; } }",
+      "function f(a) { let x; if (a) { x = undeclared; } }",
+
+      // The assignment may be observed elsewhere
+      "function f(a) { let x; const g = () => x; if (a) { x = 1; } return g; }",
+      "let x; function f(a) { if (a) { x = 1; } }",
+      "var x; if (a) { x = 1; }",
+      "export let x; if (a) { x = 1; }",
+      "let x; export { x }; if (a) { x = 1; }",
+      "function f(x) { if (x) { x = 1; } }",
+
+      // A later iteration may read the value
+      "function f(a) { let x; while (a) { use(x); if (a) { x = 1; } } }",
+      "function f(a) { for (const b of a) { var x; use(x); if (b) { x = 1; } } }",
+      "function f(a) { for (let x = 0; x < a; x++) { if (a) { x = 1; } } }",
+      "function f(a) { for (;;) { let x; while (a) { use(x); if (a) { x = 1; } } } }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_conditionally_unused_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToConditionallyUnused,
+      "function f(a) { let x = 0; if (a) { x = 1; } return a; }": [
+        {
+          col: 36,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f(a) { let x = 0; use(x); if (x > a) x = a; }": [
+        {
+          col: 46,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f(a) { let x; if (a) { use(a); } else { x = [a]; x = null; } x = 2; }": [
+        {
+          col: 49,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        },
+        {
+          col: 58,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "let x; if (a) { x = [1, 2]; }": [
+        {
+          col: 16,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      // The binding is fresh in each iteration
+      "for (const b of c) { let x; use(x); if (b) { x = b; } }": [
+        {
+          col: 45,
+          message: variant!(NoUselessAssignmentToConditionallyUnusedMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,298 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::FunctionLike;
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Decl, Expr, ObjectPatProp, Pat, PatOrExpr, Stmt,
+  VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToDestructured;
+
+const CODE: &str = "no-useless-assignment-to-destructured";
+
+#[derive(Display)]
+enum NoUselessAssignmentToDestructuredMessage {
+  #[display(
+    fmt = "The value destructured into `{}` is overwritten before it's read",
+    _0
+  )]
+  Overwritten(String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentToDestructuredHint {
+  #[display(
+    fmt = "Remove `{}` from the destructuring pattern and declare it separately",
+    _0
+  )]
+  Remove(String),
+}
+
+impl LintRule for NoUselessAssignmentToDestructured {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToDestructuredHandler {
+      functions: vec![0],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_destructured.md")
+  }
+}
+
+/// A binding of a `let` destructuring declaration that a later statement of
+/// the same block assigns to, e.g. `a` in `let { a } = obj; a = 1;`.
+struct Candidate {
+  id: Id,
+  /// The function the statements are in.
+  function: usize,
+  binding: SourceRange,
+  /// The target of the assignment.
+  target: SourceRange,
+  /// From the end of the binding to the end of the assignment. Reading the
+  /// variable anywhere in here may observe the destructured value.
+  observable: SourceRange,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToDestructuredHandler {
+  /// Stack of the ids of the functions being visited. Zero is the top level.
+  functions: Vec<usize>,
+  function_count: usize,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+  candidates: Vec<Candidate>,
+}
+
+/// Collects the identifiers that a destructuring pattern binds.
+fn collect_bindings<'a>(
+  pat: &Pat<'a>,
+  bindings: &mut Vec<&'a ast_view::Ident<'a>>,
+) {
+  match pat {
+    Pat::Ident(binding) => bindings.push(binding.id),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        collect_bindings(elem, bindings);
+      }
+    }
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            collect_bindings(&key_value.value, bindings)
+          }
+          ObjectPatProp::Assign(assign) => bindings.push(assign.key),
+          ObjectPatProp::Rest(rest) => collect_bindings(&rest.arg, bindings),
+        }
+      }
+    }
+    Pat::Assign(assign) => collect_bindings(&assign.left, bindings),
+    Pat::Rest(rest) => collect_bindings(&rest.arg, bindings),
+    Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+/// Returns the target if the statement is a plain assignment to a variable,
+/// e.g. `a = 1;`.
+fn assigned_ident<'a>(stmt: &Stmt<'a>) -> Option<&'a ast_view::Ident<'a>> {
+  let Stmt::Expr(expr_stmt) = stmt else {
+    return None;
+  };
+  let Expr::Assign(assign_expr) = expr_stmt.expr else {
+    return None;
+  };
+  match assign_expr.left {
+    PatOrExpr::Pat(Pat::Ident(target))
+      if assign_expr.op() == AssignOp::Assign =>
+    {
+      Some(target.id)
+    }
+    _ => None,
+  }
+}
+
+impl NoUselessAssignmentToDestructuredHandler {
+  fn check_stmts(&mut self, stmts: &[Stmt]) {
+    let function = *self.functions.last().unwrap();
+    for (i, stmt) in stmts.iter().enumerate() {
+      let Stmt::Decl(Decl::Var(var_decl)) = stmt else {
+        continue;
+      };
+      // `var`s can be read outside of the block, and `const`s can't be
+      // reassigned, which `no-const-assign` reports
+      if var_decl.decl_kind() != VarDeclKind::Let {
+        continue;
+      }
+
+      let mut bindings = Vec::new();
+      for declarator in &var_decl.decls {
+        if matches!(declarator.name, Pat::Object(_) | Pat::Array(_))
+          && declarator.init.is_some()
+        {
+          collect_bindings(&declarator.name, &mut bindings);
+        }
+      }
+
+      for binding in bindings {
+        let id = binding.inner.to_id();
+        let assignment = stmts[i + 1..].iter().find_map(|stmt| {
+          assigned_ident(stmt)
+            .filter(|target| target.inner.to_id() == id)
+            .map(|target| (target, stmt.range()))
+        });
+        let Some((target, assign_stmt)) = assignment else {
+          continue;
+        };
+        self.candidates.push(Candidate {
+          id,
+          function,
+          binding: binding.range(),
+          target: target.range(),
+          observable: SourceRange::new(binding.range().end, assign_stmt.end),
+        });
+      }
+    }
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      // Closures may read the variable at any time
+      let is_overwritten =
+        self.occurrences[&candidate.id]
+          .iter()
+          .all(|(function, range)| {
+            *function == candidate.function
+              && (*range == candidate.target
+                || !candidate.observable.contains(range))
+          });
+      if !is_overwritten {
+        continue;
+      }
+
+      let name = candidate.id.0.to_string();
+      ctx.add_diagnostic_with_hint(
+        candidate.binding,
+        CODE,
+        NoUselessAssignmentToDestructuredMessage::Overwritten(name.clone()),
+        NoUselessAssignmentToDestructuredHint::Remove(name),
+      );
+    }
+  }
+}
+
+impl Handler for NoUselessAssignmentToDestructuredHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(self.function_count);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = *self.functions.last().unwrap();
+    self
+      .occurrences
+      .entry(ident.inner.to_id())
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn block_stmt(
+    &mut self,
+    block_stmt: &ast_view::BlockStmt,
+    _ctx: &mut Context,
+  ) {
+    self.check_stmts(&block_stmt.stmts);
+  }
+
+  fn switch_case(
+    &mut self,
+    switch_case: &ast_view::SwitchCase,
+    _ctx: &mut Context,
+  ) {
+    self.check_stmts(&switch_case.cons);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_destructured_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToDestructured,
+      "function f(obj) { let { a } = obj; use(a); a = 1; }",
+      "function f(obj) { let { a } = obj; a = a + 1; }",
+      "function f(obj) { let { a } = obj; a += 1; }",
+      "function f(obj) { let { a } = obj; if (c) { use(a); } a = 1; }",
+      "function f(obj) { let { a, b = a } = obj; a = 1; use(b); }",
+      "function f(obj) { let { a } = obj; const g = () => a; g(); a = 1; }",
+      "function f(obj) { let { a } = obj; if (c) { a = 1; } use(a); }",
+      "function f(obj) { var { a } = obj; a = 1; }",
+      "function f(obj) { let a = obj; a = 1; }",
+      "function f(obj) { let { a } = obj; log(); a = 1; function log() { use(a); } }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_destructured_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToDestructured,
+      "function f(obj) { let { a } = obj; a = 1; return a; }": [
+        {
+          col: 24,
+          message: variant!(NoUselessAssignmentToDestructuredMessage, Overwritten, "a"),
+          hint: variant!(NoUselessAssignmentToDestructuredHint, Remove, "a"),
+        }
+      ],
+      "function f(obj) { let { a: { b }, c } = obj; init(); b = 1; use(b, c); }": [
+        {
+          col: 29,
+          message: variant!(NoUselessAssignmentToDestructuredMessage, Overwritten, "b"),
+          hint: variant!(NoUselessAssignmentToDestructuredHint, Remove, "b"),
+        }
+      ],
+      "for (const item of items) { let [first, ...rest] = item; rest = []; use(first, rest); }": [
+        {
+          col: 43,
+          message: variant!(NoUselessAssignmentToDestructuredMessage, Overwritten, "rest"),
+          hint: variant!(NoUselessAssignmentToDestructuredHint, Remove, "rest"),
+        }
+      ],
+      "switch (x) { case 1: { let { a = 1 } = obj; a = 2; use(a); } }": [
+        {
+          col: 29,
+          message: variant!(NoUselessAssignmentToDestructuredMessage, Overwritten, "a"),
+          hint: variant!(NoUselessAssignmentToDestructuredHint, Remove, "a"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,237 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{self as ast_view, Expr, Node, Pat};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToDiscardedAwait;
+
+const CODE: &str = "no-useless-assignment-to-discarded-await";
+const HINT: &str = "Remove the variable and keep the `await`";
+const FIX_DESC: &str = "Remove the variable";
+
+#[derive(Display)]
+enum NoUselessAssignmentToDiscardedAwaitMessage {
+  #[display(fmt = "`{}` is assigned an awaited value but never used", _0)]
+  Unused(String),
+}
+
+impl LintRule for NoUselessAssignmentToDiscardedAwait {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToDiscardedAwaitHandler::default();
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_discarded_await.md")
+  }
+}
+
+/// A declaration of a single variable initialized with an `await`, e.g.
+/// `const x = await foo();`.
+struct Candidate {
+  id: Id,
+  declarator: SourceRange,
+  var_decl: SourceRange,
+  await_expr: SourceRange,
+  /// Whether the declaration is a statement of its own, which can be
+  /// replaced with the `await`.
+  is_stmt: bool,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToDiscardedAwaitHandler {
+  /// How often each identifier occurs, including its declaration.
+  occurrences: HashMap<Id, usize>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentToDiscardedAwaitHandler {
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      if self.occurrences.get(&candidate.id) != Some(&1) {
+        continue;
+      }
+
+      // Only comments in the awaited expression are kept
+      let has_comments = ctx.all_comments().any(|comment| {
+        candidate.var_decl.contains(&comment.range())
+          && !candidate.await_expr.contains(&comment.range())
+      });
+      let fixes = if candidate.is_stmt && !has_comments {
+        let semicolon = if ctx.text_of(candidate.var_decl).ends_with(';') {
+          ";"
+        } else {
+          ""
+        };
+        vec![LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![LintFixChange {
+            new_text: format!(
+              "{}{}",
+              ctx.text_of(candidate.await_expr),
+              semicolon
+            )
+            .into(),
+            range: candidate.var_decl,
+          }],
+        }]
+      } else {
+        vec![]
+      };
+
+      ctx.add_diagnostic_with_fixes(
+        candidate.declarator,
+        CODE,
+        NoUselessAssignmentToDiscardedAwaitMessage::Unused(
+          candidate.id.0.to_string(),
+        ),
+        Some(HINT.to_string()),
+        fixes,
+      );
+    }
+  }
+}
+
+impl Handler for NoUselessAssignmentToDiscardedAwaitHandler {
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    *self.occurrences.entry(ident.inner.to_id()).or_default() += 1;
+  }
+
+  fn var_decl(&mut self, var_decl: &ast_view::VarDecl, _ctx: &mut Context) {
+    let [declarator] = var_decl.decls[..] else {
+      return;
+    };
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let mut init = declarator.init;
+    while let Some(Expr::Paren(paren_expr)) = init {
+      init = Some(paren_expr.expr);
+    }
+    let Some(Expr::Await(await_expr)) = init else {
+      return;
+    };
+    // Variables starting with an underscore are meant to be unused
+    if binding.id.sym().starts_with('_') {
+      return;
+    }
+    let is_stmt = match var_decl.parent() {
+      Node::BlockStmt(_)
+      | Node::Module(_)
+      | Node::Script(_)
+      | Node::SwitchCase(_)
+      | Node::TsModuleBlock(_) => true,
+      Node::ExportDecl(_)
+      | Node::ForStmt(_)
+      | Node::ForInStmt(_)
+      | Node::ForOfStmt(_) => return,
+      _ => false,
+    };
+
+    self.candidates.push(Candidate {
+      id: binding.id.inner.to_id(),
+      declarator: declarator.range(),
+      var_decl: var_decl.range(),
+      await_expr: await_expr.range(),
+      is_stmt,
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_discarded_await_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToDiscardedAwait,
+      "await foo();",
+      "const x = await foo(); use(x);",
+      "const x = foo();",
+      "async function f() { const x = await foo(); return x; }",
+      "async function f() { let x = await foo(); x = 1; }",
+      "async function f() { const x = await foo(); return () => x; }",
+      "const _x = await foo();",
+      "const { a } = await foo();",
+      "const x = await foo(), y = 1; use(y);",
+      "export const x = await foo();",
+      "const x = await foo(); export { x };",
+      "for (const x = await foo(); ;) {}",
+      "for await (const x of xs) {}",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_discarded_await_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToDiscardedAwait,
+      "const x = await foo();": [
+        {
+          col: 6,
+          message: variant!(NoUselessAssignmentToDiscardedAwaitMessage, Unused, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "await foo();"),
+        }
+      ],
+      "async function f() {\n  let result: Response = await fetch(url)\n  done()\n}": [
+        {
+          line: 2,
+          col: 6,
+          message: variant!(NoUselessAssignmentToDiscardedAwaitMessage, Unused, "result"),
+          hint: HINT,
+          fix: (FIX_DESC, "async function f() {\n  await fetch(url)\n  done()\n}"),
+        }
+      ],
+      "const f = async () => { if (a) { var x = (await save(/* all */ items)); } };": [
+        {
+          col: 37,
+          message: variant!(NoUselessAssignmentToDiscardedAwaitMessage, Unused, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "const f = async () => { if (a) { await save(/* all */ items); } };"),
+        }
+      ],
+      "switch (a) { case 1: const x = await foo(); }": [
+        {
+          col: 27,
+          message: variant!(NoUselessAssignmentToDiscardedAwaitMessage, Unused, "x"),
+          hint: HINT,
+          fix: (FIX_DESC, "switch (a) { case 1: await foo(); }"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_discarded_await_invalid_without_fix() {
+    for src in [
+      "const x /* result */ = await foo();",
+      "async function f() { if (a) var x = await foo(); }",
+    ] {
+      let diagnostics = crate::test_util::lint(
+        &NoUselessAssignmentToDiscardedAwait,
+        src,
+        "foo.ts",
+      );
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+}
@@ -0,0 +1,133 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, MemberProp, Pat, PatOrExpr};
+use deno_ast::SourceRanged;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToExports;
+
+const CODE: &str = "no-useless-assignment-to-exports";
+const MESSAGE: &str =
+  "CommonJS exports don't exist in ES modules, so this assignment exports nothing";
+const HINT: &str =
+  "Use `export` instead, e.g. `export default foo` or `export const foo = ...`";
+
+impl LintRule for NoUselessAssignmentToExports {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    // Scripts may be CommonJS modules, where `module` and `exports` exist
+    if let Program::Script(_) = program {
+      return;
+    }
+
+    NoUselessAssignmentToExportsHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_exports.md")
+  }
+}
+
+struct NoUselessAssignmentToExportsHandler;
+
+/// Checks if the expression is `exports`, `module.exports` or a property of
+/// either, where `module` and `exports` aren't declared in the file. They're
+/// checked with the scope rather than `Context::is_global_ident`: the point
+/// is that these globals don't exist in ES modules, whatever the
+/// `LinterBuilder::is_global` predicate says.
+fn is_commonjs_exports(expr: &Expr, ctx: &Context) -> bool {
+  match expr {
+    Expr::Ident(ident) => {
+      ident.sym() == "exports" && ctx.scope().is_global(&ident.inner.to_id())
+    }
+    Expr::Member(member_expr) => {
+      if let (Expr::Ident(obj), MemberProp::Ident(prop)) =
+        (&member_expr.obj, &member_expr.prop)
+      {
+        if obj.sym() == "module"
+          && prop.sym() == "exports"
+          && ctx.scope().is_global(&obj.inner.to_id())
+        {
+          return true;
+        }
+      }
+      is_commonjs_exports(&member_expr.obj, ctx)
+    }
+    _ => false,
+  }
+}
+
+impl Handler for NoUselessAssignmentToExportsHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    let is_exports = match &assign_expr.left {
+      PatOrExpr::Expr(expr) | PatOrExpr::Pat(Pat::Expr(expr)) => {
+        is_commonjs_exports(expr, ctx)
+      }
+      PatOrExpr::Pat(Pat::Ident(binding)) => {
+        binding.id.sym() == "exports"
+          && ctx.scope().is_global(&binding.id.inner.to_id())
+      }
+      PatOrExpr::Pat(_) => false,
+    };
+
+    if is_exports {
+      ctx.add_diagnostic_with_hint(assign_expr.range(), CODE, MESSAGE, HINT);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_exports_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToExports,
+      "export default foo;",
+      "export const foo = 42;",
+      "const module = { exports: {} }; module.exports = foo; export {};",
+      "function f(exports) { exports.foo = 42; } export {};",
+      "let exports = {}; exports = foo; export {};",
+      "foo.module.exports = bar; export {};",
+      "module.foo = bar; export {};",
+      "const a = module.exports; export {};",
+      "const b = exports.foo; export {};",
+
+      // Scripts
+      "module.exports = foo;",
+      "exports.foo = 42;",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_exports_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToExports,
+      MESSAGE,
+      HINT,
+      "module.exports = foo; export {};": [{ col: 0 }],
+      "module.exports.foo = 42; export {};": [{ col: 0 }],
+      "module.exports.foo.bar = 42; export {};": [{ col: 0 }],
+      "exports.foo = 42; export {};": [{ col: 0 }],
+      "exports = foo; export {};": [{ col: 0 }],
+      "exports.foo += 1; export {};": [{ col: 0 }],
+      "import foo from './foo.ts'; module.exports = { foo };": [{ col: 28 }],
+      "function f() { module.exports = foo; } export {};": [{ col: 15 }],
+    };
+  }
+}
@@ -0,0 +1,266 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, Decl, Expr, ModuleDecl, ModuleItem, Pat, Stmt, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToExportsDefault;
+
+const CODE: &str = "no-useless-assignment-to-exports-default";
+const HINT: &str = "Export the value directly with `export default ...`";
+const FIX_DESC: &str = "Export the value directly";
+
+#[derive(Display)]
+enum NoUselessAssignmentToExportsDefaultMessage {
+  #[display(fmt = "`{}` is only declared to be exported as default", _0)]
+  OnlyExported(String),
+}
+
+impl LintRule for NoUselessAssignmentToExportsDefault {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToExportsDefaultHandler::default();
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_exports_default.md")
+  }
+}
+
+/// A variable declaration followed by `export default` of the variable.
+struct Candidate {
+  id: Id,
+  name: String,
+  /// The declaration statement.
+  decl: SourceRange,
+  init: SourceRange,
+  export: SourceRange,
+  /// The identifier in `export default`.
+  exported_ident: SourceRange,
+  /// Whether the export directly follows the declaration, in which case the
+  /// initializer can be moved to the export without running it later.
+  adjacent: bool,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToExportsDefaultHandler {
+  candidates: Vec<Candidate>,
+  /// Number of occurrences of each identifier, including declarations.
+  occurrences: HashMap<Id, usize>,
+}
+
+impl NoUselessAssignmentToExportsDefaultHandler {
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      // The declaration and the export are the only occurrences
+      if self.occurrences.get(&candidate.id) != Some(&2) {
+        continue;
+      }
+      let fixes = if candidate.adjacent {
+        let init = ctx.text_of(candidate.init).to_string();
+        vec![LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![
+            LintFixChange {
+              new_text: "".into(),
+              range: SourceRange::new(
+                candidate.decl.start,
+                candidate.export.start,
+              ),
+            },
+            LintFixChange {
+              new_text: init.into(),
+              range: candidate.exported_ident,
+            },
+          ],
+        }]
+      } else {
+        vec![]
+      };
+      ctx.add_diagnostic_with_fixes(
+        candidate.export,
+        CODE,
+        NoUselessAssignmentToExportsDefaultMessage::OnlyExported(
+          candidate.name,
+        ),
+        Some(HINT.to_string()),
+        fixes,
+      );
+    }
+  }
+}
+
+/// Whether the expression gets its name from the variable it's assigned to,
+/// which it would lose when exported directly.
+fn is_named_by_binding(expr: &Expr) -> bool {
+  match expr {
+    Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_) => true,
+    Expr::Paren(paren) => is_named_by_binding(&paren.expr),
+    _ => false,
+  }
+}
+
+/// The identifier, initializer and range of a declaration of a single
+/// variable without a type annotation, e.g. `const foo = 42;`.
+fn single_declaration<'a>(
+  item: &ModuleItem<'a>,
+) -> Option<(&'a ast_view::Ident<'a>, Expr<'a>, SourceRange)> {
+  let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = item else {
+    return None;
+  };
+  if var_decl.decl_kind() == VarDeclKind::Var
+    || var_decl.declare()
+    || var_decl.decls.len() != 1
+  {
+    return None;
+  }
+  let declarator = var_decl.decls[0];
+  let Pat::Ident(binding) = declarator.name else {
+    return None;
+  };
+  if binding.type_ann.is_some() {
+    return None;
+  }
+  let init = declarator.init?;
+  if is_named_by_binding(&init) {
+    return None;
+  }
+  Some((binding.id, init, var_decl.range()))
+}
+
+impl Handler for NoUselessAssignmentToExportsDefaultHandler {
+  fn module(&mut self, module: &ast_view::Module, _ctx: &mut Context) {
+    for (index, item) in module.body.iter().enumerate() {
+      let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) = item
+      else {
+        continue;
+      };
+      let Expr::Ident(exported_ident) = export.expr else {
+        continue;
+      };
+      let id = exported_ident.inner.to_id();
+      let declaration =
+        module
+          .body
+          .iter()
+          .enumerate()
+          .find_map(|(decl_index, item)| {
+            let (ident, init, decl) = single_declaration(item)?;
+            (ident.inner.to_id() == id).then_some((decl_index, init, decl))
+          });
+      let Some((decl_index, init, decl)) = declaration else {
+        continue;
+      };
+      self.candidates.push(Candidate {
+        id,
+        name: exported_ident.sym().to_string(),
+        decl,
+        init: init.range(),
+        export: export.range(),
+        exported_ident: exported_ident.range(),
+        adjacent: decl_index + 1 == index,
+      });
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    *self.occurrences.entry(ident.inner.to_id()).or_default() += 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_exports_default_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToExportsDefault,
+      "export default { a: 1 };",
+      "const config = { a: 1 }; console.log(config); export default config;",
+      "const config = { a: 1 }; export default config; config.b = 2;",
+      "let config = { a: 1 }; config = {}; export default config;",
+      "const config = { a: 1 }; export { config }; export default config;",
+      "const config = { a: 1 }; type T = typeof config; export default config;",
+      "import config from './config.ts'; export default config;",
+      "export default config;",
+
+      // Functions and classes get their name from the variable
+      "const handler = () => {}; export default handler;",
+      "const handler = function () {}; export default handler;",
+      "const Foo = class {}; export default Foo;",
+      "const handler = (async () => {}); export default handler;",
+
+      // The annotation would be lost
+      "const config: Config = { a: 1 }; export default config;",
+      "var config = { a: 1 }; export default config;",
+      "const a = 1, b = 2; export default a;",
+      "const { config } = foo; export default config;",
+      "function f() { const config = {}; return config; } const config = 1; export default f;",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_exports_default_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToExportsDefault,
+      "const config = { a: 1 };\nexport default config;": [
+        {
+          line: 2,
+          col: 0,
+          message: variant!(NoUselessAssignmentToExportsDefaultMessage, OnlyExported, "config"),
+          hint: HINT,
+          fix: (FIX_DESC, "export default { a: 1 };"),
+        }
+      ],
+      "let value = compute();\nexport default value;": [
+        {
+          line: 2,
+          col: 0,
+          message: variant!(NoUselessAssignmentToExportsDefaultMessage, OnlyExported, "value"),
+          hint: HINT,
+          fix: (FIX_DESC, "export default compute();"),
+        }
+      ],
+      "import { a } from './a.ts';\nconst value = a + 1;\nexport default value;\nfoo();": [
+        {
+          line: 3,
+          col: 0,
+          message: variant!(NoUselessAssignmentToExportsDefaultMessage, OnlyExported, "value"),
+          hint: HINT,
+          fix: (FIX_DESC, "import { a } from './a.ts';\nexport default a + 1;\nfoo();"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_exports_default_no_fix() {
+    // The initializer would run after `init()`
+    let diagnostics = crate::test_util::lint(
+      &NoUselessAssignmentToExportsDefault,
+      "const value = compute();\ninit();\nexport default value;",
+      "foo.ts",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes.is_empty());
+  }
+}
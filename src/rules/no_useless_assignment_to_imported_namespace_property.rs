@@ -0,0 +1,202 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, ObjectPatProp, Pat, PatOrExpr};
+use deno_ast::{BindingKind, SourceRange, SourceRanged};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToImportedNamespaceProperty;
+
+const CODE: &str = "no-useless-assignment-to-imported-namespace-property";
+const HINT: &str = "Treat imported modules as immutable. Export a function that updates the value from the module, or change a copy instead";
+
+#[derive(Display)]
+enum NoUselessAssignmentToImportedNamespacePropertyMessage {
+  #[display(
+    fmt = "Assigning to a property of an export of the namespace import `{}` changes the state of the imported module",
+    _0
+  )]
+  Mutation(String),
+}
+
+impl LintRule for NoUselessAssignmentToImportedNamespaceProperty {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentToImportedNamespacePropertyHandler
+      .traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_to_imported_namespace_property.md"
+    )
+  }
+}
+
+struct NoUselessAssignmentToImportedNamespacePropertyHandler;
+
+/// Returns the namespace import that the expression is a nested property of,
+/// e.g. `ns` for `ns.config.value`. Properties of the namespace object
+/// itself, like `ns.config`, are left to `no-import-assign`.
+fn namespace_of_nested_property<'a>(
+  expr: &Expr<'a>,
+  ctx: &Context,
+) -> Option<&'a ast_view::Ident<'a>> {
+  let Expr::Member(member_expr) = expr else {
+    return None;
+  };
+  let mut obj = member_expr.obj;
+  let mut depth = 1;
+  loop {
+    match obj {
+      Expr::Member(member_expr) => {
+        obj = member_expr.obj;
+        depth += 1;
+      }
+      Expr::Paren(paren_expr) => obj = paren_expr.expr,
+      Expr::TsNonNull(non_null_expr) => obj = non_null_expr.expr,
+      Expr::Ident(ident) if depth >= 2 => {
+        let var = ctx.scope().var_by_ident(ident)?;
+        return (var.kind() == BindingKind::NamespaceImport).then_some(ident);
+      }
+      _ => return None,
+    }
+  }
+}
+
+/// Reports the assignment if one of the targets in the pattern is a nested
+/// property of a namespace import.
+fn check_pat(pat: &Pat, range: SourceRange, ctx: &mut Context) -> bool {
+  match pat {
+    Pat::Expr(expr) => check_target(expr, range, ctx),
+    Pat::Array(array_pat) => array_pat
+      .elems
+      .iter()
+      .flatten()
+      .any(|elem| check_pat(elem, range, ctx)),
+    Pat::Object(object_pat) => object_pat.props.iter().any(|prop| match prop {
+      ObjectPatProp::KeyValue(key_value) => {
+        check_pat(&key_value.value, range, ctx)
+      }
+      ObjectPatProp::Rest(rest_pat) => check_pat(&rest_pat.arg, range, ctx),
+      ObjectPatProp::Assign(_) => false,
+    }),
+    Pat::Assign(assign_pat) => check_pat(&assign_pat.left, range, ctx),
+    Pat::Rest(rest_pat) => check_pat(&rest_pat.arg, range, ctx),
+    Pat::Ident(_) | Pat::Invalid(_) => false,
+  }
+}
+
+fn check_target(expr: &Expr, range: SourceRange, ctx: &mut Context) -> bool {
+  let Some(namespace) = namespace_of_nested_property(expr, ctx) else {
+    return false;
+  };
+  ctx.add_diagnostic_with_hint(
+    range,
+    CODE,
+    NoUselessAssignmentToImportedNamespacePropertyMessage::Mutation(
+      namespace.sym().to_string(),
+    ),
+    HINT,
+  );
+  true
+}
+
+impl Handler for NoUselessAssignmentToImportedNamespacePropertyHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    match &assign_expr.left {
+      PatOrExpr::Expr(expr) => {
+        check_target(expr, assign_expr.range(), ctx);
+      }
+      PatOrExpr::Pat(pat) => {
+        check_pat(pat, assign_expr.range(), ctx);
+      }
+    }
+  }
+
+  fn update_expr(
+    &mut self,
+    update_expr: &ast_view::UpdateExpr,
+    ctx: &mut Context,
+  ) {
+    check_target(&update_expr.arg, update_expr.range(), ctx);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_imported_namespace_property_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToImportedNamespaceProperty,
+      r#"import * as ns from "m"; ns.x = 1;"#,
+      r#"import * as ns from "m"; const config = { ...ns.config }; config.value = 1;"#,
+      r#"import * as ns from "m"; use(ns.config.value);"#,
+      r#"import * as ns from "m"; ns.config.value === 1;"#,
+      r#"import * as ns from "m"; ns.getConfig().value = 1;"#,
+      r#"import * as ns from "m"; function f(ns) { ns.config.value = 1; }"#,
+      r#"import { config } from "m"; config.inner.value = 1;"#,
+      r#"import ns from "m"; ns.config.value = 1;"#,
+      "const ns = {}; ns.config.value = 1;",
+      r#"import * as ns from "m"; obj[ns.a.b] = 1;"#,
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_imported_namespace_property_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToImportedNamespaceProperty,
+      r#"import * as ns from "m"; ns.config.value = 1;"#: [
+        {
+          col: 25,
+          message: variant!(NoUselessAssignmentToImportedNamespacePropertyMessage, Mutation, "ns"),
+          hint: HINT,
+        }
+      ],
+      r#"import * as ns from "m"; ns.a["b"].c += 1;"#: [
+        {
+          col: 25,
+          message: variant!(NoUselessAssignmentToImportedNamespacePropertyMessage, Mutation, "ns"),
+          hint: HINT,
+        }
+      ],
+      r#"import * as ns from "m"; (ns.a)!.count++;"#: [
+        {
+          col: 25,
+          message: variant!(NoUselessAssignmentToImportedNamespacePropertyMessage, Mutation, "ns"),
+          hint: HINT,
+        }
+      ],
+      r#"import * as mod from "m"; function f() { [a, mod.state.x] = [1, 2]; }"#: [
+        {
+          col: 41,
+          message: variant!(NoUselessAssignmentToImportedNamespacePropertyMessage, Mutation, "mod"),
+          hint: HINT,
+        }
+      ],
+      r#"import * as ns from "m"; ({ x: ns.a.x, y: ns.a.y } = obj);"#: [
+        {
+          col: 26,
+          message: variant!(NoUselessAssignmentToImportedNamespacePropertyMessage, Mutation, "ns"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
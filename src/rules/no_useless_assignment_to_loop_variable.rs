@@ -0,0 +1,302 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::{is_loop, FunctionLike};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, ForHead, ObjectPatProp, Pat, PatOrExpr,
+  VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToLoopVariable;
+
+const CODE: &str = "no-useless-assignment-to-loop-variable";
+const HINT: &str =
+  "Remove the assignment, or use the value before the end of the loop body";
+
+#[derive(Display)]
+enum NoUselessAssignmentToLoopVariableMessage {
+  #[display(
+    fmt = "The value assigned to `{}` is never read before the next iteration",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentToLoopVariable {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToLoopVariableHandler {
+      functions: vec![0],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_loop_variable.md")
+  }
+}
+
+/// A variable declared with `let` in the head of a `for-of` or `for-in`
+/// loop, which gets a new binding for every iteration.
+struct LoopVariable {
+  /// The function the loop is in.
+  function: usize,
+  body: SourceRange,
+}
+
+/// A plain assignment to a variable, e.g. `x = 1`.
+struct Assignment {
+  id: Id,
+  function: usize,
+  assign_expr: SourceRange,
+  target: SourceRange,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToLoopVariableHandler {
+  /// Stack of the ids of the functions being visited. Zero is the top level.
+  functions: Vec<usize>,
+  function_count: usize,
+  loop_variables: HashMap<Id, LoopVariable>,
+  /// The ranges of all loop statements.
+  loops: Vec<SourceRange>,
+  assignments: Vec<Assignment>,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+}
+
+impl NoUselessAssignmentToLoopVariableHandler {
+  fn report(self, ctx: &mut Context) {
+    for assignment in &self.assignments {
+      let Some(variable) = self.loop_variables.get(&assignment.id) else {
+        continue;
+      };
+      if assignment.function != variable.function
+        || !variable.body.contains(&assignment.assign_expr)
+      {
+        continue;
+      }
+      // An outer loop in the body may run the code before the assignment
+      // again
+      let outer_loop = self.loops.iter().find(|range| {
+        variable.body.contains(range) && range.contains(&assignment.assign_expr)
+      });
+      let is_read = |range: &SourceRange| {
+        (range.start >= assignment.assign_expr.end
+          && variable.body.contains(range))
+          || outer_loop.map_or(false, |outer_loop| outer_loop.contains(range))
+      };
+      // Closures may read the variable at any time
+      let is_useless =
+        self.occurrences[&assignment.id]
+          .iter()
+          .all(|(function, range)| {
+            *function == variable.function
+              && (*range == assignment.target
+                || self.is_assignment_target(&assignment.id, range)
+                || !is_read(range))
+          });
+      if !is_useless {
+        continue;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        assignment.assign_expr,
+        CODE,
+        NoUselessAssignmentToLoopVariableMessage::Useless(
+          assignment.id.0.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+
+  fn is_assignment_target(&self, id: &Id, range: &SourceRange) -> bool {
+    self
+      .assignments
+      .iter()
+      .any(|assignment| assignment.id == *id && assignment.target == *range)
+  }
+
+  fn check_loop(&mut self, head: &ForHead, body: SourceRange) {
+    let ForHead::VarDecl(var_decl) = head else {
+      return;
+    };
+    // `var`s keep their value after the loop, and assigning to a `const`
+    // throws, which `no-const-assign` reports
+    if var_decl.decl_kind() != VarDeclKind::Let {
+      return;
+    }
+    let function = *self.functions.last().unwrap();
+    let mut ids = Vec::new();
+    for declarator in &var_decl.decls {
+      collect_bindings(&declarator.name, &mut ids);
+    }
+    for id in ids {
+      self
+        .loop_variables
+        .insert(id, LoopVariable { function, body });
+    }
+  }
+}
+
+/// Collects the identifiers that a pattern binds.
+fn collect_bindings(pat: &Pat, ids: &mut Vec<Id>) {
+  match pat {
+    Pat::Ident(binding) => ids.push(binding.id.inner.to_id()),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        collect_bindings(elem, ids);
+      }
+    }
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            collect_bindings(&key_value.value, ids)
+          }
+          ObjectPatProp::Assign(assign) => ids.push(assign.key.inner.to_id()),
+          ObjectPatProp::Rest(rest) => collect_bindings(&rest.arg, ids),
+        }
+      }
+    }
+    Pat::Assign(assign) => collect_bindings(&assign.left, ids),
+    Pat::Rest(rest) => collect_bindings(&rest.arg, ids),
+    Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+impl Handler for NoUselessAssignmentToLoopVariableHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.function_count += 1;
+      self.functions.push(self.function_count);
+    }
+    if is_loop(node) {
+      self.loops.push(node.range());
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = *self.functions.last().unwrap();
+    self
+      .occurrences
+      .entry(ident.inner.to_id())
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn for_of_stmt(
+    &mut self,
+    for_of_stmt: &ast_view::ForOfStmt,
+    _ctx: &mut Context,
+  ) {
+    self.check_loop(&for_of_stmt.left, for_of_stmt.body.range());
+  }
+
+  fn for_in_stmt(
+    &mut self,
+    for_in_stmt: &ast_view::ForInStmt,
+    _ctx: &mut Context,
+  ) {
+    self.check_loop(&for_in_stmt.left, for_in_stmt.body.range());
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+      return;
+    };
+    if assign_expr.op() != AssignOp::Assign {
+      return;
+    }
+    self.assignments.push(Assignment {
+      id: target.id.inner.to_id(),
+      function: *self.functions.last().unwrap(),
+      assign_expr: assign_expr.range(),
+      target: target.id.range(),
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_loop_variable_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToLoopVariable,
+      "for (let x of arr) { x = f(x); use(x); }",
+      "for (let x of arr) { if (c) { x = 1; } use(x); }",
+      "for (let x in obj) { x = x.trim(); use(x); }",
+      "for (let x of arr) { x = 1; const g = () => x; g(); }",
+      "for (let x of arr) { fns.push(() => x); x = 1; }",
+      "for (let x of arr) { while (c) { use(x); x = next(); } }",
+      "for (let x of arr) { x += 1; }",
+      "for (var x of arr) { x = 1; } use(x);",
+      "let x; for (x of arr) { x = 1; } use(x);",
+      "for (const x of arr) { x = 1; }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_loop_variable_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToLoopVariable,
+      "for (let x of arr) { use(x); x = f(x); }": [
+        {
+          col: 29,
+          message: variant!(NoUselessAssignmentToLoopVariableMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "for (let key in obj) { use(key); if (c) { key = ''; } }": [
+        {
+          col: 42,
+          message: variant!(NoUselessAssignmentToLoopVariableMessage, Useless, "key"),
+          hint: HINT,
+        }
+      ],
+      "function f(pairs) { for (let [a, b] of pairs) { use(a, b); b = null; } }": [
+        {
+          col: 59,
+          message: variant!(NoUselessAssignmentToLoopVariableMessage, Useless, "b"),
+          hint: HINT,
+        }
+      ],
+      "for (let x of arr) { for (const y of ys) { use(y); } x = 1; }": [
+        {
+          col: 53,
+          message: variant!(NoUselessAssignmentToLoopVariableMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,327 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::context::{is_loop, FunctionLike};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Callee, Node, NodeTrait, Pat, PatOrExpr,
+  VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToOuterInClosure;
+
+const CODE: &str = "no-useless-assignment-to-outer-in-closure";
+const HINT: &str = "Remove the assignment";
+
+#[derive(Display)]
+enum NoUselessAssignmentToOuterInClosureMessage {
+  #[display(
+    fmt = "`{}` is assigned in an immediately invoked function, but the value is never read",
+    _0
+  )]
+  Useless(String),
+}
+
+impl LintRule for NoUselessAssignmentToOuterInClosure {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToOuterInClosureHandler {
+      functions: vec![Function::default()],
+      ..Default::default()
+    };
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_to_outer_in_closure.md"
+    )
+  }
+}
+
+/// An assignment in an immediately invoked function to a variable that may be
+/// declared in the function around the call, e.g. `(() => { x = 1; })();`.
+struct Candidate {
+  id: Id,
+  /// The function the call is in.
+  outer_function: usize,
+  call: SourceRange,
+  assign_expr: SourceRange,
+}
+
+#[derive(Default)]
+struct Function {
+  /// Zero is the top level.
+  id: usize,
+  /// The call if the function is invoked right away, and not in a loop of
+  /// the outer function, so that it runs exactly once.
+  iife_call: Option<SourceRange>,
+  /// How many loops of this function the visitor is in.
+  loop_depth: usize,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToOuterInClosureHandler {
+  /// Stack of the functions being visited.
+  functions: Vec<Function>,
+  function_count: usize,
+  /// The function each `let` or `var` binding is declared in.
+  declarations: HashMap<Id, usize>,
+  /// Bindings that are exported, and so may be read by other modules.
+  exported: HashSet<Id>,
+  /// The function and range of each occurrence of each identifier.
+  occurrences: HashMap<Id, Vec<(usize, SourceRange)>>,
+  /// Ranges of identifiers that are only written to, by `x = ...`.
+  writes: HashSet<SourceRange>,
+  candidates: Vec<Candidate>,
+}
+
+impl NoUselessAssignmentToOuterInClosureHandler {
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      let is_outer_local = self.declarations.get(&candidate.id)
+        == Some(&candidate.outer_function)
+        && !self.exported.contains(&candidate.id);
+      // Every read must happen in the outer function before the call, since
+      // a closure could read the variable at any time
+      let is_unread =
+        self.occurrences[&candidate.id]
+          .iter()
+          .all(|(function, range)| {
+            self.writes.contains(range)
+              || (*function == candidate.outer_function
+                && range.end <= candidate.call.start)
+          });
+      if !is_outer_local || !is_unread {
+        continue;
+      }
+
+      ctx.add_diagnostic_with_hint(
+        candidate.assign_expr,
+        CODE,
+        NoUselessAssignmentToOuterInClosureMessage::Useless(
+          candidate.id.0.to_string(),
+        ),
+        HINT,
+      );
+    }
+  }
+}
+
+/// Returns the call if the function is the callee of a call, possibly in
+/// parentheses, e.g. `(function () {})()`. Async functions and generators
+/// don't run their whole body during the call, so they're never invoked
+/// right away.
+fn iife_call(node: Node) -> Option<SourceRange> {
+  let mut callee = match node {
+    Node::ArrowExpr(arrow) if !arrow.is_async() && !arrow.is_generator() => {
+      node
+    }
+    Node::Function(function)
+      if !function.is_async() && !function.is_generator() =>
+    {
+      match function.parent() {
+        Node::FnExpr(fn_expr) => fn_expr.as_node(),
+        _ => return None,
+      }
+    }
+    _ => return None,
+  };
+  while let Node::ParenExpr(paren) = callee.parent()? {
+    callee = paren.as_node();
+  }
+  match callee.parent()? {
+    Node::CallExpr(call_expr) => match call_expr.callee {
+      Callee::Expr(expr) if expr.range() == callee.range() => {
+        Some(call_expr.range())
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+impl Handler for NoUselessAssignmentToOuterInClosureHandler {
+  fn on_enter_node(&mut self, node: Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      let outer = self.functions.last().unwrap();
+      let iife_call = iife_call(node).filter(|_| outer.loop_depth == 0);
+      self.function_count += 1;
+      self.functions.push(Function {
+        id: self.function_count,
+        iife_call,
+        loop_depth: 0,
+      });
+    } else if is_loop(node) {
+      self.functions.last_mut().unwrap().loop_depth += 1;
+    }
+  }
+
+  fn on_exit_node(&mut self, node: Node, _ctx: &mut Context) {
+    if FunctionLike::from_node(node).is_some() {
+      self.functions.pop();
+    } else if is_loop(node) {
+      self.functions.last_mut().unwrap().loop_depth -= 1;
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let function = self.functions.last().unwrap().id;
+    let id = ident.inner.to_id();
+    if matches!(ident.parent(), Node::ExportNamedSpecifier(_)) {
+      self.exported.insert(id.clone());
+    }
+    self
+      .occurrences
+      .entry(id)
+      .or_default()
+      .push((function, ident.range()));
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+      return;
+    };
+    if assign_expr.op() != AssignOp::Assign {
+      return;
+    }
+    self.writes.insert(target.id.range());
+
+    let function_index = self.functions.len() - 1;
+    let function = &self.functions[function_index];
+    let Some(call) = function.iife_call else {
+      return;
+    };
+    self.candidates.push(Candidate {
+      id: target.id.inner.to_id(),
+      outer_function: self.functions[function_index - 1].id,
+      call,
+      assign_expr: assign_expr.range(),
+    });
+  }
+
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    _ctx: &mut Context,
+  ) {
+    let Pat::Ident(binding) = declarator.name else {
+      return;
+    };
+    let function = self.functions.last().unwrap().id;
+    let id = binding.id.inner.to_id();
+    let Node::VarDecl(var_decl) = declarator.parent() else {
+      return;
+    };
+    if matches!(var_decl.parent(), Node::ExportDecl(_)) {
+      self.exported.insert(id.clone());
+    }
+    // Top-level `var`s are properties of the global object
+    let is_local = match var_decl.decl_kind() {
+      VarDeclKind::Let => true,
+      VarDeclKind::Var => function != 0,
+      VarDeclKind::Const => false,
+    };
+    if is_local {
+      self.declarations.insert(id, function);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_outer_in_closure_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToOuterInClosure,
+      "let x; (() => { x = 5; })(); use(x);",
+      "let x; (function () { x = 5; })(); use(x);",
+      "function f() { let x; (() => { x = 5; })(); return x; }",
+      "let x; (() => { x += 5; })();",
+      "let x; (() => { x = 5; use(x); })();",
+      "let x; (() => { [x] = [5]; })();",
+      "let x; (() => { let x; x = 5; })(); use(x);",
+      "const o = {}; (() => { o.x = 5; })();",
+
+      // Reads in other closures may happen at any time
+      "let x; const g = () => x; (() => { x = 5; })(); g();",
+      "let x; (() => { x = 5; })(); setTimeout(() => use(x));",
+
+      // The closure isn't invoked right away, or not exactly once
+      "let x; const g = () => { x = 5; }; g();",
+      "let x; foo(() => { x = 5; });",
+      "let x; (() => { x = 5; }).call(this);",
+      "let x; (async () => { await a; x = 5; })(); setTimeout(() => use(x));",
+      "let x; (function* () { x = 5; })();",
+      "let x; while (a) { use(x); (() => { x = 5; })(); }",
+      "let x; (() => { (() => { x = 5; })(); })();",
+
+      // The variable isn't local to the outer function
+      "var x; (() => { x = 5; })();",
+      "function f() { (() => { x = 5; })(); }",
+      "function f(x) { (() => { x = 5; })(); }",
+      "export let x; (() => { x = 5; })();",
+      "let x; export { x }; (() => { x = 5; })();",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_outer_in_closure_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToOuterInClosure,
+      "let x; (() => { x = 5; })();": [
+        {
+          col: 16,
+          message: variant!(NoUselessAssignmentToOuterInClosureMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "function f() { var x = 1; use(x); (function () { if (a) { x = 5; } })(); }": [
+        {
+          col: 58,
+          message: variant!(NoUselessAssignmentToOuterInClosureMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "let x; const y = (() => { x = 1; x = 2; return 3; })(); use(y);": [
+        {
+          col: 26,
+          message: variant!(NoUselessAssignmentToOuterInClosureMessage, Useless, "x"),
+          hint: HINT,
+        },
+        {
+          col: 33,
+          message: variant!(NoUselessAssignmentToOuterInClosureMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+      "let x; for (const a of b) { use(a); } (() => { x = 5; })();": [
+        {
+          col: 47,
+          message: variant!(NoUselessAssignmentToOuterInClosureMessage, Useless, "x"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
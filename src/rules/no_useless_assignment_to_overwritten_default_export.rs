@@ -0,0 +1,200 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::find_lhs_ids;
+use crate::Program;
+use deno_ast::swc::ast::{Id, Ident};
+use deno_ast::view::{self as ast_view, Expr, ModuleDecl, ModuleItem};
+use deno_ast::{SourcePos, SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToOverwrittenDefaultExport;
+
+const CODE: &str = "no-useless-assignment-to-overwritten-default-export";
+const HINT: &str = "`export default` with an expression exports the value at that point. Use `export { name as default }` to export the binding itself";
+
+#[derive(Display)]
+enum NoUselessAssignmentToOverwrittenDefaultExportMessage {
+  #[display(
+    fmt = "`{}` is reassigned after it was exported as default, which doesn't change the default export",
+    _0
+  )]
+  Reassigned(String),
+}
+
+impl LintRule for NoUselessAssignmentToOverwrittenDefaultExport {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    // Scripts can't export anything
+    let Program::Module(module) = program else {
+      return;
+    };
+    let exports = default_exported_bindings(module, context);
+    if exports.is_empty() {
+      return;
+    }
+    NoUselessAssignmentToOverwrittenDefaultExportHandler { exports }
+      .traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_to_overwritten_default_export.md"
+    )
+  }
+}
+
+/// The bindings exported with `export default name;`, and where each export
+/// ends. Unlike `export default function name() {}` and
+/// `export { name as default }`, which export the binding, this evaluates
+/// `name` once and exports its value.
+fn default_exported_bindings(
+  module: &ast_view::Module,
+  ctx: &Context,
+) -> HashMap<Id, SourcePos> {
+  let mut exports = HashMap::new();
+  for item in &module.body {
+    let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) = item
+    else {
+      continue;
+    };
+    let mut expr = export.expr;
+    while let Expr::Paren(paren) = expr {
+      expr = paren.expr;
+    }
+    let Expr::Ident(ident) = expr else {
+      continue;
+    };
+    let id = ident.inner.to_id();
+    if ctx.scope().var(&id).is_some() {
+      exports.insert(id, export.end());
+    }
+  }
+  exports
+}
+
+struct NoUselessAssignmentToOverwrittenDefaultExportHandler {
+  exports: HashMap<Id, SourcePos>,
+}
+
+impl NoUselessAssignmentToOverwrittenDefaultExportHandler {
+  /// Reports the assignment if one of its targets is a binding that was
+  /// exported as default before it.
+  fn check_targets(
+    &self,
+    targets: &[Ident],
+    range: SourceRange,
+    ctx: &mut Context,
+  ) {
+    let exported = targets.iter().find(|target| {
+      self
+        .exports
+        .get(&target.to_id())
+        .map_or(false, |export_end| target.start() >= *export_end)
+    });
+    let Some(exported) = exported else {
+      return;
+    };
+    ctx.add_diagnostic_with_hint(
+      range,
+      CODE,
+      NoUselessAssignmentToOverwrittenDefaultExportMessage::Reassigned(
+        exported.sym.to_string(),
+      ),
+      HINT,
+    );
+  }
+}
+
+impl Handler for NoUselessAssignmentToOverwrittenDefaultExportHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    let targets: Vec<Ident> = find_lhs_ids(&assign_expr.inner.left);
+    self.check_targets(&targets, assign_expr.range(), ctx);
+  }
+
+  fn update_expr(
+    &mut self,
+    update_expr: &ast_view::UpdateExpr,
+    ctx: &mut Context,
+  ) {
+    if let Expr::Ident(ident) = update_expr.arg {
+      self.check_targets(
+        std::slice::from_ref(ident.inner),
+        update_expr.range(),
+        ctx,
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_overwritten_default_export_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToOverwrittenDefaultExport,
+      "let config = {}; export default config;",
+      "let config = {}; config = load(); export default config;",
+      "let config = {}; export default config; config.port = 80;",
+      "let config = {}; export default config; const other = config;",
+      "let config = {}; export default config; function f(config) { config = 1; }",
+      "let config = {}; export default load(config); config = 1;",
+
+      // These export the binding, so reassigning it updates the export
+      "export default function handler() {} handler = () => {};",
+      "export default class Handler {} Handler = class {};",
+      "let config = {}; export { config as default }; config = {};",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_overwritten_default_export_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToOverwrittenDefaultExport,
+      "let config = {}; export default config; config = { port: 80 };": [
+        {
+          col: 40,
+          message: variant!(NoUselessAssignmentToOverwrittenDefaultExportMessage, Reassigned, "config"),
+          hint: HINT,
+        }
+      ],
+      "let count = 0; export default (count); export function inc() { count++; }": [
+        {
+          col: 63,
+          message: variant!(NoUselessAssignmentToOverwrittenDefaultExportMessage, Reassigned, "count"),
+          hint: HINT,
+        }
+      ],
+      "var a, b; export default b; [a, { b }] = load();": [
+        {
+          col: 28,
+          message: variant!(NoUselessAssignmentToOverwrittenDefaultExportMessage, Reassigned, "b"),
+          hint: HINT,
+        }
+      ],
+      "function handler() {} export default handler; handler = () => {};": [
+        {
+          col: 46,
+          message: variant!(NoUselessAssignmentToOverwrittenDefaultExportMessage, Reassigned, "handler"),
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
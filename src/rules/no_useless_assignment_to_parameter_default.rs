@@ -0,0 +1,289 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{self as ast_view, Expr, NodeTrait, ObjectPatProp, Pat};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToParameterDefault;
+
+const CODE: &str = "no-useless-assignment-to-parameter-default";
+
+#[derive(Display)]
+enum NoUselessAssignmentToParameterDefaultMessage {
+  #[display(
+    fmt = "The default value references `{}`, which isn't initialized yet",
+    _0
+  )]
+  Uninitialized(String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentToParameterDefaultHint {
+  #[display(
+    fmt = "Move the parameter `{}` before this one, or don't use it in the default value",
+    _0
+  )]
+  Reorder(String),
+}
+
+impl LintRule for NoUselessAssignmentToParameterDefault {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentToParameterDefaultHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!(
+      "../../docs/rules/no_useless_assignment_to_parameter_default.md"
+    )
+  }
+}
+
+struct NoUselessAssignmentToParameterDefaultHandler;
+
+impl Handler for NoUselessAssignmentToParameterDefaultHandler {
+  fn function(&mut self, function: &ast_view::Function, ctx: &mut Context) {
+    let params = function.params.iter().map(|param| param.pat).collect();
+    check_params(params, ctx);
+  }
+
+  fn constructor(
+    &mut self,
+    constructor: &ast_view::Constructor,
+    ctx: &mut Context,
+  ) {
+    let params = constructor
+      .params
+      .iter()
+      .map(|param| match param {
+        ast_view::ParamOrTsParamProp::TsParamProp(ts_param_prop) => {
+          match ts_param_prop.param {
+            ast_view::TsParamPropParam::Ident(ident) => Pat::Ident(ident),
+            ast_view::TsParamPropParam::Assign(assign) => Pat::Assign(assign),
+          }
+        }
+        ast_view::ParamOrTsParamProp::Param(param) => param.pat,
+      })
+      .collect();
+    check_params(params, ctx);
+  }
+
+  fn arrow_expr(
+    &mut self,
+    arrow_expr: &ast_view::ArrowExpr,
+    ctx: &mut Context,
+  ) {
+    check_params(arrow_expr.params.clone(), ctx);
+  }
+}
+
+/// Collects the identifiers that a parameter binds.
+fn collect_bindings(pat: &Pat, ids: &mut Vec<Id>) {
+  match pat {
+    Pat::Ident(binding) => ids.push(binding.id.inner.to_id()),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        collect_bindings(elem, ids);
+      }
+    }
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            collect_bindings(&key_value.value, ids)
+          }
+          ObjectPatProp::Assign(assign) => ids.push(assign.key.inner.to_id()),
+          ObjectPatProp::Rest(rest) => collect_bindings(&rest.arg, ids),
+        }
+      }
+    }
+    Pat::Assign(assign) => collect_bindings(&assign.left, ids),
+    Pat::Rest(rest) => collect_bindings(&rest.arg, ids),
+    Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+/// Finds the first identifier in the node that refers to one of `ids`, not
+/// counting nested functions and classes, which can only run once the
+/// parameters are initialized.
+fn find_reference<'a>(
+  node: ast_view::Node<'a>,
+  ids: &[Id],
+  ctx: &Context,
+) -> Option<&'a ast_view::Ident<'a>> {
+  use deno_ast::view::Node;
+  match node {
+    Node::Ident(ident) => {
+      let id = ident.inner.to_id();
+      // References to a rest parameter in earlier defaults aren't resolved
+      // to it, so they look like globals
+      let refers_to_param = ids.contains(&id)
+        || (ident.ctxt() == ctx.unresolved_ctxt()
+          && ids.iter().any(|param_id| param_id.0 == id.0));
+      refers_to_param.then_some(ident)
+    }
+    Node::Function(_) | Node::ArrowExpr(_) | Node::Class(_) => None,
+    _ => node
+      .children()
+      .into_iter()
+      .find_map(|child| find_reference(child, ids, ctx)),
+  }
+}
+
+fn check_default(default: &Expr, ids: &[Id], ctx: &mut Context) {
+  if let Some(ident) = find_reference(default.as_node(), ids, ctx) {
+    let name = ident.sym().to_string();
+    ctx.add_diagnostic_with_hint(
+      default.range(),
+      CODE,
+      NoUselessAssignmentToParameterDefaultMessage::Uninitialized(name.clone()),
+      NoUselessAssignmentToParameterDefaultHint::Reorder(name),
+    );
+  }
+}
+
+/// Checks the default values nested in a destructuring parameter.
+fn check_nested_defaults(pat: &Pat, ids: &[Id], ctx: &mut Context) {
+  match pat {
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        check_nested_defaults(elem, ids, ctx);
+      }
+    }
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(key_value) => {
+            check_nested_defaults(&key_value.value, ids, ctx)
+          }
+          ObjectPatProp::Assign(assign) => {
+            if let Some(value) = &assign.value {
+              check_default(value, ids, ctx);
+            }
+          }
+          ObjectPatProp::Rest(rest) => {
+            check_nested_defaults(&rest.arg, ids, ctx)
+          }
+        }
+      }
+    }
+    Pat::Assign(assign) => {
+      check_default(&assign.right, ids, ctx);
+      check_nested_defaults(&assign.left, ids, ctx);
+    }
+    Pat::Rest(rest) => check_nested_defaults(&rest.arg, ids, ctx),
+    Pat::Ident(_) | Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+fn check_params(params: Vec<Pat>, ctx: &mut Context) {
+  for (i, param) in params.iter().enumerate() {
+    // Parameters are initialized from left to right
+    let mut later_ids = Vec::new();
+    for later in &params[i + 1..] {
+      collect_bindings(later, &mut later_ids);
+    }
+    match param {
+      // The parameter itself isn't initialized while its default is
+      // evaluated, e.g. `a = a`
+      Pat::Assign(assign) => {
+        let mut ids = later_ids.clone();
+        collect_bindings(&assign.left, &mut ids);
+        check_default(&assign.right, &ids, ctx);
+        check_nested_defaults(&assign.left, &later_ids, ctx);
+      }
+      _ => check_nested_defaults(param, &later_ids, ctx),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_parameter_default_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToParameterDefault,
+      "function f(a, b = a) {}",
+      "function f(a = 1, b = a + 1) {}",
+      "function f({ a, b = a }) {}",
+      "function f(a = () => b, b) {}",
+      "function f(a = function () { return b; }, b) {}",
+      "function f(a = class { x = b; }, b) {}",
+      "function f(a = b) {} const b = 1;",
+      "function f(a = x.b, b) {}",
+      "function f(a = { b: 1 }, b) {}",
+      "const f = (a, b = a) => {};",
+      "class C { constructor(a, b = a) {} }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_parameter_default_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToParameterDefault,
+      "function f(a = b, b) {}": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+      "function f(a = b + 1, b = 2) {}": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+      "function f(a = a) {}": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "a"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "a"),
+        }
+      ],
+      "function f({ x = b }, { b }) {}": [
+        {
+          col: 17,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+      "function f(a = [b], ...b) {}": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+      "const f = (a = { b }, b) => {};": [
+        {
+          col: 15,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+      "class C { constructor(private a = b, b) {} }": [
+        {
+          col: 34,
+          message: variant!(NoUselessAssignmentToParameterDefaultMessage, Uninitialized, "b"),
+          hint: variant!(NoUselessAssignmentToParameterDefaultHint, Reorder, "b"),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,232 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::no_useless_assignment_via_increment::is_same_target;
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, AssignOp, Expr, Pat, PatOrExpr, Stmt};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToSameValue;
+
+const CODE: &str = "no-useless-assignment-to-same-value";
+
+#[derive(Display)]
+enum NoUselessAssignmentToSameValueMessage {
+  #[display(
+    fmt = "`{}` is assigned its own value when the condition is {}",
+    _0,
+    _1
+  )]
+  Conditional(String, bool),
+  #[display(fmt = "`{}` is assigned its own value in this branch", _0)]
+  Branch(String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentToSameValueHint {
+  #[display(
+    fmt = "Only assign `{}` when the condition is {}, e.g. with an `if` statement",
+    _0,
+    _1
+  )]
+  Conditional(String, bool),
+  #[display(fmt = "Remove the assignment, or assign the intended value")]
+  Branch,
+}
+
+impl LintRule for NoUselessAssignmentToSameValue {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentToSameValueHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_same_value.md")
+  }
+}
+
+struct NoUselessAssignmentToSameValueHandler;
+
+/// The name of the variable that the assignment sets with `=`. Properties
+/// aren't checked, since assigning a property its own value calls its getter
+/// and setter, which may do anything.
+fn assigned_variable<'a>(
+  assign_expr: &ast_view::AssignExpr<'a>,
+) -> Option<&'a ast_view::Ident<'a>> {
+  if assign_expr.op() != AssignOp::Assign {
+    return None;
+  }
+  match assign_expr.left {
+    PatOrExpr::Pat(Pat::Ident(binding)) => Some(binding.id),
+    _ => None,
+  }
+}
+
+fn unwrap_parens(expr: Expr) -> Expr {
+  match expr {
+    Expr::Paren(paren) => unwrap_parens(paren.expr),
+    _ => expr,
+  }
+}
+
+/// Whether the value is the target itself, possibly in parentheses.
+fn is_target(target: &PatOrExpr, expr: &Expr, ctx: &Context) -> bool {
+  is_same_target(target, &unwrap_parens(*expr), ctx)
+}
+
+impl NoUselessAssignmentToSameValueHandler {
+  /// Checks the assignments that make up a branch of an `if` statement.
+  fn check_branch(&self, branch: &Stmt, ctx: &mut Context) {
+    let stmts = match branch {
+      Stmt::Block(block_stmt) => &block_stmt.stmts[..],
+      _ => std::slice::from_ref(branch),
+    };
+    for stmt in stmts {
+      let Stmt::Expr(expr_stmt) = stmt else {
+        continue;
+      };
+      let Expr::Assign(assign_expr) = expr_stmt.expr else {
+        continue;
+      };
+      let Some(target) = assigned_variable(assign_expr) else {
+        continue;
+      };
+      if is_target(&assign_expr.left, &assign_expr.right, ctx) {
+        ctx.add_diagnostic_with_hint(
+          assign_expr.range(),
+          CODE,
+          NoUselessAssignmentToSameValueMessage::Branch(
+            target.sym().to_string(),
+          ),
+          NoUselessAssignmentToSameValueHint::Branch,
+        );
+      }
+    }
+  }
+}
+
+impl Handler for NoUselessAssignmentToSameValueHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    let Some(target) = assigned_variable(assign_expr) else {
+      return;
+    };
+    let Expr::Cond(cond_expr) = unwrap_parens(assign_expr.right) else {
+      return;
+    };
+    // A value that is the target in both cases is a plain self assignment,
+    // and nested conditionals only sometimes assign the target
+    let is_target_if_true = is_target(&assign_expr.left, &cond_expr.cons, ctx);
+    let is_target_if_false = is_target(&assign_expr.left, &cond_expr.alt, ctx);
+    if is_target_if_true == is_target_if_false {
+      return;
+    }
+
+    let name = target.sym().to_string();
+    ctx.add_diagnostic_with_hint(
+      assign_expr.range(),
+      CODE,
+      NoUselessAssignmentToSameValueMessage::Conditional(
+        name.clone(),
+        is_target_if_true,
+      ),
+      NoUselessAssignmentToSameValueHint::Conditional(name, is_target_if_false),
+    );
+  }
+
+  fn if_stmt(&mut self, if_stmt: &ast_view::IfStmt, ctx: &mut Context) {
+    self.check_branch(&if_stmt.cons, ctx);
+    if let Some(alt) = &if_stmt.alt {
+      self.check_branch(alt, ctx);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_same_value_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToSameValue,
+      "x = cond ? y : z;",
+      "x = cond ? x.a : y;",
+      "x = x || y;",
+      "x += cond ? x : y;",
+      "if (cond) { x = y; }",
+      "if (cond) x = x + 1;",
+      "if (cond) { x = y; } else { y = x; }",
+      "let x = cond ? x : y;",
+
+      // Both cases are the target, which is reported by `no-self-assign`
+      "x = cond ? x : x;",
+
+      // Only some of the nested cases are the target
+      "x = a ? y : b ? x : z;",
+
+      // Nested, so it doesn't make up the branch
+      "if (cond) { if (other) {} else { foo(x = y); } }",
+
+      // Properties may have getters and setters
+      "obj.a = cond ? obj.a : b;",
+      "if (cond) { this.a = this.a; }",
+      "[x] = cond ? [x] : y;",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_same_value_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToSameValue,
+      "x = cond ? x : y;": [
+        {
+          col: 0,
+          message: NoUselessAssignmentToSameValueMessage::Conditional("x".to_string(), true),
+          hint: NoUselessAssignmentToSameValueHint::Conditional("x".to_string(), false),
+        }
+      ],
+      "x = (cond ? y : (x));": [
+        {
+          col: 0,
+          message: NoUselessAssignmentToSameValueMessage::Conditional("x".to_string(), false),
+          hint: NoUselessAssignmentToSameValueHint::Conditional("x".to_string(), true),
+        }
+      ],
+      "function f(x) { foo(x = a ? x : b ? y : z); }": [
+        {
+          col: 20,
+          message: NoUselessAssignmentToSameValueMessage::Conditional("x".to_string(), true),
+          hint: NoUselessAssignmentToSameValueHint::Conditional("x".to_string(), false),
+        }
+      ],
+      "if (cond) x = x;": [
+        {
+          col: 10,
+          message: variant!(NoUselessAssignmentToSameValueMessage, Branch, "x"),
+          hint: variant!(NoUselessAssignmentToSameValueHint, Branch),
+        }
+      ],
+      "if (cond) { foo(); } else { y = 1; x = (x); }": [
+        {
+          col: 35,
+          message: variant!(NoUselessAssignmentToSameValueMessage, Branch, "x"),
+          hint: variant!(NoUselessAssignmentToSameValueHint, Branch),
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,400 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, AssignOp, Decl, Expr, ModuleDecl, ModuleItem, Pat,
+  PatOrExpr, Stmt, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToTempInSwap;
+
+const CODE: &str = "no-useless-assignment-to-temp-in-swap";
+const FIX_DESC: &str = "Swap the variables with destructuring";
+
+#[derive(Display)]
+enum NoUselessAssignmentToTempInSwapMessage {
+  #[display(fmt = "`{}` is only used to swap `{}` and `{}`", _0, _1, _2)]
+  TempSwap(String, String, String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentToTempInSwapHint {
+  #[display(
+    fmt = "Swap them with `[{}, {}] = [{}, {}];` instead",
+    _0,
+    _1,
+    _1,
+    _0
+  )]
+  Destructure(String, String),
+}
+
+impl LintRule for NoUselessAssignmentToTempInSwap {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let mut handler = NoUselessAssignmentToTempInSwapHandler::default();
+    handler.traverse(program, context);
+    handler.report(context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_temp_in_swap.md")
+  }
+}
+
+/// Three statements that swap two variables through a temporary one, e.g.
+/// `const t = a; a = b; b = t;`.
+struct Candidate {
+  temp: Id,
+  first: String,
+  second: String,
+  range: SourceRange,
+  /// Whether the statement before the swap may end in an expression without
+  /// a semicolon, so that a statement starting with `[` would continue it.
+  needs_semicolon: bool,
+}
+
+#[derive(Default)]
+struct NoUselessAssignmentToTempInSwapHandler {
+  /// How often each identifier occurs, including its declaration.
+  occurrences: HashMap<Id, usize>,
+  candidates: Vec<Candidate>,
+}
+
+/// Returns the target and value of an `a = b;` statement where both sides
+/// are identifiers.
+fn ident_assignment<'a>(
+  stmt: &Stmt<'a>,
+) -> Option<(&'a ast_view::Ident<'a>, &'a ast_view::Ident<'a>)> {
+  let Stmt::Expr(expr_stmt) = stmt else {
+    return None;
+  };
+  let Expr::Assign(assign_expr) = expr_stmt.expr else {
+    return None;
+  };
+  let PatOrExpr::Pat(Pat::Ident(target)) = assign_expr.left else {
+    return None;
+  };
+  let Expr::Ident(value) = assign_expr.right else {
+    return None;
+  };
+  (assign_expr.op() == AssignOp::Assign).then_some((target.id, value))
+}
+
+/// Returns the variable and its initializer of a `const t = a;` statement.
+fn ident_declaration<'a>(
+  stmt: &Stmt<'a>,
+) -> Option<(&'a ast_view::Ident<'a>, &'a ast_view::Ident<'a>)> {
+  let Stmt::Decl(Decl::Var(var_decl)) = stmt else {
+    return None;
+  };
+  if var_decl.decl_kind() == VarDeclKind::Var || var_decl.declare() {
+    return None;
+  }
+  let [declarator] = var_decl.decls[..] else {
+    return None;
+  };
+  let Pat::Ident(binding) = declarator.name else {
+    return None;
+  };
+  let Some(Expr::Ident(init)) = declarator.init else {
+    return None;
+  };
+  Some((binding.id, init))
+}
+
+/// Whether the statement may end in an expression, which a following
+/// statement starting with `[` would continue if there's no semicolon.
+fn ends_with_expr(stmt: &Stmt) -> bool {
+  match stmt {
+    Stmt::Expr(_)
+    | Stmt::Return(_)
+    | Stmt::Throw(_)
+    | Stmt::Decl(Decl::Var(_) | Decl::TsTypeAlias(_)) => true,
+    Stmt::If(if_stmt) => ends_with_expr(&if_stmt.alt.unwrap_or(if_stmt.cons)),
+    Stmt::For(for_stmt) => ends_with_expr(&for_stmt.body),
+    Stmt::ForIn(for_in_stmt) => ends_with_expr(&for_in_stmt.body),
+    Stmt::ForOf(for_of_stmt) => ends_with_expr(&for_of_stmt.body),
+    Stmt::While(while_stmt) => ends_with_expr(&while_stmt.body),
+    Stmt::Labeled(labeled_stmt) => ends_with_expr(&labeled_stmt.body),
+    Stmt::With(with_stmt) => ends_with_expr(&with_stmt.body),
+    _ => false,
+  }
+}
+
+fn module_decl_ends_with_expr(module_decl: &ModuleDecl) -> bool {
+  match module_decl {
+    ModuleDecl::ExportDecl(export_decl) => {
+      matches!(export_decl.decl, Decl::Var(_) | Decl::TsTypeAlias(_))
+    }
+    ModuleDecl::ExportDefaultExpr(_) | ModuleDecl::TsExportAssignment(_) => {
+      true
+    }
+    _ => false,
+  }
+}
+
+fn needs_semicolon_after(
+  range: SourceRange,
+  ends_with_expr: bool,
+  ctx: &Context,
+) -> bool {
+  ends_with_expr && !ctx.text_of(range).ends_with(';')
+}
+
+impl NoUselessAssignmentToTempInSwapHandler {
+  /// Looks for swaps in a list of statements. `needs_semicolon` tells if the
+  /// statement before the list may continue into the first one.
+  fn check_stmts(
+    &mut self,
+    stmts: &[Stmt],
+    needs_semicolon: bool,
+    ctx: &Context,
+  ) {
+    for (i, window) in stmts.windows(3).enumerate() {
+      let Some((temp, first)) = ident_declaration(&window[0]) else {
+        continue;
+      };
+      let Some((first_target, second)) = ident_assignment(&window[1]) else {
+        continue;
+      };
+      let Some((second_target, temp_value)) = ident_assignment(&window[2])
+      else {
+        continue;
+      };
+      let temp_id = temp.inner.to_id();
+      let first_id = first.inner.to_id();
+      let second_id = second.inner.to_id();
+      if first_target.inner.to_id() != first_id
+        || second_target.inner.to_id() != second_id
+        || temp_value.inner.to_id() != temp_id
+        || first_id == second_id
+        || temp_id == first_id
+        || temp_id == second_id
+      {
+        continue;
+      }
+
+      let needs_semicolon = match i.checked_sub(1) {
+        Some(prev) => needs_semicolon_after(
+          stmts[prev].range(),
+          ends_with_expr(&stmts[prev]),
+          ctx,
+        ),
+        None => needs_semicolon,
+      };
+      self.candidates.push(Candidate {
+        temp: temp_id,
+        first: first.sym().to_string(),
+        second: second.sym().to_string(),
+        range: SourceRange::new(window[0].start(), window[2].end()),
+        needs_semicolon,
+      });
+    }
+  }
+
+  fn report(self, ctx: &mut Context) {
+    for candidate in self.candidates {
+      // The temporary variable must only be declared and read by the swap
+      if self.occurrences.get(&candidate.temp) != Some(&2) {
+        continue;
+      }
+
+      let has_comments = ctx
+        .all_comments()
+        .any(|comment| candidate.range.contains(&comment.range()));
+      let fixes = if has_comments {
+        vec![]
+      } else {
+        vec![LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![LintFixChange {
+            new_text: format!(
+              "{}[{}, {}] = [{}, {}];",
+              if candidate.needs_semicolon { ";" } else { "" },
+              candidate.first,
+              candidate.second,
+              candidate.second,
+              candidate.first
+            )
+            .into(),
+            range: candidate.range,
+          }],
+        }]
+      };
+
+      ctx.add_diagnostic_with_fixes(
+        candidate.range,
+        CODE,
+        NoUselessAssignmentToTempInSwapMessage::TempSwap(
+          candidate.temp.0.to_string(),
+          candidate.first.clone(),
+          candidate.second.clone(),
+        ),
+        Some(
+          NoUselessAssignmentToTempInSwapHint::Destructure(
+            candidate.first,
+            candidate.second,
+          )
+          .to_string(),
+        ),
+        fixes,
+      );
+    }
+  }
+}
+
+impl Handler for NoUselessAssignmentToTempInSwapHandler {
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    *self.occurrences.entry(ident.inner.to_id()).or_default() += 1;
+  }
+
+  fn module(&mut self, module: &ast_view::Module, ctx: &mut Context) {
+    let mut needs_semicolon = false;
+    let mut stmts = Vec::new();
+    for item in module.body.iter() {
+      match item {
+        ModuleItem::Stmt(stmt) => stmts.push(*stmt),
+        ModuleItem::ModuleDecl(module_decl) => {
+          self.check_stmts(&stmts, needs_semicolon, ctx);
+          stmts.clear();
+          needs_semicolon = needs_semicolon_after(
+            module_decl.range(),
+            module_decl_ends_with_expr(module_decl),
+            ctx,
+          );
+        }
+      }
+    }
+    self.check_stmts(&stmts, needs_semicolon, ctx);
+  }
+
+  fn script(&mut self, script: &ast_view::Script, ctx: &mut Context) {
+    self.check_stmts(&script.body, false, ctx);
+  }
+
+  fn block_stmt(
+    &mut self,
+    block_stmt: &ast_view::BlockStmt,
+    ctx: &mut Context,
+  ) {
+    self.check_stmts(&block_stmt.stmts, false, ctx);
+  }
+
+  fn switch_case(
+    &mut self,
+    switch_case: &ast_view::SwitchCase,
+    ctx: &mut Context,
+  ) {
+    self.check_stmts(&switch_case.cons, false, ctx);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_temp_in_swap_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToTempInSwap,
+      "let a = 1, b = 2; [a, b] = [b, a];",
+      "function f(a, b) { const t = a; a = b; b = t; return t; }",
+      "function f(a, b) { const t = a; a = b; b = t; use(a, b); log(t); }",
+      "function f(a, b, c) { const t = a; a = b; c = t; }",
+      "function f(a, b) { const t = a; b = a; a = t; }",
+      "function f(a, b) { const t = a; a = a; a = t; }",
+      "function f(a, b) { const t = a; a += b; b = t; }",
+      "function f(a, b) { var t = a; a = b; b = t; }",
+      "function f(a, b) { const t = a; foo(); a = b; b = t; }",
+      "function f(a, b) { const t = a, u = b; a = b; b = t; }",
+      "function f(o) { const t = o.a; o.a = o.b; o.b = t; }",
+      "function f(a, b) { const g = () => t; const t = a; a = b; b = t; }",
+      "export function f(a, b) { let t = a; a = b; b = t; t = 1; }",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_temp_in_swap_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToTempInSwap,
+      "function f(a, b) { const t = a; a = b; b = t; return [a, b]; }": [
+        {
+          col: 19,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "t", "a", "b"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "a", "b"),
+          fix: (FIX_DESC, "function f(a, b) { [a, b] = [b, a]; return [a, b]; }"),
+        }
+      ],
+      "let x = 1, y = 2;\nlet tmp: number = y;\ny = x;\nx = tmp;\n": [
+        {
+          line: 2,
+          col: 0,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "tmp", "y", "x"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "y", "x"),
+          fix: (FIX_DESC, "let x = 1, y = 2;\n[y, x] = [x, y];\n"),
+        }
+      ],
+      "for (let i = 0; i < n; i++) { switch (k) { case 1: const t = a; a = b; b = t; } }": [
+        {
+          col: 51,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "t", "a", "b"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "a", "b"),
+          fix: (FIX_DESC, "for (let i = 0; i < n; i++) { switch (k) { case 1: [a, b] = [b, a]; } }"),
+        }
+      ],
+      // The previous statement would continue into `[`
+      "function f(a, b) {\n  g()\n  const t = a\n  a = b\n  b = t\n}": [
+        {
+          line: 3,
+          col: 2,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "t", "a", "b"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "a", "b"),
+          fix: (FIX_DESC, "function f(a, b) {\n  g()\n  ;[a, b] = [b, a];\n}"),
+        }
+      ],
+      "export const o = {}\nconst t = a\na = b\nb = t\n": [
+        {
+          line: 2,
+          col: 0,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "t", "a", "b"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "a", "b"),
+          fix: (FIX_DESC, "export const o = {}\n;[a, b] = [b, a];\n"),
+        }
+      ],
+      "function f(a, b) {\n  if (a > b) {}\n  const t = a\n  a = b\n  b = t\n}": [
+        {
+          line: 3,
+          col: 2,
+          message: variant!(NoUselessAssignmentToTempInSwapMessage, TempSwap, "t", "a", "b"),
+          hint: variant!(NoUselessAssignmentToTempInSwapHint, Destructure, "a", "b"),
+          fix: (FIX_DESC, "function f(a, b) {\n  if (a > b) {}\n  [a, b] = [b, a];\n}"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_temp_in_swap_invalid_without_fix() {
+    let src = "function f(a, b) { const t = a; /* swap */ a = b; b = t; }";
+    let diagnostics =
+      crate::test_util::lint(&NoUselessAssignmentToTempInSwap, src, "foo.ts");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes.is_empty());
+  }
+}
@@ -0,0 +1,180 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, NodeTrait, Pat, PatOrExpr};
+use deno_ast::SourceRanged;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentToThisInArrow;
+
+const CODE: &str = "no-useless-assignment-to-this-in-arrow";
+const MESSAGE: &str =
+  "`this` in this arrow function doesn't refer to an object, so the assignment is lost";
+const HINT: &str = "Arrow functions take `this` from where they're defined, which is the top level here. Use a method or a `function` instead";
+
+impl LintRule for NoUselessAssignmentToThisInArrow {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    // At the top level of a script, `this` is the global object
+    if matches!(program, Program::Script(_)) {
+      return;
+    }
+    NoUselessAssignmentToThisInArrowHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_to_this_in_arrow.md")
+  }
+}
+
+struct NoUselessAssignmentToThisInArrowHandler;
+
+/// Checks if the expression is a property of `this`, e.g. `this.foo.bar`.
+fn is_this_property(expr: &Expr) -> bool {
+  match expr {
+    Expr::Member(member_expr) => match member_expr.obj {
+      Expr::This(_) => true,
+      obj => is_this_property(&obj),
+    },
+    _ => false,
+  }
+}
+
+/// Checks if the node is in an arrow function that takes `this` from the top
+/// level, through any number of nested arrow functions.
+fn is_in_top_level_arrow(node: ast_view::Node) -> bool {
+  use deno_ast::view::Node;
+  let mut in_arrow = false;
+  for ancestor in node.ancestors() {
+    match ancestor {
+      Node::ArrowExpr(_) => in_arrow = true,
+      // These bind their own `this`
+      Node::Function(_)
+      | Node::Constructor(_)
+      | Node::GetterProp(_)
+      | Node::SetterProp(_)
+      | Node::ClassProp(_)
+      | Node::PrivateProp(_)
+      | Node::AutoAccessor(_)
+      | Node::StaticBlock(_) => return false,
+      Node::Module(_) | Node::Script(_) => return in_arrow,
+      _ => {}
+    }
+  }
+  false
+}
+
+impl Handler for NoUselessAssignmentToThisInArrowHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    let is_this_target = match &assign_expr.left {
+      PatOrExpr::Expr(expr) | PatOrExpr::Pat(Pat::Expr(expr)) => {
+        is_this_property(expr)
+      }
+      PatOrExpr::Pat(_) => false,
+    };
+    if is_this_target && is_in_top_level_arrow(assign_expr.as_node()) {
+      ctx.add_diagnostic_with_hint(assign_expr.range(), CODE, MESSAGE, HINT);
+    }
+  }
+
+  fn update_expr(
+    &mut self,
+    update_expr: &ast_view::UpdateExpr,
+    ctx: &mut Context,
+  ) {
+    if is_this_property(&update_expr.arg)
+      && is_in_top_level_arrow(update_expr.as_node())
+    {
+      ctx.add_diagnostic_with_hint(update_expr.range(), CODE, MESSAGE, HINT);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_to_this_in_arrow_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentToThisInArrow,
+      "const obj = { count: 0, inc() { this.count++; } }; export {};",
+      "class Foo { bar = () => { this.x = 1; }; } export {};",
+      "class Foo { #bar = () => { this.x = 1; }; } export {};",
+      "class A { accessor p = () => { this.x = 1 } } export {};",
+      "class Foo { static { const f = () => { this.x = 1; }; } } export {};",
+      "class Foo { constructor() { setTimeout(() => { this.x = 1; }); } } export {};",
+      "class Foo { get x() { return () => { this.y = 1; }; } } export {};",
+      "function Foo() { const f = () => { this.x = 1; }; } export {};",
+      "const f = function () { return () => { this.x = 1; }; }; export {};",
+      "const f = () => { const x = this.x; }; export {};",
+      "const f = () => { obj.x = 1; }; export {};",
+      "const f = () => { x = this; }; export {};",
+      "this.x = 1; export {};",
+
+      // At the top level of a script, `this` is the global object
+      "const f = () => { this.x = 1; };",
+      "const obj = { inc: () => this.count++ };",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_to_this_in_arrow_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentToThisInArrow,
+      "const f = () => { this.x = 1; }; export {};": [
+        {
+          col: 18,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      "const obj = { count: 0, inc: () => { this.count += 1; } }; export {};": [
+        {
+          col: 37,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      "const obj = { inc: () => this.count++ }; export {};": [
+        {
+          col: 25,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      "const f = () => () => { this.a.b = 1; this['c'] = 2; }; export {};": [
+        {
+          col: 24,
+          message: MESSAGE,
+          hint: HINT,
+        },
+        {
+          col: 38,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      "class Foo { [(() => { this.x = 1; })()]() {} } export {};": [
+        {
+          col: 22,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
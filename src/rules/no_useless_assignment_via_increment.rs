@@ -0,0 +1,366 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, AssignOp, BinaryOp, Expr, Lit, MemberProp, Pat, PatOrExpr,
+};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessAssignmentViaIncrement;
+
+const CODE: &str = "no-useless-assignment-via-increment";
+
+#[derive(Display)]
+enum NoUselessAssignmentViaIncrementMessage {
+  #[display(
+    fmt = "`{0} = {0} {1} ...` can be written as `{0} {1}= ...`",
+    _0,
+    _1
+  )]
+  Compound(String, String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentViaIncrementHint {
+  #[display(fmt = "Use `{}` instead", _0)]
+  Compound(String),
+  #[display(
+    fmt = "Use `{}` instead, or `{}` if the value of the assignment isn't used",
+    _0,
+    _1
+  )]
+  Update(String, String),
+}
+
+#[derive(Display)]
+enum NoUselessAssignmentViaIncrementFix {
+  #[display(fmt = "Use `{}=`", _0)]
+  Compound(String),
+}
+
+impl LintRule for NoUselessAssignmentViaIncrement {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessAssignmentViaIncrementHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_assignment_via_increment.md")
+  }
+}
+
+struct NoUselessAssignmentViaIncrementHandler;
+
+/// Binary operators that have a compound assignment form.
+fn has_compound_form(op: BinaryOp) -> bool {
+  use BinaryOp::*;
+  matches!(
+    op,
+    Add
+      | Sub
+      | Mul
+      | Div
+      | Mod
+      | Exp
+      | LShift
+      | RShift
+      | ZeroFillRShift
+      | BitAnd
+      | BitOr
+      | BitXor
+  )
+}
+
+/// Whether `a op b` equals `b op a`. `+` only commutes for numbers, so it's
+/// handled separately.
+fn is_commutative(op: BinaryOp) -> bool {
+  use BinaryOp::*;
+  matches!(op, Mul | BitAnd | BitOr | BitXor)
+}
+
+/// Whether evaluating the member expression twice is the same as evaluating
+/// it once, i.e. it only consists of identifiers, `this` and literal keys.
+fn is_simple_member(member: &ast_view::MemberExpr) -> bool {
+  let obj_is_simple = match &member.obj {
+    Expr::Ident(_) | Expr::This(_) => true,
+    Expr::Member(obj) => is_simple_member(obj),
+    _ => false,
+  };
+  let prop_is_simple = match &member.prop {
+    MemberProp::Ident(_) | MemberProp::PrivateName(_) => true,
+    MemberProp::Computed(computed) => matches!(computed.expr, Expr::Lit(_)),
+  };
+  obj_is_simple && prop_is_simple
+}
+
+/// Whether `expr` refers to the same variable or property as the assignment
+/// target.
+pub(crate) fn is_same_target(
+  target: &PatOrExpr,
+  expr: &Expr,
+  ctx: &Context,
+) -> bool {
+  let target = match target {
+    PatOrExpr::Pat(Pat::Ident(binding)) => {
+      return matches!(expr, Expr::Ident(ident) if ident.inner.to_id() == binding.id.inner.to_id());
+    }
+    PatOrExpr::Pat(Pat::Expr(target)) | PatOrExpr::Expr(target) => target,
+    _ => return false,
+  };
+  match (target, expr) {
+    (Expr::Ident(target), Expr::Ident(ident)) => {
+      target.inner.to_id() == ident.inner.to_id()
+    }
+    (Expr::Member(target), Expr::Member(member)) => {
+      is_simple_member(target)
+        && target.text_fast(ctx.text_info())
+          == member.text_fast(ctx.text_info())
+    }
+    _ => false,
+  }
+}
+
+fn is_number_literal(expr: &Expr) -> bool {
+  matches!(expr, Expr::Lit(Lit::Num(_)))
+}
+
+impl Handler for NoUselessAssignmentViaIncrementHandler {
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    ctx: &mut Context,
+  ) {
+    if assign_expr.op() != AssignOp::Assign {
+      return;
+    }
+    let Expr::Bin(bin_expr) = assign_expr.right else {
+      return;
+    };
+    let op = bin_expr.op();
+    if !has_compound_form(op) {
+      return;
+    }
+
+    // `1 + x` is only the same as `x + 1` if `x` is a number, so that case is
+    // reported without a fix
+    let mut fixable = true;
+    let operand = if is_same_target(&assign_expr.left, &bin_expr.left, ctx) {
+      bin_expr.right
+    } else if is_same_target(&assign_expr.left, &bin_expr.right, ctx)
+      // `'a' + x` prepends to a string, so `+` needs a number literal
+      && (is_commutative(op)
+        || op == BinaryOp::Add && is_number_literal(&bin_expr.left))
+    {
+      fixable = is_commutative(op);
+      bin_expr.left
+    } else {
+      return;
+    };
+
+    let target = assign_expr.left.text_fast(ctx.text_info());
+    let operand_text = operand.text_fast(ctx.text_info());
+    let op = op.to_string();
+    let compound = format!("{} {}= {}", target, op, operand_text);
+    let hint = match operand {
+      Expr::Lit(Lit::Num(num))
+        if num.value() == 1.0 && (op == "+" || op == "-") =>
+      {
+        NoUselessAssignmentViaIncrementHint::Update(
+          compound.clone(),
+          format!("{}{}{}", target, op, op),
+        )
+        .to_string()
+      }
+      _ => NoUselessAssignmentViaIncrementHint::Compound(compound.clone())
+        .to_string(),
+    };
+    let message = NoUselessAssignmentViaIncrementMessage::Compound(
+      target.to_string(),
+      op.clone(),
+    );
+    let fixes = if fixable {
+      vec![LintFix {
+        description: NoUselessAssignmentViaIncrementFix::Compound(op)
+          .to_string()
+          .into(),
+        changes: vec![LintFixChange {
+          new_text: compound.into(),
+          range: assign_expr.range(),
+        }],
+      }]
+    } else {
+      vec![]
+    };
+    ctx.add_diagnostic_with_fixes(
+      assign_expr.range(),
+      CODE,
+      message,
+      Some(hint),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_assignment_via_increment_valid() {
+    assert_lint_ok! {
+      NoUselessAssignmentViaIncrement,
+      "x += 1;",
+      "x++;",
+      "x = y + 1;",
+      "x = 1 - x;",
+      "x = 2 / x;",
+      "x = y + x;",
+      "x = 'prefix' + x;",
+      "x = x && y;",
+      "x = x ?? y;",
+      "x = x === y;",
+      "x = x + y * z + w;",
+      "x = x * y + z;",
+      "obj.x = obj.y + 1;",
+      "obj.x = other.x + 1;",
+      "f().x = f().x + 1;",
+      "obj[key] = obj[key] + 1;",
+      "x.y = x + 1;",
+      "function f(x) { x = y; } x = x;",
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_via_increment_invalid() {
+    assert_lint_err! {
+      NoUselessAssignmentViaIncrement,
+      "x = x + 1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Update, "x += 1", "x++"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "+"), "x += 1;"),
+        }
+      ],
+      "x = x - 1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "-"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Update, "x -= 1", "x--"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "-"), "x -= 1;"),
+        }
+      ],
+      "x = x * 2;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "*"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x *= 2"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "*"), "x *= 2;"),
+        }
+      ],
+      "x = 2 * x;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "*"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x *= 2"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "*"), "x *= 2;"),
+        }
+      ],
+      "x = 1 + x;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Update, "x += 1", "x++"),
+        }
+      ],
+      "x = x / y;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "/"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x /= y"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "/"), "x /= y;"),
+        }
+      ],
+      "x = x - (y + z);": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "-"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x -= (y + z)"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "-"), "x -= (y + z);"),
+        }
+      ],
+      "x = x + y * z;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x += y * z"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "+"), "x += y * z;"),
+        }
+      ],
+      "x = y | x;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "|"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "x |= y"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "|"), "x |= y;"),
+        }
+      ],
+      "s = s + 'suffix';": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "s", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "s += 'suffix'"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "+"), "s += 'suffix';"),
+        }
+      ],
+      "this.count = this.count + 1;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "this.count", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Update, "this.count += 1", "this.count++"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "+"), "this.count += 1;"),
+        }
+      ],
+      "obj['a'].b = obj['a'].b ** 2;": [
+        {
+          col: 0,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "obj['a'].b", "**"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Compound, "obj['a'].b **= 2"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "**"), "obj['a'].b **= 2;"),
+        }
+      ],
+      "const y = (x = x + 1);": [
+        {
+          col: 11,
+          message: variant!(NoUselessAssignmentViaIncrementMessage, Compound, "x", "+"),
+          hint: variant!(NoUselessAssignmentViaIncrementHint, Update, "x += 1", "x++"),
+          fix: (variant!(NoUselessAssignmentViaIncrementFix, Compound, "+"), "const y = (x += 1);"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_assignment_via_increment_invalid_without_fix() {
+    // `x` may be a string, so `1 + x` isn't the same as `x + 1`
+    let diagnostics = crate::test_util::lint(
+      &NoUselessAssignmentViaIncrement,
+      "x = 1 + x;",
+      "foo.ts",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes.is_empty());
+  }
+}
@@ -0,0 +1,185 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::{to_jsx_attribute_value, to_jsx_text};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, JSXExpr};
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessComputedInJsx;
+
+const CODE: &str = "no-useless-computed-in-jsx";
+const HINT: &str =
+  "Replace the template literal and the curly braces with the plain text";
+const FIX_DESC: &str = "Replace with plain text";
+
+#[derive(Display)]
+enum NoUselessComputedInJsxMessage {
+  #[display(
+    fmt = "Template literals without expressions can be written as plain attribute values"
+  )]
+  Attribute,
+  #[display(
+    fmt = "Template literals without expressions can be written as plain text"
+  )]
+  Child,
+}
+
+impl LintRule for NoUselessComputedInJsx {
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsx"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessComputedInJsxHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_computed_in_jsx.md")
+  }
+}
+
+struct NoUselessComputedInJsxHandler;
+
+impl Handler for NoUselessComputedInJsxHandler {
+  fn jsx_expr_container(
+    &mut self,
+    container: &ast_view::JSXExprContainer,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let JSXExpr::Expr(Expr::Tpl(tpl)) = container.expr else {
+      return;
+    };
+    if !tpl.exprs.is_empty() {
+      return;
+    }
+    // Templates with invalid escape sequences have no cooked value
+    let Some(value) = tpl.quasis.first().and_then(|q| q.inner.cooked.as_ref())
+    else {
+      return;
+    };
+
+    let (message, new_text) = match container.parent() {
+      Node::JSXAttr(_) => (
+        NoUselessComputedInJsxMessage::Attribute,
+        to_jsx_attribute_value(value),
+      ),
+      Node::JSXElement(_) | Node::JSXFragment(_) => {
+        (NoUselessComputedInJsxMessage::Child, to_jsx_text(value))
+      }
+      _ => return,
+    };
+    // The template is needed if the text can't be written without it
+    let Some(new_text) = new_text else { return };
+
+    ctx.add_diagnostic_with_fixes(
+      container.range(),
+      CODE,
+      message,
+      Some(HINT.to_string()),
+      vec![LintFix {
+        description: FIX_DESC.into(),
+        changes: vec![LintFixChange {
+          new_text: new_text.into(),
+          range: container.range(),
+        }],
+      }],
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_computed_in_jsx_valid() {
+    assert_lint_ok! {
+      NoUselessComputedInJsx,
+      filename: "foo.tsx",
+      r#"<Foo bar="hello" />"#,
+      "<Foo bar={`hello ${name}`} />",
+      "<Foo bar={42} />",
+      "<Foo bar={tag`hello`} />",
+      "<div>{`${count} items`}</div>",
+      "<div>text</div>",
+
+      // Text that can't be written without the template literal
+      r#"<Foo bar={`it's "quoted"`} />"#,
+      "<Foo bar={`a &amp; b`} />",
+      "<Foo bar={`a\nb`} />",
+      "<div>{` `}</div>",
+      "<div>{`{`}</div>",
+      "<div>{`a < b`}</div>",
+    };
+  }
+
+  #[test]
+  fn no_useless_computed_in_jsx_invalid() {
+    assert_lint_err! {
+      NoUselessComputedInJsx,
+      filename: "foo.tsx",
+      "<Foo bar={`hello`} />": [
+        {
+          col: 9,
+          message: NoUselessComputedInJsxMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="hello" />"#),
+        }
+      ],
+      r#"<Foo bar={`say "hi"`} />"#: [
+        {
+          col: 9,
+          message: NoUselessComputedInJsxMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar='say "hi"' />"#),
+        }
+      ],
+      r#"<Foo bar={`a\`b`} />"#: [
+        {
+          col: 9,
+          message: NoUselessComputedInJsxMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="a`b" />"#),
+        }
+      ],
+      "<Foo bar={``} />": [
+        {
+          col: 9,
+          message: NoUselessComputedInJsxMessage::Attribute,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo bar="" />"#),
+        }
+      ],
+      "<div>{`text`}</div>": [
+        {
+          col: 5,
+          message: NoUselessComputedInJsxMessage::Child,
+          hint: HINT,
+          fix: (FIX_DESC, "<div>text</div>"),
+        }
+      ],
+      "<>{`it's fine`}</>": [
+        {
+          col: 2,
+          message: NoUselessComputedInJsxMessage::Child,
+          hint: HINT,
+          fix: (FIX_DESC, "<>it's fine</>"),
+        }
+      ],
+    };
+  }
+}
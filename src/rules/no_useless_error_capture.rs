@@ -0,0 +1,237 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, NodeTrait, Pat};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessErrorCapture;
+
+const CODE: &str = "no-useless-error-capture";
+
+/// Error constructors that accept a `cause` option.
+const ERROR_CONSTRUCTORS: &[&str] = &[
+  "Error",
+  "EvalError",
+  "RangeError",
+  "ReferenceError",
+  "SyntaxError",
+  "TypeError",
+  "URIError",
+];
+
+#[derive(Display)]
+enum NoUselessErrorCaptureMessage {
+  #[display(
+    fmt = "Wrapping the caught error in a new error loses its stack trace"
+  )]
+  Wrapped,
+  #[display(fmt = "The thrown error doesn't keep the caught error")]
+  Dropped,
+}
+
+#[derive(Display)]
+enum NoUselessErrorCaptureHint {
+  #[display(
+    fmt = "Pass the caught error as the cause, e.g. `new Error(\"message\", {{ cause: {} }})`",
+    _0
+  )]
+  Cause(String),
+}
+
+#[derive(Display)]
+enum NoUselessErrorCaptureFix {
+  #[display(fmt = "Add `{{ cause: {} }}`", _0)]
+  Cause(String),
+}
+
+impl LintRule for NoUselessErrorCapture {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessErrorCaptureHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_error_capture.md")
+  }
+}
+
+struct NoUselessErrorCaptureHandler;
+
+/// Returns the parameter of the catch clause the statement is directly in, if
+/// it's a plain identifier. Statements in nested functions or in a nested
+/// `try` block don't count, as the thrown error doesn't leave the clause
+/// there.
+fn enclosing_catch_param<'a>(
+  throw_stmt: &ast_view::ThrowStmt<'a>,
+) -> Option<&'a ast_view::BindingIdent<'a>> {
+  use deno_ast::view::Node;
+  let mut child = throw_stmt.as_node();
+  for ancestor in throw_stmt.ancestors() {
+    match ancestor {
+      Node::CatchClause(catch_clause) => {
+        return match catch_clause.param {
+          Some(Pat::Ident(param)) => Some(param),
+          _ => None,
+        };
+      }
+      Node::TryStmt(try_stmt) if try_stmt.block.range() == child.range() => {
+        return None;
+      }
+      Node::Function(_)
+      | Node::ArrowExpr(_)
+      | Node::Constructor(_)
+      | Node::GetterProp(_)
+      | Node::SetterProp(_)
+      | Node::ClassProp(_)
+      | Node::StaticBlock(_) => return None,
+      _ => {}
+    }
+    child = ancestor;
+  }
+  None
+}
+
+impl Handler for NoUselessErrorCaptureHandler {
+  fn throw_stmt(
+    &mut self,
+    throw_stmt: &ast_view::ThrowStmt,
+    ctx: &mut Context,
+  ) {
+    let Expr::New(new_expr) = throw_stmt.arg else {
+      return;
+    };
+    let Expr::Ident(callee) = new_expr.callee else {
+      return;
+    };
+    if !ERROR_CONSTRUCTORS.contains(&callee.sym().as_ref())
+      || !ctx.is_global(&callee.to_id())
+    {
+      return;
+    }
+    // A second argument may already set the cause
+    let Some([arg]) = new_expr.args.as_deref() else {
+      return;
+    };
+    if arg.spread().is_some() {
+      return;
+    }
+    let Some(param) = enclosing_catch_param(throw_stmt) else {
+      return;
+    };
+
+    let param_name = param.id.sym().to_string();
+    let hint = NoUselessErrorCaptureHint::Cause(param_name.clone());
+    match arg.expr {
+      Expr::Ident(ident) if ident.to_id() == param.id.to_id() => {
+        ctx.add_diagnostic_with_hint(
+          new_expr.range(),
+          CODE,
+          NoUselessErrorCaptureMessage::Wrapped,
+          hint,
+        );
+      }
+      _ => {
+        let end = arg.expr.range().end;
+        let fix = LintFix {
+          description: NoUselessErrorCaptureFix::Cause(param_name.clone())
+            .to_string()
+            .into(),
+          changes: vec![LintFixChange {
+            new_text: format!(", {{ cause: {} }}", param_name).into(),
+            range: SourceRange::new(end, end),
+          }],
+        };
+        ctx.add_diagnostic_with_fixes(
+          new_expr.range(),
+          CODE,
+          NoUselessErrorCaptureMessage::Dropped,
+          Some(hint.to_string()),
+          vec![fix],
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_error_capture_valid() {
+    assert_lint_ok! {
+      NoUselessErrorCapture,
+      "try {} catch (e) { throw e; }",
+      "try {} catch (e) { throw new Error('failed', { cause: e }); }",
+      "try {} catch (e) { throw new Error(e, options); }",
+      "try {} catch (e) { throw new Error(...args); }",
+      "try {} catch (e) { throw new CustomError('failed'); }",
+      "try {} catch (e) { throw new AggregateError([e], 'failed'); }",
+      "try {} catch { throw new Error('failed'); }",
+      "try {} catch ({ message }) { throw new Error(message); }",
+      "throw new Error('failed');",
+      "try {} catch (e) { const f = () => { throw new Error('failed'); }; }",
+      "try {} catch (e) { try { throw new Error('retry'); } catch { } }",
+      "class Error {} try {} catch (e) { throw new Error('failed'); }",
+    };
+  }
+
+  #[test]
+  fn no_useless_error_capture_invalid() {
+    assert_lint_err! {
+      NoUselessErrorCapture,
+      "try {} catch (e) { throw new Error(e); }": [
+        {
+          col: 25,
+          message: NoUselessErrorCaptureMessage::Wrapped,
+          hint: NoUselessErrorCaptureHint::Cause("e".to_string()),
+        }
+      ],
+      "try {} catch (err) { throw new Error('failed'); }": [
+        {
+          col: 27,
+          message: NoUselessErrorCaptureMessage::Dropped,
+          hint: NoUselessErrorCaptureHint::Cause("err".to_string()),
+          fix: (
+            "Add `{ cause: err }`",
+            "try {} catch (err) { throw new Error('failed', { cause: err }); }"
+          ),
+        }
+      ],
+      "try {} catch (e) { if (a) { throw new TypeError(`bad: ${e.message}`); } }": [
+        {
+          col: 34,
+          message: NoUselessErrorCaptureMessage::Dropped,
+          hint: NoUselessErrorCaptureHint::Cause("e".to_string()),
+          fix: (
+            "Add `{ cause: e }`",
+            "try {} catch (e) { if (a) { throw new TypeError(`bad: ${e.message}`, { cause: e }); } }"
+          ),
+        }
+      ],
+      "try {} catch (e) { try {} finally { throw new Error('failed'); } }": [
+        {
+          col: 42,
+          message: NoUselessErrorCaptureMessage::Dropped,
+          hint: NoUselessErrorCaptureHint::Cause("e".to_string()),
+          fix: (
+            "Add `{ cause: e }`",
+            "try {} catch (e) { try {} finally { throw new Error('failed', { cause: e }); } }"
+          ),
+        }
+      ],
+    };
+  }
+}
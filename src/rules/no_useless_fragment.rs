@@ -0,0 +1,265 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, JSXElementChild, JSXElementName, JSXObject, NodeTrait,
+};
+use deno_ast::{SourceRange, SourceRanged};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct NoUselessFragment;
+
+const CODE: &str = "no-useless-fragment";
+const HINT: &str = "Remove the fragment and keep its contents";
+const FIX_DESC: &str = "Remove fragment";
+
+#[derive(Display)]
+enum NoUselessFragmentMessage {
+  #[display(fmt = "Fragments with fewer than two children are unnecessary")]
+  SingleChild,
+  #[display(
+    fmt = "Fragments that are the only child of an element are unnecessary"
+  )]
+  OnlyChild,
+}
+
+impl LintRule for NoUselessFragment {
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsx", "react"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessFragmentHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_fragment.md")
+  }
+}
+
+struct NoUselessFragmentHandler;
+
+/// Children that render something, i.e. anything but whitespace-only text.
+fn meaningful_children<'a>(
+  children: &[JSXElementChild<'a>],
+) -> Vec<JSXElementChild<'a>> {
+  children
+    .iter()
+    .filter(|child| match child {
+      JSXElementChild::JSXText(text) => !text.value().trim().is_empty(),
+      _ => true,
+    })
+    .copied()
+    .collect()
+}
+
+/// Checks if the element is `<Fragment>` or `<React.Fragment>`.
+fn is_fragment_element(element: &ast_view::JSXElement) -> bool {
+  match element.opening.name {
+    JSXElementName::Ident(ident) => ident.sym() == "Fragment",
+    JSXElementName::JSXMemberExpr(member_expr) => {
+      matches!(member_expr.obj, JSXObject::Ident(obj) if obj.sym() == "React")
+        && member_expr.prop.sym() == "Fragment"
+    }
+    JSXElementName::JSXNamespacedName(_) => false,
+  }
+}
+
+fn check_fragment(
+  node: ast_view::Node,
+  children: &[JSXElementChild],
+  content: Option<SourceRange>,
+  ctx: &mut Context,
+) {
+  use deno_ast::view::Node;
+  let meaningful = meaningful_children(children);
+  let parent = node.parent();
+  let parent_children = match parent {
+    Some(Node::JSXElement(element)) => Some(&element.children),
+    Some(Node::JSXFragment(fragment)) => Some(&fragment.children),
+    _ => None,
+  };
+
+  let message = if meaningful.len() < 2 {
+    NoUselessFragmentMessage::SingleChild
+  } else if matches!(parent, Some(Node::JSXElement(_)))
+    && parent_children.map_or(false, |c| meaningful_children(c).len() == 1)
+  {
+    NoUselessFragmentMessage::OnlyChild
+  } else {
+    return;
+  };
+
+  let new_text = if parent_children.is_some() {
+    // As a child of another element, the contents can be inlined as they are
+    Some(content.map_or("", |range| ctx.text_of(range)))
+  } else {
+    // Elsewhere, the fragment can only be replaced with a single element
+    match meaningful.as_slice() {
+      [child @ (JSXElementChild::JSXElement(_)
+      | JSXElementChild::JSXFragment(_))] => Some(ctx.text_of(child.range())),
+      _ => None,
+    }
+  };
+  let fixes = new_text
+    .map(|new_text| LintFix {
+      description: FIX_DESC.into(),
+      changes: vec![LintFixChange {
+        new_text: new_text.to_string().into(),
+        range: node.range(),
+      }],
+    })
+    .into_iter()
+    .collect();
+
+  ctx.add_diagnostic_with_fixes(
+    node.range(),
+    CODE,
+    message,
+    Some(HINT.to_string()),
+    fixes,
+  );
+}
+
+impl Handler for NoUselessFragmentHandler {
+  fn jsx_fragment(
+    &mut self,
+    fragment: &ast_view::JSXFragment,
+    ctx: &mut Context,
+  ) {
+    let content =
+      SourceRange::new(fragment.opening.end(), fragment.closing.start());
+    check_fragment(fragment.as_node(), &fragment.children, Some(content), ctx);
+  }
+
+  fn jsx_element(&mut self, element: &ast_view::JSXElement, ctx: &mut Context) {
+    // Keyed fragments are needed when rendering lists
+    if !is_fragment_element(element) || !element.opening.attrs.is_empty() {
+      return;
+    }
+
+    let content = element
+      .closing
+      .map(|closing| SourceRange::new(element.opening.end(), closing.start()));
+    check_fragment(element.as_node(), &element.children, content, ctx);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_fragment_valid() {
+    assert_lint_ok! {
+      NoUselessFragment,
+      filename: "foo.tsx",
+      "<><Foo /><Bar /></>",
+      "<>foo <b>bar</b></>",
+      "<Fragment><Foo /><Bar /></Fragment>",
+      "<React.Fragment><Foo /><Bar /></React.Fragment>",
+      "<Fragment key={id}><Foo /></Fragment>",
+      "<React.Fragment key={id}><Foo /></React.Fragment>",
+      "<div><Foo /><><Bar /><Baz /></></div>",
+      "<Foo.Fragment><Bar /></Foo.Fragment>",
+      "<div><Foo /></div>",
+    };
+  }
+
+  #[test]
+  fn no_useless_fragment_invalid() {
+    assert_lint_err! {
+      NoUselessFragment,
+      filename: "foo.tsx",
+      "<><Foo /></>": [
+        {
+          col: 0,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo />"),
+        }
+      ],
+      r#"
+const a = (
+  <>
+    <Foo />
+  </>
+);
+      "#: [
+        {
+          line: 3,
+          col: 2,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, r#"
+const a = (
+  <Foo />
+);
+      "#),
+        }
+      ],
+      "<Fragment><Foo /></Fragment>": [
+        {
+          col: 0,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo />"),
+        }
+      ],
+      "<React.Fragment><Foo /></React.Fragment>": [
+        {
+          col: 0,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo />"),
+        }
+      ],
+      "<div><>foo</></div>": [
+        {
+          col: 5,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<div>foo</div>"),
+        }
+      ],
+      "<div><><Foo /><Bar /></></div>": [
+        {
+          col: 5,
+          message: NoUselessFragmentMessage::OnlyChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<div><Foo /><Bar /></div>"),
+        }
+      ],
+      "<div><Fragment /></div>": [
+        {
+          col: 5,
+          message: NoUselessFragmentMessage::SingleChild,
+          hint: HINT,
+          fix: (FIX_DESC, "<div></div>"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_fragment_invalid_without_fix() {
+    for src in ["<>foo</>", "<>{foo}</>", "<></>"] {
+      let diagnostics =
+        crate::test_util::lint(&NoUselessFragment, src, "foo.tsx");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
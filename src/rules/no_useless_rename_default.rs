@@ -0,0 +1,423 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::swc::ast::Id;
+use deno_ast::view::{
+  self as ast_view, ImportSpecifier, ModuleExportName, Pat, VarDeclKind,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct NoUselessRenameDefault;
+
+const CODE: &str = "no-useless-rename-default";
+const IMPORT_HINT: &str =
+  "Use a default import instead, e.g. `import Foo from \"./foo.ts\"`";
+const EXPORT_HINT: &str =
+  "Use a default export instead, e.g. `export default Foo`";
+const IMPORT_FIX_DESC: &str = "Use a default import";
+const EXPORT_FIX_DESC: &str = "Use a default export";
+
+#[derive(Display)]
+enum NoUselessRenameDefaultMessage {
+  #[display(fmt = "`default` is imported by name as `{}`", _0)]
+  Import(String),
+  #[display(fmt = "`{}` is exported by name as `default`", _0)]
+  Export(String),
+}
+
+impl LintRule for NoUselessRenameDefault {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessRenameDefaultHandler::default().traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_rename_default.md")
+  }
+}
+
+#[derive(Default)]
+struct NoUselessRenameDefaultHandler {
+  /// Bindings declared so far that can't be reassigned, i.e. constants,
+  /// classes and functions.
+  immutable_bindings: HashSet<Id>,
+}
+
+fn is_default(name: &ModuleExportName) -> bool {
+  match name {
+    ModuleExportName::Ident(ident) => ident.sym() == "default",
+    ModuleExportName::Str(str) => str.value() == "default",
+  }
+}
+
+/// The braces of an import or export declaration and the specifiers between
+/// them.
+struct SpecifierList {
+  braces: SourceRange,
+  specifiers: Vec<SourceRange>,
+}
+
+impl SpecifierList {
+  /// Locates the braces in the declaration, unless it contains comments that
+  /// a fix could drop.
+  fn new(
+    decl: SourceRange,
+    specifiers: Vec<SourceRange>,
+    ctx: &Context,
+  ) -> Option<Self> {
+    if ctx
+      .all_comments()
+      .any(|comment| decl.contains(&comment.range()))
+    {
+      return None;
+    }
+    let first = specifiers.first()?;
+    let last = specifiers.last()?;
+    let head = ctx.text_of(SourceRange::new(decl.start, first.start));
+    let tail = ctx.text_of(SourceRange::new(last.end, decl.end));
+    let start = decl.start + head.rfind('{')?;
+    let end = last.end + tail.find('}')? + 1;
+    Some(Self {
+      braces: SourceRange::new(start, end),
+      specifiers,
+    })
+  }
+
+  /// The range to remove to drop a specifier along with its comma.
+  fn removal_range(&self, index: usize) -> SourceRange {
+    let specifier = self.specifiers[index];
+    match (self.specifiers.get(index + 1), index.checked_sub(1)) {
+      (Some(next), _) => SourceRange::new(specifier.start, next.start),
+      (None, Some(prev)) => {
+        SourceRange::new(self.specifiers[prev].end, specifier.end)
+      }
+      (None, None) => specifier,
+    }
+  }
+}
+
+/// Turns `import { default as Foo, bar } from "m"` into
+/// `import Foo, { bar } from "m"`.
+fn import_fix(
+  specifier: &ast_view::ImportNamedSpecifier,
+  ctx: &Context,
+) -> Option<LintFix> {
+  let import_decl = specifier.parent();
+  // There can't be two default imports
+  if specifier.is_type_only()
+    || import_decl
+      .specifiers
+      .iter()
+      .any(|s| !matches!(s, ImportSpecifier::Named(_)))
+  {
+    return None;
+  }
+
+  let ranges = import_decl
+    .specifiers
+    .iter()
+    .map(|s| s.range())
+    .collect::<Vec<_>>();
+  let index = ranges.iter().position(|r| *r == specifier.range())?;
+  let list = SpecifierList::new(import_decl.range(), ranges, ctx)?;
+  let local = specifier.local.sym();
+  let changes = if list.specifiers.len() == 1 {
+    vec![LintFixChange {
+      new_text: local.to_string().into(),
+      range: list.braces,
+    }]
+  } else if import_decl.type_only() {
+    // Type-only imports can't have both a default import and named ones
+    return None;
+  } else {
+    vec![
+      LintFixChange {
+        new_text: format!("{}, ", local).into(),
+        range: SourceRange::new(list.braces.start, list.braces.start),
+      },
+      LintFixChange {
+        new_text: "".into(),
+        range: list.removal_range(index),
+      },
+    ]
+  };
+
+  Some(LintFix {
+    description: IMPORT_FIX_DESC.into(),
+    changes,
+  })
+}
+
+impl NoUselessRenameDefaultHandler {
+  /// Turns `export { Foo as default }` into `export default Foo;`. Unlike the
+  /// named export, `export default` evaluates `Foo` once, so this is only done
+  /// for bindings that can't be reassigned and are declared before the export.
+  fn export_fix(
+    &self,
+    specifier: &ast_view::ExportNamedSpecifier,
+    orig: &ast_view::Ident,
+    ctx: &Context,
+  ) -> Option<LintFix> {
+    let named_export = specifier.parent();
+    if named_export.type_only()
+      || specifier.is_type_only()
+      || !self.immutable_bindings.contains(&orig.inner.to_id())
+    {
+      return None;
+    }
+
+    let ranges = named_export
+      .specifiers
+      .iter()
+      .map(|s| s.range())
+      .collect::<Vec<_>>();
+    let index = ranges.iter().position(|r| *r == specifier.range())?;
+    let list = SpecifierList::new(named_export.range(), ranges, ctx)?;
+    let export_default = format!("export default {};", orig.sym());
+    let changes = if list.specifiers.len() == 1 {
+      vec![LintFixChange {
+        new_text: export_default.into(),
+        range: named_export.range(),
+      }]
+    } else {
+      let end = named_export.end();
+      vec![
+        LintFixChange {
+          new_text: "".into(),
+          range: list.removal_range(index),
+        },
+        LintFixChange {
+          new_text: format!("\n{}", export_default).into(),
+          range: SourceRange::new(end, end),
+        },
+      ]
+    };
+
+    Some(LintFix {
+      description: EXPORT_FIX_DESC.into(),
+      changes,
+    })
+  }
+}
+
+impl Handler for NoUselessRenameDefaultHandler {
+  fn fn_decl(&mut self, fn_decl: &ast_view::FnDecl, _ctx: &mut Context) {
+    self.immutable_bindings.insert(fn_decl.ident.inner.to_id());
+  }
+
+  fn class_decl(
+    &mut self,
+    class_decl: &ast_view::ClassDecl,
+    _ctx: &mut Context,
+  ) {
+    self
+      .immutable_bindings
+      .insert(class_decl.ident.inner.to_id());
+  }
+
+  fn var_decl(&mut self, var_decl: &ast_view::VarDecl, _ctx: &mut Context) {
+    if var_decl.decl_kind() != VarDeclKind::Const {
+      return;
+    }
+    for decl in &var_decl.decls {
+      if let Pat::Ident(binding) = decl.name {
+        self.immutable_bindings.insert(binding.id.inner.to_id());
+      }
+    }
+  }
+
+  fn import_named_specifier(
+    &mut self,
+    specifier: &ast_view::ImportNamedSpecifier,
+    ctx: &mut Context,
+  ) {
+    if !specifier.imported.as_ref().map_or(false, is_default) {
+      return;
+    }
+
+    let fixes = import_fix(specifier, ctx).into_iter().collect();
+    ctx.add_diagnostic_with_fixes(
+      specifier.range(),
+      CODE,
+      NoUselessRenameDefaultMessage::Import(specifier.local.sym().to_string()),
+      Some(IMPORT_HINT.to_string()),
+      fixes,
+    );
+  }
+
+  fn export_named_specifier(
+    &mut self,
+    specifier: &ast_view::ExportNamedSpecifier,
+    ctx: &mut Context,
+  ) {
+    // `export { foo as default } from "m"` has no shorter form
+    if specifier.parent().src.is_some()
+      || !specifier.exported.as_ref().map_or(false, is_default)
+    {
+      return;
+    }
+    let ModuleExportName::Ident(orig) = specifier.orig else {
+      return;
+    };
+
+    let fixes = self.export_fix(specifier, orig, ctx).into_iter().collect();
+    ctx.add_diagnostic_with_fixes(
+      specifier.range(),
+      CODE,
+      NoUselessRenameDefaultMessage::Export(orig.sym().to_string()),
+      Some(EXPORT_HINT.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_rename_default_valid() {
+    assert_lint_ok! {
+      NoUselessRenameDefault,
+      r#"import Foo from "./foo.ts";"#,
+      r#"import Foo, { bar } from "./foo.ts";"#,
+      r#"import { foo as bar } from "./foo.ts";"#,
+      r#"import * as foo from "./foo.ts";"#,
+      "const Foo = 1; export default Foo;",
+      "const foo = 1; export { foo as bar };",
+      r#"export { default } from "./foo.ts";"#,
+      r#"export { default as Foo } from "./foo.ts";"#,
+      r#"export { Foo as default } from "./foo.ts";"#,
+      r#"export * from "./foo.ts";"#,
+    };
+  }
+
+  #[test]
+  fn no_useless_rename_default_invalid() {
+    assert_lint_err! {
+      NoUselessRenameDefault,
+      r#"import { default as Foo } from "./foo.ts";"#: [
+        {
+          col: 9,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"import Foo from "./foo.ts";"#),
+        }
+      ],
+      r#"import { "default" as Foo } from "./foo.ts";"#: [
+        {
+          col: 9,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"import Foo from "./foo.ts";"#),
+        }
+      ],
+      r#"import type { default as Foo } from "./foo.ts";"#: [
+        {
+          col: 14,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"import type Foo from "./foo.ts";"#),
+        }
+      ],
+      r#"import { default as Foo, bar } from "./foo.ts";"#: [
+        {
+          col: 9,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"import Foo, { bar } from "./foo.ts";"#),
+        }
+      ],
+      r#"import { bar, default as Foo, } from "./foo.ts";"#: [
+        {
+          col: 14,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"import Foo, { bar, } from "./foo.ts";"#),
+        }
+      ],
+      r#"
+import {
+  bar,
+  default as Foo,
+  baz,
+} from "./foo.ts";
+      "#: [
+        {
+          line: 4,
+          col: 2,
+          message: variant!(NoUselessRenameDefaultMessage, Import, "Foo"),
+          hint: IMPORT_HINT,
+          fix: (IMPORT_FIX_DESC, r#"
+import Foo, {
+  bar,
+  baz,
+} from "./foo.ts";
+      "#),
+        }
+      ],
+      "const Foo = 1; export { Foo as default };": [
+        {
+          col: 24,
+          message: variant!(NoUselessRenameDefaultMessage, Export, "Foo"),
+          hint: EXPORT_HINT,
+          fix: (EXPORT_FIX_DESC, "const Foo = 1; export default Foo;"),
+        }
+      ],
+      r#"class Foo {} export { Foo as "default" };"#: [
+        {
+          col: 22,
+          message: variant!(NoUselessRenameDefaultMessage, Export, "Foo"),
+          hint: EXPORT_HINT,
+          fix: (EXPORT_FIX_DESC, "class Foo {} export default Foo;"),
+        }
+      ],
+      "function foo() {}\nconst bar = 1;\nexport { foo as default, bar };": [
+        {
+          line: 3,
+          col: 9,
+          message: variant!(NoUselessRenameDefaultMessage, Export, "foo"),
+          hint: EXPORT_HINT,
+          fix: (
+            EXPORT_FIX_DESC,
+            "function foo() {}\nconst bar = 1;\nexport { bar };\nexport default foo;"
+          ),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_rename_default_invalid_without_fix() {
+    for src in [
+      // There can't be two default imports
+      r#"import Bar, { default as Foo } from "./foo.ts";"#,
+      // Type-only imports can't mix default and named imports
+      r#"import type { default as Foo, Bar } from "./foo.ts";"#,
+      r#"import { /* foo */ default as Foo } from "./foo.ts";"#,
+      // `export default` doesn't keep the binding live
+      "let foo = 1; export { foo as default }; foo = 2;",
+      // `export default` would run before the declaration
+      "export { Foo as default }; const Foo = 1;",
+      "interface Foo {} export type { Foo as default };",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&NoUselessRenameDefault, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
@@ -0,0 +1,284 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::swc_util::{to_jsx_attribute_value, StringRepr};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, Expr, Lit, ObjectLit, Prop, PropName, PropOrSpread,
+};
+use deno_ast::{SourcePos, SourceRange, SourceRanged};
+use derive_more::Display;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct NoUselessSpreadInJsx;
+
+const CODE: &str = "no-useless-spread-in-jsx";
+const HINT: &str = "Pass the properties of the object as separate attributes";
+const FIX_DESC: &str = "Replace with separate attributes";
+
+#[derive(Display)]
+enum NoUselessSpreadInJsxMessage {
+  #[display(
+    fmt = "Spreading an object literal into JSX attributes is unnecessary"
+  )]
+  ObjectLiteral,
+}
+
+impl LintRule for NoUselessSpreadInJsx {
+  fn tags(&self) -> &'static [&'static str] {
+    &["jsx", "react"]
+  }
+
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessSpreadInJsxHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_spread_in_jsx.md")
+  }
+}
+
+struct NoUselessSpreadInJsxHandler;
+
+fn unwrap_parens<'a>(expr: &Expr<'a>) -> Expr<'a> {
+  match expr {
+    Expr::Paren(paren_expr) => unwrap_parens(&paren_expr.expr),
+    _ => *expr,
+  }
+}
+
+/// Checks if all property names of the object are known without running
+/// the code.
+fn has_static_keys(object: &ObjectLit) -> bool {
+  object.props.iter().all(|prop| match prop {
+    PropOrSpread::Spread(_) => false,
+    PropOrSpread::Prop(Prop::Shorthand(_)) => true,
+    PropOrSpread::Prop(prop) => prop.string_repr().is_some(),
+  })
+}
+
+fn is_jsx_attribute_name(name: &str) -> bool {
+  let mut chars = name.chars();
+  chars
+    .next()
+    .map_or(false, |c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '-'))
+}
+
+/// Writes the properties of the object as JSX attributes, or `None` if some
+/// property can't be written as an attribute. A `__proto__` key sets the
+/// prototype rather than a property, and a repeated key would be a repeated
+/// attribute, so neither can be written.
+fn to_jsx_attributes(object: &ObjectLit, ctx: &Context) -> Option<String> {
+  let mut attributes = Vec::new();
+  let mut names = HashSet::new();
+  for prop in &object.props {
+    let attribute = match prop {
+      PropOrSpread::Prop(Prop::Shorthand(ident)) => {
+        if *ident.sym() == *"__proto__"
+          || !names.insert(ident.sym().to_string())
+        {
+          return None;
+        }
+        format!("{0}={{{0}}}", ident.sym())
+      }
+      PropOrSpread::Prop(Prop::KeyValue(key_value)) => {
+        // Computed keys are skipped even if they're literals
+        if matches!(key_value.key, PropName::Computed(_)) {
+          return None;
+        }
+        let name = key_value.key.string_repr()?;
+        if !is_jsx_attribute_name(&name)
+          || name == "__proto__"
+          || !names.insert(name.clone())
+        {
+          return None;
+        }
+        let value = match key_value.value {
+          Expr::Lit(Lit::Str(str)) => to_jsx_attribute_value(str.value()),
+          _ => None,
+        };
+        let value = value.unwrap_or_else(|| {
+          format!("{{{}}}", ctx.text_of(key_value.value.range()))
+        });
+        format!("{}={}", name, value)
+      }
+      _ => return None,
+    };
+    attributes.push(attribute);
+  }
+  Some(attributes.join(" "))
+}
+
+/// Finds the range of the spread attribute including its braces, along with
+/// the end of whatever precedes it, unless there are comments in between.
+fn spread_attribute_range(
+  spread: &ast_view::SpreadElement,
+  opening: &ast_view::JSXOpeningElement,
+  ctx: &Context,
+) -> Option<(SourcePos, SourceRange)> {
+  let index = opening
+    .attrs
+    .iter()
+    .position(|attr| attr.range() == spread.range())?;
+  let prev_end = match index {
+    0 => opening.name.range().end,
+    _ => opening.attrs[index - 1].range().end,
+  };
+  let next_start = match opening.attrs.get(index + 1) {
+    Some(next) => next.range().start,
+    None => opening.range().end,
+  };
+
+  let before = ctx.text_of(SourceRange::new(prev_end, spread.start()));
+  let after = ctx.text_of(SourceRange::new(spread.end(), next_start));
+  if before.trim() != "{" || !after.trim_start().starts_with('}') {
+    return None;
+  }
+  let start = prev_end + before.find('{')?;
+  let end = spread.end() + after.find('}')? + 1;
+  Some((prev_end, SourceRange::new(start, end)))
+}
+
+impl Handler for NoUselessSpreadInJsxHandler {
+  fn spread_element(
+    &mut self,
+    spread: &ast_view::SpreadElement,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::Node;
+    let Node::JSXOpeningElement(opening) = spread.parent() else {
+      return;
+    };
+    let Expr::Object(object) = unwrap_parens(&spread.expr) else {
+      return;
+    };
+    if !has_static_keys(object) {
+      return;
+    }
+
+    let attribute_range = spread_attribute_range(spread, opening, ctx);
+    let fix = attribute_range.and_then(|(prev_end, range)| {
+      let attributes = to_jsx_attributes(object, ctx)?;
+      // An empty object is removed along with the whitespace before it
+      let range = if attributes.is_empty() {
+        SourceRange::new(prev_end, range.end)
+      } else {
+        range
+      };
+      Some(LintFix {
+        description: FIX_DESC.into(),
+        changes: vec![LintFixChange {
+          new_text: attributes.into(),
+          range,
+        }],
+      })
+    });
+
+    ctx.add_diagnostic_with_fixes(
+      attribute_range.map_or(spread.range(), |(_, range)| range),
+      CODE,
+      NoUselessSpreadInJsxMessage::ObjectLiteral,
+      Some(HINT.to_string()),
+      fix.into_iter().collect(),
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_spread_in_jsx_valid() {
+    assert_lint_ok! {
+      NoUselessSpreadInJsx,
+      filename: "foo.tsx",
+      "<Foo a={1} />",
+      "<Foo {...props} />",
+      "<Foo {...getProps()} />",
+      "<Foo {...{ ...props }} />",
+      "<Foo {...{ a: 1, ...props }} />",
+      "<Foo {...{ [key]: 1 }} />",
+      "foo({ ...{ a: 1 } });",
+    };
+  }
+
+  #[test]
+  fn no_useless_spread_in_jsx_invalid() {
+    assert_lint_err! {
+      NoUselessSpreadInJsx,
+      filename: "foo.tsx",
+      "<Foo {...{ a: 1 }} />": [
+        {
+          col: 5,
+          message: NoUselessSpreadInJsxMessage::ObjectLiteral,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo a={1} />"),
+        }
+      ],
+      r#"<Foo b="x" {...{ a, c: "text", 'aria-label': label }} d />"#: [
+        {
+          col: 11,
+          message: NoUselessSpreadInJsxMessage::ObjectLiteral,
+          hint: HINT,
+          fix: (FIX_DESC, r#"<Foo b="x" a={a} c="text" aria-label={label} d />"#),
+        }
+      ],
+      "<Foo {...({ onClick: () => go() })}></Foo>": [
+        {
+          col: 5,
+          message: NoUselessSpreadInJsxMessage::ObjectLiteral,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo onClick={() => go()}></Foo>"),
+        }
+      ],
+      "<Foo {...{}} />": [
+        {
+          col: 5,
+          message: NoUselessSpreadInJsxMessage::ObjectLiteral,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo />"),
+        }
+      ],
+      "<Foo bar { ... { a: 1 } }/>": [
+        {
+          col: 9,
+          message: NoUselessSpreadInJsxMessage::ObjectLiteral,
+          hint: HINT,
+          fix: (FIX_DESC, "<Foo bar a={1}/>"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn no_useless_spread_in_jsx_invalid_without_fix() {
+    for src in [
+      "<Foo {...{ ['a']: 1 }} />",
+      "<Foo {...{ 'not valid': 1 }} />",
+      "<Foo {...{ method() {} }} />",
+      "<Foo {/* keep */ ...{ a: 1 }} />",
+      "<Foo {...{ __proto__: p }} />",
+      "<Foo {...{ '__proto__': p }} />",
+      "<Foo {...{ a: 1, a: 2 }} />",
+      "<Foo {...{ a, 'a': 2 }} />",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&NoUselessSpreadInJsx, src, "foo.tsx");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
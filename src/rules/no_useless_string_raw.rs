@@ -0,0 +1,145 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Expr, MemberProp};
+use deno_ast::{SourceRange, SourceRanged};
+use if_chain::if_chain;
+
+#[derive(Debug)]
+pub struct NoUselessStringRaw;
+
+const CODE: &str = "no-useless-string-raw";
+const MESSAGE: &str =
+  "`String.raw` is unnecessary because the template contains no backslashes";
+const HINT: &str = "Remove the `String.raw` tag";
+const FIX_DESC: &str = "Remove `String.raw`";
+
+impl LintRule for NoUselessStringRaw {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    NoUselessStringRawHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/no_useless_string_raw.md")
+  }
+}
+
+struct NoUselessStringRawHandler;
+
+impl Handler for NoUselessStringRawHandler {
+  fn tagged_tpl(
+    &mut self,
+    tagged_tpl: &ast_view::TaggedTpl,
+    ctx: &mut Context,
+  ) {
+    if_chain! {
+      if let Expr::Member(member_expr) = &tagged_tpl.tag;
+      if let Expr::Ident(obj) = &member_expr.obj;
+      if obj.sym() == "String";
+      if ctx.is_global_ident(obj);
+      if let MemberProp::Ident(prop) = &member_expr.prop;
+      if prop.sym() == "raw";
+      if tagged_tpl.type_params.is_none();
+      if !tagged_tpl.tpl.quasis.iter().any(|quasi| quasi.raw().contains('\\'));
+      then {
+        let fix = LintFix {
+          description: FIX_DESC.into(),
+          changes: vec![LintFixChange {
+            new_text: "".into(),
+            range: SourceRange::new(
+              tagged_tpl.start(),
+              tagged_tpl.tpl.start(),
+            ),
+          }],
+        };
+        ctx.add_diagnostic_with_fixes(
+          tagged_tpl.range(),
+          CODE,
+          MESSAGE,
+          Some(HINT.to_string()),
+          vec![fix],
+        );
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn no_useless_string_raw_valid() {
+    assert_lint_ok! {
+      NoUselessStringRaw,
+      r"String.raw`C:\Users`;",
+      r"String.raw`foo${bar}\n`;",
+      r"String.raw`\${foo}`;",
+      "`foo`;",
+      "foo`bar`;",
+      "String.foo`bar`;",
+      "String[raw]`bar`;",
+      "const String = { raw: (s) => s }; String.raw`bar`;",
+      "function f(String) { return String.raw`bar`; }",
+    };
+  }
+
+  #[test]
+  fn no_useless_string_raw_invalid() {
+    assert_lint_err! {
+      NoUselessStringRaw,
+      "String.raw`foo`;": [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "`foo`;"),
+        }
+      ],
+      "const a = String.raw`foo${bar}baz`;": [
+        {
+          col: 10,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = `foo${bar}baz`;"),
+        }
+      ],
+      "String.raw``;": [
+        {
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "``;"),
+        }
+      ],
+      r#"
+const a = String.raw`
+multiline
+`;
+      "#: [
+        {
+          line: 2,
+          col: 10,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, r#"
+const a = `
+multiline
+`;
+      "#),
+        }
+      ],
+    };
+  }
+}
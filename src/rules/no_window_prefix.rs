@@ -1,15 +1,23 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::Context;
 use super::LintRule;
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange, LintRelatedLocation};
 use crate::handler::Handler;
 use crate::handler::Traverse;
+use crate::swc_util::find_lhs_ids;
 use crate::Program;
 
-use deno_ast::view as ast_view;
-use deno_ast::SourceRanged;
+use deno_ast::swc::ast::{ForHead, Id, Ident};
+use deno_ast::swc::utils::find_pat_ids;
+use deno_ast::view::{self as ast_view, Node};
+use deno_ast::{SourceRange, SourceRanged};
 use if_chain::if_chain;
 use once_cell::sync::Lazy;
-use std::collections::HashSet;
+use serde::Deserialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
 pub struct NoWindowPrefix;
@@ -18,6 +26,26 @@ const CODE: &str = "no-window-prefix";
 const MESSAGE: &str = "For compatibility between the Window context and the Web Workers, calling Web APIs via `window` is disallowed";
 const HINT: &str =
   "Instead, call this API via `self`, `globalThis`, or no extra prefix";
+const FIX_DESC: &str = "Remove the `window` prefix";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct NoWindowPrefixOptions {
+  /// Properties that are allowed to be accessed via `window`.
+  allow: HashSet<String>,
+  /// Properties that are disallowed in addition to the builtin ones, e.g.
+  /// globals that a project defines itself.
+  deny: HashSet<String>,
+}
+
+impl NoWindowPrefixOptions {
+  /// Check if accessing the property via `window` is disallowed.
+  pub(crate) fn is_denied(&self, prop_symbol: &str) -> bool {
+    (PROPERTY_DENY_LIST.contains(prop_symbol)
+      || self.deny.contains(prop_symbol))
+      && !self.allow.contains(prop_symbol)
+  }
+}
 
 impl LintRule for NoWindowPrefix {
   fn tags(&self) -> &'static [&'static str] {
@@ -33,7 +61,19 @@ impl LintRule for NoWindowPrefix {
     context: &mut Context,
     program: Program<'_>,
   ) {
-    NoWindowPrefixHandler.traverse(program, context);
+    let options: NoWindowPrefixOptions = context.rule_options(CODE);
+    let mut alias_finder = WindowAliasFinder::default();
+    alias_finder.traverse(program, context);
+    NoWindowPrefixHandler {
+      options,
+      aliases: alias_finder.into_aliases(),
+      declarations: None,
+    }
+    .traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<NoWindowPrefixOptions>(options)
   }
 
   #[cfg(feature = "docs")]
@@ -206,26 +246,236 @@ static PROPERTY_DENY_LIST: Lazy<HashSet<&'static str>> = Lazy::new(|| {
 
 /// Extracts a symbol from the given expression if the symbol is statically determined (otherwise,
 /// return `None`).
-fn extract_symbol<'a>(expr: &'a ast_view::MemberExpr) -> Option<&'a str> {
-  use deno_ast::view::{Expr, Lit, MemberProp, Tpl};
+pub(crate) fn extract_symbol<'a>(
+  expr: &'a ast_view::MemberExpr,
+) -> Option<Cow<'a, str>> {
+  use deno_ast::view::MemberProp;
   match &expr.prop {
-    MemberProp::Ident(ident) => Some(ident.sym()),
-    MemberProp::PrivateName(name) => Some(name.id.sym()),
-    MemberProp::Computed(prop) => match &prop.expr {
-      Expr::Lit(Lit::Str(s)) => Some(s.value()),
-      // If it's computed, this MemberExpr looks like `foo[bar]`
-      Expr::Ident(_) => None,
-      Expr::Tpl(Tpl {
-        ref exprs,
-        ref quasis,
-        ..
-      }) if exprs.is_empty() && quasis.len() == 1 => Some(quasis[0].raw()),
-      _ => None,
-    },
+    MemberProp::Ident(ident) => Some(Cow::Borrowed(ident.sym())),
+    MemberProp::PrivateName(name) => Some(Cow::Borrowed(name.id.sym())),
+    MemberProp::Computed(prop) => static_string(&prop.expr),
+  }
+}
+
+/// Returns the value of a string literal, a template literal without
+/// expressions, or a concatenation of those (e.g. `"fe" + "tch"`).
+fn static_string<'a>(expr: &'a ast_view::Expr) -> Option<Cow<'a, str>> {
+  use deno_ast::view::{BinaryOp, Expr, Lit};
+  match expr {
+    Expr::Lit(Lit::Str(s)) => Some(Cow::Borrowed(s.value())),
+    // Use the cooked value, which is what the template evaluates to. It's
+    // missing if the template has an invalid escape sequence.
+    Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl
+      .quasis
+      .first()
+      .and_then(|quasi| quasi.inner.cooked.as_deref())
+      .map(Cow::Borrowed),
+    Expr::Bin(bin) if bin.op() == BinaryOp::Add => {
+      let mut value = static_string(&bin.left)?.into_owned();
+      value.push_str(&static_string(&bin.right)?);
+      Some(Cow::Owned(value))
+    }
+    Expr::Paren(paren) => static_string(&paren.expr),
+    _ => None,
+  }
+}
+
+/// Replaces `window.foo` or `window["foo"]` with `foo`, unless `foo` isn't an
+/// identifier, is declared somewhere in the file, or is the operand of
+/// `delete`, which doesn't accept a plain identifier in strict mode.
+fn bare_fix(
+  member_expr: &ast_view::MemberExpr,
+  prop_symbol: &str,
+  ctx: &Context,
+) -> Option<LintFix> {
+  use deno_ast::swc::ast::UnaryOp;
+  if Ident::verify_symbol(prop_symbol).is_err()
+    || ctx.scope().ids_with_symbol(&prop_symbol.into()).is_some()
+  {
+    return None;
+  }
+  if matches!(member_expr.parent(), Node::UnaryExpr(unary)
+    if unary.op() == UnaryOp::Delete)
+  {
+    return None;
+  }
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: prop_symbol.to_string().into(),
+      range: member_expr.range(),
+    }],
+  })
+}
+
+/// Collects the ranges of the identifiers that declare a binding, by name.
+#[derive(Default)]
+struct DeclarationFinder {
+  declarations: HashMap<String, Vec<SourceRange>>,
+}
+
+impl Handler for DeclarationFinder {
+  fn ident(&mut self, ident: &ast_view::Ident, _ctx: &mut Context) {
+    let is_declaration = match ident.parent() {
+      Node::BindingIdent(_)
+      | Node::FnDecl(_)
+      | Node::FnExpr(_)
+      | Node::ClassDecl(_)
+      | Node::ClassExpr(_)
+      | Node::TsEnumDecl(_)
+      | Node::ImportDefaultSpecifier(_)
+      | Node::ImportStarAsSpecifier(_) => true,
+      Node::ImportNamedSpecifier(specifier) => {
+        specifier.local.range() == ident.range()
+      }
+      _ => false,
+    };
+    if is_declaration {
+      self
+        .declarations
+        .entry(ident.sym().to_string())
+        .or_default()
+        .push(ident.range());
+    }
   }
 }
 
-struct NoWindowPrefixHandler;
+/// Collects the `const` and `let` bindings that are initialized with the
+/// global `window`, e.g. `const w = window;`, so that `w.fetch()` is reported
+/// like `window.fetch()`.
+#[derive(Default)]
+struct WindowAliasFinder {
+  aliases: HashSet<Id>,
+  reassigned: HashSet<Id>,
+}
+
+impl WindowAliasFinder {
+  /// The aliases that are never reassigned, and so always refer to `window`.
+  fn into_aliases(self) -> HashSet<Id> {
+    let reassigned = self.reassigned;
+    self
+      .aliases
+      .into_iter()
+      .filter(|id| !reassigned.contains(id))
+      .collect()
+  }
+
+  /// Marks the variables assigned by the head of a `for...in` or `for...of`
+  /// loop without a declaration, e.g. `for (w of items)`, as reassigned.
+  fn add_for_head(&mut self, head: &ForHead) {
+    if let ForHead::Pat(pat) = head {
+      self.reassigned.extend(find_pat_ids::<_, Id>(pat));
+    }
+  }
+}
+
+impl Handler for WindowAliasFinder {
+  fn var_declarator(
+    &mut self,
+    declarator: &ast_view::VarDeclarator,
+    ctx: &mut Context,
+  ) {
+    use deno_ast::view::{Expr, Pat, VarDeclKind};
+    if_chain! {
+      if let Node::VarDecl(var_decl) = declarator.parent();
+      if var_decl.decl_kind() != VarDeclKind::Var;
+      if let Pat::Ident(binding) = declarator.name;
+      if let Some(Expr::Ident(init)) = declarator.init;
+      if init.sym() == "window";
+      if ctx.is_global_ident(init);
+      then {
+        self.aliases.insert(binding.id.inner.to_id());
+      }
+    }
+  }
+
+  fn assign_expr(
+    &mut self,
+    assign_expr: &ast_view::AssignExpr,
+    _ctx: &mut Context,
+  ) {
+    self
+      .reassigned
+      .extend(find_lhs_ids::<Id>(&assign_expr.inner.left));
+  }
+
+  fn update_expr(
+    &mut self,
+    update_expr: &ast_view::UpdateExpr,
+    _ctx: &mut Context,
+  ) {
+    if let ast_view::Expr::Ident(ident) = update_expr.arg {
+      self.reassigned.insert(ident.inner.to_id());
+    }
+  }
+
+  fn for_in_stmt(
+    &mut self,
+    for_in_stmt: &ast_view::ForInStmt,
+    _ctx: &mut Context,
+  ) {
+    self.add_for_head(&for_in_stmt.inner.left);
+  }
+
+  fn for_of_stmt(
+    &mut self,
+    for_of_stmt: &ast_view::ForOfStmt,
+    _ctx: &mut Context,
+  ) {
+    self.add_for_head(&for_of_stmt.inner.left);
+  }
+}
+
+struct NoWindowPrefixHandler {
+  options: NoWindowPrefixOptions,
+  /// Bindings that always refer to the global `window`.
+  aliases: HashSet<Id>,
+  /// The ranges of the declarations in the file by name, collected when the
+  /// first diagnostic is reported.
+  declarations: Option<HashMap<String, Vec<SourceRange>>>,
+}
+
+impl NoWindowPrefixHandler {
+  /// Whether the identifier is the global `window` or an alias of it.
+  fn is_window(&self, ident: &ast_view::Ident, ctx: &Context) -> bool {
+    if ident.sym() == "window" && ctx.is_global_ident(ident) {
+      return true;
+    }
+    self.aliases.contains(&ident.inner.to_id())
+  }
+
+  /// Returns the locations of the local declarations of `symbol`, which keep
+  /// `window.symbol` from being shortened to `symbol`.
+  fn local_declarations(
+    &mut self,
+    symbol: &str,
+    ctx: &mut Context,
+  ) -> Vec<LintRelatedLocation> {
+    if ctx.scope().ids_with_symbol(&symbol.into()).is_none() {
+      return Vec::new();
+    }
+    let declarations = self.declarations.get_or_insert_with(|| {
+      let mut finder = DeclarationFinder::default();
+      finder.traverse(ctx.program(), ctx);
+      finder.declarations
+    });
+    let Some(ranges) = declarations.get(symbol) else {
+      return Vec::new();
+    };
+    ranges
+      .iter()
+      .map(|range| {
+        ctx.related_location(
+          *range,
+          format!(
+            "`{}` is declared here, so the `window` prefix can't simply be removed",
+            symbol
+          ),
+        )
+      })
+      .collect()
+  }
+}
 
 impl Handler for NoWindowPrefixHandler {
   fn member_expr(
@@ -241,18 +491,31 @@ impl Handler for NoWindowPrefixHandler {
     use deno_ast::view::Expr;
     if_chain! {
       if let Expr::Ident(obj) = &member_expr.obj;
-      let obj_symbol = obj.sym();
-      if obj_symbol == "window";
-      if ctx.scope().is_global(&obj.inner.to_id());
+      if self.is_window(obj, ctx);
       if let Some(prop_symbol) = extract_symbol(member_expr);
-      if PROPERTY_DENY_LIST.contains(prop_symbol);
+      if self.options.is_denied(&prop_symbol);
       then {
-        ctx.add_diagnostic_with_hint(
-          member_expr.range(),
-          CODE,
-          MESSAGE,
-          HINT,
-        );
+        let related = self.local_declarations(&prop_symbol, ctx);
+        if related.is_empty() {
+          let fixes = bare_fix(member_expr, &prop_symbol, ctx)
+            .into_iter()
+            .collect();
+          ctx.add_diagnostic_with_fixes(
+            member_expr.range(),
+            CODE,
+            MESSAGE,
+            Some(HINT.to_string()),
+            fixes,
+          );
+        } else {
+          ctx.add_diagnostic_with_related(
+            member_expr.range(),
+            CODE,
+            MESSAGE,
+            Some(HINT.to_string()),
+            related,
+          );
+        }
       }
     }
   }
@@ -369,10 +632,52 @@ mod tests {
       // Ignore property access with variables
       r#"const f = "fetch"; window[f]();"#,
       r#"const f = "fetch"; window[`${f}`]();"#,
+      r#"const f = "tch"; window["fe" + f]();"#,
+      r#"window["fe" - "tch"]();"#,
+
+      // Only the evaluated value of strings is checked
+      r#"window["on" + "load"]();"#,
+      r#"window[`\u006Fnload`]();"#,
 
       // Make sure that no false positives are triggered on chained member
       // expressions
       r#"foo.window.fetch();"#,
+      r#"foo?.window?.fetch();"#,
+      r#"foo?.window.fetch();"#,
+
+      // Only `const` and `let` aliases of the global `window` that are never
+      // reassigned are followed
+      "const w = window; w.alert();",
+      "const w = foo ? window : self; w.fetch();",
+      "const w = window as any; w.fetch();",
+      "const w = (0, window); w.fetch();",
+      "var w = window; w.fetch();",
+      "let w = window; w = self; w.fetch();",
+      "let w = window; [w] = [self]; w.fetch();",
+      "let w = window; for (w of frames) {} w.fetch();",
+      "const window = {}; const w = window; w.fetch();",
+      "const a = window; const b = a; b.fetch();",
+      "const w = window; function f(w) { w.fetch(); }",
+      "const w = window; { const w = self; w.fetch(); }",
+
+      // Properties allowed via a config comment
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["fetch"] } } }
+window.fetch();
+      "#,
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["fetch", "alert"] } } }
+window["fetch"]();
+      "#,
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "deny": ["myGlobal"] } } }
+window.otherGlobal;
+      "#,
+      // `allow` takes precedence
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["myGlobal"], "deny": ["myGlobal"] } } }
+window.myGlobal;
+      "#,
     };
   }
 
@@ -385,16 +690,97 @@ mod tests {
       r#"window.fetch()"#: [
         {
           col: 0,
+          fix: (FIX_DESC, "fetch()"),
         }
       ],
       r#"window["fetch"]()"#: [
         {
           col: 0,
+          fix: (FIX_DESC, "fetch()"),
         }
       ],
       r#"window[`fetch`]()"#: [
         {
           col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window[`\u0066etch`]()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window["fe\x74ch"]()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window["fe" + "tch"]()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window[`fe` + ("t" + `ch`)]()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window?.fetch()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window?.["fetch"]()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch()"),
+        }
+      ],
+      r#"window?.fetch?.()"#: [
+        {
+          col: 0,
+          fix: (FIX_DESC, "fetch?.()"),
+        }
+      ],
+      r#"const res = await window.fetch(url);"#: [
+        {
+          col: 18,
+          fix: (FIX_DESC, "const res = await fetch(url);"),
+        }
+      ],
+      // `\r\n` is a single line terminator and the byte order mark isn't
+      // part of the first column
+      "a;\nb;\n  window.fetch();\n": [
+        {
+          line: 3,
+          col: 2,
+          fix: (FIX_DESC, "a;\nb;\n  fetch();\n"),
+        }
+      ],
+      "a;\r\nb;\r\n  window.fetch();\r\n": [
+        {
+          line: 3,
+          col: 2,
+          fix: (FIX_DESC, "a;\r\nb;\r\n  fetch();\r\n"),
+        }
+      ],
+      "\u{FEFF}a;\r\nb;\r\n  window.fetch();\r\n": [
+        {
+          line: 3,
+          col: 2,
+          fix: (FIX_DESC, "\u{FEFF}a;\r\nb;\r\n  fetch();\r\n"),
+        }
+      ],
+      "\u{FEFF}window.fetch();": [
+        {
+          line: 1,
+          col: 0,
+          fix: (FIX_DESC, "\u{FEFF}fetch();"),
         }
       ],
       r#"
@@ -409,6 +795,77 @@ window.fetch();
           line: 6,
         }
       ],
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "allow": ["alert"] } } }
+window.fetch();
+      "#: [
+        {
+          col: 0,
+          line: 3,
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "no-window-prefix": { "deny": ["myGlobal", "fetch"] } } }
+window.myGlobal();
+      "#: [
+        {
+          col: 0,
+          line: 3,
+        }
+      ],
+      "const w = window; w.fetch();": [
+        {
+          col: 18,
+          fix: (FIX_DESC, "const w = window; fetch();"),
+        }
+      ],
+      r#"let w = window; function f() { return w["setTimeout"](g); }"#: [
+        {
+          col: 38,
+          fix: (FIX_DESC, "let w = window; function f() { return setTimeout(g); }"),
+        }
+      ],
     };
   }
+
+  #[test]
+  fn no_window_prefix_invalid_without_fix() {
+    for src in [
+      // `fetch` would refer to the local
+      "function f() { const fetch = 1; } window.fetch();",
+      "import { fetch } from './fetch.ts'; window.fetch();",
+      "delete window.fetch;",
+    ] {
+      let diagnostics = crate::test_util::lint(&NoWindowPrefix, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+
+  #[test]
+  fn no_window_prefix_related_declarations() {
+    let src = r#"
+function f(fetch) {}
+class C { m() { const fetch = 1; } }
+import { a as fetch } from "./a.ts";
+window.fetch();
+window.atob("");
+"#;
+    let diagnostics = crate::test_util::lint(&NoWindowPrefix, src, "foo.ts");
+    assert_eq!(diagnostics.len(), 2);
+    let related = &diagnostics[0].related;
+    assert_eq!(related.len(), 3);
+    let positions: Vec<_> = related
+      .iter()
+      .map(|r| (r.range.start.line_index, r.range.start.column_index))
+      .collect();
+    assert_eq!(positions, vec![(1, 11), (2, 22), (3, 14)]);
+    assert!(related.iter().all(|r| r.specifier.is_none()
+      && r.message
+        == "`fetch` is declared here, so the `window` prefix can't simply be removed"));
+    assert_eq!(diagnostics[0].hint.as_deref(), Some(HINT));
+    assert!(diagnostics[0].fixes.is_empty());
+    assert!(diagnostics[1].related.is_empty());
+    assert_eq!(diagnostics[1].fixes.len(), 1);
+  }
 }
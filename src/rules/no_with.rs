@@ -10,6 +10,7 @@ pub struct NoWith;
 
 const CODE: &str = "no-with";
 const MESSAGE: &str = "`with` statement is not allowed";
+const HINT: &str = "Access the object's properties explicitly, or destructure them into variables";
 
 impl LintRule for NoWith {
   fn tags(&self) -> &'static [&'static str] {
@@ -38,7 +39,7 @@ struct NoWithHandler;
 
 impl Handler for NoWithHandler {
   fn with_stmt(&mut self, with_stmt: &ast_view::WithStmt, ctx: &mut Context) {
-    ctx.add_diagnostic(with_stmt.range(), CODE, MESSAGE);
+    ctx.add_diagnostic_with_hint(with_stmt.range(), CODE, MESSAGE, HINT);
   }
 }
 
@@ -50,7 +51,11 @@ mod tests {
   fn no_with_invalid() {
     assert_lint_err! {
       NoWith,
-      "with (someVar) { console.log('asdf'); }": [{ col: 0, message: MESSAGE }],
+      "with (someVar) { console.log('asdf'); }": [{ col: 0, message: MESSAGE, hint: HINT }],
+      "function f() { with (a) { with (b) {} } }": [
+        { col: 15, message: MESSAGE, hint: HINT },
+        { col: 26, message: MESSAGE, hint: HINT },
+      ],
     }
   }
 }
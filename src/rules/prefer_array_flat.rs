@@ -0,0 +1,448 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, BlockStmtOrExpr, Callee, Expr, MemberProp, Pat, Stmt,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct PreferArrayFlat;
+
+const CODE: &str = "prefer-array-flat";
+
+#[derive(Display)]
+enum PreferArrayFlatMessage {
+  #[display(fmt = "`[].concat(...arrays)` can be replaced with `.flat()`")]
+  Concat,
+  #[display(
+    fmt = "Flattening with `.reduce()` and `.concat()` can be replaced with `.flat()`"
+  )]
+  Reduce,
+  #[display(
+    fmt = "Flattening the result of `.map()` with `.reduce()` and `.concat()` can be replaced with `.flatMap()`"
+  )]
+  MapReduce,
+}
+
+#[derive(Display)]
+enum PreferArrayFlatHint {
+  #[display(fmt = "Use `.flat()` instead, e.g. `arrays.flat()`")]
+  Flat,
+  #[display(fmt = "Use `.flatMap()` instead, e.g. `items.flatMap(f)`")]
+  FlatMap,
+}
+
+#[derive(Display)]
+enum PreferArrayFlatFixDesc {
+  #[display(fmt = "Use `.flat()`")]
+  Flat,
+  #[display(fmt = "Use `.flatMap()`")]
+  FlatMap,
+}
+
+impl LintRule for PreferArrayFlat {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferArrayFlatHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_array_flat.md")
+  }
+}
+
+struct PreferArrayFlatHandler;
+
+/// Returns the object of the call if it's `obj.name(...)`.
+fn method_call<'a>(
+  call_expr: &ast_view::CallExpr<'a>,
+  name: &str,
+) -> Option<Expr<'a>> {
+  let Callee::Expr(Expr::Member(member)) = call_expr.callee else {
+    return None;
+  };
+  match member.prop {
+    MemberProp::Ident(prop) if prop.sym() == name => Some(member.obj),
+    _ => None,
+  }
+}
+
+/// Returns the array of `[].concat(...arrays)`.
+fn concat_spread<'a>(call_expr: &ast_view::CallExpr<'a>) -> Option<Expr<'a>> {
+  let Expr::Array(array) = method_call(call_expr, "concat")? else {
+    return None;
+  };
+  let [arg] = call_expr.args.as_slice() else {
+    return None;
+  };
+  (array.elems.is_empty() && arg.spread().is_some()).then_some(arg.expr)
+}
+
+/// Whether the expression is known to evaluate to an array: an array literal
+/// or the result of `Array.from(...)` or `Array.of(...)`. A spread accepts
+/// any iterable, but `.flat()` is only a method of arrays.
+fn is_known_array(expr: &Expr, ctx: &Context) -> bool {
+  match expr {
+    Expr::Array(_) => true,
+    Expr::Paren(paren) => is_known_array(&paren.expr, ctx),
+    Expr::Call(call_expr) => {
+      let Callee::Expr(Expr::Member(member)) = call_expr.callee else {
+        return false;
+      };
+      matches!(member.obj, Expr::Ident(obj)
+        if *obj.sym() == *"Array" && ctx.is_global_ident(obj))
+        && matches!(member.prop, MemberProp::Ident(prop)
+          if prop.sym() == "from" || prop.sym() == "of")
+    }
+    _ => false,
+  }
+}
+
+/// Whether the expression is `a.concat(b)`, where `a` and `b` are the
+/// parameters.
+fn is_concat_of(expr: &Expr, params: (&str, &str)) -> bool {
+  let Expr::Call(call_expr) = expr else {
+    return false;
+  };
+  let Some(Expr::Ident(obj)) = method_call(call_expr, "concat") else {
+    return false;
+  };
+  match call_expr.args.as_slice() {
+    [arg] => {
+      arg.spread().is_none()
+        && matches!(arg.expr, Expr::Ident(ident) if ident.sym() == params.1)
+        && obj.sym() == params.0
+    }
+    _ => false,
+  }
+}
+
+/// The names of the two parameters, if the function takes exactly two
+/// identifiers without defaults.
+fn param_names<'a>(
+  params: impl Iterator<Item = Pat<'a>>,
+) -> Option<(&'a str, &'a str)> {
+  let names = params
+    .map(|param| match param {
+      Pat::Ident(ident) => Some(ident.id.sym().as_ref()),
+      _ => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+  match names.as_slice() {
+    [a, b] if a != b => Some((a, b)),
+    _ => None,
+  }
+}
+
+/// The returned value of a body that's a single `return` statement.
+fn returned<'a>(stmts: &[Stmt<'a>]) -> Option<Expr<'a>> {
+  match stmts {
+    [Stmt::Return(return_stmt)] => return_stmt.arg,
+    _ => None,
+  }
+}
+
+/// Whether the expression is a function like `(a, b) => a.concat(b)` or
+/// `function (a, b) { return a.concat(b); }`.
+fn is_concat_reducer(expr: &Expr) -> bool {
+  match expr {
+    Expr::Arrow(arrow) if !arrow.is_async() => {
+      let Some(params) = param_names(arrow.params.iter().copied()) else {
+        return false;
+      };
+      let body = match arrow.body {
+        BlockStmtOrExpr::Expr(expr) => Some(expr),
+        BlockStmtOrExpr::BlockStmt(block) => returned(&block.stmts),
+      };
+      body.map_or(false, |body| is_concat_of(&body, params))
+    }
+    Expr::Fn(fn_expr)
+      if !fn_expr.function.is_async() && !fn_expr.function.is_generator() =>
+    {
+      let function = fn_expr.function;
+      let Some(params) =
+        param_names(function.params.iter().map(|param| param.pat))
+      else {
+        return false;
+      };
+      function
+        .body
+        .and_then(|body| returned(&body.stmts))
+        .map_or(false, |body| is_concat_of(&body, params))
+    }
+    Expr::Paren(paren) => is_concat_reducer(&paren.expr),
+    _ => false,
+  }
+}
+
+/// Returns the array of `arrays.reduce((a, b) => a.concat(b), [])`.
+fn concat_reduce<'a>(call_expr: &ast_view::CallExpr<'a>) -> Option<Expr<'a>> {
+  let obj = method_call(call_expr, "reduce")?;
+  let [reducer, initial] = call_expr.args.as_slice() else {
+    return None;
+  };
+  let is_empty_array =
+    matches!(initial.expr, Expr::Array(array) if array.elems.is_empty());
+  (reducer.spread().is_none()
+    && initial.spread().is_none()
+    && is_empty_array
+    && is_concat_reducer(&reducer.expr))
+  .then_some(obj)
+}
+
+/// Whether the expression has to be wrapped in parentheses to call a method
+/// on it.
+fn needs_parens(expr: &Expr) -> bool {
+  !matches!(
+    expr,
+    Expr::Ident(_)
+      | Expr::This(_)
+      | Expr::Member(_)
+      | Expr::Call(_)
+      | Expr::Paren(_)
+      | Expr::Array(_)
+      | Expr::Tpl(_)
+  )
+}
+
+fn fix(
+  call_expr: &ast_view::CallExpr,
+  new_text: String,
+  description: PreferArrayFlatFixDesc,
+  ctx: &Context,
+) -> Option<LintFix> {
+  if ctx
+    .all_comments()
+    .any(|comment| call_expr.range().contains(&comment.range()))
+  {
+    return None;
+  }
+  Some(LintFix {
+    description: description.to_string().into(),
+    changes: vec![LintFixChange {
+      new_text: new_text.into(),
+      range: call_expr.range(),
+    }],
+  })
+}
+
+impl Handler for PreferArrayFlatHandler {
+  fn call_expr(&mut self, call_expr: &ast_view::CallExpr, ctx: &mut Context) {
+    if let Some(arrays) = concat_spread(call_expr) {
+      let text = ctx.text_of(arrays.range());
+      let new_text = if needs_parens(&arrays) {
+        format!("({}).flat()", text)
+      } else {
+        format!("{}.flat()", text)
+      };
+      let fixes = if is_known_array(&arrays, ctx) {
+        fix(call_expr, new_text, PreferArrayFlatFixDesc::Flat, ctx)
+          .into_iter()
+          .collect()
+      } else {
+        vec![]
+      };
+      ctx.add_diagnostic_with_fixes(
+        call_expr.range(),
+        CODE,
+        PreferArrayFlatMessage::Concat,
+        Some(PreferArrayFlatHint::Flat.to_string()),
+        fixes,
+      );
+      return;
+    }
+
+    let Some(obj) = concat_reduce(call_expr) else {
+      return;
+    };
+    let map_call = match obj {
+      Expr::Call(map_call) => method_call(map_call, "map")
+        .filter(|_| (1..=2).contains(&map_call.args.len()))
+        .map(|items| (items, map_call)),
+      _ => None,
+    };
+    let (message, hint, new_text, description) = match map_call {
+      Some((items, map_call)) => {
+        let args = SourceRange::new(
+          map_call.args.first().unwrap().start(),
+          map_call.args.last().unwrap().end(),
+        );
+        (
+          PreferArrayFlatMessage::MapReduce,
+          PreferArrayFlatHint::FlatMap,
+          format!(
+            "{}.flatMap({})",
+            ctx.text_of(items.range()),
+            ctx.text_of(args)
+          ),
+          PreferArrayFlatFixDesc::FlatMap,
+        )
+      }
+      None => (
+        PreferArrayFlatMessage::Reduce,
+        PreferArrayFlatHint::Flat,
+        format!("{}.flat()", ctx.text_of(obj.range())),
+        PreferArrayFlatFixDesc::Flat,
+      ),
+    };
+    let fixes = fix(call_expr, new_text, description, ctx)
+      .into_iter()
+      .collect();
+    ctx.add_diagnostic_with_fixes(
+      call_expr.range(),
+      CODE,
+      message,
+      Some(hint.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_array_flat_valid() {
+    assert_lint_ok! {
+      PreferArrayFlat,
+      "arrays.flat();",
+      "items.flatMap(f);",
+      "[].concat(arrays);",
+      "[].concat(a, ...arrays);",
+      "[1].concat(...arrays);",
+      "foo.concat(...arrays);",
+      "arrays.reduce((a, b) => a.concat(b));",
+      "arrays.reduce((a, b) => a.concat(b), [1]);",
+      "arrays.reduce((a, b) => a.concat(b), init);",
+      "arrays.reduce((a, b) => b.concat(a), []);",
+      "arrays.reduce((a, b) => a.concat(b, c), []);",
+      "arrays.reduce((a, b) => a.concat(...b), []);",
+      "arrays.reduce((a, b = []) => a.concat(b), []);",
+      "arrays.reduce((a, b, i) => a.concat(b), []);",
+      "arrays.reduce((a, b) => { log(b); return a.concat(b); }, []);",
+      "arrays.reduce(async (a, b) => a.concat(b), []);",
+      "arrays.reduce(concat, []);",
+      "arrays.reduceRight((a, b) => a.concat(b), []);",
+      "arrays?.reduce((a, b) => a.concat(b), []);",
+    };
+  }
+
+  #[test]
+  fn prefer_array_flat_invalid() {
+    assert_lint_err! {
+      PreferArrayFlat,
+      "const flat = [].concat(...[a, b]);": [
+        {
+          col: 13,
+          message: PreferArrayFlatMessage::Concat,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "const flat = [a, b].flat();"),
+        }
+      ],
+      "[].concat(...(Array.from(rows)));": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Concat,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "(Array.from(rows)).flat();"),
+        }
+      ],
+      "[].concat(...Array.of(a, b));": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Concat,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "Array.of(a, b).flat();"),
+        }
+      ],
+      // The spread argument may be any iterable, which lacks `.flat()`
+      "const flat = [].concat(...arrays);": [
+        {
+          col: 13,
+          message: PreferArrayFlatMessage::Concat,
+          hint: PreferArrayFlatHint::Flat,
+        }
+      ],
+      "function f(Array) { [].concat(...Array.from(rows)); }": [
+        {
+          col: 20,
+          message: PreferArrayFlatMessage::Concat,
+          hint: PreferArrayFlatHint::Flat,
+        }
+      ],
+      "arrays.reduce((a, b) => a.concat(b), []);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Reduce,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "arrays.flat();"),
+        }
+      ],
+      "this.rows.reduce((acc: number[], row: number[]) => { return acc.concat(row); }, []);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Reduce,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "this.rows.flat();"),
+        }
+      ],
+      "arrays.reduce(function (a, b) { return a.concat(b); }, []);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Reduce,
+          hint: PreferArrayFlatHint::Flat,
+          fix: ("Use `.flat()`", "arrays.flat();"),
+        }
+      ],
+      "arr.map(f).reduce((a, b) => a.concat(b), []);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::MapReduce,
+          hint: PreferArrayFlatHint::FlatMap,
+          fix: ("Use `.flatMap()`", "arr.flatMap(f);"),
+        }
+      ],
+      "users.map((user) => user.roles, ctx).reduce((a, b) => a.concat(b), []);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::MapReduce,
+          hint: PreferArrayFlatHint::FlatMap,
+          fix: ("Use `.flatMap()`", "users.flatMap((user) => user.roles, ctx);"),
+        }
+      ],
+      "arrays.reduce((a, b) => a.concat(b), [] /* flat */);": [
+        {
+          col: 0,
+          message: PreferArrayFlatMessage::Reduce,
+          hint: PreferArrayFlatHint::Flat,
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn prefer_array_flat_invalid_without_fix() {
+    for src in [
+      "[].concat(...arrays);",
+      "[].concat(...new Set(arrays));",
+      "arrays.reduce((a, b) => a.concat(b), [] /* flat */);",
+    ] {
+      let diagnostics = crate::test_util::lint(&PreferArrayFlat, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+}
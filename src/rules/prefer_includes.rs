@@ -0,0 +1,284 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, BinaryOp, Callee, Expr, Lit, MemberProp, UnaryOp,
+};
+use deno_ast::{SourceRanged, SourceRangedForSpanned};
+
+#[derive(Debug)]
+pub struct PreferIncludes;
+
+const CODE: &str = "prefer-includes";
+const MESSAGE: &str =
+  "Checking whether `.indexOf()` found the value can be replaced with `.includes()`";
+const HINT: &str =
+  "Use `.includes(value)` or `!.includes(value)` instead, e.g. `items.includes(item)`";
+const FIX_DESC: &str = "Use `.includes()`";
+
+impl LintRule for PreferIncludes {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferIncludesHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_includes.md")
+  }
+}
+
+struct PreferIncludesHandler;
+
+/// Returns the call if the expression is `foo.indexOf(...)` with one or two
+/// plain arguments.
+fn index_of_call<'a>(
+  expr: &Expr<'a>,
+) -> Option<(&'a ast_view::CallExpr<'a>, &'a ast_view::MemberExpr<'a>)> {
+  let Expr::Call(call_expr) = expr else {
+    return None;
+  };
+  let Callee::Expr(Expr::Member(member)) = call_expr.callee else {
+    return None;
+  };
+  if !matches!(member.prop, MemberProp::Ident(prop) if prop.sym() == "indexOf")
+  {
+    return None;
+  }
+  if !(1..=2).contains(&call_expr.args.len())
+    || call_expr.args.iter().any(|arg| arg.spread().is_some())
+  {
+    return None;
+  }
+  Some((call_expr, member))
+}
+
+/// The value of a number literal, or a negated one.
+fn number_value(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Lit(Lit::Num(num)) => Some(num.value()),
+    Expr::Unary(unary) if unary.op() == UnaryOp::Minus => {
+      number_value(&unary.arg).map(|value| -value)
+    }
+    _ => None,
+  }
+}
+
+/// Returns whether `indexOf(...) <op> <value>` checks that the value was
+/// found (`true`) or that it wasn't (`false`), or `None` for any other
+/// comparison.
+fn checks_found(op: BinaryOp, value: f64) -> Option<bool> {
+  use BinaryOp::*;
+  match op {
+    NotEqEq | NotEq | Gt if value == -1.0 => Some(true),
+    GtEq if value == 0.0 => Some(true),
+    EqEqEq | EqEq | LtEq if value == -1.0 => Some(false),
+    Lt if value == 0.0 => Some(false),
+    _ => None,
+  }
+}
+
+/// The operator for the same comparison with the operands swapped.
+fn flip(op: BinaryOp) -> BinaryOp {
+  use BinaryOp::*;
+  match op {
+    Lt => Gt,
+    LtEq => GtEq,
+    Gt => Lt,
+    GtEq => LtEq,
+    op => op,
+  }
+}
+
+fn includes_fix(
+  bin_expr: &ast_view::BinExpr,
+  call_expr: &ast_view::CallExpr,
+  member: &ast_view::MemberExpr,
+  found: bool,
+  ctx: &Context,
+) -> Option<LintFix> {
+  // `includes` treats `fromIndex` differently for negative and out of range
+  // values
+  if call_expr.args.len() != 1 {
+    return None;
+  }
+  if ctx
+    .all_comments()
+    .any(|comment| bin_expr.range().contains(&comment.range()))
+  {
+    return None;
+  }
+
+  let new_text = format!(
+    "{}{}.includes({})",
+    if found { "" } else { "!" },
+    ctx.text_of(member.obj.range()),
+    ctx.text_of(call_expr.args[0].range()),
+  );
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: new_text.into(),
+      range: bin_expr.range(),
+    }],
+  })
+}
+
+impl Handler for PreferIncludesHandler {
+  fn bin_expr(&mut self, bin_expr: &ast_view::BinExpr, ctx: &mut Context) {
+    let (call_expr, member, op, value) =
+      if let Some((call_expr, member)) = index_of_call(&bin_expr.left) {
+        (
+          call_expr,
+          member,
+          bin_expr.op(),
+          number_value(&bin_expr.right),
+        )
+      } else if let Some((call_expr, member)) = index_of_call(&bin_expr.right) {
+        (
+          call_expr,
+          member,
+          flip(bin_expr.op()),
+          number_value(&bin_expr.left),
+        )
+      } else {
+        return;
+      };
+    let Some(found) = value.and_then(|value| checks_found(op, value)) else {
+      return;
+    };
+
+    let fixes = includes_fix(bin_expr, call_expr, member, found, ctx)
+      .into_iter()
+      .collect();
+    ctx.add_diagnostic_with_fixes(
+      bin_expr.range(),
+      CODE,
+      MESSAGE,
+      Some(HINT.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_includes_valid() {
+    assert_lint_ok! {
+      PreferIncludes,
+      "if (items.includes(item)) {}",
+      "const i = items.indexOf(item);",
+      "if (items.indexOf(item) > 0) {}",
+      "if (items.indexOf(item) === 0) {}",
+      "if (items.indexOf(item) !== i) {}",
+      "if (items.lastIndexOf(item) !== -1) {}",
+      "if (items.indexOf() !== -1) {}",
+      "if (items.indexOf(...args) !== -1) {}",
+      "if (items['indexOf'](item) !== -1) {}",
+      "if (indexOf(item) !== -1) {}",
+    };
+  }
+
+  #[test]
+  fn prefer_includes_invalid() {
+    assert_lint_err! {
+      PreferIncludes,
+      "if (items.indexOf(item) !== -1) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (items.includes(item)) {}"),
+        }
+      ],
+      "if (items.indexOf(item) != -1) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (items.includes(item)) {}"),
+        }
+      ],
+      "if (str.indexOf('a') > -1) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (str.includes('a')) {}"),
+        }
+      ],
+      "if (str.indexOf('a') >= 0) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (str.includes('a')) {}"),
+        }
+      ],
+      "if (items.indexOf(item) === -1) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (!items.includes(item)) {}"),
+        }
+      ],
+      "if (items.indexOf(item) < 0) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (!items.includes(item)) {}"),
+        }
+      ],
+      "if (-1 !== foo.bar.indexOf(x)) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (foo.bar.includes(x)) {}"),
+        }
+      ],
+      "if (0 > getItems().indexOf(x)) {}": [
+        {
+          col: 4,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "if (!getItems().includes(x)) {}"),
+        }
+      ],
+      "const found = (a || b).indexOf(x) !== -1;": [
+        {
+          col: 14,
+          message: MESSAGE,
+          hint: HINT,
+          fix: (FIX_DESC, "const found = (a || b).includes(x);"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn prefer_includes_invalid_without_fix() {
+    for src in [
+      "if (items.indexOf(item, 1) !== -1) {}",
+      "if (items.indexOf(item /* value */) !== -1) {}",
+    ] {
+      let diagnostics = crate::test_util::lint(&PreferIncludes, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+}
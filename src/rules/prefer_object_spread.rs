@@ -0,0 +1,236 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Callee, Expr, MemberProp, NodeTrait};
+use deno_ast::{SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct PreferObjectSpread;
+
+const CODE: &str = "prefer-object-spread";
+const HINT: &str =
+  "Use an object literal with spread properties, e.g. `{ ...foo, ...bar }`";
+const FIX_DESC: &str = "Use object spread";
+
+#[derive(Display)]
+enum PreferObjectSpreadMessage {
+  #[display(
+    fmt = "`Object.assign` with an empty object as the target can be replaced with an object spread"
+  )]
+  Unexpected,
+}
+
+impl LintRule for PreferObjectSpread {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferObjectSpreadHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_object_spread.md")
+  }
+}
+
+struct PreferObjectSpreadHandler;
+
+/// Checks if the callee is the global `Object.assign`.
+fn is_object_assign(callee: &Callee, ctx: &Context) -> bool {
+  let Callee::Expr(Expr::Member(member)) = callee else {
+    return false;
+  };
+  matches!(member.obj, Expr::Ident(obj)
+    if obj.sym() == "Object" && ctx.is_global(&obj.to_id()))
+    && matches!(member.prop, MemberProp::Ident(prop) if prop.sym() == "assign")
+}
+
+/// Checks if an object literal replacing the call would be parsed as a block,
+/// i.e. the call starts an expression statement or the body of an arrow
+/// function.
+fn needs_parens(call_expr: &ast_view::CallExpr) -> bool {
+  use deno_ast::view::Node;
+  let start = call_expr.start();
+  for ancestor in call_expr.ancestors() {
+    match ancestor {
+      Node::ExprStmt(_) => return ancestor.start() == start,
+      Node::ArrowExpr(arrow) => return arrow.body.start() == start,
+      _ if ancestor.start() != start => return false,
+      _ => {}
+    }
+  }
+  false
+}
+
+fn spread_fix(
+  call_expr: &ast_view::CallExpr,
+  ctx: &Context,
+) -> Option<LintFix> {
+  if ctx
+    .all_comments()
+    .any(|comment| call_expr.range().contains(&comment.range()))
+  {
+    return None;
+  }
+
+  let props = call_expr.args[1..]
+    .iter()
+    .map(|arg| format!("...{}", ctx.text_of(arg.range())))
+    .collect::<Vec<_>>();
+  let object = if props.is_empty() {
+    "{}".to_string()
+  } else {
+    format!("{{ {} }}", props.join(", "))
+  };
+  let new_text = if needs_parens(call_expr) {
+    format!("({})", object)
+  } else {
+    object
+  };
+
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: new_text.into(),
+      range: call_expr.range(),
+    }],
+  })
+}
+
+impl Handler for PreferObjectSpreadHandler {
+  fn call_expr(&mut self, call_expr: &ast_view::CallExpr, ctx: &mut Context) {
+    if !is_object_assign(&call_expr.callee, ctx) {
+      return;
+    }
+    let Some(target) = call_expr.args.first() else {
+      return;
+    };
+    if target.spread().is_some()
+      || !matches!(target.expr, Expr::Object(object) if object.props.is_empty())
+    {
+      return;
+    }
+    // A spread argument may expand to any number of sources, which can't be
+    // written as properties
+    if call_expr.args.iter().any(|arg| arg.spread().is_some()) {
+      return;
+    }
+
+    let fixes = spread_fix(call_expr, ctx).into_iter().collect();
+    ctx.add_diagnostic_with_fixes(
+      call_expr.range(),
+      CODE,
+      PreferObjectSpreadMessage::Unexpected,
+      Some(HINT.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_object_spread_valid() {
+    assert_lint_ok! {
+      PreferObjectSpread,
+      "const a = { ...foo };",
+      "Object.assign(foo, bar);",
+      "Object.assign({ a: 1 }, bar);",
+      "Object.assign(...args);",
+      "Object.assign({}, ...sources);",
+      "Object.assign();",
+      "foo.assign({}, bar);",
+      "Object.keys({}, bar);",
+      "const Object = { assign() {} }; Object.assign({}, bar);",
+    };
+  }
+
+  #[test]
+  fn prefer_object_spread_invalid() {
+    assert_lint_err! {
+      PreferObjectSpread,
+      "const a = Object.assign({}, foo);": [
+        {
+          col: 10,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = { ...foo };"),
+        }
+      ],
+      "const a = Object.assign({}, foo, bar.baz, getDefaults());": [
+        {
+          col: 10,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = { ...foo, ...bar.baz, ...getDefaults() };"),
+        }
+      ],
+      "const a = Object.assign({}, cond ? foo : bar, { a: 1 });": [
+        {
+          col: 10,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = { ...cond ? foo : bar, ...{ a: 1 } };"),
+        }
+      ],
+      "const a = Object.assign({});": [
+        {
+          col: 10,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = {};"),
+        }
+      ],
+      "Object.assign({}, foo).bar;": [
+        {
+          col: 0,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "({ ...foo }).bar;"),
+        }
+      ],
+      "const f = () => Object.assign({}, foo);": [
+        {
+          col: 16,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const f = () => ({ ...foo });"),
+        }
+      ],
+      "const a = Object.assign({}, Object.assign({}, foo));": [
+        {
+          col: 10,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = { ...Object.assign({}, foo) };"),
+        },
+        {
+          col: 28,
+          message: PreferObjectSpreadMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const a = Object.assign({}, { ...foo });"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn prefer_object_spread_invalid_without_fix() {
+    let src = "const a = Object.assign({}, foo /* defaults */, bar);";
+    let diagnostics =
+      crate::test_util::lint(&PreferObjectSpread, src, "foo.ts");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fixes.is_empty());
+  }
+}
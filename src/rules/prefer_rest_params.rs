@@ -0,0 +1,166 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view as ast_view;
+use deno_ast::SourceRanged;
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct PreferRestParams;
+
+const CODE: &str = "prefer-rest-params";
+const HINT: &str = "Declare a rest parameter like `function foo(...args) {}` and use `args` instead";
+
+#[derive(Display)]
+enum PreferRestParamsMessage {
+  #[display(fmt = "Use rest parameters instead of `arguments`")]
+  Unexpected,
+}
+
+impl LintRule for PreferRestParams {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferRestParamsHandler::default().traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_rest_params.md")
+  }
+}
+
+#[derive(Default)]
+struct PreferRestParamsHandler {
+  /// For each enclosing function, whether it has its own `arguments` object,
+  /// i.e. isn't an arrow function.
+  functions: Vec<bool>,
+}
+
+/// Returns `Some(true)` for functions that have their own `arguments` object,
+/// `Some(false)` for arrow functions and `None` for any other node.
+fn has_own_arguments(node: ast_view::Node) -> Option<bool> {
+  use deno_ast::view::Node;
+  match node {
+    Node::Function(_)
+    | Node::Constructor(_)
+    | Node::GetterProp(_)
+    | Node::SetterProp(_) => Some(true),
+    Node::ArrowExpr(_) => Some(false),
+    _ => None,
+  }
+}
+
+impl Handler for PreferRestParamsHandler {
+  fn on_enter_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if let Some(own_arguments) = has_own_arguments(node) {
+      self.functions.push(own_arguments);
+    }
+  }
+
+  fn on_exit_node(&mut self, node: ast_view::Node, _ctx: &mut Context) {
+    if has_own_arguments(node).is_some() {
+      self.functions.pop();
+    }
+  }
+
+  fn ident(&mut self, ident: &ast_view::Ident, ctx: &mut Context) {
+    // Property names aren't resolved, and a declared `arguments` variable
+    // shadows the implicit one. `arguments` is part of the language rather
+    // than the environment, so the `LinterBuilder::is_global` predicate
+    // doesn't apply
+    if ident.sym() != "arguments"
+      || ident.ctxt() != ctx.unresolved_ctxt()
+      || !ctx.scope().is_global(&ident.to_id())
+    {
+      return;
+    }
+    if self.functions.last() != Some(&true) {
+      return;
+    }
+
+    ctx.add_diagnostic_with_hint(
+      ident.range(),
+      CODE,
+      PreferRestParamsMessage::Unexpected,
+      HINT,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_rest_params_valid() {
+    assert_lint_ok! {
+      PreferRestParams,
+      "function foo(...args) { return args; }",
+      "function foo(arguments) { return arguments; }",
+      "function foo() { var arguments; return arguments; }",
+      "function foo() { const arguments = 1; return arguments; }",
+      "function foo() { return bar.arguments; }",
+      "function foo() { return { arguments: 1 }; }",
+      "const foo = () => arguments;",
+      "function foo() { return () => arguments[0]; }",
+      "arguments;",
+    };
+  }
+
+  #[test]
+  fn prefer_rest_params_invalid() {
+    assert_lint_err! {
+      PreferRestParams,
+      "function foo() { return arguments; }": [
+        {
+          col: 24,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "function foo() { return arguments.length; }": [
+        {
+          col: 24,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "const foo = function () { return [].slice.call(arguments, 1); };": [
+        {
+          col: 47,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "class Foo { constructor() { this.args = arguments; } }": [
+        {
+          col: 40,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "const foo = { bar() { return arguments; } };": [
+        {
+          col: 29,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+      "function foo() { return () => { function bar() { return arguments; } }; }": [
+        {
+          col: 56,
+          message: PreferRestParamsMessage::Unexpected,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,218 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{self as ast_view, Callee, Expr, MemberProp};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct PreferSpreadInNew;
+
+const CODE: &str = "prefer-spread-in-new";
+const HINT: &str =
+  "Call the constructor with `new` directly, e.g. `new Foo(a, b)`";
+const FIX_DESC: &str = "Use `new`";
+
+#[derive(Display)]
+enum PreferSpreadInNewMessage {
+  #[display(
+    fmt = "`Reflect.construct` with an array literal can be replaced with `new`"
+  )]
+  Unexpected,
+}
+
+impl LintRule for PreferSpreadInNew {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferSpreadInNewHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_spread_in_new.md")
+  }
+}
+
+struct PreferSpreadInNewHandler;
+
+/// Checks if the callee is the global `Reflect.construct`.
+fn is_reflect_construct(callee: &Callee, ctx: &Context) -> bool {
+  let Callee::Expr(Expr::Member(member)) = callee else {
+    return false;
+  };
+  matches!(member.obj, Expr::Ident(obj)
+    if obj.sym() == "Reflect" && ctx.is_global(&obj.to_id()))
+    && matches!(member.prop, MemberProp::Ident(prop) if prop.sym() == "construct")
+}
+
+/// Checks if the expression can be the target of `new` without parentheses.
+/// E.g. `foo.bar` can, but in `new foo()(a)` only `foo` would be constructed.
+fn is_new_target(expr: &Expr) -> bool {
+  match expr {
+    Expr::Ident(_) | Expr::This(_) | Expr::Paren(_) => true,
+    Expr::Member(member) => is_new_target(&member.obj),
+    _ => false,
+  }
+}
+
+fn new_fix(
+  call_expr: &ast_view::CallExpr,
+  target: &Expr,
+  array: &ast_view::ArrayLit,
+  ctx: &Context,
+) -> Option<LintFix> {
+  // Holes would have to become `undefined`
+  if array.elems.iter().any(Option::is_none)
+    || ctx
+      .all_comments()
+      .any(|comment| call_expr.range().contains(&comment.range()))
+  {
+    return None;
+  }
+
+  let target = if is_new_target(target) {
+    ctx.text_of(target.range()).to_string()
+  } else {
+    format!("({})", ctx.text_of(target.range()))
+  };
+  let args = match (array.elems.first(), array.elems.last()) {
+    (Some(Some(first)), Some(Some(last))) => {
+      ctx.text_of(SourceRange::new(first.start(), last.end()))
+    }
+    _ => "",
+  };
+
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: format!("new {}({})", target, args).into(),
+      range: call_expr.range(),
+    }],
+  })
+}
+
+impl Handler for PreferSpreadInNewHandler {
+  fn call_expr(&mut self, call_expr: &ast_view::CallExpr, ctx: &mut Context) {
+    if !is_reflect_construct(&call_expr.callee, ctx) {
+      return;
+    }
+    // A third argument sets `new.target`, which `new` can't express
+    let [target, args] = call_expr.args.as_slice() else {
+      return;
+    };
+    if target.spread().is_some() || args.spread().is_some() {
+      return;
+    }
+    let Expr::Array(array) = args.expr else {
+      return;
+    };
+
+    let fixes = new_fix(call_expr, &target.expr, array, ctx)
+      .into_iter()
+      .collect();
+    ctx.add_diagnostic_with_fixes(
+      call_expr.range(),
+      CODE,
+      PreferSpreadInNewMessage::Unexpected,
+      Some(HINT.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_spread_in_new_valid() {
+    assert_lint_ok! {
+      PreferSpreadInNew,
+      "new Foo(a, b);",
+      "new Foo(...args);",
+      "Reflect.construct(Foo, args);",
+      "Reflect.construct(Foo, [a, b], Bar);",
+      "Reflect.construct(...args);",
+      "Reflect.apply(foo, null, [a, b]);",
+      "foo.construct(Foo, [a, b]);",
+      "const Reflect = {}; Reflect.construct(Foo, [a, b]);",
+    };
+  }
+
+  #[test]
+  fn prefer_spread_in_new_invalid() {
+    assert_lint_err! {
+      PreferSpreadInNew,
+      "Reflect.construct(Foo, [a, b]);": [
+        {
+          col: 0,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "new Foo(a, b);"),
+        }
+      ],
+      "Reflect.construct(Foo, []);": [
+        {
+          col: 0,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "new Foo();"),
+        }
+      ],
+      "Reflect.construct(foo.Bar, [a, ...args]);": [
+        {
+          col: 0,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "new foo.Bar(a, ...args);"),
+        }
+      ],
+      "Reflect.construct(getFoo(), [a]);": [
+        {
+          col: 0,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "new (getFoo())(a);"),
+        }
+      ],
+      "Reflect.construct(foo().Bar, [a]);": [
+        {
+          col: 0,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "new (foo().Bar)(a);"),
+        }
+      ],
+      "const foo = Reflect.construct(Foo, [\n  a,\n  b,\n]);": [
+        {
+          col: 12,
+          message: PreferSpreadInNewMessage::Unexpected,
+          hint: HINT,
+          fix: (FIX_DESC, "const foo = new Foo(a,\n  b);"),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn prefer_spread_in_new_invalid_without_fix() {
+    for src in [
+      "Reflect.construct(Foo, [a, , b]);",
+      "Reflect.construct(Foo, [a /* first */, b]);",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&PreferSpreadInNew, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
+}
@@ -0,0 +1,375 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::handler::{Handler, Traverse};
+use crate::Program;
+use deno_ast::view::{
+  self as ast_view, BinaryOp, Callee, Expr, Lit, MemberProp, UnaryOp,
+};
+use deno_ast::{SourceRange, SourceRanged, SourceRangedForSpanned};
+use derive_more::Display;
+
+#[derive(Debug)]
+pub struct PreferStringStartsEndsWith;
+
+const CODE: &str = "prefer-string-starts-ends-with";
+
+#[derive(Clone, Copy, Display)]
+enum Method {
+  #[display(fmt = "startsWith")]
+  StartsWith,
+  #[display(fmt = "endsWith")]
+  EndsWith,
+}
+
+#[derive(Display)]
+enum PreferStringStartsEndsWithMessage {
+  #[display(fmt = "Use `startsWith()` to check the start of a string")]
+  StartsWith,
+  #[display(fmt = "Use `endsWith()` to check the end of a string")]
+  EndsWith,
+}
+
+#[derive(Display)]
+enum PreferStringStartsEndsWithHint {
+  #[display(fmt = "Replace the comparison with `str.startsWith(value)`")]
+  StartsWith,
+  #[display(
+    fmt = "Replace the comparison with `str.endsWith(value)`, which also handles strings shorter than the value"
+  )]
+  EndsWith,
+}
+
+impl LintRule for PreferStringStartsEndsWith {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    PreferStringStartsEndsWithHandler.traverse(program, context);
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/prefer_string_starts_ends_with.md")
+  }
+}
+
+struct PreferStringStartsEndsWithHandler;
+
+/// A comparison that can be written with `startsWith` or `endsWith`.
+struct Replacement {
+  method: Method,
+  /// The string being checked.
+  string: SourceRange,
+  /// The value to pass to the method.
+  value: SourceRange,
+  /// Whether the comparison can be replaced without changing its result,
+  /// e.g. the receiver is known to be a string.
+  fixable: bool,
+}
+
+/// Returns `(object, method name, arguments)` for calls like `foo.bar(...)`
+/// without spread arguments.
+fn method_call<'a>(
+  expr: &Expr<'a>,
+) -> Option<(Expr<'a>, &'a str, &'a [&'a ast_view::ExprOrSpread<'a>])> {
+  let Expr::Call(call_expr) = expr else {
+    return None;
+  };
+  let Callee::Expr(Expr::Member(member)) = call_expr.callee else {
+    return None;
+  };
+  let MemberProp::Ident(prop) = member.prop else {
+    return None;
+  };
+  if call_expr.args.iter().any(|arg| arg.spread().is_some()) {
+    return None;
+  }
+  Some((member.obj, prop.sym().as_ref(), &call_expr.args))
+}
+
+/// The value of a number literal, or a negated one.
+fn number_value(expr: &Expr) -> Option<f64> {
+  match expr {
+    Expr::Lit(Lit::Num(num)) => Some(num.value()),
+    Expr::Unary(unary) if unary.op() == UnaryOp::Minus => {
+      number_value(&unary.arg).map(|value| -value)
+    }
+    _ => None,
+  }
+}
+
+/// The length of a string literal as measured by JavaScript.
+fn string_length(expr: &Expr) -> Option<usize> {
+  match expr {
+    Expr::Lit(Lit::Str(str)) => Some(str.value().encode_utf16().count()),
+    _ => None,
+  }
+}
+
+/// Checks if the expression is a string or template literal.
+fn is_string(expr: &Expr) -> bool {
+  matches!(expr, Expr::Lit(Lit::Str(_)) | Expr::Tpl(_))
+}
+
+/// Checks if the expression is known to evaluate to a string: a literal, a
+/// template, or the result of `String(...)` or `.toString()`. Arrays have
+/// `indexOf` and `slice` too, so other receivers aren't fixed.
+fn is_known_string(expr: &Expr, ctx: &Context) -> bool {
+  match expr {
+    Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => true,
+    Expr::Paren(paren) => is_known_string(&paren.expr, ctx),
+    Expr::Call(call_expr) => match call_expr.callee {
+      Callee::Expr(Expr::Ident(ident)) => {
+        *ident.sym() == *"String" && ctx.is_global_ident(ident)
+      }
+      Callee::Expr(Expr::Member(member)) => {
+        call_expr.args.is_empty()
+          && matches!(member.prop, MemberProp::Ident(prop) if prop.sym() == "toString")
+      }
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+/// Checks if the expression is `<string>.length - <n>` where `<string>` has
+/// the same text as `string`.
+fn is_length_minus(
+  expr: &Expr,
+  string: &Expr,
+  n: usize,
+  ctx: &Context,
+) -> bool {
+  let Expr::Bin(bin_expr) = expr else {
+    return false;
+  };
+  if bin_expr.op() != BinaryOp::Sub
+    || number_value(&bin_expr.right) != Some(n as f64)
+  {
+    return false;
+  }
+  matches!(bin_expr.left, Expr::Member(member)
+    if matches!(member.prop, MemberProp::Ident(prop) if prop.sym() == "length")
+      && ctx.text_of(member.obj.range()) == ctx.text_of(string.range()))
+}
+
+/// Matches `call` compared against `other`, e.g. `str.indexOf(x)` against
+/// `0`.
+fn replacement(
+  call: &Expr,
+  other: &Expr,
+  ctx: &Context,
+) -> Option<Replacement> {
+  let (string, method, args) = method_call(call)?;
+  match (method, args) {
+    // `indexOf` with a `fromIndex` isn't about the start of the string
+    ("indexOf", [value]) if number_value(other) == Some(0.0) => {
+      // Only strings are searched for, as arrays have `indexOf` too
+      if !is_string(&value.expr) {
+        return None;
+      }
+      Some(Replacement {
+        method: Method::StartsWith,
+        string: string.range(),
+        value: value.range(),
+        fixable: is_known_string(&string, ctx),
+      })
+    }
+    ("lastIndexOf", [value]) => {
+      let length = string_length(&value.expr)?;
+      if !is_length_minus(other, &string, length, ctx) {
+        return None;
+      }
+      // The comparison is also true when the string is one character shorter
+      // than the value, so replacing it changes the result
+      Some(Replacement {
+        method: Method::EndsWith,
+        string: string.range(),
+        value: value.range(),
+        fixable: false,
+      })
+    }
+    ("charAt", [index])
+      if number_value(&index.expr) == Some(0.0)
+        && string_length(other) == Some(1) =>
+    {
+      Some(Replacement {
+        method: Method::StartsWith,
+        string: string.range(),
+        value: other.range(),
+        fixable: true,
+      })
+    }
+    ("slice", [start]) => {
+      let length = string_length(other)?;
+      if length == 0 || number_value(&start.expr) != Some(-(length as f64)) {
+        return None;
+      }
+      Some(Replacement {
+        method: Method::EndsWith,
+        string: string.range(),
+        value: other.range(),
+        fixable: is_known_string(&string, ctx),
+      })
+    }
+    _ => None,
+  }
+}
+
+impl Handler for PreferStringStartsEndsWithHandler {
+  fn bin_expr(&mut self, bin_expr: &ast_view::BinExpr, ctx: &mut Context) {
+    let negated = match bin_expr.op() {
+      BinaryOp::EqEqEq | BinaryOp::EqEq => false,
+      BinaryOp::NotEqEq | BinaryOp::NotEq => true,
+      _ => return,
+    };
+    let Some(replacement) =
+      replacement(&bin_expr.left, &bin_expr.right, ctx)
+        .or_else(|| replacement(&bin_expr.right, &bin_expr.left, ctx))
+    else {
+      return;
+    };
+
+    let has_comments = ctx
+      .all_comments()
+      .any(|comment| bin_expr.range().contains(&comment.range()));
+    let fixes = if replacement.fixable && !has_comments {
+      vec![LintFix {
+        description: format!("Use `{}()`", replacement.method).into(),
+        changes: vec![LintFixChange {
+          new_text: format!(
+            "{}{}.{}({})",
+            if negated { "!" } else { "" },
+            ctx.text_of(replacement.string),
+            replacement.method,
+            ctx.text_of(replacement.value),
+          )
+          .into(),
+          range: bin_expr.range(),
+        }],
+      }]
+    } else {
+      vec![]
+    };
+    let (message, hint) = match replacement.method {
+      Method::StartsWith => (
+        PreferStringStartsEndsWithMessage::StartsWith,
+        PreferStringStartsEndsWithHint::StartsWith,
+      ),
+      Method::EndsWith => (
+        PreferStringStartsEndsWithMessage::EndsWith,
+        PreferStringStartsEndsWithHint::EndsWith,
+      ),
+    };
+    ctx.add_diagnostic_with_fixes(
+      bin_expr.range(),
+      CODE,
+      message,
+      Some(hint.to_string()),
+      fixes,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefer_string_starts_ends_with_valid() {
+    assert_lint_ok! {
+      PreferStringStartsEndsWith,
+      "str.startsWith('a');",
+      "str.endsWith('a');",
+      "str.indexOf('a') === 1;",
+      "str.indexOf('a', 1) === 0;",
+      "arr.indexOf(item) === 0;",
+      "str.indexOf('a') > 0;",
+      "str.charAt(1) === 'a';",
+      "str.charAt(0) === 'ab';",
+      "str.charAt(0) === c;",
+      "str.slice(-1) === 'ab';",
+      "str.slice(-2, -1) === 'a';",
+      "str.slice(1) === 'a';",
+      "str.lastIndexOf('ab') === str.length - 1;",
+      "str.lastIndexOf('ab') === other.length - 2;",
+    };
+  }
+
+  #[test]
+  fn prefer_string_starts_ends_with_invalid() {
+    assert_lint_err! {
+      PreferStringStartsEndsWith,
+      "if (String(str).indexOf('foo') === 0) {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: ("Use `startsWith()`", "if (String(str).startsWith('foo')) {}"),
+        }
+      ],
+      "if (0 !== path.toString().indexOf(`${root}/`)) {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: ("Use `startsWith()`", "if (!path.toString().startsWith(`${root}/`)) {}"),
+        }
+      ],
+      "if (str.charAt(0) == 'a') {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::StartsWith,
+          hint: PreferStringStartsEndsWithHint::StartsWith,
+          fix: ("Use `startsWith()`", "if (str.startsWith('a')) {}"),
+        }
+      ],
+      "if (`${name}`.slice(-1) === '/') {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+          fix: ("Use `endsWith()`", "if (`${name}`.endsWith('/')) {}"),
+        }
+      ],
+      "if (String(file).slice(-3) !== '.ts') {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+          fix: ("Use `endsWith()`", "if (!String(file).endsWith('.ts')) {}"),
+        }
+      ],
+      "if (file.lastIndexOf('.ts') === file.length - 3) {}": [
+        {
+          col: 4,
+          message: PreferStringStartsEndsWithMessage::EndsWith,
+          hint: PreferStringStartsEndsWithHint::EndsWith,
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn prefer_string_starts_ends_with_invalid_without_fix() {
+    for src in [
+      "file.lastIndexOf('.ts') === file.length - 3;",
+      "String(str).indexOf('a' /* prefix */) === 0;",
+      // The receivers may be arrays, which have `indexOf` and `slice` too
+      "str.indexOf('foo') === 0;",
+      "name.slice(-1) === '/';",
+      "function f(String) { String(str).indexOf('a') === 0; }",
+    ] {
+      let diagnostics =
+        crate::test_util::lint(&PreferStringStartsEndsWith, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+}
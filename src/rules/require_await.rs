@@ -1,5 +1,7 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange};
 use crate::handler::{Handler, Traverse};
 use crate::swc_util::StringRepr;
 
@@ -9,11 +11,14 @@ use deno_ast::SourceRange;
 use deno_ast::SourceRanged;
 use deno_ast::SourceRangedForSpanned;
 use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
 
 #[derive(Debug)]
 pub struct RequireAwait;
 
 const CODE: &str = "require-await";
+const FIX_DESC: &str = "Remove the `async` keyword";
 
 #[derive(Display)]
 enum RequireAwaitMessage {
@@ -37,6 +42,16 @@ enum RequireAwaitHint {
   RemoveOrUse,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RequireAwaitOptions {
+  /// Don't report async functions with an empty body.
+  allow_empty: bool,
+  /// Offer a fix that removes `async`. It's opt-in since callers may rely on
+  /// the function always returning a promise.
+  fix_by_removing_async: bool,
+}
+
 impl LintRule for RequireAwait {
   fn tags(&self) -> &'static [&'static str] {
     &["recommended"]
@@ -51,7 +66,12 @@ impl LintRule for RequireAwait {
     context: &mut Context,
     program: Program,
   ) {
-    RequireAwaitHandler.traverse(program, context);
+    let options: RequireAwaitOptions = context.rule_options(CODE);
+    RequireAwaitHandler { options }.traverse(program, context);
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<RequireAwaitOptions>(options)
   }
 
   #[cfg(feature = "docs")]
@@ -89,12 +109,17 @@ struct FunctionInfo {
   is_async: bool,
   is_generator: bool,
   is_empty: bool,
+  has_return_type: bool,
   has_await: bool,
 }
 
 impl FunctionInfo {
-  fn should_report(self) -> Option<RequireAwaitMessage> {
-    if self.is_async && !self.is_generator && !self.is_empty && !self.has_await
+  fn should_report(
+    self,
+    options: &RequireAwaitOptions,
+  ) -> Option<RequireAwaitMessage> {
+    let allowed_empty = self.is_empty && options.allow_empty;
+    if self.is_async && !self.is_generator && !allowed_empty && !self.has_await
     {
       Some(self.kind.into())
     } else {
@@ -103,19 +128,37 @@ impl FunctionInfo {
   }
 }
 
-fn find_async_token_range(
-  node: deno_ast::view::Node,
-  ctx: &Context,
-) -> SourceRange {
+fn find_async_token_index(node: deno_ast::view::Node, ctx: &Context) -> usize {
   node
     .tokens_fast(ctx.program())
     .iter()
-    .find(|t| t.token == Token::Word(Word::Ident("async".into())))
+    .position(|t| t.token == Token::Word(Word::Ident("async".into())))
     .expect("there must be a async span")
-    .range()
 }
 
-struct RequireAwaitHandler;
+fn find_async_token_range(
+  node: deno_ast::view::Node,
+  ctx: &Context,
+) -> SourceRange {
+  node.tokens_fast(ctx.program())[find_async_token_index(node, ctx)].range()
+}
+
+/// Removes `async` along with the whitespace up to the next token.
+fn remove_async_fix(node: deno_ast::view::Node, ctx: &Context) -> LintFix {
+  let tokens = node.tokens_fast(ctx.program());
+  let index = find_async_token_index(node, ctx);
+  LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: "".into(),
+      range: SourceRange::new(tokens[index].start(), tokens[index + 1].start()),
+    }],
+  }
+}
+
+struct RequireAwaitHandler {
+  options: RequireAwaitOptions,
+}
 
 impl Handler for RequireAwaitHandler {
   fn fn_decl(&mut self, fn_decl: &deno_ast::view::FnDecl, ctx: &mut Context) {
@@ -126,6 +169,7 @@ impl Handler for RequireAwaitHandler {
       is_async: fn_decl.function.is_async(),
       is_generator: fn_decl.function.is_generator(),
       is_empty: is_body_empty(fn_decl.function.body),
+      has_return_type: fn_decl.function.return_type.is_some(),
       has_await: false,
     };
 
@@ -135,7 +179,13 @@ impl Handler for RequireAwaitHandler {
       fn_decl.ident.range()
     };
 
-    process_function(fn_decl.as_node(), range, function_info, ctx);
+    process_function(
+      fn_decl.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 
   fn fn_expr(&mut self, fn_expr: &deno_ast::view::FnExpr, ctx: &mut Context) {
@@ -146,6 +196,7 @@ impl Handler for RequireAwaitHandler {
       is_async: fn_expr.function.is_async(),
       is_generator: fn_expr.function.is_generator(),
       is_empty: is_body_empty(fn_expr.function.body),
+      has_return_type: fn_expr.function.return_type.is_some(),
       has_await: false,
     };
     let range = if function_info.is_async {
@@ -153,7 +204,13 @@ impl Handler for RequireAwaitHandler {
     } else {
       fn_expr.range()
     };
-    process_function(fn_expr.as_node(), range, function_info, ctx);
+    process_function(
+      fn_expr.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 
   fn arrow_expr(
@@ -169,6 +226,7 @@ impl Handler for RequireAwaitHandler {
         &arrow_expr.body,
         deno_ast::view::BlockStmtOrExpr::BlockStmt(block_stmt) if block_stmt.stmts.is_empty()
       ),
+      has_return_type: arrow_expr.return_type.is_some(),
       has_await: false,
     };
     let range = if function_info.is_async {
@@ -176,7 +234,13 @@ impl Handler for RequireAwaitHandler {
     } else {
       arrow_expr.range()
     };
-    process_function(arrow_expr.as_node(), range, function_info, ctx);
+    process_function(
+      arrow_expr.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 
   fn method_prop(
@@ -189,6 +253,7 @@ impl Handler for RequireAwaitHandler {
       is_async: method_prop.function.is_async(),
       is_generator: method_prop.function.is_generator(),
       is_empty: is_body_empty(method_prop.function.body),
+      has_return_type: method_prop.function.return_type.is_some(),
       has_await: false,
     };
 
@@ -198,7 +263,13 @@ impl Handler for RequireAwaitHandler {
       method_prop.inner.key.range()
     };
 
-    process_function(method_prop.as_node(), range, function_info, ctx);
+    process_function(
+      method_prop.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 
   fn class_method(
@@ -211,6 +282,7 @@ impl Handler for RequireAwaitHandler {
       is_async: class_method.function.is_async(),
       is_generator: class_method.function.is_generator(),
       is_empty: is_body_empty(class_method.function.body),
+      has_return_type: class_method.function.return_type.is_some(),
       has_await: false,
     };
 
@@ -220,7 +292,13 @@ impl Handler for RequireAwaitHandler {
       class_method.inner.key.range()
     };
 
-    process_function(class_method.as_node(), range, function_info, ctx);
+    process_function(
+      class_method.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 
   fn private_method(
@@ -233,6 +311,7 @@ impl Handler for RequireAwaitHandler {
       is_async: private_method.function.is_async(),
       is_generator: private_method.function.is_generator(),
       is_empty: is_body_empty(private_method.function.body),
+      has_return_type: private_method.function.return_type.is_some(),
       has_await: false,
     };
     let range = if function_info.is_async {
@@ -240,7 +319,13 @@ impl Handler for RequireAwaitHandler {
     } else {
       private_method.inner.key.range()
     };
-    process_function(private_method.as_node(), range, function_info, ctx);
+    process_function(
+      private_method.as_node(),
+      range,
+      function_info,
+      &self.options,
+      ctx,
+    );
   }
 }
 
@@ -308,6 +393,7 @@ fn process_function<'a, N>(
   node: N,
   range: SourceRange,
   function_info: FunctionInfo,
+  options: &RequireAwaitOptions,
   ctx: &mut Context,
 ) where
   N: NodeTrait<'a>,
@@ -322,12 +408,20 @@ fn process_function<'a, N>(
 
   let function_info = function_handler.function_info.take().unwrap();
 
-  if let Some(message) = function_info.should_report() {
-    ctx.add_diagnostic_with_hint(
+  // Without `async`, a `Promise` return type annotation would be wrong
+  let fixable = options.fix_by_removing_async && !function_info.has_return_type;
+  if let Some(message) = function_info.should_report(options) {
+    let fixes = if fixable {
+      vec![remove_async_fix(node.as_node(), ctx)]
+    } else {
+      vec![]
+    };
+    ctx.add_diagnostic_with_fixes(
       range,
       CODE,
       message,
-      RequireAwaitHint::RemoveOrUse,
+      Some(RequireAwaitHint::RemoveOrUse.to_string()),
+      fixes,
     );
   }
 }
@@ -358,9 +452,9 @@ mod tests {
       "async function foo() { await (async () => { await doSomething() }) }",
       "async function foo() { const bar = <number>await doSomething() }",
 
-      // empty functions are ok.
-      "async function foo() {}",
-      "async () => {}",
+      // empty functions are ok when allowed.
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"allowEmpty\": true } } }\nasync function foo() {}",
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"allowEmpty\": true } } }\nasync () => {}",
 
       // normal functions are ok.
       "function foo() { doSomething() }",
@@ -392,6 +486,20 @@ async function* run() {
     };
   }
 
+  #[test]
+  fn require_await_no_fix() {
+    for src in [
+      // The fix is opt-in
+      "async function foo() { doSomething() }",
+      // The return type would have to change too
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nasync function foo(): Promise<void> { doSomething() }",
+    ] {
+      let diagnostics = crate::test_util::lint(&RequireAwait, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+
   #[test]
   fn require_await_invalid() {
     assert_lint_err! {
@@ -473,6 +581,47 @@ async function* run() {
           hint: RequireAwaitHint::RemoveOrUse,
         },
       ],
+      "async function foo() {}": [
+        {
+          col: 0,
+          message: variant!(RequireAwaitMessage, Function, "foo"),
+          hint: RequireAwaitHint::RemoveOrUse,
+        },
+      ],
+      "const f = async () => {};": [
+        {
+          col: 10,
+          message: RequireAwaitMessage::ArrowFunction,
+          hint: RequireAwaitHint::RemoveOrUse,
+        },
+      ],
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nexport async function foo() { doSomething() }": [
+        {
+          line: 3,
+          col: 7,
+          message: variant!(RequireAwaitMessage, Function, "foo"),
+          hint: RequireAwaitHint::RemoveOrUse,
+          fix: (FIX_DESC, "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nexport function foo() { doSomething() }"),
+        },
+      ],
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nconst f = async  x => doSomething(x);": [
+        {
+          line: 3,
+          col: 10,
+          message: RequireAwaitMessage::ArrowFunction,
+          hint: RequireAwaitHint::RemoveOrUse,
+          fix: (FIX_DESC, "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nconst f = x => doSomething(x);"),
+        },
+      ],
+      "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nclass A { private async foo() { doSomething() } }": [
+        {
+          line: 3,
+          col: 18,
+          message: variant!(RequireAwaitMessage, Method, "foo"),
+          hint: RequireAwaitHint::RemoveOrUse,
+          fix: (FIX_DESC, "\n// deno-lint-config { \"rules\": { \"require-await\": { \"fixByRemovingAsync\": true } } }\nclass A { private foo() { doSomething() } }"),
+        },
+      ],
     };
   }
 }
@@ -0,0 +1,109 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::Program;
+use deno_ast::SourceRange;
+
+#[derive(Debug)]
+pub struct RequireIgnoreReason;
+
+const CODE: &str = "require-ignore-reason";
+const MESSAGE: &str = "Ignore directive requires a reason";
+const HINT: &str = "Explain why the rules are ignored after `--`.  E.g. // deno-lint-ignore no-explicit-any -- the library is untyped";
+
+impl LintRule for RequireIgnoreReason {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    _program: Program,
+  ) {
+    let mut violated_ranges: Vec<SourceRange> = context
+      .file_ignore_directive()
+      .iter()
+      .filter_map(|d| d.reason().is_none().then(|| d.range()))
+      .collect();
+
+    violated_ranges.extend(
+      context
+        .line_ignore_directives()
+        .values()
+        .filter_map(|d| d.reason().is_none().then(|| d.range())),
+    );
+
+    for range in violated_ranges {
+      context.add_diagnostic_with_hint(range, CODE, MESSAGE, HINT)
+    }
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/require_ignore_reason.md")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn require_ignore_reason_valid() {
+    assert_lint_ok! {
+      RequireIgnoreReason,
+      r#"
+// deno-lint-ignore no-explicit-any -- the library is untyped
+export const foo: any = 42;
+    "#,
+      r#"
+// deno-lint-ignore-file no-explicit-any -- generated code
+export const foo: any = 42;
+    "#,
+      r#"
+// deno-lint-ignore no-explicit-any, no-empty -- see #123
+export const foo: any = 42;
+    "#,
+    };
+  }
+
+  #[test]
+  fn require_ignore_reason_invalid() {
+    assert_lint_err! {
+      RequireIgnoreReason,
+      r#"
+// deno-lint-ignore no-explicit-any
+export const foo: any = 42;
+      "#: [
+        {
+          line: 2,
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-ignore no-explicit-any --
+export const foo: any = 42;
+      "#: [
+        {
+          line: 2,
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+      r#"
+// deno-lint-ignore-file no-explicit-any
+export const foo: any = 42;
+      "#: [
+        {
+          line: 2,
+          col: 0,
+          message: MESSAGE,
+          hint: HINT,
+        }
+      ],
+    };
+  }
+}
@@ -0,0 +1,255 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+use super::{Context, LintRule};
+use crate::config::validate_rule_options;
+use crate::diagnostic::{LintFix, LintFixChange};
+use crate::sort_imports::{
+  leading_imports, ImportBlock, SortOptions, Unsorted,
+};
+use crate::Program;
+use deno_ast::{SourceRange, SourceRangedForSpanned};
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct SortImports;
+
+const CODE: &str = "sort-imports";
+const FIX_DESC: &str = "Sort imports";
+
+#[derive(Display)]
+enum SortImportsMessage {
+  #[display(fmt = "Imports should be sorted by their module specifiers")]
+  Import,
+  #[display(fmt = "Named imports should be sorted by name")]
+  Named,
+}
+
+#[derive(Display)]
+enum SortImportsHint {
+  #[display(fmt = "Move this import above the imports that sort after it")]
+  Import,
+  #[display(fmt = "Sort the names inside the braces alphabetically")]
+  Named,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum SideEffectImports {
+  /// Side effect imports stay in place, and the imports above and below them
+  /// are sorted separately.
+  #[default]
+  Keep,
+  /// Side effect imports are sorted along with the other imports.
+  Sort,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SortImportsOptions {
+  /// Compare specifiers and names case-insensitively.
+  ignore_case: bool,
+  /// Also require the named imports of each declaration to be sorted.
+  sort_named_imports: bool,
+  side_effect_imports: SideEffectImports,
+}
+
+impl Default for SortImportsOptions {
+  fn default() -> Self {
+    Self {
+      ignore_case: true,
+      sort_named_imports: true,
+      side_effect_imports: SideEffectImports::default(),
+    }
+  }
+}
+
+impl LintRule for SortImports {
+  fn code(&self) -> &'static str {
+    CODE
+  }
+
+  fn lint_program_with_ast_view(
+    &self,
+    context: &mut Context,
+    program: Program<'_>,
+  ) {
+    let Program::Module(module) = program else {
+      return;
+    };
+    let options: SortImportsOptions = context.rule_options(CODE);
+    let start = context.text_info().range().start;
+    let block = ImportBlock {
+      source: context.text_info().text_str(),
+      imports: leading_imports(&module.inner.body, start),
+      comments: context
+        .all_comments()
+        .map(|comment| comment.range().as_byte_range(start))
+        .collect(),
+      options: SortOptions {
+        ignore_case: options.ignore_case,
+        sort_named: options.sort_named_imports,
+        sort_side_effects: options.side_effect_imports
+          == SideEffectImports::Sort,
+      },
+    };
+    let Some((import, unsorted)) = block.first_unsorted() else {
+      return;
+    };
+
+    let range =
+      SourceRange::new(start + import.range.start, start + import.range.end);
+    let fixes = block
+      .sorted()
+      .map(|(block_range, sorted)| LintFix {
+        description: FIX_DESC.into(),
+        changes: vec![LintFixChange {
+          new_text: sorted.into(),
+          range: SourceRange::new(
+            start + block_range.start,
+            start + block_range.end,
+          ),
+        }],
+      })
+      .into_iter()
+      .collect();
+    let (message, hint) = match unsorted {
+      Unsorted::Import => (SortImportsMessage::Import, SortImportsHint::Import),
+      Unsorted::Named => (SortImportsMessage::Named, SortImportsHint::Named),
+    };
+    context.add_diagnostic_with_fixes(
+      range,
+      CODE,
+      message,
+      Some(hint.to_string()),
+      fixes,
+    );
+  }
+
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<SortImportsOptions>(options)
+  }
+
+  #[cfg(feature = "docs")]
+  fn docs(&self) -> &'static str {
+    include_str!("../../docs/rules/sort_imports.md")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sort_imports_valid() {
+    assert_lint_ok! {
+      SortImports,
+      "import { a } from \"./a.ts\";\nimport { b } from \"./b.ts\";",
+      r#"import { A } from "./A.ts";
+import { b } from "./b.ts";
+import { C } from "./C.ts";"#,
+      r#"import Default, { a, b as c, d } from "./mod.ts";"#,
+      // Groups are sorted separately
+      r#"import { z } from "./z.ts";
+
+import { a } from "./a.ts";"#,
+      r#"import { z } from "./z.ts";
+import "./polyfill.ts";
+import { a } from "./a.ts";"#,
+      // Only the imports at the start of the file are checked
+      r#"import { b } from "./b.ts";
+foo();
+import { a } from "./a.ts";"#,
+      r#"
+// deno-lint-config { "rules": { "sort-imports": { "sortNamedImports": false } } }
+import { b, a } from "./mod.ts";
+"#,
+    };
+  }
+
+  #[test]
+  fn sort_imports_invalid() {
+    assert_lint_err! {
+      SortImports,
+      r#"import { b } from "./b.ts";
+// Parses input
+import { a } from "./a.ts";"#: [
+        {
+          line: 3,
+          col: 0,
+          message: SortImportsMessage::Import,
+          hint: SortImportsHint::Import,
+          fix: (FIX_DESC, r#"// Parses input
+import { a } from "./a.ts";
+import { b } from "./b.ts";"#),
+        }
+      ],
+      r#"import { b, a as c, type A } from "./mod.ts";"#: [
+        {
+          col: 0,
+          message: SortImportsMessage::Named,
+          hint: SortImportsHint::Named,
+          fix: (FIX_DESC, r#"import { type A, b, a as c } from "./mod.ts";"#),
+        }
+      ],
+      r#"import { y, x } from "./b.ts";
+import { a } from "./a.ts";
+"#: [
+        {
+          col: 0,
+          message: SortImportsMessage::Named,
+          hint: SortImportsHint::Named,
+          fix: (FIX_DESC, r#"import { a } from "./a.ts";
+import { x, y } from "./b.ts";
+"#),
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "sort-imports": { "ignoreCase": false } } }
+import { b } from "./b.ts";
+import { C } from "./C.ts";
+"#: [
+        {
+          line: 4,
+          col: 0,
+          message: SortImportsMessage::Import,
+          hint: SortImportsHint::Import,
+          fix: (FIX_DESC, r#"
+// deno-lint-config { "rules": { "sort-imports": { "ignoreCase": false } } }
+import { C } from "./C.ts";
+import { b } from "./b.ts";
+"#),
+        }
+      ],
+      r#"
+// deno-lint-config { "rules": { "sort-imports": { "sideEffectImports": "sort" } } }
+import { z } from "./z.ts";
+import "./polyfill.ts";
+"#: [
+        {
+          line: 4,
+          col: 0,
+          message: SortImportsMessage::Import,
+          hint: SortImportsHint::Import,
+          fix: (FIX_DESC, r#"
+// deno-lint-config { "rules": { "sort-imports": { "sideEffectImports": "sort" } } }
+import "./polyfill.ts";
+import { z } from "./z.ts";
+"#),
+        }
+      ],
+    };
+  }
+
+  #[test]
+  fn sort_imports_invalid_without_fix() {
+    for src in [
+      r#"import { b } from "./b.ts"; import { a } from "./a.ts";"#,
+      r#"import { b, /* first */ a } from "./mod.ts";"#,
+    ] {
+      let diagnostics = crate::test_util::lint(&SortImports, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1);
+      assert!(diagnostics[0].fixes.is_empty());
+    }
+  }
+}
@@ -1,8 +1,9 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::{Context, LintRule};
+use crate::diagnostic::{LintFix, LintFixChange};
 use crate::handler::{Handler, Traverse};
 use crate::Program;
-use deno_ast::view::{BinExpr, BinaryOp, Expr, Ident, SwitchStmt};
+use deno_ast::view::{BinExpr, BinaryOp, Expr, MemberProp, SwitchStmt};
 use deno_ast::SourceRanged;
 use derive_more::Display;
 
@@ -10,6 +11,7 @@ use derive_more::Display;
 pub struct UseIsNaN;
 
 const CODE: &str = "use-isnan";
+const FIX_DESC: &str = "Use `Number.isNaN` instead";
 
 #[derive(Display)]
 enum UseIsNaNMessage {
@@ -52,8 +54,55 @@ impl LintRule for UseIsNaN {
 
 struct UseIsNaNHandler;
 
-fn is_nan_identifier(ident: &Ident) -> bool {
-  *ident.sym() == *"NaN"
+/// Checks if the expression is the global `NaN` or `Number.NaN`.
+fn is_nan(expr: &Expr, ctx: &Context) -> bool {
+  match expr {
+    // swc's resolver binds every `NaN` to the global, even where a local
+    // `NaN` is declared, so look for declarations by name
+    Expr::Ident(ident) => {
+      *ident.sym() == *"NaN"
+        && ctx.is_global_ident(ident)
+        && ctx.scope().ids_with_symbol(ident.sym()).is_none()
+    }
+    Expr::Member(member_expr) => {
+      let is_number = match member_expr.obj {
+        Expr::Ident(obj) => *obj.sym() == *"Number" && ctx.is_global_ident(obj),
+        _ => false,
+      };
+      is_number
+        && matches!(member_expr.prop, MemberProp::Ident(prop) if *prop.sym() == *"NaN")
+    }
+    _ => false,
+  }
+}
+
+/// Creates a fix replacing an (in)equality comparison with `Number.isNaN`.
+fn number_is_nan_fix(
+  bin_expr: &BinExpr,
+  operand: &Expr,
+  ctx: &Context,
+) -> Option<LintFix> {
+  let negation = match bin_expr.op() {
+    BinaryOp::EqEq | BinaryOp::EqEqEq => "",
+    BinaryOp::NotEq | BinaryOp::NotEqEq => "!",
+    _ => return None,
+  };
+  // `Number.isNaN` would refer to something else if `Number` is declared
+  if ctx.scope().ids_with_symbol(&"Number".into()).is_some() {
+    return None;
+  }
+  Some(LintFix {
+    description: FIX_DESC.into(),
+    changes: vec![LintFixChange {
+      new_text: format!(
+        "{}Number.isNaN({})",
+        negation,
+        ctx.text_of(operand.range())
+      )
+      .into(),
+      range: bin_expr.range(),
+    }],
+  })
 }
 
 impl Handler for UseIsNaNHandler {
@@ -67,47 +116,38 @@ impl Handler for UseIsNaNHandler {
       || bin_expr.op() == BinaryOp::Gt
       || bin_expr.op() == BinaryOp::GtEq
     {
-      if let Expr::Ident(ident) = bin_expr.left {
-        if is_nan_identifier(ident) {
-          ctx.add_diagnostic(
-            bin_expr.range(),
-            CODE,
-            UseIsNaNMessage::Comparison,
-          );
-        }
-      }
-      if let Expr::Ident(ident) = bin_expr.right {
-        if is_nan_identifier(ident) {
-          ctx.add_diagnostic(
-            bin_expr.range(),
-            CODE,
-            UseIsNaNMessage::Comparison,
-          );
-        }
-      }
+      let operand = if is_nan(&bin_expr.right, ctx) {
+        &bin_expr.left
+      } else if is_nan(&bin_expr.left, ctx) {
+        &bin_expr.right
+      } else {
+        return;
+      };
+      let fixes = number_is_nan_fix(bin_expr, operand, ctx)
+        .into_iter()
+        .collect();
+      ctx.add_diagnostic_with_fixes(
+        bin_expr.range(),
+        CODE,
+        UseIsNaNMessage::Comparison,
+        None,
+        fixes,
+      );
     }
   }
 
   fn switch_stmt(&mut self, switch_stmt: &SwitchStmt, ctx: &mut Context) {
-    if let Expr::Ident(ident) = switch_stmt.discriminant {
-      if is_nan_identifier(ident) {
-        ctx.add_diagnostic(
-          switch_stmt.range(),
-          CODE,
-          UseIsNaNMessage::SwitchUnmatched,
-        );
-      }
+    if is_nan(&switch_stmt.discriminant, ctx) {
+      ctx.add_diagnostic(
+        switch_stmt.range(),
+        CODE,
+        UseIsNaNMessage::SwitchUnmatched,
+      );
     }
 
     for case in &switch_stmt.cases {
-      if let Some(Expr::Ident(ident)) = &case.test {
-        if is_nan_identifier(ident) {
-          ctx.add_diagnostic(
-            case.range(),
-            CODE,
-            UseIsNaNMessage::CaseUnmatched,
-          );
-        }
+      if matches!(&case.test, Some(test) if is_nan(test, ctx)) {
+        ctx.add_diagnostic(case.range(), CODE, UseIsNaNMessage::CaseUnmatched);
       }
     }
   }
@@ -148,6 +188,14 @@ mod tests {
       "foo(2 / Number.NaN)",
       "var x; if (x = Number.NaN) { }",
       "x === Number[NaN];",
+
+      // A shadowed `Number` isn't the builtin
+      "const Number = { NaN: 1 }; x === Number.NaN;",
+      "function f(Number) { switch (Number.NaN) { case Number.NaN: break; } }",
+
+      // A shadowed `NaN` isn't the builtin either
+      "function f() { const NaN = 1; return x === NaN; }",
+      "function f(NaN) { switch (NaN) { case NaN: break; } }",
     };
   }
 
@@ -156,11 +204,68 @@ mod tests {
     assert_lint_err! {
       UseIsNaN,
       "42 === NaN": [
+      {
+        col: 0,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "Number.isNaN(42)"),
+      }],
+      "if (x == NaN) {}": [
+      {
+        col: 4,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "if (Number.isNaN(x)) {}"),
+      }],
+      "if (x !== NaN) {}": [
+      {
+        col: 4,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "if (!Number.isNaN(x)) {}"),
+      }],
+      "if (NaN != foo.bar()) {}": [
+      {
+        col: 4,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "if (!Number.isNaN(foo.bar())) {}"),
+      }],
+      "x === Number.NaN": [
+      {
+        col: 0,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "Number.isNaN(x)"),
+      }],
+      "Number.NaN !== a + b": [
+      {
+        col: 0,
+        message: UseIsNaNMessage::Comparison,
+        fix: (FIX_DESC, "!Number.isNaN(a + b)"),
+      }],
+      "x < NaN": [
+      {
+        col: 0,
+        message: UseIsNaNMessage::Comparison,
+      }],
+      "x >= Number.NaN": [
       {
         col: 0,
         message: UseIsNaNMessage::Comparison,
       }],
       r#"
+switch (Number.NaN) {
+  case Number.NaN:
+    break;
+}
+        "#: [
+      {
+        line: 2,
+        col: 0,
+        message: UseIsNaNMessage::SwitchUnmatched,
+      },
+      {
+        line: 3,
+        col: 2,
+        message: UseIsNaNMessage::CaseUnmatched,
+      }],
+      r#"
 switch (NaN) {
   case NaN:
     break;
@@ -180,4 +285,13 @@ switch (NaN) {
       }],
     }
   }
+
+  #[test]
+  fn use_isnan_invalid_without_fix() {
+    for src in ["x < NaN;", "NaN >= x;", "const Number = {}; x === NaN;"] {
+      let diagnostics = crate::test_util::lint(&UseIsNaN, src, "foo.ts");
+      assert_eq!(diagnostics.len(), 1, "{}", src);
+      assert!(diagnostics[0].fixes.is_empty(), "{}", src);
+    }
+  }
 }
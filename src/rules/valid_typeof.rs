@@ -1,6 +1,7 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 use super::program_ref;
 use super::{Context, LintRule};
+use crate::config::validate_rule_options;
 use crate::swc_util::StringRepr;
 use crate::Program;
 use crate::ProgramRef;
@@ -10,7 +11,11 @@ use deno_ast::swc::ast::Expr::{Lit, Tpl, Unary};
 use deno_ast::swc::ast::Lit::Str;
 use deno_ast::swc::ast::UnaryOp::TypeOf;
 use deno_ast::swc::visit::{noop_visit_type, Visit};
+use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
+use derive_more::Display;
+use serde::Deserialize;
+use serde_json::Value;
 
 #[derive(Debug)]
 pub struct ValidTypeof;
@@ -18,6 +23,45 @@ pub struct ValidTypeof;
 const CODE: &str = "valid-typeof";
 const MESSAGE: &str = "Invalid typeof comparison value";
 
+const VALID_TYPEOF_STRINGS: [&str; 8] = [
+  "undefined",
+  "object",
+  "boolean",
+  "number",
+  "string",
+  "function",
+  "symbol",
+  "bigint",
+];
+
+#[derive(Display)]
+enum ValidTypeofHint {
+  #[display(fmt = "Did you mean \"{}\"?", _0)]
+  DidYouMean(String),
+  #[display(
+    fmt = "`typeof` can only return \"undefined\", \"object\", \"boolean\", \"number\", \"string\", \"function\", \"symbol\" or \"bigint\""
+  )]
+  UseValidString,
+  #[display(fmt = "Compare against a string literal, e.g. \"undefined\"")]
+  UseStringLiteral,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ValidTypeofOptions {
+  /// If set, `typeof` results can only be compared against string literals
+  /// or other `typeof` results, and not against e.g. variables.
+  require_string_literals: bool,
+}
+
+impl Default for ValidTypeofOptions {
+  fn default() -> Self {
+    Self {
+      require_string_literals: true,
+    }
+  }
+}
+
 impl LintRule for ValidTypeof {
   fn tags(&self) -> &'static [&'static str] {
     &["recommended"]
@@ -32,14 +76,19 @@ impl LintRule for ValidTypeof {
     context: &mut Context,
     program: Program,
   ) {
+    let options: ValidTypeofOptions = context.rule_options(CODE);
     let program = program_ref(program);
-    let mut visitor = ValidTypeofVisitor::new(context);
+    let mut visitor = ValidTypeofVisitor::new(context, options);
     match program {
       ProgramRef::Module(m) => visitor.visit_module(m),
       ProgramRef::Script(s) => visitor.visit_script(s),
     }
   }
 
+  fn validate_options(&self, options: &Value) -> Result<(), String> {
+    validate_rule_options::<ValidTypeofOptions>(options)
+  }
+
   #[cfg(feature = "docs")]
   fn docs(&self) -> &'static str {
     include_str!("../../docs/rules/valid_typeof.md")
@@ -48,11 +97,24 @@ impl LintRule for ValidTypeof {
 
 struct ValidTypeofVisitor<'c, 'view> {
   context: &'c mut Context<'view>,
+  options: ValidTypeofOptions,
 }
 
 impl<'c, 'view> ValidTypeofVisitor<'c, 'view> {
-  fn new(context: &'c mut Context<'view>) -> Self {
-    Self { context }
+  fn new(context: &'c mut Context<'view>, options: ValidTypeofOptions) -> Self {
+    Self { context, options }
+  }
+
+  fn check_string(&mut self, value: &str, range: SourceRange) {
+    if !is_valid_typeof_string(value) {
+      let hint = match closest_typeof_string(value) {
+        Some(closest) => ValidTypeofHint::DidYouMean(closest.to_string()),
+        None => ValidTypeofHint::UseValidString,
+      };
+      self
+        .context
+        .add_diagnostic_with_hint(range, CODE, MESSAGE, hint);
+    }
   }
 }
 
@@ -70,22 +132,21 @@ impl<'c, 'view> Visit for ValidTypeofVisitor<'c, 'view> {
       {
         match operand {
           Unary(unary) if unary.op == TypeOf => {}
-          Lit(Str(str)) => {
-            if !is_valid_typeof_string(&str.value) {
-              self.context.add_diagnostic(str.range(), CODE, MESSAGE);
-            }
-          }
+          Lit(Str(str)) => self.check_string(&str.value, str.range()),
           Tpl(tpl) => {
-            if tpl
-              .string_repr()
-              .map_or(false, |s| !is_valid_typeof_string(&s))
-            {
-              self.context.add_diagnostic(tpl.range(), CODE, MESSAGE);
+            if let Some(s) = tpl.string_repr() {
+              self.check_string(&s, tpl.range());
             }
           }
-          _ => {
-            self.context.add_diagnostic(operand.range(), CODE, MESSAGE);
+          _ if self.options.require_string_literals => {
+            self.context.add_diagnostic_with_hint(
+              operand.range(),
+              CODE,
+              MESSAGE,
+              ValidTypeofHint::UseStringLiteral,
+            );
           }
+          _ => {}
         }
       }
       _ => {}
@@ -94,17 +155,35 @@ impl<'c, 'view> Visit for ValidTypeofVisitor<'c, 'view> {
 }
 
 fn is_valid_typeof_string(str: &str) -> bool {
-  matches!(
-    str,
-    "undefined"
-      | "object"
-      | "boolean"
-      | "number"
-      | "string"
-      | "function"
-      | "symbol"
-      | "bigint"
-  )
+  VALID_TYPEOF_STRINGS.contains(&str)
+}
+
+/// Finds the valid `typeof` result that the given string is most likely a
+/// typo of, if any is close enough.
+fn closest_typeof_string(str: &str) -> Option<&'static str> {
+  let str = str.to_lowercase();
+  VALID_TYPEOF_STRINGS
+    .iter()
+    .map(|valid| (*valid, edit_distance(&str, valid)))
+    .filter(|(valid, distance)| *distance <= (valid.len() / 3).max(2))
+    .min_by_key(|(_, distance)| *distance)
+    .map(|(valid, _)| valid)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut row = (0..=b.len()).collect::<Vec<_>>();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut prev_diagonal = row[0];
+    row[0] = i + 1;
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = prev_diagonal + usize::from(a_char != *b_char);
+      prev_diagonal = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+    }
+  }
+  row[b.len()]
 }
 
 trait EqExpr {
@@ -154,6 +233,13 @@ mod tests {
       r#"typeof foo !== `bigint`"#,
 
       r#"typeof bar != typeof qux"#,
+
+      // Non-literals are allowed if configured
+      r#"
+// deno-lint-config { "rules": { "valid-typeof": { "requireStringLiterals": false } } }
+typeof foo === undefined;
+typeof bar == type;
+      "#,
     };
   }
 
@@ -163,31 +249,62 @@ mod tests {
       ValidTypeof,
       r#"typeof foo === "strnig""#: [{
         col: 15,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("string".to_string()),
       }],
       r#"typeof foo == "undefimed""#: [{
         col: 14,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("undefined".to_string()),
       }],
       r#"typeof bar != "nunber""#: [{
         col: 14,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("number".to_string()),
       }],
       r#"typeof bar !== "fucntion""#: [{
         col: 15,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("function".to_string()),
       }],
       r#"typeof foo === undefined"#: [{
         col: 15,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::UseStringLiteral,
       }],
       r#"typeof bar == Object"#: [{
         col: 14,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::UseStringLiteral,
       }],
       r#"typeof baz === anotherVariable"#: [{
         col: 15,
-        message: MESSAGE
+        message: MESSAGE,
+        hint: ValidTypeofHint::UseStringLiteral,
+      }],
+      r#"typeof foo === "String""#: [{
+        col: 15,
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("string".to_string()),
+      }],
+      r#"typeof foo === `nubmer`"#: [{
+        col: 15,
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("number".to_string()),
+      }],
+      r#"typeof foo === "array""#: [{
+        col: 15,
+        message: MESSAGE,
+        hint: ValidTypeofHint::UseValidString,
+      }],
+      r#"
+// deno-lint-config { "rules": { "valid-typeof": { "requireStringLiterals": false } } }
+typeof foo === "strnig"
+      "#: [{
+        line: 3,
+        col: 15,
+        message: MESSAGE,
+        hint: ValidTypeofHint::DidYouMean("string".to_string()),
       }],
     }
   }
@@ -0,0 +1,449 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+//! Sorting of the import declarations at the start of a file, used by the
+//! `sort-imports` rule and to sort imports that fixes changed.
+
+use crate::ast_parser::parse_program;
+use deno_ast::swc::ast::{
+  ImportDecl, ImportSpecifier, ModuleDecl, ModuleItem, Program,
+};
+use deno_ast::{MediaType, SourceRangedForSpanned, StartSourcePos};
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// An import declaration at the start of the file, in byte offsets.
+pub(crate) struct Import {
+  pub(crate) range: Range<usize>,
+  specifier: String,
+  /// Imports without bindings, e.g. `import "./polyfill.ts";`, run for their
+  /// side effects, so by default other imports are never moved across them.
+  is_side_effect: bool,
+  /// The named imports and their local names, e.g. `b as c` and `c` in
+  /// `import { b as c } from "./b.ts";`.
+  named: Vec<(Range<usize>, String)>,
+}
+
+impl Import {
+  fn new(import: &ImportDecl, start: StartSourcePos) -> Self {
+    Import {
+      range: import.range().as_byte_range(start),
+      specifier: import.src.value.to_string(),
+      is_side_effect: import.specifiers.is_empty() && !import.type_only,
+      named: import
+        .specifiers
+        .iter()
+        .filter_map(|specifier| match specifier {
+          ImportSpecifier::Named(named) => Some((
+            named.range().as_byte_range(start),
+            named.local.sym.to_string(),
+          )),
+          _ => None,
+        })
+        .collect(),
+    }
+  }
+}
+
+/// The import declarations that a module starts with.
+pub(crate) fn leading_imports(
+  body: &[ModuleItem],
+  start: StartSourcePos,
+) -> Vec<Import> {
+  body
+    .iter()
+    .map_while(|item| match item {
+      ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+        Some(Import::new(import, start))
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Parses the file and returns its leading imports along with the ranges of
+/// all comments.
+fn parse_leading_imports(
+  file_name: &str,
+  media_type: MediaType,
+  source: &str,
+) -> Option<(Vec<Import>, Vec<Range<usize>>)> {
+  let parsed_source = parse_program(
+    file_name,
+    deno_ast::get_syntax(media_type),
+    source.to_string(),
+  )
+  .ok()?;
+  let start = parsed_source.text_info().range().start;
+  let Program::Module(module) = parsed_source.program_ref() else {
+    return None;
+  };
+  let comments = parsed_source
+    .comments()
+    .get_vec()
+    .iter()
+    .map(|comment| comment.range().as_byte_range(start))
+    .collect();
+  Some((leading_imports(&module.body, start), comments))
+}
+
+/// The byte range from the start of the first import declaration of the file
+/// to the end of the last one.
+pub(crate) fn import_block_range(
+  file_name: &str,
+  media_type: MediaType,
+  source: &str,
+) -> Option<Range<usize>> {
+  let (imports, _) = parse_leading_imports(file_name, media_type, source)?;
+  Some(imports.first()?.range.start..imports.last()?.range.end)
+}
+
+/// Sorts the import declarations at the start of the file by their
+/// specifiers. Returns `None` if they're already sorted or can't be sorted
+/// line by line, e.g. because two of them share a line.
+pub(crate) fn sort_import_block(
+  file_name: &str,
+  media_type: MediaType,
+  source: &str,
+) -> Option<String> {
+  let (imports, comments) =
+    parse_leading_imports(file_name, media_type, source)?;
+  let block = ImportBlock {
+    source,
+    imports,
+    comments,
+    options: SortOptions::default(),
+  };
+  let (range, sorted) = block.sorted()?;
+  Some(format!(
+    "{}{}{}",
+    &source[..range.start],
+    sorted,
+    &source[range.end..]
+  ))
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SortOptions {
+  /// Compare specifiers and names case-insensitively.
+  pub(crate) ignore_case: bool,
+  /// Also sort the named imports of each declaration.
+  pub(crate) sort_named: bool,
+  /// Sort side effect imports along with the others instead of keeping them
+  /// in place.
+  pub(crate) sort_side_effects: bool,
+}
+
+/// What's out of order in an import declaration.
+pub(crate) enum Unsorted {
+  /// The declaration should come before the one above it.
+  Import,
+  /// Its named imports aren't sorted.
+  Named,
+}
+
+/// The import declarations at the start of a file.
+///
+/// Blank lines, and side effect imports unless they're sorted too, split the
+/// imports into groups that are sorted separately. Comments on the lines
+/// above an import move with it, except for those above the first import,
+/// which usually describe the file.
+pub(crate) struct ImportBlock<'a> {
+  pub(crate) source: &'a str,
+  pub(crate) imports: Vec<Import>,
+  /// The ranges of all comments in the file.
+  pub(crate) comments: Vec<Range<usize>>,
+  pub(crate) options: SortOptions,
+}
+
+/// The import declaration together with the comment lines directly above it
+/// and a comment after it on the same line.
+struct Entry<'a> {
+  range: Range<usize>,
+  import: &'a Import,
+}
+
+impl<'a> ImportBlock<'a> {
+  fn compare(&self, a: &str, b: &str) -> Ordering {
+    if self.options.ignore_case {
+      a.to_lowercase().cmp(&b.to_lowercase())
+    } else {
+      a.cmp(b)
+    }
+  }
+
+  fn in_comment(&self, pos: usize) -> bool {
+    self
+      .comments
+      .iter()
+      .any(|comment| comment.start < pos && pos < comment.end)
+  }
+
+  /// Checks if there's an empty line between the two positions that isn't
+  /// part of a comment.
+  fn has_blank_line(&self, range: Range<usize>) -> bool {
+    let newlines = self.source[range.clone()]
+      .match_indices('\n')
+      .map(|(i, _)| range.start + i)
+      .collect::<Vec<_>>();
+    newlines.windows(2).any(|pair| {
+      self.source[pair[0] + 1..pair[1]].trim().is_empty()
+        && !self.in_comment(pair[0])
+    })
+  }
+
+  /// The ranges of indexes of the imports in each group.
+  fn groups(&self) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut group_start = 0;
+    for (i, import) in self.imports.iter().enumerate() {
+      let is_barrier = import.is_side_effect && !self.options.sort_side_effects;
+      let splits = is_barrier
+        || (i > 0
+          && self
+            .has_blank_line(self.imports[i - 1].range.end..import.range.start));
+      if splits && group_start < i {
+        groups.push(group_start..i);
+        group_start = i;
+      }
+      if is_barrier {
+        groups.push(i..i + 1);
+        group_start = i + 1;
+      }
+    }
+    if group_start < self.imports.len() {
+      groups.push(group_start..self.imports.len());
+    }
+    groups
+  }
+
+  /// The indexes of the named imports in sorted order.
+  fn sorted_named(&self, import: &Import) -> Vec<usize> {
+    let mut order = (0..import.named.len()).collect::<Vec<_>>();
+    if self.options.sort_named {
+      order
+        .sort_by(|a, b| self.compare(&import.named[*a].1, &import.named[*b].1));
+    }
+    order
+  }
+
+  fn has_sorted_named(&self, import: &Import) -> bool {
+    self
+      .sorted_named(import)
+      .iter()
+      .enumerate()
+      .all(|(i, j)| i == *j)
+  }
+
+  /// Finds the first import declaration that's out of order.
+  pub(crate) fn first_unsorted(&self) -> Option<(&Import, Unsorted)> {
+    for group in self.groups() {
+      for i in group.clone() {
+        let import = &self.imports[i];
+        if i > group.start
+          && self
+            .compare(&self.imports[i - 1].specifier, &import.specifier)
+            .is_gt()
+        {
+          return Some((import, Unsorted::Import));
+        }
+        if !self.has_sorted_named(import) {
+          return Some((import, Unsorted::Named));
+        }
+      }
+    }
+    None
+  }
+
+  /// The text of the entry with its named imports sorted, ending with a
+  /// newline.
+  fn entry_text(&self, entry: &Entry) -> Option<String> {
+    let named = &entry.import.named;
+    let order = self.sorted_named(entry.import);
+    let mut text = String::new();
+    let mut pos = entry.range.start;
+    if order.iter().enumerate().any(|(i, j)| i != *j) {
+      let list = named[0].0.start..named[named.len() - 1].0.end;
+      if self
+        .comments
+        .iter()
+        .any(|comment| list.start < comment.end && comment.start < list.end)
+      {
+        return None;
+      }
+      for (i, j) in order.into_iter().enumerate() {
+        text.push_str(&self.source[pos..named[i].0.start]);
+        text.push_str(&self.source[named[j].0.clone()]);
+        pos = named[i].0.end;
+      }
+    }
+    text.push_str(&self.source[pos..entry.range.end]);
+    if !text.ends_with('\n') {
+      text.push('\n');
+    }
+    Some(text)
+  }
+
+  /// Sorts the imports, returning the range of the lines they're on and the
+  /// sorted text to replace it with. Returns `None` if they're already
+  /// sorted or can't be sorted line by line, e.g. because two of them share
+  /// a line.
+  pub(crate) fn sorted(&self) -> Option<(Range<usize>, String)> {
+    self.first_unsorted()?;
+    let source = self.source;
+    let line_start =
+      |pos: usize| source[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = |pos: usize| {
+      source[pos..]
+        .find('\n')
+        .map_or(source.len(), |i| pos + i + 1)
+    };
+    let block_start = line_start(self.imports.first()?.range.start);
+    let block_end = line_end(self.imports.last()?.range.end);
+
+    // Each entry ends with the line its import ends on
+    let mut entries = Vec::with_capacity(self.imports.len());
+    let mut pos = block_start;
+    for import in &self.imports {
+      let start_line = line_start(import.range.start);
+      let end = line_end(import.range.end);
+      let rest_of_line = source[import.range.end..end].trim();
+      if import.range.start < pos
+        || !source[start_line..import.range.start].trim().is_empty()
+        || !(rest_of_line.is_empty()
+          || rest_of_line.starts_with("//")
+          || rest_of_line.starts_with("/*"))
+        || self.in_comment(end - 1)
+      {
+        return None;
+      }
+
+      // The comment lines after the last blank line move with the import
+      let mut entry_start = pos;
+      let mut offset = pos;
+      for line in source[pos..start_line].split_inclusive('\n') {
+        let in_comment = self.in_comment(offset);
+        offset += line.len();
+        if line.trim().is_empty() && !in_comment {
+          entry_start = offset;
+        }
+      }
+      entries.push(Entry {
+        range: entry_start..end,
+        import,
+      });
+      pos = end;
+    }
+
+    let mut sorted = String::with_capacity(block_end - block_start);
+    let mut sorted_pos = block_start;
+    for group in self.groups() {
+      let mut group = entries[group].iter().collect::<Vec<_>>();
+      // Keep what's between the groups, i.e. blank lines
+      sorted.push_str(&source[sorted_pos..group[0].range.start]);
+      sorted_pos = group[group.len() - 1].range.end;
+      group
+        .sort_by(|a, b| self.compare(&a.import.specifier, &b.import.specifier));
+      for entry in group {
+        sorted.push_str(&self.entry_text(entry)?);
+      }
+    }
+    sorted.push_str(&source[sorted_pos..block_end]);
+    if !source[..block_end].ends_with('\n') {
+      sorted.pop();
+    }
+    Some((block_start..block_end, sorted))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sort(source: &str) -> Option<String> {
+    sort_import_block("file.ts", MediaType::TypeScript, source)
+  }
+
+  #[test]
+  fn sorts_imports_by_specifier() {
+    assert_eq!(
+      sort(
+        "import { b } from \"./b.ts\";\nimport a from \"./a.ts\";\n\nfoo();\n"
+      )
+      .unwrap(),
+      "import a from \"./a.ts\";\nimport { b } from \"./b.ts\";\n\nfoo();\n"
+    );
+    assert_eq!(
+      sort("import { b } from \"./b.ts\";\nimport a from \"./a.ts\";").unwrap(),
+      "import a from \"./a.ts\";\nimport { b } from \"./b.ts\";"
+    );
+    assert_eq!(
+      sort("import a from \"./a.ts\";\nimport { b } from \"./b.ts\";\n"),
+      None
+    );
+  }
+
+  #[test]
+  fn keeps_comments_with_imports() {
+    let source = r#"// Copyright header
+
+import { parse } from "./parse.ts"; // local
+/*
+ * Formats output
+
+ */
+import { format } from "./format.ts";
+// Checks input
+import { check } from "./check.ts";
+"#;
+    assert_eq!(
+      sort(source).unwrap(),
+      r#"// Copyright header
+
+// Checks input
+import { check } from "./check.ts";
+/*
+ * Formats output
+
+ */
+import { format } from "./format.ts";
+import { parse } from "./parse.ts"; // local
+"#
+    );
+  }
+
+  #[test]
+  fn sorts_groups_separately() {
+    let source = r#"import { z } from "https://deno.land/z.ts";
+import { y } from "https://deno.land/y.ts";
+
+import { c } from "./c.ts";
+import "./polyfill.ts";
+import { b } from "./b.ts";
+import { a } from "./a.ts";
+"#;
+    assert_eq!(
+      sort(source).unwrap(),
+      r#"import { y } from "https://deno.land/y.ts";
+import { z } from "https://deno.land/z.ts";
+
+import { c } from "./c.ts";
+import "./polyfill.ts";
+import { a } from "./a.ts";
+import { b } from "./b.ts";
+"#
+    );
+  }
+
+  #[test]
+  fn skips_imports_sharing_a_line() {
+    assert_eq!(
+      sort("import { b } from \"./b.ts\"; import { a } from \"./a.ts\";\n"),
+      None
+    );
+    assert_eq!(
+      sort(
+        "import { b } from \"./b.ts\";\nimport { a } from \"./a.ts\"; foo();\n"
+      ),
+      None
+    );
+  }
+}
@@ -300,3 +300,69 @@ where
     PatOrExpr::Pat(p) => find_pat_ids(p),
   }
 }
+
+/// Writes the string as a JSX attribute value. Attribute values can't contain
+/// escape sequences, so `None` is returned if the string contains both kinds
+/// of quotes, or characters that would be read as an HTML entity.
+pub(crate) fn to_jsx_attribute_value(value: &str) -> Option<String> {
+  if value.contains('&') || value.contains('\n') {
+    None
+  } else if !value.contains('"') {
+    Some(format!("\"{}\"", value))
+  } else if !value.contains('\'') {
+    Some(format!("'{}'", value))
+  } else {
+    None
+  }
+}
+
+/// Writes the string as JSX text. `None` is returned if the string contains
+/// characters with a special meaning in JSX, or leading or trailing whitespace
+/// that JSX would trim.
+pub(crate) fn to_jsx_text(value: &str) -> Option<String> {
+  let has_special_chars = value.contains(['{', '}', '<', '>', '&']);
+  let has_trimmed_whitespace =
+    value.trim() != value || value.contains(['\n', '\r']);
+  if has_special_chars || has_trimmed_whitespace {
+    None
+  } else {
+    Some(value.to_string())
+  }
+}
+
+/// Checks if evaluating the expression can't have any side effects, so that
+/// it can be removed without changing anything else. Identifiers must refer
+/// to bindings declared in the file, since reading an undeclared global may
+/// throw or call a getter.
+pub(crate) fn is_side_effect_free(
+  expr: &ast_view::Expr,
+  scope: &Scope,
+) -> bool {
+  use deno_ast::view::{Expr, Prop, PropName, PropOrSpread, UnaryOp};
+  match expr {
+    Expr::Lit(_) | Expr::This(_) | Expr::Arrow(_) | Expr::Fn(_) => true,
+    Expr::Ident(ident) => scope.var(&ident.inner.to_id()).is_some(),
+    Expr::Paren(paren) => is_side_effect_free(&paren.expr, scope),
+    // Interpolated values are converted with `toString`
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    Expr::Array(array) => array.elems.iter().flatten().all(|elem| {
+      elem.spread().is_none() && is_side_effect_free(&elem.expr, scope)
+    }),
+    Expr::Object(object) => object.props.iter().all(|prop| match prop {
+      PropOrSpread::Prop(Prop::Shorthand(ident)) => {
+        scope.var(&ident.inner.to_id()).is_some()
+      }
+      PropOrSpread::Prop(Prop::KeyValue(key_value)) => {
+        !matches!(key_value.key, PropName::Computed(_))
+          && is_side_effect_free(&key_value.value, scope)
+      }
+      _ => false,
+    }),
+    // Other operators can call `valueOf` or `toString`
+    Expr::Unary(unary) => {
+      matches!(unary.op(), UnaryOp::Bang | UnaryOp::TypeOf | UnaryOp::Void)
+        && is_side_effect_free(&unary.arg, scope)
+    }
+    _ => false,
+  }
+}
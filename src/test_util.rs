@@ -1,14 +1,39 @@
 // Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
 
+//! Assertions for testing lint rules, including rules defined outside of this
+//! crate. Enabled with the `testing` feature.
+//!
+//! ```ignore
+//! use deno_lint::{assert_lint_err, assert_lint_ok};
+//!
+//! #[test]
+//! fn ban_foo() {
+//!   assert_lint_ok! { BanFoo, "bar();" };
+//!   assert_lint_err! {
+//!     BanFoo,
+//!     "foo();": [{ col: 0, message: "`foo` is banned", hint: "Use `bar`" }],
+//!   };
+//! }
+//! ```
+//!
+//! The rule can be a `'static` value like `BanFoo` or an
+//! `Arc<dyn LintRule>`.
+
 use crate::ast_parser;
+use crate::diagnostic::split_bom;
 use crate::diagnostic::LintDiagnostic;
+use crate::diagnostic::LintFix;
 use crate::linter::LinterBuilder;
 use crate::rules::LintRule;
 use deno_ast::view as ast_view;
 use deno_ast::MediaType;
 use deno_ast::ParsedSource;
+use deno_ast::StartSourcePos;
+use deno_ast::TextChange;
 use std::path::Path;
+use std::sync::Arc;
 
+/// Asserts that the rule reports nothing for each of the sources.
 #[macro_export]
 macro_rules! assert_lint_ok {
   (
@@ -22,7 +47,7 @@ macro_rules! assert_lint_ok {
     )*
   };
   ($rule:expr, $($src:literal),+ $(,)?) => {
-    assert_lint_ok! {
+    $crate::assert_lint_ok! {
       $rule,
       filename: "deno_lint_ok_test.ts",
       $($src,)*
@@ -30,6 +55,12 @@ macro_rules! assert_lint_ok {
   };
 }
 
+/// Asserts that the rule reports the given diagnostics for each of the
+/// sources, in order. A diagnostic can set its `line` (1-based, defaults to
+/// 1), `col` (0-based, defaults to 0), `message`, `hint` and `fix`, which is
+/// the description of a fix along with the source after applying it. The
+/// message and hint can also be given once for all diagnostics, after the
+/// rule.
 #[macro_export]
 macro_rules! assert_lint_err {
   (
@@ -39,7 +70,7 @@ macro_rules! assert_lint_err {
     $(,)?
   ) => {
     $(
-      let errors = parse_err_test!($test);
+      let errors = $crate::parse_err_test!($test);
       let tester = $crate::test_util::LintErrTester::new(
         &$rule,
         $src,
@@ -54,7 +85,7 @@ macro_rules! assert_lint_err {
     $($src:literal : $test:tt),+
     $(,)?
   ) => {
-    assert_lint_err! {
+    $crate::assert_lint_err! {
       $rule,
       filename: "deno_lint_err_test.ts",
       $($src: $test,)*
@@ -70,7 +101,7 @@ macro_rules! assert_lint_err {
     $(,)?
   ) => {
     $(
-      let errors = parse_err_test!($message, $hint, $test);
+      let errors = $crate::parse_err_test!($message, $hint, $test);
       let tester = $crate::test_util::LintErrTester::new(
         &$rule,
         $src,
@@ -87,7 +118,7 @@ macro_rules! assert_lint_err {
     $($src:literal : $test:tt),+
     $(,)?
   ) => {
-    assert_lint_err! {
+    $crate::assert_lint_err! {
       $rule,
       $message,
       $hint,
@@ -97,6 +128,7 @@ macro_rules! assert_lint_err {
   };
 }
 
+/// Creates a message or hint enum variant, converting its values to strings.
 #[macro_export]
 macro_rules! variant {
   ($enum:ident, $variant:ident) => {{
@@ -111,6 +143,8 @@ macro_rules! variant {
   }};
 }
 
+#[doc(hidden)]
+#[macro_export]
 macro_rules! parse_err_test {
   (
     [
@@ -139,7 +173,7 @@ macro_rules! parse_err_test {
       errors : $errors:tt $(,)?
     }
   ) => {{
-    let (errors, _) = parse_err_test!($errors);
+    let (errors, _) = $crate::parse_err_test!($errors);
     (errors, $filename)
   }};
 
@@ -154,7 +188,7 @@ macro_rules! parse_err_test {
       ),* $(,)?
     ]
   ) => {{
-    let errors = parse_err_test!(
+    let errors = $crate::parse_err_test!(
       $(
         [
           {
@@ -171,16 +205,58 @@ macro_rules! parse_err_test {
   }};
 }
 
+/// A rule to test, either a `'static` one as passed to
+/// [`LinterBuilder::rules`] or a plugin rule as passed to
+/// [`LinterBuilder::plugin_rule`].
+#[derive(Clone)]
+pub enum TestRule {
+  Static(&'static dyn LintRule),
+  Plugin(Arc<dyn LintRule>),
+}
+
+impl TestRule {
+  fn code(&self) -> &'static str {
+    match self {
+      TestRule::Static(rule) => rule.code(),
+      TestRule::Plugin(rule) => rule.code(),
+    }
+  }
+}
+
+impl<R: LintRule> From<&'static R> for TestRule {
+  fn from(rule: &'static R) -> Self {
+    TestRule::Static(rule)
+  }
+}
+
+impl From<&'static dyn LintRule> for TestRule {
+  fn from(rule: &'static dyn LintRule) -> Self {
+    TestRule::Static(rule)
+  }
+}
+
+impl From<Arc<dyn LintRule>> for TestRule {
+  fn from(rule: Arc<dyn LintRule>) -> Self {
+    TestRule::Plugin(rule)
+  }
+}
+
+impl From<&Arc<dyn LintRule>> for TestRule {
+  fn from(rule: &Arc<dyn LintRule>) -> Self {
+    TestRule::Plugin(rule.clone())
+  }
+}
+
 pub struct LintErrTester {
   src: &'static str,
   errors: Vec<LintErr>,
   filename: &'static str,
-  rule: &'static dyn LintRule,
+  rule: TestRule,
 }
 
 impl LintErrTester {
   pub fn new(
-    rule: &'static dyn LintRule,
+    rule: impl Into<TestRule>,
     src: &'static str,
     errors: Vec<LintErr>,
     filename: &'static str,
@@ -189,7 +265,7 @@ impl LintErrTester {
       src,
       errors,
       filename,
-      rule,
+      rule: rule.into(),
     }
   }
 
@@ -200,9 +276,10 @@ impl LintErrTester {
     assert_eq!(
       self.errors.len(),
       diagnostics.len(),
-      "{} diagnostics expected, but got {}.\n\nsource:\n{}\n",
+      "{} diagnostics expected, but got {}:\n{:#?}\n\nsource:\n{}\n",
       self.errors.len(),
       diagnostics.len(),
+      diagnostics,
       self.src,
     );
 
@@ -212,6 +289,7 @@ impl LintErrTester {
         col,
         message,
         hint,
+        fix,
       } = error;
       assert_diagnostic_2(
         diagnostic,
@@ -222,6 +300,9 @@ impl LintErrTester {
         message,
         hint.as_deref(),
       );
+      if let Some((description, expected)) = fix {
+        assert_fix(diagnostic, self.src, description, expected);
+      }
     }
   }
 }
@@ -232,6 +313,8 @@ pub struct LintErr {
   pub col: usize,
   pub message: String,
   pub hint: Option<String>,
+  /// Description of an expected fix and the source after applying it.
+  pub fix: Option<(String, String)>,
 }
 
 #[derive(Default)]
@@ -240,6 +323,7 @@ pub struct LintErrBuilder {
   col: Option<usize>,
   message: Option<String>,
   hint: Option<String>,
+  fix: Option<(String, String)>,
 }
 
 impl LintErrBuilder {
@@ -269,25 +353,36 @@ impl LintErrBuilder {
     self
   }
 
+  pub fn fix(&mut self, fix: (impl ToString, impl ToString)) -> &mut Self {
+    self.fix = Some((fix.0.to_string(), fix.1.to_string()));
+    self
+  }
+
   pub fn build(self) -> LintErr {
     LintErr {
       line: self.line.unwrap_or(1),
       col: self.col.unwrap_or(0),
       message: self.message.unwrap_or_default(),
       hint: self.hint,
+      fix: self.fix,
     }
   }
 }
 
-fn lint(
-  rule: &'static dyn LintRule,
+/// Lints the source with only the given rule, panicking if it can't be
+/// parsed.
+pub fn lint(
+  rule: impl Into<TestRule>,
   source: &str,
   filename: &str,
 ) -> Vec<LintDiagnostic> {
-  let linter = LinterBuilder::default()
-    .media_type(MediaType::from_path(Path::new(filename)))
-    .rules(vec![rule])
-    .build();
+  let builder = LinterBuilder::default()
+    .media_type(MediaType::from_path(Path::new(filename)));
+  let linter = match rule.into() {
+    TestRule::Static(rule) => builder.rules(vec![rule]),
+    TestRule::Plugin(rule) => builder.plugin_rule(rule),
+  }
+  .build();
 
   match linter.lint(filename.to_string(), source.to_string()) {
     Ok((_, diagnostics)) => diagnostics,
@@ -366,8 +461,47 @@ fn assert_diagnostic_2(
   );
 }
 
+#[track_caller]
+fn assert_fix(
+  diagnostic: &LintDiagnostic,
+  source: &str,
+  description: &str,
+  expected: &str,
+) {
+  let fix = diagnostic
+    .fixes
+    .iter()
+    .find(|fix| fix.description == description)
+    .unwrap_or_else(|| {
+      panic!(
+        "Fix \"{}\" is expected, but got {:?}\n\nsource:\n{}\n",
+        description, diagnostic.fixes, source
+      )
+    });
+  let actual = apply_fix(source, fix);
+  assert_eq!(
+    expected, actual,
+    "Fixed source is expected to be \"{}\", but got \"{}\"\n\nsource:\n{}\n",
+    expected, actual, source
+  );
+}
+
+fn apply_fix(source: &str, fix: &LintFix) -> String {
+  let changes = fix
+    .changes
+    .iter()
+    .map(|change| TextChange {
+      range: change.range.as_byte_range(StartSourcePos::START_SOURCE_POS),
+      new_text: change.new_text.to_string(),
+    })
+    .collect();
+  let (bom, rest) = split_bom(source);
+  format!("{}{}", bom, deno_ast::apply_text_changes(rest, changes))
+}
+
+#[track_caller]
 pub fn assert_lint_ok(
-  rule: &'static dyn LintRule,
+  rule: impl Into<TestRule>,
   source: &str,
   filename: &'static str,
 ) {
@@ -381,7 +515,7 @@ pub fn assert_lint_ok(
 }
 
 /// Just run the specified lint on the source code to make sure it doesn't panic.
-pub fn assert_lint_not_panic(rule: &'static dyn LintRule, source: &str) {
+pub fn assert_lint_not_panic(rule: impl Into<TestRule>, source: &str) {
   let _result = lint(rule, source, TEST_FILE_NAME);
 }
 
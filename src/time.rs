@@ -0,0 +1,28 @@
+// Copyright 2020-2021 the Deno authors. All rights reserved. MIT license.
+
+//! The clock used to log how long the linter takes. `std::time::Instant`
+//! panics on `wasm32-unknown-unknown`, so on WebAssembly, or with the `wasm`
+//! feature, nothing is measured and every duration is zero.
+
+#[cfg(not(any(feature = "wasm", target_arch = "wasm32")))]
+pub(crate) use std::time::Instant;
+
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Instant;
+
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+impl Instant {
+  pub(crate) fn now() -> Self {
+    Instant
+  }
+}
+
+#[cfg(any(feature = "wasm", target_arch = "wasm32"))]
+impl std::ops::Sub for Instant {
+  type Output = std::time::Duration;
+
+  fn sub(self, _earlier: Self) -> Self::Output {
+    std::time::Duration::ZERO
+  }
+}
@@ -107,6 +107,10 @@ function Rule(props: { rule: RuleData }) {
             <Badge color="blue">Recommended</Badge>}
           {rule.tags.includes("fresh") &&
             <Badge color="green">Fresh</Badge>}
+          {rule.tags.includes("jsx") &&
+            <Badge color="purple">JSX</Badge>}
+          {rule.tags.includes("react") &&
+            <Badge color="indigo">React</Badge>}
         </div>
       </div>
       <div class="relative bg-gray-50 dark:bg-[#192029] dark:text-white p-3">